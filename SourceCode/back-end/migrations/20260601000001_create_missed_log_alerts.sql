@@ -0,0 +1,28 @@
+-- Per-company configuration for missed-log alert emails.
+-- A company without a row uses the column defaults. escalation_hours = 0 disables escalation.
+CREATE TABLE missed_log_alert_settings (
+    company_id TEXT PRIMARY KEY,
+    enabled BOOLEAN NOT NULL DEFAULT TRUE,
+    escalation_hours INTEGER NOT NULL DEFAULT 4,
+    digest BOOLEAN NOT NULL DEFAULT FALSE,
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    FOREIGN KEY (company_id) REFERENCES companies(id) ON DELETE CASCADE,
+    CONSTRAINT valid_escalation_hours CHECK (escalation_hours BETWEEN 0 AND 168)
+);
+
+-- One row per overdue template period that has been alerted on
+CREATE TABLE missed_log_alerts (
+    id TEXT PRIMARY KEY,
+    company_id TEXT NOT NULL,
+    branch_id TEXT,
+    template_name TEXT NOT NULL,
+    period TEXT NOT NULL,
+    alerted_at TIMESTAMPTZ NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    escalated_at TIMESTAMPTZ,
+    resolved_at TIMESTAMPTZ,
+    FOREIGN KEY (company_id) REFERENCES companies(id) ON DELETE CASCADE,
+    CONSTRAINT unique_missed_log_alert UNIQUE (company_id, template_name, period)
+);
+
+CREATE INDEX idx_missed_log_alerts_open ON missed_log_alerts(company_id)
+    WHERE escalated_at IS NULL AND resolved_at IS NULL;
@@ -0,0 +1,11 @@
+-- Per-user opt-in for scheduled compliance digest emails
+CREATE TYPE digest_frequency AS ENUM ('daily', 'weekly');
+
+CREATE TABLE compliance_digest_subscriptions (
+    user_id TEXT PRIMARY KEY,
+    frequency digest_frequency NOT NULL,
+    last_sent_at TIMESTAMPTZ,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    FOREIGN KEY (user_id) REFERENCES users(id) ON DELETE CASCADE
+);
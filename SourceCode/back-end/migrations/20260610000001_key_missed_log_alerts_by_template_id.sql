@@ -0,0 +1,7 @@
+-- Templates are identified by an immutable id; template_name is kept for display only.
+-- Rows recorded before this migration have no template_id until the alert task matches them by name.
+ALTER TABLE missed_log_alerts ADD COLUMN template_id TEXT;
+
+ALTER TABLE missed_log_alerts DROP CONSTRAINT unique_missed_log_alert;
+ALTER TABLE missed_log_alerts
+    ADD CONSTRAINT unique_missed_log_alert UNIQUE (company_id, template_id, period);
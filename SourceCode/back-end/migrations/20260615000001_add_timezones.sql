@@ -0,0 +1,5 @@
+-- IANA timezone used to compute log periods, availability windows and due times.
+ALTER TABLE companies ADD COLUMN timezone TEXT NOT NULL DEFAULT 'Europe/London';
+
+-- Branches inherit the company timezone unless they set their own.
+ALTER TABLE branches ADD COLUMN timezone TEXT;
@@ -0,0 +1,25 @@
+-- Weekly opening hours of a branch. A branch without any rows is treated as open every day;
+-- once hours are set, weekdays without a row are closed.
+CREATE TABLE branch_opening_hours (
+    branch_id TEXT NOT NULL REFERENCES branches(id) ON DELETE CASCADE,
+    -- 0 = Sunday, matching schedule weekday numbering
+    day_of_week SMALLINT NOT NULL CHECK (day_of_week BETWEEN 0 AND 6),
+    opens_at TIME NOT NULL,
+    closes_at TIME NOT NULL CHECK (closes_at > opens_at),
+    PRIMARY KEY (branch_id, day_of_week)
+);
+
+-- Days a branch is closed, such as bank holidays and refurbishments. Both dates are inclusive.
+CREATE TABLE branch_closures (
+    id TEXT PRIMARY KEY,
+    branch_id TEXT NOT NULL REFERENCES branches(id) ON DELETE CASCADE,
+    starts_on DATE NOT NULL,
+    ends_on DATE NOT NULL CHECK (ends_on >= starts_on),
+    reason TEXT NOT NULL,
+    -- UID of the iCalendar event a closure was imported from, so re-imports update it
+    source_uid TEXT,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    CONSTRAINT unique_branch_closure_source UNIQUE (branch_id, source_uid)
+);
+
+CREATE INDEX idx_branch_closures_branch_dates ON branch_closures(branch_id, starts_on, ends_on);
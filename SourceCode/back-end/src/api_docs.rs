@@ -13,6 +13,8 @@ use utoipa::OpenApi;
         handlers::accept_invitation,
         handlers::cancel_invitation,
         handlers::update_profile,
+        handlers::get_digest_preference,
+        handlers::update_digest_preference,
         handlers::request_password_reset,
         handlers::reset_password,
         handlers::add_template,
@@ -22,16 +24,41 @@ use utoipa::OpenApi;
         handlers::admin_delete_member,
         handlers::get_invitation_details,
         handlers::get_all_templates,
+        handlers::preview_schedule,
         handlers::update_template,
         handlers::rename_template,
         handlers::delete_template,
+        handlers::unarchive_template,
+        handlers::purge_template,
+        handlers::distribute_template,
+        handlers::preview_template_propagation,
+        handlers::propagate_template,
+        handlers::unlink_template,
+        handlers::export_template_bundle,
+        handlers::import_template_bundle,
+        handlers::list_library_templates,
+        handlers::add_library_template,
+        handlers::update_library_template,
+        handlers::delete_library_template,
+        handlers::clone_library_template,
         handlers::get_template_versions,
+        handlers::diff_template_versions,
         handlers::restore_template_version,
         handlers::list_due_forms_today,
         handlers::create_log_entry,
         handlers::get_log_entry,
         handlers::update_log_entry,
         handlers::submit_log_entry,
+        handlers::review_log_entry,
+        handlers::get_log_entry_history,
+        handlers::get_entry_signature,
+        handlers::upload_entry_attachment,
+        handlers::list_entry_attachments,
+        handlers::download_entry_attachment,
+        handlers::delete_entry_attachment,
+        handlers::list_corrective_action_issues,
+        handlers::acknowledge_corrective_action_issue,
+        handlers::close_corrective_action_issue,
         handlers::unsubmit_log_entry,
         handlers::delete_log_entry,
         handlers::list_user_log_entries,
@@ -39,6 +66,9 @@ use utoipa::OpenApi;
         handlers::list_report_runs,
         handlers::use_report_run,
         handlers::delete_report_run,
+        handlers::generate_report_pdf,
+        handlers::generate_report_run_pdf,
+        handlers::export_log_entries,
         handlers::generate_layout,
         handlers::get_db_health,
         handlers::get_db_slow_queries,
@@ -56,13 +86,21 @@ use utoipa::OpenApi;
         handlers::create_branch,
         handlers::list_branches,
         handlers::update_branch,
+        handlers::get_branch_calendar,
+        handlers::update_branch_opening_hours,
+        handlers::create_branch_closure,
+        handlers::import_branch_closures,
+        handlers::delete_branch_closure,
         handlers::list_company_log_entries,
+        handlers::list_template_log_entries,
         handlers::clock_in,
         handlers::clock_out,
         handlers::get_clock_status,
         handlers::get_company_clock_events,
         handlers::get_company,
         handlers::update_company,
+        handlers::get_missed_log_alert_settings,
+        handlers::update_missed_log_alert_settings,
         handlers::delete_company,
         handlers::upload_company_logo,
         handlers::get_company_logo,
@@ -86,6 +124,9 @@ use utoipa::OpenApi;
             dto::ResetPasswordRequest,
             dto::PasswordResetResponse,
             dto::AddTemplateRequest,
+            dto::SchedulePreviewRequest,
+            dto::SchedulePreviewPeriod,
+            dto::SchedulePreviewResponse,
             dto::GetTemplateRequest,
             dto::GetTemplateResponse,
             dto::AddTemplateResponse,
@@ -101,6 +142,12 @@ use utoipa::OpenApi;
             dto::ConfirmBranchDeletionRequest,
             dto::ConfirmBranchDeletionResponse,
             dto::ListBranchesResponse,
+            dto::OpeningHoursDto,
+            dto::BranchClosureDto,
+            dto::BranchCalendarResponse,
+            dto::UpdateOpeningHoursRequest,
+            dto::CreateBranchClosureRequest,
+            dto::ImportBranchClosuresResponse,
             dto::GetInvitationDetailsRequest,
             dto::GetInvitationDetailsResponse,
             dto::GetPendingInvitationsResponse,
@@ -112,7 +159,39 @@ use utoipa::OpenApi;
             dto::RenameTemplateResponse,
             dto::DeleteTemplateRequest,
             dto::DeleteTemplateResponse,
+            dto::UnarchiveTemplateRequest,
+            dto::UnarchiveTemplateResponse,
+            dto::GetAllTemplatesQuery,
+            dto::DistributeTemplateRequest,
+            dto::DistributedTemplateInfo,
+            dto::DistributeTemplateResponse,
+            dto::LinkedCopyPreview,
+            dto::PropagationPreviewResponse,
+            dto::PropagateTemplateRequest,
+            dto::PropagateTemplateResponse,
+            dto::PropagationFailure,
+            dto::UnlinkTemplateRequest,
+            logs_db::LayoutDiff,
+            logs_db::LayoutFieldSummary,
+            logs_db::FieldChange,
+            logs_db::FieldMove,
+            logs_db::PropertyChange,
+            dto::TemplateBundle,
+            dto::TemplateBundlePayload,
+            dto::BundledTemplate,
+            dto::BundledTemplateVersion,
+            dto::ExportTemplateBundleRequest,
+            dto::BundleNameConflict,
+            dto::ImportTemplateBundleRequest,
+            dto::ImportedTemplateInfo,
+            dto::ImportTemplateBundleResponse,
+            dto::LibraryTemplateResponse,
+            dto::ListLibraryTemplatesQuery,
+            dto::ListLibraryTemplatesResponse,
+            dto::LibraryTemplateRequest,
+            dto::CloneLibraryTemplateRequest,
             dto::GetTemplateVersionsResponse,
+            dto::TemplateVersionDiffResponse,
             dto::RestoreTemplateVersionRequest,
             dto::TemplateVersionInfo,
             dto::CreateLogEntryRequest,
@@ -120,6 +199,20 @@ use utoipa::OpenApi;
             dto::LogEntryResponse,
             dto::UpdateLogEntryRequest,
             dto::SubmitLogEntryRequest,
+            dto::CorrectiveActionIssueResponse,
+            dto::ListCorrectiveActionIssuesResponse,
+            dto::CloseCorrectiveActionIssueRequest,
+            logs_db::OutOfRangeReading,
+            logs_db::IssueStatus,
+            dto::ReviewLogEntryRequest,
+            dto::ReviewLogEntryResponse,
+            dto::EntryTransitionResponse,
+            dto::EntryHistoryResponse,
+            dto::AttachmentResponse,
+            dto::ListAttachmentsResponse,
+            logs_db::EntryReview,
+            logs_db::ReviewDecision,
+            logs_db::EntryAction,
             dto::SubmitLogEntryResponse,
             dto::ReportRunParams,
             dto::CreateReportRunRequest,
@@ -128,8 +221,10 @@ use utoipa::OpenApi;
             dto::ListReportRunsResponse,
             dto::UseReportRunResponse,
             dto::DeleteReportRunResponse,
+            dto::ExportLogEntriesQuery,
             dto::DueFormInfo,
             dto::DueFormsResponse,
+            dto::StartableFormInfo,
             dto::ListLogEntriesResponse,
             dto::LayoutGenerationRequest,
             dto::LayoutGenerationResponse,
@@ -149,9 +244,12 @@ use utoipa::OpenApi;
             logs_db::TemplateField,
             logs_db::Position,
             logs_db::Schedule,
+            logs_db::TimeSlot,
             logs_db::Frequency,
             logs_db::TemplateLayout,
             logs_db::TemplateFieldProps,
+            logs_db::FieldCondition,
+            logs_db::ConditionOperator,
             dto::OAuthCallbackRequest,
             dto::OAuthInitiateResponse,
             handlers::OAuthLinkConfirmRequest,
@@ -160,6 +258,11 @@ use utoipa::OpenApi;
             dto::CompanyClockEventResponse,
             dto::CompanyClockEventsResponse,
             dto::CompanyResponse,
+            dto::MissedLogAlertSettingsResponse,
+            dto::DigestPreferenceResponse,
+            dto::UpdateDigestPreferenceRequest,
+            crate::db::DigestFrequency,
+            dto::UpdateMissedLogAlertSettingsRequest,
         )
     ),
     tags(
@@ -0,0 +1,365 @@
+//! Files attached to log entries, stored GridFS-style: one metadata document per file and
+//! its contents split into fixed-size chunks, so attachments are not limited by MongoDB's
+//! 16MB document size.
+
+use anyhow::Result;
+use chrono::{DateTime, Utc};
+use futures_util::TryStreamExt;
+use utoipa::ToSchema;
+
+const ATTACHMENTS_COLLECTION: &str = "entry_attachments";
+const CHUNKS_COLLECTION: &str = "entry_attachment_chunks";
+const USAGE_COLLECTION: &str = "company_attachment_usage";
+
+/// Size of each stored chunk, in bytes. Matches the GridFS default.
+pub const CHUNK_SIZE: usize = 255 * 1024;
+
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize, ToSchema)]
+pub struct AttachmentDocument {
+    pub attachment_id: String,
+    pub entry_id: String,
+    pub company_id: String,
+    pub filename: String,
+    pub content_type: String,
+    /// Size of the file in bytes.
+    pub size: i64,
+    pub chunk_count: i32,
+    pub uploaded_by: String,
+    pub uploaded_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+struct AttachmentChunk {
+    attachment_id: String,
+    n: i32,
+    data: mongodb::bson::Binary,
+}
+
+fn attachments(client: &mongodb::Client) -> mongodb::Collection<AttachmentDocument> {
+    client
+        .database("logs_db")
+        .collection(ATTACHMENTS_COLLECTION)
+}
+
+fn chunks(client: &mongodb::Client) -> mongodb::Collection<AttachmentChunk> {
+    client.database("logs_db").collection(CHUNKS_COLLECTION)
+}
+
+fn usage(client: &mongodb::Client) -> mongodb::Collection<mongodb::bson::Document> {
+    client.database("logs_db").collection(USAGE_COLLECTION)
+}
+
+/// Creates the indexes used to look up attachments by entry and company, and to read chunks in order.
+///
+/// # Errors
+/// Returns an error if an index cannot be created.
+pub async fn ensure_attachment_indexes(client: &mongodb::Client) -> Result<()> {
+    let index = |keys: mongodb::bson::Document, name: &str, unique: bool| {
+        mongodb::IndexModel::builder()
+            .keys(keys)
+            .options(
+                mongodb::options::IndexOptions::builder()
+                    .name(Some(name.to_string()))
+                    .unique(Some(unique))
+                    .build(),
+            )
+            .build()
+    };
+
+    attachments(client)
+        .create_indexes([
+            index(
+                mongodb::bson::doc! { "attachment_id": 1 },
+                "entry_attachments_id_idx",
+                true,
+            ),
+            index(
+                mongodb::bson::doc! { "entry_id": 1, "uploaded_at": 1 },
+                "entry_attachments_entry_idx",
+                false,
+            ),
+            index(
+                mongodb::bson::doc! { "company_id": 1 },
+                "entry_attachments_company_idx",
+                false,
+            ),
+        ])
+        .await?;
+
+    chunks(client)
+        .create_index(index(
+            mongodb::bson::doc! { "attachment_id": 1, "n": 1 },
+            "entry_attachment_chunks_idx",
+            true,
+        ))
+        .await?;
+
+    Ok(())
+}
+
+/// Stores an attachment's chunks and then its metadata, so a partially written file is never listed.
+///
+/// # Errors
+/// Returns an error if the database insertion fails.
+pub async fn store_attachment(
+    client: &mongodb::Client,
+    attachment: &AttachmentDocument,
+    data: &[u8],
+) -> Result<()> {
+    let documents: Vec<AttachmentChunk> = data
+        .chunks(CHUNK_SIZE)
+        .enumerate()
+        .map(|(n, chunk)| AttachmentChunk {
+            attachment_id: attachment.attachment_id.clone(),
+            n: i32::try_from(n).unwrap_or(i32::MAX),
+            data: mongodb::bson::Binary {
+                subtype: mongodb::bson::spec::BinarySubtype::Generic,
+                bytes: chunk.to_vec(),
+            },
+        })
+        .collect();
+
+    chunks(client).insert_many(documents).await?;
+
+    if let Err(e) = attachments(client).insert_one(attachment).await {
+        delete_chunks(client, std::slice::from_ref(&attachment.attachment_id)).await?;
+        return Err(e.into());
+    }
+    Ok(())
+}
+
+/// Reads an attachment's contents back from its chunks.
+///
+/// # Errors
+/// Returns an error if the query fails or chunks are missing.
+pub async fn read_attachment(
+    client: &mongodb::Client,
+    attachment: &AttachmentDocument,
+) -> Result<Vec<u8>> {
+    let mut data = Vec::with_capacity(usize::try_from(attachment.size).unwrap_or_default());
+    read_attachment_chunks(client, attachment, |chunk| {
+        data.extend_from_slice(&chunk);
+        async { Ok(()) }
+    })
+    .await?;
+    Ok(data)
+}
+
+/// Passes an attachment's chunks to `write` in order, so the whole file is never held in memory.
+///
+/// # Errors
+/// Returns an error if the query fails, chunks are missing, or `write` fails.
+pub async fn read_attachment_chunks<F, Fut>(
+    client: &mongodb::Client,
+    attachment: &AttachmentDocument,
+    mut write: F,
+) -> Result<()>
+where
+    F: FnMut(Vec<u8>) -> Fut,
+    Fut: std::future::Future<Output = Result<()>>,
+{
+    let mut cursor = chunks(client)
+        .find(mongodb::bson::doc! { "attachment_id": &attachment.attachment_id })
+        .sort(mongodb::bson::doc! { "n": 1 })
+        .await?;
+
+    let mut expected = 0;
+    while let Some(chunk) = cursor.try_next().await? {
+        if chunk.n != expected {
+            anyhow::bail!(
+                "Attachment {} is missing chunk {expected}",
+                attachment.attachment_id
+            );
+        }
+        write(chunk.data.bytes).await?;
+        expected += 1;
+    }
+
+    if expected != attachment.chunk_count {
+        anyhow::bail!("Attachment {} is incomplete", attachment.attachment_id);
+    }
+    Ok(())
+}
+
+/// Lists the attachments of an entry, oldest first.
+///
+/// # Errors
+/// Returns an error if the database query fails.
+pub async fn get_entry_attachments(
+    client: &mongodb::Client,
+    entry_id: &str,
+) -> Result<Vec<AttachmentDocument>> {
+    let cursor = attachments(client)
+        .find(mongodb::bson::doc! { "entry_id": entry_id })
+        .sort(mongodb::bson::doc! { "uploaded_at": 1 })
+        .await?;
+    Ok(cursor.try_collect().await?)
+}
+
+/// Lists every attachment belonging to a company.
+///
+/// # Errors
+/// Returns an error if the database query fails.
+pub async fn get_company_attachments(
+    client: &mongodb::Client,
+    company_id: &str,
+) -> Result<Vec<AttachmentDocument>> {
+    let cursor = attachments(client)
+        .find(mongodb::bson::doc! { "company_id": company_id })
+        .sort(mongodb::bson::doc! { "entry_id": 1, "uploaded_at": 1 })
+        .await?;
+    Ok(cursor.try_collect().await?)
+}
+
+/// Fetches one attachment of an entry.
+///
+/// # Errors
+/// Returns an error if the database query fails.
+pub async fn get_attachment(
+    client: &mongodb::Client,
+    entry_id: &str,
+    attachment_id: &str,
+) -> Result<Option<AttachmentDocument>> {
+    Ok(attachments(client)
+        .find_one(mongodb::bson::doc! {
+            "entry_id": entry_id,
+            "attachment_id": attachment_id,
+        })
+        .await?)
+}
+
+/// Total size in bytes of the attachments stored by a company.
+///
+/// # Errors
+/// Returns an error if the aggregation fails.
+pub async fn get_company_attachment_usage(
+    client: &mongodb::Client,
+    company_id: &str,
+) -> Result<i64> {
+    let pipeline = vec![
+        mongodb::bson::doc! { "$match": { "company_id": company_id } },
+        mongodb::bson::doc! { "$group": { "_id": null, "total": { "$sum": "$size" } } },
+    ];
+
+    let mut cursor = attachments(client).aggregate(pipeline).await?;
+    let total = match cursor.try_next().await? {
+        Some(doc) => match doc.get("total") {
+            Some(mongodb::bson::Bson::Int64(n)) => *n,
+            Some(mongodb::bson::Bson::Int32(n)) => i64::from(*n),
+            _ => 0,
+        },
+        None => 0,
+    };
+    Ok(total)
+}
+
+/// Reserves `size` bytes of a company's attachment storage. Returns `false`, reserving
+/// nothing, if that would take the company over `quota` bytes.
+///
+/// The reservation is a single conditional update, so concurrent uploads cannot together
+/// exceed the quota. The company's counter starts from its stored attachments.
+///
+/// # Errors
+/// Returns an error if the database update fails.
+pub async fn reserve_attachment_storage(
+    client: &mongodb::Client,
+    company_id: &str,
+    size: i64,
+    quota: i64,
+) -> Result<bool> {
+    let existing = usage(client)
+        .find_one(mongodb::bson::doc! { "_id": company_id })
+        .await?;
+    if existing.is_none() {
+        let used = get_company_attachment_usage(client, company_id).await?;
+        usage(client)
+            .update_one(
+                mongodb::bson::doc! { "_id": company_id },
+                mongodb::bson::doc! { "$setOnInsert": { "used_bytes": used } },
+            )
+            .upsert(true)
+            .await?;
+    }
+
+    let result = usage(client)
+        .update_one(
+            mongodb::bson::doc! {
+                "_id": company_id,
+                "used_bytes": { "$lte": quota.saturating_sub(size) },
+            },
+            mongodb::bson::doc! { "$inc": { "used_bytes": size } },
+        )
+        .await?;
+    Ok(result.matched_count > 0)
+}
+
+/// Returns `size` bytes to a company's attachment storage.
+///
+/// # Errors
+/// Returns an error if the database update fails.
+pub async fn release_attachment_storage(
+    client: &mongodb::Client,
+    company_id: &str,
+    size: i64,
+) -> Result<()> {
+    usage(client)
+        .update_one(
+            mongodb::bson::doc! { "_id": company_id },
+            mongodb::bson::doc! { "$inc": { "used_bytes": -size } },
+        )
+        .await?;
+    Ok(())
+}
+
+async fn delete_chunks(client: &mongodb::Client, attachment_ids: &[String]) -> Result<()> {
+    chunks(client)
+        .delete_many(mongodb::bson::doc! { "attachment_id": { "$in": attachment_ids } })
+        .await?;
+    Ok(())
+}
+
+/// Deletes an attachment and its chunks, returning its storage to the company. Returns
+/// `false` if it does not exist.
+///
+/// # Errors
+/// Returns an error if the database deletion fails.
+pub async fn delete_attachment(
+    client: &mongodb::Client,
+    entry_id: &str,
+    attachment_id: &str,
+) -> Result<bool> {
+    let Some(deleted) = attachments(client)
+        .find_one_and_delete(mongodb::bson::doc! {
+            "entry_id": entry_id,
+            "attachment_id": attachment_id,
+        })
+        .await?
+    else {
+        return Ok(false);
+    };
+    delete_chunks(client, &[attachment_id.to_string()]).await?;
+    release_attachment_storage(client, &deleted.company_id, deleted.size).await?;
+    Ok(true)
+}
+
+/// Deletes every attachment of an entry, returning their storage to the company.
+///
+/// # Errors
+/// Returns an error if the database deletion fails.
+pub async fn delete_entry_attachments(client: &mongodb::Client, entry_id: &str) -> Result<()> {
+    let entry_attachments = get_entry_attachments(client, entry_id).await?;
+    let Some(company_id) = entry_attachments.first().map(|a| a.company_id.clone()) else {
+        return Ok(());
+    };
+    let attachment_ids: Vec<String> = entry_attachments
+        .iter()
+        .map(|a| a.attachment_id.clone())
+        .collect();
+    let size = entry_attachments.iter().map(|a| a.size).sum();
+
+    attachments(client)
+        .delete_many(mongodb::bson::doc! { "entry_id": entry_id })
+        .await?;
+    delete_chunks(client, &attachment_ids).await?;
+    release_attachment_storage(client, &company_id, size).await
+}
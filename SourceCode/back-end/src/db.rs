@@ -5,6 +5,9 @@ use std::fmt::Write;
 use utoipa::ToSchema;
 use uuid::Uuid;
 
+/// Timezone of companies that have not chosen one.
+pub const DEFAULT_TIMEZONE: &str = "Europe/London";
+
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, sqlx::Type, ToSchema)]
 #[sqlx(type_name = "user_role")]
 #[sqlx(rename_all = "lowercase")]
@@ -131,6 +134,8 @@ pub struct Branch {
     pub name: String,
     pub address: String,
     pub created_at: chrono::DateTime<chrono::Utc>,
+    /// IANA timezone overriding the company's, if the branch is elsewhere.
+    pub timezone: Option<String>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, sqlx::FromRow)]
@@ -145,6 +150,8 @@ pub struct Company {
     pub deletion_requested_at: Option<chrono::DateTime<chrono::Utc>>,
     pub deletion_token: Option<String>,
     pub deletion_requested_by_email: Option<String>,
+    /// IANA timezone used to compute log periods and due times.
+    pub timezone: String,
 }
 
 impl Default for Company {
@@ -167,6 +174,7 @@ impl Company {
             deletion_requested_at: None,
             deletion_token: None,
             deletion_requested_by_email: None,
+            timezone: DEFAULT_TIMEZONE.to_string(),
         }
     }
 
@@ -291,6 +299,207 @@ impl ClockEvent {
     }
 }
 
+#[derive(Debug, Clone, Serialize, Deserialize, sqlx::FromRow)]
+pub struct MissedLogAlertSettings {
+    pub company_id: String,
+    pub enabled: bool,
+    /// Hours after the first alert before the company manager is notified. 0 disables escalation.
+    pub escalation_hours: i32,
+    /// Send one summary email per recipient per run instead of one email per missed period.
+    pub digest: bool,
+    pub updated_at: chrono::DateTime<chrono::Utc>,
+}
+
+impl MissedLogAlertSettings {
+    #[must_use]
+    pub fn defaults(company_id: &str) -> Self {
+        Self {
+            company_id: company_id.to_string(),
+            enabled: true,
+            escalation_hours: 4,
+            digest: false,
+            updated_at: chrono::Utc::now(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, sqlx::FromRow)]
+pub struct MissedLogAlert {
+    pub id: String,
+    pub company_id: String,
+    pub branch_id: Option<String>,
+    /// `None` for alerts recorded before templates had ids that have not been matched yet.
+    pub template_id: Option<String>,
+    pub template_name: String,
+    pub period: String,
+    pub alerted_at: chrono::DateTime<chrono::Utc>,
+    pub escalated_at: Option<chrono::DateTime<chrono::Utc>>,
+    pub resolved_at: Option<chrono::DateTime<chrono::Utc>>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, sqlx::FromRow, PartialEq)]
+pub struct BranchOpeningHours {
+    pub branch_id: String,
+    /// 0 = Sunday, matching schedule weekday numbering.
+    pub day_of_week: i16,
+    pub opens_at: chrono::NaiveTime,
+    pub closes_at: chrono::NaiveTime,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, sqlx::FromRow, PartialEq)]
+pub struct BranchClosure {
+    pub id: String,
+    pub branch_id: String,
+    pub starts_on: chrono::NaiveDate,
+    /// Last closed day, inclusive.
+    pub ends_on: chrono::NaiveDate,
+    pub reason: String,
+    /// UID of the iCalendar event the closure was imported from.
+    pub source_uid: Option<String>,
+    pub created_at: chrono::DateTime<chrono::Utc>,
+}
+
+/// A closure read from an all-day iCalendar `VEVENT`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ImportedClosure {
+    /// UID of the event, so importing it again updates the closure.
+    pub uid: String,
+    pub starts_on: chrono::NaiveDate,
+    /// Last closed day, inclusive.
+    pub ends_on: chrono::NaiveDate,
+    pub reason: String,
+}
+
+/// Opening hours and closures of a branch, used to leave closed days out of due periods.
+#[derive(Debug, Clone, Default)]
+pub struct BranchCalendar {
+    pub opening_hours: Vec<BranchOpeningHours>,
+    pub closures: Vec<BranchClosure>,
+}
+
+impl BranchCalendar {
+    /// Opening hours on a date's weekday, or `None` if the branch is closed that weekday.
+    /// A branch without opening hours is open all day, every day.
+    #[must_use]
+    pub fn hours_on(
+        &self,
+        date: chrono::NaiveDate,
+    ) -> Option<(chrono::NaiveTime, chrono::NaiveTime)> {
+        use chrono::Datelike;
+
+        if self.opening_hours.is_empty() {
+            return Some((chrono::NaiveTime::MIN, chrono::NaiveTime::MIN));
+        }
+        let weekday = i16::try_from(date.weekday().num_days_from_sunday()).ok()?;
+        self.opening_hours
+            .iter()
+            .find(|h| h.day_of_week == weekday)
+            .map(|h| (h.opens_at, h.closes_at))
+    }
+
+    #[must_use]
+    pub fn is_open_on(&self, date: chrono::NaiveDate) -> bool {
+        self.hours_on(date).is_some()
+            && !self
+                .closures
+                .iter()
+                .any(|c| c.starts_on <= date && date <= c.ends_on)
+    }
+
+    /// Whether the branch is open during a period of a schedule. Periods longer than a day
+    /// are only closed when every day they cover is, so a closure on the due day alone does
+    /// not excuse a weekly or monthly log. Sub-daily periods must also open within the
+    /// day's opening hours.
+    #[must_use]
+    pub fn is_period_open(&self, schedule: &crate::logs_db::Schedule, period: &str) -> bool {
+        let Some((start, end)) = crate::logs_db::period_date_range(schedule, period) else {
+            return true;
+        };
+        if !start
+            .iter_days()
+            .take_while(|date| *date <= end)
+            .any(|date| self.is_open_on(date))
+        {
+            return false;
+        }
+        match (
+            crate::logs_db::period_slot_minutes(period),
+            self.hours_on(start),
+        ) {
+            (Some(minutes), Some((opens_at, closes_at))) if !self.opening_hours.is_empty() => {
+                chrono::NaiveTime::from_hms_opt(minutes / 60, minutes % 60, 0)
+                    .is_some_and(|time| opens_at <= time && time < closes_at)
+            }
+            _ => true,
+        }
+    }
+}
+
+/// Calendars of a company's branches. Company-wide templates are not tied to a branch and
+/// are never suppressed.
+#[derive(Debug, Clone, Default)]
+pub struct CompanyCalendars {
+    pub branches: std::collections::HashMap<String, BranchCalendar>,
+}
+
+impl CompanyCalendars {
+    #[must_use]
+    pub fn is_period_open(
+        &self,
+        branch_id: Option<&str>,
+        schedule: &crate::logs_db::Schedule,
+        period: &str,
+    ) -> bool {
+        branch_id
+            .and_then(|id| self.branches.get(id))
+            .is_none_or(|calendar| calendar.is_period_open(schedule, period))
+    }
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Hash, sqlx::Type, ToSchema)]
+#[sqlx(type_name = "digest_frequency")]
+#[sqlx(rename_all = "lowercase")]
+#[serde(rename_all = "lowercase")]
+pub enum DigestFrequency {
+    Daily,
+    Weekly,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, sqlx::FromRow)]
+pub struct DigestSubscription {
+    pub user_id: String,
+    pub frequency: DigestFrequency,
+    pub last_sent_at: Option<chrono::DateTime<chrono::Utc>>,
+    pub created_at: chrono::DateTime<chrono::Utc>,
+    pub updated_at: chrono::DateTime<chrono::Utc>,
+}
+
+/// A digest subscription joined with the subscriber's details.
+#[derive(Debug, Clone, sqlx::FromRow)]
+pub struct DigestSubscriber {
+    pub user_id: String,
+    pub email: String,
+    pub role: UserRole,
+    pub company_id: String,
+    pub branch_id: Option<String>,
+    pub frequency: DigestFrequency,
+    pub last_sent_at: Option<chrono::DateTime<chrono::Utc>>,
+    pub company_timezone: String,
+    pub branch_timezone: Option<String>,
+}
+
+impl DigestSubscriber {
+    /// Timezone the subscriber's digest is sent and dated in: their branch's for branch
+    /// managers, whose digest covers that branch, and the company's for everyone else.
+    #[must_use]
+    pub fn timezone(&self) -> chrono_tz::Tz {
+        match (&self.role, &self.branch_timezone) {
+            (UserRole::BranchManager, Some(tz)) => resolve_timezone(tz),
+            _ => resolve_timezone(&self.company_timezone),
+        }
+    }
+}
+
 /// Initialize database by running `SQLx` migrations
 ///
 /// This function runs all pending migrations from the `migrations/` directory.
@@ -720,7 +929,7 @@ pub async fn update_company_logo_id(
         UPDATE companies
         SET logo_id = $1
         WHERE id = $2
-        RETURNING id, name, address, created_at, logo_id, data_exported_at, deleted_at, deletion_requested_at, deletion_token, deletion_requested_by_email
+        RETURNING id, name, address, created_at, logo_id, data_exported_at, deleted_at, deletion_requested_at, deletion_token, deletion_requested_by_email, timezone
         ",
     )
     .bind(company_logo_id)
@@ -737,7 +946,7 @@ pub async fn update_company_logo_id(
 pub async fn get_company_by_id(pool: &PgPool, id: &str) -> Result<Option<Company>> {
     let company = sqlx::query_as::<_, Company>(
         r"
-        SELECT id, name, address, created_at, logo_id, data_exported_at, deleted_at, deletion_requested_at, deletion_token, deletion_requested_by_email
+        SELECT id, name, address, created_at, logo_id, data_exported_at, deleted_at, deletion_requested_at, deletion_token, deletion_requested_by_email, timezone
         FROM companies
         WHERE id = $1 AND deleted_at IS NULL
         ",
@@ -749,7 +958,7 @@ pub async fn get_company_by_id(pool: &PgPool, id: &str) -> Result<Option<Company
     Ok(company)
 }
 
-/// Updates a company's name and address.
+/// Updates a company's name, address and timezone.
 ///
 /// # Errors
 /// Returns an error if database query fails.
@@ -758,17 +967,19 @@ pub async fn update_company(
     company_id: &str,
     name: &str,
     address: &str,
+    timezone: &str,
 ) -> Result<Company> {
     sqlx::query_as(
         r"
         UPDATE companies
-        SET name = $1, address = $2
-        WHERE id = $3
-        RETURNING id, name, address, created_at, logo_id, data_exported_at, deleted_at, deletion_requested_at, deletion_token, deletion_requested_by_email
+        SET name = $1, address = $2, timezone = $3
+        WHERE id = $4
+        RETURNING id, name, address, created_at, logo_id, data_exported_at, deleted_at, deletion_requested_at, deletion_token, deletion_requested_by_email, timezone
         ",
     )
     .bind(name)
     .bind(address)
+    .bind(timezone)
     .bind(company_id)
     .fetch_one(pool)
     .await
@@ -785,7 +996,7 @@ pub async fn mark_company_data_exported(pool: &PgPool, company_id: &str) -> Resu
         UPDATE companies
         SET data_exported_at = NOW()
         WHERE id = $1
-        RETURNING id, name, address, created_at, logo_id, data_exported_at, deleted_at, deletion_requested_at, deletion_token, deletion_requested_by_email
+        RETURNING id, name, address, created_at, logo_id, data_exported_at, deleted_at, deletion_requested_at, deletion_token, deletion_requested_by_email, timezone
         ",
     )
     .bind(company_id)
@@ -809,7 +1020,7 @@ pub async fn request_company_deletion(
         UPDATE companies
         SET deletion_requested_at = NOW(), deletion_token = $1, deletion_requested_by_email = $2
         WHERE id = $3
-        RETURNING id, name, address, created_at, logo_id, data_exported_at, deleted_at, deletion_requested_at, deletion_token, deletion_requested_by_email
+        RETURNING id, name, address, created_at, logo_id, data_exported_at, deleted_at, deletion_requested_at, deletion_token, deletion_requested_by_email, timezone
         ",
     )
     .bind(&token)
@@ -834,7 +1045,7 @@ pub async fn confirm_company_deletion(
         UPDATE companies
         SET deleted_at = NOW(), deletion_token = NULL, deletion_requested_at = NULL
         WHERE id = $1 AND deletion_token = $2 AND deletion_requested_at IS NOT NULL AND deletion_requested_at > NOW() - INTERVAL '6 hours'
-        RETURNING id, name, address, created_at, logo_id, data_exported_at, deleted_at, deletion_requested_at, deletion_token, deletion_requested_by_email
+        RETURNING id, name, address, created_at, logo_id, data_exported_at, deleted_at, deletion_requested_at, deletion_token, deletion_requested_by_email, timezone
         ",
     )
     .bind(company_id)
@@ -1853,6 +2064,7 @@ pub async fn create_branch<'a, E>(
     company_id: String,
     name: String,
     address: String,
+    timezone: Option<String>,
 ) -> Result<Branch>
 where
     E: sqlx::Executor<'a, Database = sqlx::Postgres>,
@@ -1862,8 +2074,8 @@ where
 
     sqlx::query(
         r"
-        INSERT INTO branches (id, company_id, name, address, created_at)
-        VALUES ($1, $2, $3, $4, $5)
+        INSERT INTO branches (id, company_id, name, address, created_at, timezone)
+        VALUES ($1, $2, $3, $4, $5, $6)
         ",
     )
     .bind(&id)
@@ -1871,6 +2083,7 @@ where
     .bind(&name)
     .bind(&address)
     .bind(now)
+    .bind(&timezone)
     .execute(executor)
     .await?;
 
@@ -1880,6 +2093,7 @@ where
         name,
         address,
         created_at: now,
+        timezone,
     })
 }
 
@@ -1890,7 +2104,7 @@ where
 pub async fn get_branches_by_company_id(pool: &PgPool, company_id: &str) -> Result<Vec<Branch>> {
     let branches = sqlx::query_as::<_, Branch>(
         r"
-        SELECT id, company_id, name, address, created_at
+        SELECT id, company_id, name, address, created_at, timezone
         FROM branches
         WHERE company_id = $1
         ORDER BY name ASC
@@ -1920,7 +2134,7 @@ pub async fn get_branches_by_company_id_with_deletion_status(
 ) -> Result<Vec<BranchWithDeletionStatus>> {
     let branches = sqlx::query_as::<_, Branch>(
         r"
-        SELECT id, company_id, name, address, created_at
+        SELECT id, company_id, name, address, created_at, timezone
         FROM branches
         WHERE company_id = $1
         ORDER BY name ASC
@@ -1964,7 +2178,7 @@ pub async fn get_branches_by_company_id_with_deletion_status(
 pub async fn get_branch_by_id(pool: &PgPool, branch_id: &str) -> Result<Option<Branch>> {
     let branch = sqlx::query_as::<_, Branch>(
         r"
-        SELECT id, company_id, name, address, created_at
+        SELECT id, company_id, name, address, created_at, timezone
         FROM branches
         WHERE id = $1
         ",
@@ -1985,17 +2199,19 @@ pub async fn update_branch(
     branch_id: &str,
     name: &str,
     address: &str,
+    timezone: Option<&str>,
 ) -> Result<Branch> {
     let updated_branch = sqlx::query_as::<_, Branch>(
         r"
         UPDATE branches
-        SET name = $1, address = $2
-        WHERE id = $3
-        RETURNING id, company_id, name, address, created_at
+        SET name = $1, address = $2, timezone = $3
+        WHERE id = $4
+        RETURNING id, company_id, name, address, created_at, timezone
         ",
     )
     .bind(name)
     .bind(address)
+    .bind(timezone)
     .bind(branch_id)
     .fetch_one(pool)
     .await?;
@@ -2003,6 +2219,126 @@ pub async fn update_branch(
     Ok(updated_branch)
 }
 
+/// Timezones a company's schedules are evaluated in: the company's own and any branch overrides.
+#[derive(Debug, Clone)]
+pub struct CompanyTimezones {
+    pub company: chrono_tz::Tz,
+    pub branches: std::collections::HashMap<String, chrono_tz::Tz>,
+}
+
+impl CompanyTimezones {
+    /// Timezone of a branch, falling back to the company's for HQ and branches without one.
+    #[must_use]
+    pub fn for_branch(&self, branch_id: Option<&str>) -> chrono_tz::Tz {
+        branch_id
+            .and_then(|id| self.branches.get(id))
+            .copied()
+            .unwrap_or(self.company)
+    }
+
+    /// Date `at` falls on in a branch's timezone.
+    #[must_use]
+    pub fn local_date(
+        &self,
+        branch_id: Option<&str>,
+        at: chrono::DateTime<chrono::Utc>,
+    ) -> chrono::NaiveDate {
+        at.with_timezone(&self.for_branch(branch_id)).date_naive()
+    }
+
+    /// UTC instants from the start of local date `from` to the end of local date `to` in any
+    /// of the company's timezones. Records queried by this range still need their date
+    /// checked in their own branch's timezone with [`Self::local_date`].
+    #[must_use]
+    pub fn utc_range(
+        &self,
+        from: chrono::NaiveDate,
+        to: chrono::NaiveDate,
+    ) -> (chrono::DateTime<chrono::Utc>, chrono::DateTime<chrono::Utc>) {
+        use chrono::{Offset, TimeZone};
+
+        let start_of = |date: chrono::NaiveDate, tz: chrono_tz::Tz| {
+            let midnight = date.and_time(chrono::NaiveTime::MIN);
+            (midnight - tz.offset_from_utc_datetime(&midnight).fix()).and_utc()
+        };
+        let timezones: Vec<chrono_tz::Tz> = std::iter::once(self.company)
+            .chain(self.branches.values().copied())
+            .collect();
+        let next_day = to.succ_opt().unwrap_or(to);
+
+        let start = timezones
+            .iter()
+            .map(|tz| start_of(from, *tz))
+            .min()
+            .unwrap_or_else(|| from.and_time(chrono::NaiveTime::MIN).and_utc());
+        let end = timezones
+            .iter()
+            .map(|tz| start_of(next_day, *tz))
+            .max()
+            .unwrap_or_else(|| next_day.and_time(chrono::NaiveTime::MIN).and_utc());
+        (start, end)
+    }
+}
+
+/// Parses a stored IANA timezone name, falling back to the default if it is not recognised.
+#[must_use]
+pub fn resolve_timezone(name: &str) -> chrono_tz::Tz {
+    name.parse().unwrap_or_else(|_| {
+        tracing::warn!("Unknown timezone '{name}', using {DEFAULT_TIMEZONE}");
+        chrono_tz::Europe::London
+    })
+}
+
+/// Retrieves the timezones of a company and its branches.
+///
+/// # Errors
+/// Returns an error if database query fails.
+pub async fn get_company_timezones(pool: &PgPool, company_id: &str) -> Result<CompanyTimezones> {
+    let company: Option<(String,)> = sqlx::query_as(
+        r"
+        SELECT timezone
+        FROM companies
+        WHERE id = $1
+        ",
+    )
+    .bind(company_id)
+    .fetch_optional(pool)
+    .await?;
+
+    let branches: Vec<(String, String)> = sqlx::query_as(
+        r"
+        SELECT id, timezone
+        FROM branches
+        WHERE company_id = $1 AND timezone IS NOT NULL
+        ",
+    )
+    .bind(company_id)
+    .fetch_all(pool)
+    .await?;
+
+    Ok(CompanyTimezones {
+        company: company.map_or(chrono_tz::Europe::London, |(tz,)| resolve_timezone(&tz)),
+        branches: branches
+            .into_iter()
+            .map(|(id, tz)| (id, resolve_timezone(&tz)))
+            .collect(),
+    })
+}
+
+/// Retrieves the timezone schedules of a branch are evaluated in, or the company's for HQ.
+///
+/// # Errors
+/// Returns an error if database query fails.
+pub async fn get_branch_timezone(
+    pool: &PgPool,
+    company_id: &str,
+    branch_id: Option<&str>,
+) -> Result<chrono_tz::Tz> {
+    Ok(get_company_timezones(pool, company_id)
+        .await?
+        .for_branch(branch_id))
+}
+
 /// Deletes a branch by its ID.
 ///
 /// # Errors
@@ -2837,6 +3173,503 @@ pub fn parse_clock_events_cursor(cursor: &str) -> Result<(chrono::DateTime<chron
     Ok((created_at, id.to_string()))
 }
 
+/// Retrieves the opening hours and closures of a branch.
+///
+/// # Errors
+/// Returns an error if database query fails.
+pub async fn get_branch_calendar(pool: &PgPool, branch_id: &str) -> Result<BranchCalendar> {
+    let opening_hours = sqlx::query_as::<_, BranchOpeningHours>(
+        r"
+        SELECT branch_id, day_of_week, opens_at, closes_at
+        FROM branch_opening_hours
+        WHERE branch_id = $1
+        ORDER BY day_of_week ASC
+        ",
+    )
+    .bind(branch_id)
+    .fetch_all(pool)
+    .await?;
+
+    let closures = sqlx::query_as::<_, BranchClosure>(
+        r"
+        SELECT id, branch_id, starts_on, ends_on, reason, source_uid, created_at
+        FROM branch_closures
+        WHERE branch_id = $1
+        ORDER BY starts_on ASC, ends_on ASC
+        ",
+    )
+    .bind(branch_id)
+    .fetch_all(pool)
+    .await?;
+
+    Ok(BranchCalendar {
+        opening_hours,
+        closures,
+    })
+}
+
+/// Retrieves the calendars of every branch of a company.
+///
+/// # Errors
+/// Returns an error if database query fails.
+pub async fn get_company_calendars(pool: &PgPool, company_id: &str) -> Result<CompanyCalendars> {
+    let opening_hours = sqlx::query_as::<_, BranchOpeningHours>(
+        r"
+        SELECT h.branch_id, h.day_of_week, h.opens_at, h.closes_at
+        FROM branch_opening_hours h
+        JOIN branches b ON b.id = h.branch_id
+        WHERE b.company_id = $1
+        ",
+    )
+    .bind(company_id)
+    .fetch_all(pool)
+    .await?;
+
+    let closures = sqlx::query_as::<_, BranchClosure>(
+        r"
+        SELECT c.id, c.branch_id, c.starts_on, c.ends_on, c.reason, c.source_uid, c.created_at
+        FROM branch_closures c
+        JOIN branches b ON b.id = c.branch_id
+        WHERE b.company_id = $1
+        ",
+    )
+    .bind(company_id)
+    .fetch_all(pool)
+    .await?;
+
+    let mut calendars = CompanyCalendars::default();
+    for hours in opening_hours {
+        calendars
+            .branches
+            .entry(hours.branch_id.clone())
+            .or_default()
+            .opening_hours
+            .push(hours);
+    }
+    for closure in closures {
+        calendars
+            .branches
+            .entry(closure.branch_id.clone())
+            .or_default()
+            .closures
+            .push(closure);
+    }
+    Ok(calendars)
+}
+
+/// Replaces the weekly opening hours of a branch. An empty list leaves the branch open every day.
+///
+/// # Errors
+/// Returns an error if database query fails.
+pub async fn replace_branch_opening_hours(
+    pool: &PgPool,
+    branch_id: &str,
+    opening_hours: &[BranchOpeningHours],
+) -> Result<()> {
+    let mut tx = pool.begin().await?;
+
+    sqlx::query(
+        r"
+        DELETE FROM branch_opening_hours
+        WHERE branch_id = $1
+        ",
+    )
+    .bind(branch_id)
+    .execute(&mut *tx)
+    .await?;
+
+    for hours in opening_hours {
+        sqlx::query(
+            r"
+            INSERT INTO branch_opening_hours (branch_id, day_of_week, opens_at, closes_at)
+            VALUES ($1, $2, $3, $4)
+            ",
+        )
+        .bind(branch_id)
+        .bind(hours.day_of_week)
+        .bind(hours.opens_at)
+        .bind(hours.closes_at)
+        .execute(&mut *tx)
+        .await?;
+    }
+
+    tx.commit().await?;
+    Ok(())
+}
+
+/// Adds a closure to a branch, or updates the closure previously imported from the same
+/// iCalendar event.
+///
+/// # Errors
+/// Returns an error if database query fails.
+pub async fn upsert_branch_closure<'a, E>(
+    executor: E,
+    branch_id: &str,
+    starts_on: chrono::NaiveDate,
+    ends_on: chrono::NaiveDate,
+    reason: &str,
+    source_uid: Option<&str>,
+) -> Result<BranchClosure>
+where
+    E: sqlx::Executor<'a, Database = sqlx::Postgres>,
+{
+    sqlx::query_as(
+        r"
+        INSERT INTO branch_closures (id, branch_id, starts_on, ends_on, reason, source_uid)
+        VALUES ($1, $2, $3, $4, $5, $6)
+        ON CONFLICT (branch_id, source_uid) DO UPDATE
+        SET starts_on = EXCLUDED.starts_on,
+            ends_on = EXCLUDED.ends_on,
+            reason = EXCLUDED.reason
+        RETURNING id, branch_id, starts_on, ends_on, reason, source_uid, created_at
+        ",
+    )
+    .bind(Uuid::new_v4().to_string())
+    .bind(branch_id)
+    .bind(starts_on)
+    .bind(ends_on)
+    .bind(reason)
+    .bind(source_uid)
+    .fetch_one(executor)
+    .await
+    .map_err(|e| anyhow::anyhow!("Failed to save branch closure: {e}"))
+}
+
+/// Adds or updates the closures imported from an iCalendar file. Either every closure is
+/// saved or none are.
+///
+/// # Errors
+/// Returns an error if database query fails.
+pub async fn import_branch_closures(
+    pool: &PgPool,
+    branch_id: &str,
+    closures: &[ImportedClosure],
+) -> Result<()> {
+    let mut tx = pool.begin().await?;
+
+    for closure in closures {
+        upsert_branch_closure(
+            &mut *tx,
+            branch_id,
+            closure.starts_on,
+            closure.ends_on,
+            &closure.reason,
+            Some(&closure.uid),
+        )
+        .await?;
+    }
+
+    tx.commit().await?;
+    Ok(())
+}
+
+/// Deletes a closure of a branch. Returns `false` if it does not exist.
+///
+/// # Errors
+/// Returns an error if database query fails.
+pub async fn delete_branch_closure(
+    pool: &PgPool,
+    branch_id: &str,
+    closure_id: &str,
+) -> Result<bool> {
+    let result = sqlx::query(
+        r"
+        DELETE FROM branch_closures
+        WHERE id = $1 AND branch_id = $2
+        ",
+    )
+    .bind(closure_id)
+    .bind(branch_id)
+    .execute(pool)
+    .await?;
+
+    Ok(result.rows_affected() > 0)
+}
+
+/// Retrieves the IDs of all companies that have not been deleted.
+///
+/// # Errors
+/// Returns an error if database query fails.
+pub async fn get_active_company_ids(pool: &PgPool) -> Result<Vec<String>> {
+    let ids = sqlx::query_scalar::<_, String>(
+        r"
+        SELECT id
+        FROM companies
+        WHERE deleted_at IS NULL
+        ",
+    )
+    .fetch_all(pool)
+    .await?;
+
+    Ok(ids)
+}
+
+/// Retrieves a company's missed-log alert settings, falling back to the defaults when none are stored.
+///
+/// # Errors
+/// Returns an error if database query fails.
+pub async fn get_missed_log_alert_settings(
+    pool: &PgPool,
+    company_id: &str,
+) -> Result<MissedLogAlertSettings> {
+    let settings = sqlx::query_as::<_, MissedLogAlertSettings>(
+        r"
+        SELECT company_id, enabled, escalation_hours, digest, updated_at
+        FROM missed_log_alert_settings
+        WHERE company_id = $1
+        ",
+    )
+    .bind(company_id)
+    .fetch_optional(pool)
+    .await?;
+
+    Ok(settings.unwrap_or_else(|| MissedLogAlertSettings::defaults(company_id)))
+}
+
+/// Creates or updates a company's missed-log alert settings.
+///
+/// # Errors
+/// Returns an error if database query fails.
+pub async fn upsert_missed_log_alert_settings(
+    pool: &PgPool,
+    company_id: &str,
+    enabled: bool,
+    escalation_hours: i32,
+    digest: bool,
+) -> Result<MissedLogAlertSettings> {
+    sqlx::query_as(
+        r"
+        INSERT INTO missed_log_alert_settings (company_id, enabled, escalation_hours, digest, updated_at)
+        VALUES ($1, $2, $3, $4, NOW())
+        ON CONFLICT (company_id) DO UPDATE
+        SET enabled = EXCLUDED.enabled,
+            escalation_hours = EXCLUDED.escalation_hours,
+            digest = EXCLUDED.digest,
+            updated_at = NOW()
+        RETURNING company_id, enabled, escalation_hours, digest, updated_at
+        ",
+    )
+    .bind(company_id)
+    .bind(enabled)
+    .bind(escalation_hours)
+    .bind(digest)
+    .fetch_one(pool)
+    .await
+    .map_err(|e| anyhow::anyhow!("Failed to save missed log alert settings: {e}"))
+}
+
+/// Records that a missed period has been alerted on.
+/// Returns `false` if the period was already recorded.
+///
+/// # Errors
+/// Returns an error if database query fails.
+pub async fn record_missed_log_alert(
+    pool: &PgPool,
+    company_id: &str,
+    branch_id: Option<&str>,
+    template_id: &str,
+    template_name: &str,
+    period: &str,
+) -> Result<bool> {
+    let result = sqlx::query(
+        r"
+        INSERT INTO missed_log_alerts (id, company_id, branch_id, template_id, template_name, period)
+        VALUES ($1, $2, $3, $4, $5, $6)
+        ON CONFLICT (company_id, template_id, period) DO NOTHING
+        ",
+    )
+    .bind(Uuid::new_v4().to_string())
+    .bind(company_id)
+    .bind(branch_id)
+    .bind(template_id)
+    .bind(template_name)
+    .bind(period)
+    .execute(pool)
+    .await?;
+
+    Ok(result.rows_affected() == 1)
+}
+
+/// Sets the template id on alerts recorded before templates had ids, matching them by
+/// the template's name. `templates` pairs each template name with its id.
+///
+/// # Errors
+/// Returns an error if database update fails.
+pub async fn backfill_missed_log_alert_template_ids(
+    pool: &PgPool,
+    company_id: &str,
+    templates: &[(String, String)],
+) -> Result<()> {
+    let (names, ids): (Vec<String>, Vec<String>) = templates.iter().cloned().unzip();
+
+    sqlx::query(
+        r"
+        UPDATE missed_log_alerts AS a
+        SET template_id = t.template_id
+        FROM UNNEST($2::text[], $3::text[]) AS t(template_name, template_id)
+        WHERE a.company_id = $1 AND a.template_id IS NULL AND a.template_name = t.template_name
+        ",
+    )
+    .bind(company_id)
+    .bind(names)
+    .bind(ids)
+    .execute(pool)
+    .await?;
+
+    Ok(())
+}
+
+/// Retrieves a company's alerts that are neither escalated nor resolved.
+///
+/// # Errors
+/// Returns an error if database query fails.
+pub async fn get_open_missed_log_alerts(
+    pool: &PgPool,
+    company_id: &str,
+) -> Result<Vec<MissedLogAlert>> {
+    let alerts = sqlx::query_as::<_, MissedLogAlert>(
+        r"
+        SELECT id, company_id, branch_id, template_id, template_name, period, alerted_at, escalated_at, resolved_at
+        FROM missed_log_alerts
+        WHERE company_id = $1 AND escalated_at IS NULL AND resolved_at IS NULL
+        ORDER BY alerted_at
+        ",
+    )
+    .bind(company_id)
+    .fetch_all(pool)
+    .await?;
+
+    Ok(alerts)
+}
+
+/// Marks a missed-log alert as escalated to the company manager.
+///
+/// # Errors
+/// Returns an error if database update fails.
+pub async fn mark_missed_log_alert_escalated(pool: &PgPool, alert_id: &str) -> Result<()> {
+    sqlx::query("UPDATE missed_log_alerts SET escalated_at = NOW() WHERE id = $1")
+        .bind(alert_id)
+        .execute(pool)
+        .await?;
+
+    Ok(())
+}
+
+/// Marks a missed-log alert as resolved once the period has been completed.
+///
+/// # Errors
+/// Returns an error if database update fails.
+pub async fn mark_missed_log_alert_resolved(pool: &PgPool, alert_id: &str) -> Result<()> {
+    sqlx::query("UPDATE missed_log_alerts SET resolved_at = NOW() WHERE id = $1")
+        .bind(alert_id)
+        .execute(pool)
+        .await?;
+
+    Ok(())
+}
+
+/// Retrieves a user's compliance digest subscription, if they have opted in.
+///
+/// # Errors
+/// Returns an error if database query fails.
+pub async fn get_digest_subscription(
+    pool: &PgPool,
+    user_id: &str,
+) -> Result<Option<DigestSubscription>> {
+    let subscription = sqlx::query_as::<_, DigestSubscription>(
+        r"
+        SELECT user_id, frequency, last_sent_at, created_at, updated_at
+        FROM compliance_digest_subscriptions
+        WHERE user_id = $1
+        ",
+    )
+    .bind(user_id)
+    .fetch_optional(pool)
+    .await?;
+
+    Ok(subscription)
+}
+
+/// Opts a user in to the compliance digest, or changes the frequency of an existing subscription.
+///
+/// # Errors
+/// Returns an error if database query fails.
+pub async fn upsert_digest_subscription(
+    pool: &PgPool,
+    user_id: &str,
+    frequency: DigestFrequency,
+) -> Result<DigestSubscription> {
+    sqlx::query_as(
+        r"
+        INSERT INTO compliance_digest_subscriptions (user_id, frequency)
+        VALUES ($1, $2)
+        ON CONFLICT (user_id) DO UPDATE
+        SET frequency = EXCLUDED.frequency,
+            updated_at = NOW()
+        RETURNING user_id, frequency, last_sent_at, created_at, updated_at
+        ",
+    )
+    .bind(user_id)
+    .bind(frequency)
+    .fetch_one(pool)
+    .await
+    .map_err(|e| anyhow::anyhow!("Failed to save digest subscription: {e}"))
+}
+
+/// Opts a user out of the compliance digest.
+///
+/// # Errors
+/// Returns an error if database query fails.
+pub async fn delete_digest_subscription(pool: &PgPool, user_id: &str) -> Result<()> {
+    sqlx::query("DELETE FROM compliance_digest_subscriptions WHERE user_id = $1")
+        .bind(user_id)
+        .execute(pool)
+        .await?;
+
+    Ok(())
+}
+
+/// Retrieves every digest subscriber belonging to an active company.
+///
+/// # Errors
+/// Returns an error if database query fails.
+pub async fn get_digest_subscribers(pool: &PgPool) -> Result<Vec<DigestSubscriber>> {
+    let subscribers = sqlx::query_as::<_, DigestSubscriber>(
+        r"
+        SELECT s.user_id, u.email, u.role, u.company_id, u.branch_id, s.frequency, s.last_sent_at,
+               c.timezone AS company_timezone, b.timezone AS branch_timezone
+        FROM compliance_digest_subscriptions s
+        JOIN users u ON u.id = s.user_id
+        JOIN companies c ON c.id = u.company_id
+        LEFT JOIN branches b ON b.id = u.branch_id
+        WHERE u.deleted_at IS NULL AND c.deleted_at IS NULL
+        ORDER BY u.company_id
+        ",
+    )
+    .fetch_all(pool)
+    .await?;
+
+    Ok(subscribers)
+}
+
+/// Records that a digest was sent to a subscriber.
+///
+/// # Errors
+/// Returns an error if database update fails.
+pub async fn mark_digest_sent(
+    pool: &PgPool,
+    user_id: &str,
+    sent_at: chrono::DateTime<chrono::Utc>,
+) -> Result<()> {
+    sqlx::query("UPDATE compliance_digest_subscriptions SET last_sent_at = $2 WHERE user_id = $1")
+        .bind(user_id)
+        .bind(sent_at)
+        .execute(pool)
+        .await?;
+
+    Ok(())
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -2924,6 +3757,35 @@ mod tests {
         assert_eq!(parsed.0.timestamp_millis(), created_at.timestamp_millis());
     }
 
+    #[test]
+    fn test_company_timezones_utc_range_spans_every_timezone() {
+        let timezones = CompanyTimezones {
+            company: chrono_tz::Europe::London,
+            branches: [
+                ("tokyo".to_string(), chrono_tz::Asia::Tokyo),
+                ("ny".to_string(), chrono_tz::America::New_York),
+            ]
+            .into_iter()
+            .collect(),
+        };
+        let day = chrono::NaiveDate::from_ymd_opt(2026, 7, 1).unwrap();
+
+        let (start, end) = timezones.utc_range(day, day);
+        // Tokyo (UTC+9) starts the day first, New York (UTC-4 in summer) ends it last
+        assert_eq!(start.to_rfc3339(), "2026-06-30T15:00:00+00:00");
+        assert_eq!(end.to_rfc3339(), "2026-07-02T04:00:00+00:00");
+
+        let at = chrono::DateTime::parse_from_rfc3339("2026-07-01T23:30:00Z")
+            .unwrap()
+            .with_timezone(&chrono::Utc);
+        assert_eq!(timezones.local_date(None, at), day.succ_opt().unwrap());
+        assert_eq!(timezones.local_date(Some("ny"), at), day);
+        assert_eq!(
+            timezones.local_date(Some("unknown"), at),
+            day.succ_opt().unwrap()
+        );
+    }
+
     #[test]
     fn test_security_logs_cursor_invalid_value() {
         let result = parse_security_logs_cursor("not-a-valid-cursor");
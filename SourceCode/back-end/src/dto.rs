@@ -78,6 +78,9 @@ pub struct BranchDto {
     pub name: String,
     pub address: String,
     pub created_at: chrono::DateTime<chrono::Utc>,
+    /// IANA timezone of the branch. `None` means the company timezone applies.
+    #[serde(default)]
+    pub timezone: Option<String>,
     #[serde(default)]
     pub has_pending_deletion: bool,
     #[serde(default)]
@@ -92,6 +95,7 @@ impl From<db::Branch> for BranchDto {
             name: branch.name,
             address: branch.address,
             created_at: branch.created_at,
+            timezone: branch.timezone,
             has_pending_deletion: false,
             deletion_requested_at: None,
         }
@@ -106,6 +110,7 @@ impl From<db::BranchWithDeletionStatus> for BranchDto {
             name: branch_with_status.branch.name,
             address: branch_with_status.branch.address,
             created_at: branch_with_status.branch.created_at,
+            timezone: branch_with_status.branch.timezone,
             has_pending_deletion: branch_with_status.has_pending_deletion,
             deletion_requested_at: branch_with_status.deletion_requested_at,
         }
@@ -118,6 +123,10 @@ pub struct CreateBranchRequest {
     pub name: String,
     #[schema(example = "123 Regent St, London")]
     pub address: String,
+    /// IANA timezone of the branch. Omit to use the company timezone.
+    #[schema(example = "Europe/Dublin")]
+    #[serde(default)]
+    pub timezone: Option<String>,
 }
 
 #[derive(Debug, Deserialize, ToSchema)]
@@ -128,6 +137,11 @@ pub struct UpdateBranchRequest {
     pub name: String,
     #[schema(example = "123 Regent St, London")]
     pub address: String,
+    /// IANA timezone of the branch. Omit to keep the current one; an empty string reverts
+    /// to the company timezone.
+    #[schema(example = "Europe/Dublin")]
+    #[serde(default)]
+    pub timezone: Option<String>,
 }
 
 #[derive(Debug, Deserialize, ToSchema)]
@@ -157,6 +171,96 @@ pub struct ListBranchesResponse {
     pub branches: Vec<BranchDto>,
 }
 
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct OpeningHoursDto {
+    /// 0 = Sunday through 6 = Saturday.
+    #[schema(example = 1)]
+    pub day_of_week: u8,
+    #[schema(example = "09:00")]
+    pub opens_at: String,
+    #[schema(example = "17:30")]
+    pub closes_at: String,
+}
+
+impl From<db::BranchOpeningHours> for OpeningHoursDto {
+    fn from(hours: db::BranchOpeningHours) -> Self {
+        Self {
+            day_of_week: u8::try_from(hours.day_of_week).unwrap_or_default(),
+            opens_at: hours.opens_at.format("%H:%M").to_string(),
+            closes_at: hours.closes_at.format("%H:%M").to_string(),
+        }
+    }
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct BranchClosureDto {
+    pub id: String,
+    #[schema(example = "2026-12-25")]
+    pub starts_on: chrono::NaiveDate,
+    /// Last closed day, inclusive.
+    #[schema(example = "2026-12-26")]
+    pub ends_on: chrono::NaiveDate,
+    #[schema(example = "Christmas")]
+    pub reason: String,
+    /// Whether the closure came from an imported iCalendar file.
+    pub imported: bool,
+    pub created_at: chrono::DateTime<chrono::Utc>,
+}
+
+impl From<db::BranchClosure> for BranchClosureDto {
+    fn from(closure: db::BranchClosure) -> Self {
+        Self {
+            id: closure.id,
+            starts_on: closure.starts_on,
+            ends_on: closure.ends_on,
+            reason: closure.reason,
+            imported: closure.source_uid.is_some(),
+            created_at: closure.created_at,
+        }
+    }
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct BranchCalendarResponse {
+    /// Weekly opening hours. Days without hours are closed; an empty list means the branch
+    /// is open every day.
+    pub opening_hours: Vec<OpeningHoursDto>,
+    pub closures: Vec<BranchClosureDto>,
+}
+
+impl From<db::BranchCalendar> for BranchCalendarResponse {
+    fn from(calendar: db::BranchCalendar) -> Self {
+        Self {
+            opening_hours: calendar.opening_hours.into_iter().map(Into::into).collect(),
+            closures: calendar.closures.into_iter().map(Into::into).collect(),
+        }
+    }
+}
+
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct UpdateOpeningHoursRequest {
+    pub opening_hours: Vec<OpeningHoursDto>,
+}
+
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct CreateBranchClosureRequest {
+    #[schema(example = "2026-12-25")]
+    pub starts_on: chrono::NaiveDate,
+    /// Last closed day, inclusive. Omit for a single day.
+    #[schema(example = "2026-12-26")]
+    #[serde(default)]
+    pub ends_on: Option<chrono::NaiveDate>,
+    #[schema(example = "Christmas")]
+    pub reason: String,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct ImportBranchClosuresResponse {
+    pub imported: usize,
+    /// Timed, recurring or invalid events that were not imported.
+    pub skipped: usize,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct CompanyDto {
     pub id: String,
@@ -342,10 +446,54 @@ pub struct AddTemplateRequest {
     pub template_layout: logs_db::TemplateLayout,
     #[schema(example = "{\"frequency\": \"daily\", \"time\": \"08:00\"}")]
     pub schedule: logs_db::Schedule,
+    /// `scheduled` (default), `repeatable` or `unscheduled`. Cannot be changed later.
+    #[schema(example = "scheduled")]
+    #[serde(default)]
+    pub entry_mode: logs_db::EntryMode,
     #[schema(example = "branch-uuid-here")]
     pub branch_id: Option<String>,
 }
 
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct SchedulePreviewRequest {
+    #[schema(example = "{\"frequency\": \"quarterly\", \"day_of_month\": 15}")]
+    pub schedule: logs_db::Schedule,
+    /// Creation date of the template being edited. Periods due before it are left out.
+    #[serde(default)]
+    pub created_at: Option<chrono::DateTime<chrono::Utc>>,
+    /// Branch whose timezone the times are shown in. The company timezone is used when omitted.
+    #[serde(default)]
+    pub branch_id: Option<String>,
+    /// Number of periods to return, from 1 to 50. Defaults to 10.
+    #[schema(example = 10)]
+    #[serde(default)]
+    pub count: Option<usize>,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct SchedulePreviewPeriod {
+    /// Period label as stored on entries and substituted for `{period}` in template names.
+    #[schema(example = "2/2026")]
+    pub period: String,
+    #[schema(example = "2026-04-15")]
+    pub due_date: chrono::NaiveDate,
+    #[schema(example = "2026-04-15T08:00:00+01:00")]
+    pub available_from: Option<String>,
+    #[schema(example = "2026-04-15T17:59:00+01:00")]
+    pub due_at: Option<String>,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct SchedulePreviewResponse {
+    /// IANA timezone the times are given in.
+    #[schema(example = "Europe/London")]
+    pub timezone: String,
+    pub periods: Vec<SchedulePreviewPeriod>,
+    /// Settings that are invalid or name a day some periods do not have. Empty when the
+    /// schedule behaves as written.
+    pub problems: Vec<String>,
+}
+
 #[derive(Debug, Serialize, ToSchema)]
 pub struct UpdateTemplateResponse {
     pub message: String,
@@ -353,8 +501,11 @@ pub struct UpdateTemplateResponse {
 
 #[derive(Debug, Deserialize, Serialize, ToSchema)]
 pub struct UpdateTemplateRequest {
+    #[schema(example = "template-uuid-here")]
+    pub template_id: Option<String>,
+    /// Accepted when `template_id` is not given.
     #[schema(example = "Kitchen Daily Log")]
-    pub template_name: String,
+    pub template_name: Option<String>,
     #[schema(example = "[\"field1\", \"field2\"]")]
     pub template_layout: Option<logs_db::TemplateLayout>,
     #[schema(example = "{\"frequency\": \"daily\", \"time\": \"08:00\"}")]
@@ -383,19 +534,54 @@ pub struct RestoreTemplateVersionRequest {
     pub version: u16,
 }
 
-#[derive(Debug, Deserialize, Serialize, ToSchema)]
+#[derive(Debug, Deserialize, IntoParams)]
+pub struct TemplateVersionDiffQuery {
+    pub template_id: Option<String>,
+    /// Accepted when `template_id` is not given.
+    pub template_name: Option<String>,
+    /// Version to compare from.
+    pub from: u16,
+    /// Version to compare to. Defaults to the current template.
+    pub to: Option<u16>,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct TemplateVersionDiffResponse {
+    pub template_id: String,
+    pub from_version: u16,
+    pub to_version: u16,
+    pub from_version_name: Option<String>,
+    pub to_version_name: Option<String>,
+    pub layout_changes: logs_db::LayoutDiff,
+    pub schedule_changes: Vec<logs_db::PropertyChange>,
+}
+
+#[derive(Debug, Deserialize, Serialize, ToSchema, IntoParams)]
 pub struct GetTemplateRequest {
+    #[schema(example = "template-uuid-here")]
+    pub template_id: Option<String>,
+    /// Accepted when `template_id` is not given.
     #[schema(example = "Kitchen Daily Log")]
-    pub template_name: String,
+    pub template_name: Option<String>,
 }
 
 #[derive(Debug, Serialize, ToSchema)]
 pub struct TemplateInfo {
+    pub template_id: String,
     pub template_name: String,
     pub schedule: logs_db::Schedule,
+    pub entry_mode: logs_db::EntryMode,
     pub created_by: String,
     pub created_at: String,
     pub updated_at: String,
+    pub archived_at: Option<String>,
+}
+
+#[derive(Debug, Deserialize, IntoParams, ToSchema)]
+pub struct GetAllTemplatesQuery {
+    /// List archived templates instead of active ones.
+    #[serde(default)]
+    pub archived: bool,
 }
 
 #[derive(Debug, Serialize, ToSchema)]
@@ -515,6 +701,30 @@ pub struct UpdateProfileRequest {
     pub last_name: String,
 }
 
+#[derive(Debug, Serialize, ToSchema)]
+pub struct DigestPreferenceResponse {
+    /// `null` when the user has not opted in to compliance digests.
+    pub frequency: Option<crate::db::DigestFrequency>,
+    pub last_sent_at: Option<String>,
+}
+
+impl From<Option<crate::db::DigestSubscription>> for DigestPreferenceResponse {
+    fn from(subscription: Option<crate::db::DigestSubscription>) -> Self {
+        Self {
+            frequency: subscription.as_ref().map(|s| s.frequency),
+            last_sent_at: subscription
+                .and_then(|s| s.last_sent_at)
+                .map(|ts| ts.to_rfc3339()),
+        }
+    }
+}
+
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct UpdateDigestPreferenceRequest {
+    /// `daily`, `weekly`, or `null` to opt out.
+    pub frequency: Option<crate::db::DigestFrequency>,
+}
+
 #[derive(Debug, Deserialize, ToSchema)]
 pub struct RequestPasswordResetRequest {
     #[schema(example = "user@example.com")]
@@ -529,15 +739,20 @@ pub struct AddTokenResponse {
 #[derive(Debug, Serialize, ToSchema)]
 pub struct AddTemplateResponse {
     pub message: String,
+    pub template_id: String,
 }
 
 #[derive(Debug, Serialize, ToSchema)]
 pub struct GetTemplateResponse {
+    pub template_id: String,
     pub template_name: String,
     pub template_layout: logs_db::TemplateLayout,
     pub version: u16,
     pub version_name: Option<String>,
     pub branch_id: Option<String>,
+    pub archived_at: Option<String>,
+    pub linked_from: Option<String>,
+    pub entry_mode: logs_db::EntryMode,
 }
 
 #[derive(Debug, Serialize, ToSchema)]
@@ -568,8 +783,11 @@ pub struct OAuthCallbackRequest {
 
 #[derive(Debug, Deserialize, ToSchema)]
 pub struct CreateLogEntryRequest {
+    #[schema(example = "template-uuid-here")]
+    pub template_id: Option<String>,
+    /// Accepted when `template_id` is not given.
     #[schema(example = "Kitchen Daily Log")]
-    pub template_name: String,
+    pub template_name: Option<String>,
     #[schema(example = "17/03/2026")]
     pub period: Option<String>,
 }
@@ -577,6 +795,7 @@ pub struct CreateLogEntryRequest {
 #[derive(Debug, Serialize, ToSchema)]
 pub struct LogEntryResponse {
     pub id: String,
+    pub template_id: String,
     pub template_name: String,
     pub template_layout: logs_db::TemplateLayout,
     pub entry_data: serde_json::Value,
@@ -586,6 +805,95 @@ pub struct LogEntryResponse {
     pub updated_at: String,
     pub submitted_at: Option<String>,
     pub period: String,
+    pub template_version: Option<u16>,
+    /// Whether the entry was submitted with out-of-range readings.
+    pub flagged: bool,
+    pub corrective_action: Option<String>,
+    /// Latest manager review, if any.
+    pub review: Option<logs_db::EntryReview>,
+}
+
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct ReviewLogEntryRequest {
+    pub decision: logs_db::ReviewDecision,
+    /// Required when rejecting.
+    #[serde(default)]
+    pub comment: Option<String>,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct ReviewLogEntryResponse {
+    pub message: String,
+    pub status: String,
+    pub review: Option<logs_db::EntryReview>,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct EntryTransitionResponse {
+    pub action: logs_db::EntryAction,
+    pub from_status: Option<String>,
+    pub to_status: String,
+    pub actor_id: String,
+    pub comment: Option<String>,
+    pub at: String,
+}
+
+impl From<logs_db::EntryTransition> for EntryTransitionResponse {
+    fn from(transition: logs_db::EntryTransition) -> Self {
+        Self {
+            action: transition.action,
+            from_status: transition.from_status.map(|s| s.as_str().to_string()),
+            to_status: transition.to_status.as_str().to_string(),
+            actor_id: transition.actor_id,
+            comment: transition.comment,
+            at: transition.at.to_rfc3339(),
+        }
+    }
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct EntryHistoryResponse {
+    pub transitions: Vec<EntryTransitionResponse>,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct AttachmentResponse {
+    pub id: String,
+    pub entry_id: String,
+    pub filename: String,
+    pub content_type: String,
+    pub size: i64,
+    pub uploaded_by: String,
+    pub uploaded_at: String,
+}
+
+impl From<crate::attachments_db::AttachmentDocument> for AttachmentResponse {
+    fn from(attachment: crate::attachments_db::AttachmentDocument) -> Self {
+        Self {
+            id: attachment.attachment_id,
+            entry_id: attachment.entry_id,
+            filename: attachment.filename,
+            content_type: attachment.content_type,
+            size: attachment.size,
+            uploaded_by: attachment.uploaded_by,
+            uploaded_at: attachment.uploaded_at.to_rfc3339(),
+        }
+    }
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct ListAttachmentsResponse {
+    pub attachments: Vec<AttachmentResponse>,
+    /// Attachment storage used by the company, in bytes.
+    pub used_bytes: i64,
+    /// Attachment storage allowed for the company, in bytes.
+    pub quota_bytes: i64,
+}
+
+#[derive(Debug, Deserialize, IntoParams)]
+pub struct UploadAttachmentQuery {
+    /// Original name of the uploaded file.
+    pub filename: Option<String>,
 }
 
 #[derive(Debug, Deserialize, ToSchema)]
@@ -594,10 +902,15 @@ pub struct UpdateLogEntryRequest {
 }
 
 #[derive(Debug, Deserialize, ToSchema)]
-pub struct SubmitLogEntryRequest {}
+pub struct SubmitLogEntryRequest {
+    /// Required when any temperature reading is outside its field's range.
+    #[serde(default)]
+    pub corrective_action: Option<String>,
+}
 
 #[derive(Debug, Serialize, ToSchema)]
 pub struct DueFormInfo {
+    pub template_id: String,
     pub template_name: String,
     pub template_layout: logs_db::TemplateLayout,
     pub last_submitted: Option<String>,
@@ -608,14 +921,26 @@ pub struct DueFormInfo {
     pub due_at: Option<String>,
 }
 
+/// A repeatable or unscheduled template. Entries can be started for it at any time.
+#[derive(Debug, Serialize, ToSchema)]
+pub struct StartableFormInfo {
+    pub template_id: String,
+    pub template_name: String,
+    pub entry_mode: logs_db::EntryMode,
+}
+
 #[derive(Debug, Serialize, ToSchema)]
 pub struct DueFormsResponse {
     pub forms: Vec<DueFormInfo>,
+    /// Repeatable and unscheduled templates, which are never due.
+    pub startable: Vec<StartableFormInfo>,
 }
 
 #[derive(Debug, Serialize, ToSchema)]
 pub struct ListLogEntriesResponse {
     pub entries: Vec<LogEntryResponse>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub next_cursor: Option<String>,
 }
 
 #[derive(Debug, Serialize, ToSchema)]
@@ -627,6 +952,63 @@ pub struct CreateLogEntryResponse {
 #[derive(Debug, Serialize, ToSchema)]
 pub struct SubmitLogEntryResponse {
     pub message: String,
+    /// Corrective-action issue raised for out-of-range readings.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub issue_id: Option<String>,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct CorrectiveActionIssueResponse {
+    pub id: String,
+    pub branch_id: Option<String>,
+    pub entry_id: String,
+    pub template_id: String,
+    pub template_name: String,
+    pub period: String,
+    pub readings: Vec<logs_db::OutOfRangeReading>,
+    pub corrective_action: Option<String>,
+    pub status: logs_db::IssueStatus,
+    pub raised_by: String,
+    pub raised_at: String,
+    pub acknowledged_by: Option<String>,
+    pub acknowledged_at: Option<String>,
+    pub closed_by: Option<String>,
+    pub closed_at: Option<String>,
+    pub resolution_note: Option<String>,
+}
+
+impl From<logs_db::CorrectiveActionIssue> for CorrectiveActionIssueResponse {
+    fn from(issue: logs_db::CorrectiveActionIssue) -> Self {
+        Self {
+            id: issue.issue_id,
+            branch_id: issue.branch_id,
+            entry_id: issue.entry_id,
+            template_id: issue.template_id,
+            template_name: issue.template_name,
+            period: issue.period,
+            readings: issue.readings,
+            corrective_action: issue.corrective_action,
+            status: issue.status,
+            raised_by: issue.raised_by,
+            raised_at: issue.raised_at.to_rfc3339(),
+            acknowledged_by: issue.acknowledged_by,
+            acknowledged_at: issue.acknowledged_at.map(|ts| ts.to_rfc3339()),
+            closed_by: issue.closed_by,
+            closed_at: issue.closed_at.map(|ts| ts.to_rfc3339()),
+            resolution_note: issue.resolution_note,
+        }
+    }
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct ListCorrectiveActionIssuesResponse {
+    pub issues: Vec<CorrectiveActionIssueResponse>,
+}
+
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct CloseCorrectiveActionIssueRequest {
+    #[serde(default)]
+    pub resolution_note: Option<String>,
 }
 
 #[derive(Debug, Clone, Deserialize, Serialize, ToSchema)]
@@ -676,9 +1058,31 @@ pub struct DeleteReportRunResponse {
     pub message: String,
 }
 
+#[derive(Debug, Deserialize, IntoParams, ToSchema)]
+pub struct ExportLogEntriesQuery {
+    #[schema(example = "template-uuid-here")]
+    pub template_id: Option<String>,
+    /// Accepted when `template_id` is not given.
+    #[schema(example = "Fridge Temperatures")]
+    pub template_name: Option<String>,
+    /// `csv` (default) or `xlsx`.
+    #[schema(example = "csv")]
+    pub format: Option<String>,
+    /// Inclusive lower bound on the entry creation date (YYYY-MM-DD) in its branch's timezone.
+    #[schema(example = "2026-03-01")]
+    pub date_from: Option<String>,
+    /// Inclusive upper bound on the entry creation date (YYYY-MM-DD) in its branch's timezone.
+    #[schema(example = "2026-03-31")]
+    pub date_to: Option<String>,
+    /// Comma-separated branch IDs. Only applies to company managers and HQ staff.
+    pub branch_ids: Option<String>,
+}
+
 #[derive(Deserialize, ToSchema)]
 pub struct RenameTemplateRequest {
-    pub old_template_name: String,
+    pub template_id: Option<String>,
+    /// Accepted when `template_id` is not given.
+    pub old_template_name: Option<String>,
     pub new_template_name: String,
 }
 
@@ -689,7 +1093,9 @@ pub struct RenameTemplateResponse {
 
 #[derive(Deserialize, ToSchema, IntoParams)]
 pub struct DeleteTemplateRequest {
-    pub template_name: String,
+    pub template_id: Option<String>,
+    /// Accepted when `template_id` is not given.
+    pub template_name: Option<String>,
 }
 
 #[derive(Serialize, ToSchema)]
@@ -697,6 +1103,232 @@ pub struct DeleteTemplateResponse {
     pub message: String,
 }
 
+#[derive(Debug, Serialize, ToSchema)]
+pub struct LibraryTemplateResponse {
+    pub library_template_id: String,
+    pub template_name: String,
+    pub category: String,
+    pub description: String,
+    pub template_layout: logs_db::TemplateLayout,
+    pub schedule: logs_db::Schedule,
+    pub updated_at: String,
+}
+
+impl From<logs_db::LibraryTemplateDocument> for LibraryTemplateResponse {
+    fn from(template: logs_db::LibraryTemplateDocument) -> Self {
+        Self {
+            library_template_id: template.library_template_id,
+            template_name: template.template_name,
+            category: template.category,
+            description: template.description,
+            template_layout: template.template_layout,
+            schedule: template.schedule,
+            updated_at: template.updated_at.to_rfc3339(),
+        }
+    }
+}
+
+#[derive(Debug, Deserialize, IntoParams, ToSchema)]
+pub struct ListLibraryTemplatesQuery {
+    #[schema(example = "Temperature Control")]
+    pub category: Option<String>,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct ListLibraryTemplatesResponse {
+    pub templates: Vec<LibraryTemplateResponse>,
+    /// Every category in the library, regardless of the `category` filter.
+    pub categories: Vec<String>,
+}
+
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct LibraryTemplateRequest {
+    pub template_name: String,
+    #[schema(example = "Temperature Control")]
+    pub category: String,
+    #[serde(default)]
+    pub description: String,
+    pub template_layout: logs_db::TemplateLayout,
+    pub schedule: logs_db::Schedule,
+}
+
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct CloneLibraryTemplateRequest {
+    /// Defaults to the library template's name.
+    pub template_name: Option<String>,
+    /// Clones a company-wide template when omitted.
+    pub branch_id: Option<String>,
+}
+
+/// A portable, signed set of templates that can be imported into another company.
+#[derive(Debug, Deserialize, Serialize, ToSchema)]
+pub struct TemplateBundle {
+    #[schema(example = "logsmart-template-bundle")]
+    pub format: String,
+    #[schema(example = 1)]
+    pub format_version: u32,
+    #[schema(value_type = TemplateBundlePayload)]
+    pub payload: serde_json::Value,
+    /// HMAC-SHA256 of the canonical JSON payload, base64url encoded.
+    pub signature: String,
+}
+
+#[derive(Debug, Deserialize, Serialize, ToSchema)]
+pub struct TemplateBundlePayload {
+    pub exported_at: String,
+    pub templates: Vec<BundledTemplate>,
+}
+
+#[derive(Debug, Deserialize, Serialize, ToSchema)]
+pub struct BundledTemplate {
+    pub template_name: String,
+    pub template_layout: logs_db::TemplateLayout,
+    pub schedule: logs_db::Schedule,
+    #[serde(default)]
+    pub entry_mode: logs_db::EntryMode,
+    pub version: u16,
+    pub version_name: Option<String>,
+    /// Earlier versions, oldest first. Empty unless history was exported.
+    #[serde(default)]
+    pub versions: Vec<BundledTemplateVersion>,
+}
+
+#[derive(Debug, Deserialize, Serialize, ToSchema)]
+pub struct BundledTemplateVersion {
+    pub version: u16,
+    pub version_name: Option<String>,
+    pub template_layout: logs_db::TemplateLayout,
+    pub schedule: logs_db::Schedule,
+    pub created_at: String,
+}
+
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct ExportTemplateBundleRequest {
+    pub template_ids: Vec<String>,
+    #[serde(default)]
+    pub include_versions: bool,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize, Serialize, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum BundleNameConflict {
+    /// Import under a free name such as "Fridge Checks (2)".
+    #[default]
+    Rename,
+    /// Leave the existing template alone and skip the bundled one.
+    Skip,
+}
+
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct ImportTemplateBundleRequest {
+    pub bundle: TemplateBundle,
+    /// Imports company-wide templates when omitted. Branch managers always import into their branch.
+    pub branch_id: Option<String>,
+    #[serde(default)]
+    pub on_name_conflict: BundleNameConflict,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct ImportedTemplateInfo {
+    pub source_name: String,
+    pub template_id: String,
+    pub template_name: String,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct ImportTemplateBundleResponse {
+    pub imported: Vec<ImportedTemplateInfo>,
+    /// Names of bundled templates skipped because the name was taken.
+    pub skipped: Vec<String>,
+}
+
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct DistributeTemplateRequest {
+    pub template_id: Option<String>,
+    /// Accepted when `template_id` is not given.
+    pub template_name: Option<String>,
+    pub branch_ids: Vec<String>,
+    /// Keep the copies linked so later changes to this template can be pushed to them.
+    #[serde(default)]
+    pub linked: bool,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct DistributedTemplateInfo {
+    pub branch_id: String,
+    pub template_id: String,
+    pub template_name: String,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct DistributeTemplateResponse {
+    pub copies: Vec<DistributedTemplateInfo>,
+    /// Branches that own the template or already have a linked copy of it.
+    pub skipped_branch_ids: Vec<String>,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct LinkedCopyPreview {
+    pub template_id: String,
+    pub template_name: String,
+    pub branch_id: Option<String>,
+    pub up_to_date: bool,
+    /// Changes the copy's layout would receive.
+    pub layout_changes: logs_db::LayoutDiff,
+    pub schedule_changed: bool,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct PropagationPreviewResponse {
+    pub template_id: String,
+    pub version: u16,
+    pub copies: Vec<LinkedCopyPreview>,
+}
+
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct PropagateTemplateRequest {
+    pub template_id: Option<String>,
+    /// Accepted when `template_id` is not given.
+    pub template_name: Option<String>,
+    /// Only update these linked copies. All linked copies are updated when omitted.
+    pub copy_ids: Option<Vec<String>>,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct PropagationFailure {
+    pub template_id: String,
+    pub error: String,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct PropagateTemplateResponse {
+    /// Copies that received a new version.
+    pub updated: Vec<String>,
+    /// Copies that already matched the template.
+    pub up_to_date: Vec<String>,
+    /// Copies that could not be updated, with the reason. The other copies are still updated.
+    pub failed: Vec<PropagationFailure>,
+}
+
+#[derive(Deserialize, ToSchema)]
+pub struct UnlinkTemplateRequest {
+    pub template_id: Option<String>,
+    /// Accepted when `template_id` is not given.
+    pub template_name: Option<String>,
+}
+
+#[derive(Deserialize, ToSchema)]
+pub struct UnarchiveTemplateRequest {
+    pub template_id: Option<String>,
+    /// Accepted when `template_id` is not given.
+    pub template_name: Option<String>,
+}
+
+#[derive(Serialize, ToSchema)]
+pub struct UnarchiveTemplateResponse {
+    pub message: String,
+}
+
 #[derive(Debug, Clone, Deserialize, Serialize, ToSchema)]
 pub struct LayoutGenerationRequest {
     pub user_prompt: String,
@@ -846,6 +1478,9 @@ pub struct CompanyResponse {
     pub data_exported_at: Option<chrono::DateTime<chrono::Utc>>,
     pub deleted_at: Option<chrono::DateTime<chrono::Utc>>,
     pub deletion_requested_at: Option<chrono::DateTime<chrono::Utc>>,
+    /// IANA timezone used for log periods and due times.
+    #[schema(example = "Europe/London")]
+    pub timezone: String,
 }
 
 impl From<db::Company> for CompanyResponse {
@@ -863,6 +1498,7 @@ impl From<db::Company> for CompanyResponse {
             data_exported_at: company.data_exported_at,
             deleted_at: company.deleted_at,
             deletion_requested_at: company.deletion_requested_at,
+            timezone: company.timezone,
         }
     }
 }
@@ -871,6 +1507,38 @@ impl From<db::Company> for CompanyResponse {
 pub struct UpdateCompanyRequest {
     pub name: String,
     pub address: String,
+    /// IANA timezone used for log periods and due times. Omit to keep the current one.
+    #[schema(example = "Europe/London")]
+    #[serde(default)]
+    pub timezone: Option<String>,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct MissedLogAlertSettingsResponse {
+    pub enabled: bool,
+    /// Hours before an outstanding alert is escalated to company managers. 0 disables escalation.
+    #[schema(example = 4)]
+    pub escalation_hours: i32,
+    /// Send one summary email per recipient instead of one email per missed log.
+    pub digest: bool,
+}
+
+impl From<crate::db::MissedLogAlertSettings> for MissedLogAlertSettingsResponse {
+    fn from(settings: crate::db::MissedLogAlertSettings) -> Self {
+        Self {
+            enabled: settings.enabled,
+            escalation_hours: settings.escalation_hours,
+            digest: settings.digest,
+        }
+    }
+}
+
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct UpdateMissedLogAlertSettingsRequest {
+    pub enabled: bool,
+    #[schema(example = 4)]
+    pub escalation_hours: i32,
+    pub digest: bool,
 }
 
 #[derive(Debug, Serialize, ToSchema)]
@@ -1,6 +1,7 @@
 use anyhow::{Result, anyhow};
 use lettre::{
-    Message, SmtpTransport, Transport, message::header::ContentType,
+    Message, SmtpTransport, Transport,
+    message::{MessageBuilder, MultiPart, header::ContentType},
     transport::smtp::authentication::Credentials,
 };
 
@@ -44,19 +45,14 @@ impl SmtpConfig {
     }
 }
 
-/// Sends a plain text email.
+/// Starts a message from the configured sender to `to_email`.
 ///
 /// # Errors
-/// Returns an error if SMTP is not configured, email parsing fails, or sending fails.
-///
-/// # Panics
-/// Panics if SMTP configuration cannot be loaded.
-async fn send_email(to_email: &str, subject: &str, body: &str) -> Result<()> {
-    let config = SmtpConfig::load().map_err(|e| anyhow!("SMTP configuration error: {e}"))?;
-
+/// Returns an error if the sender or recipient address cannot be parsed.
+fn message_builder(config: &SmtpConfig, to_email: &str, subject: &str) -> Result<MessageBuilder> {
     let sender = config.sender_address();
 
-    let email = Message::builder()
+    Ok(Message::builder()
         .sender(
             sender
                 .parse()
@@ -75,11 +71,14 @@ async fn send_email(to_email: &str, subject: &str, body: &str) -> Result<()> {
         .to(to_email
             .parse()
             .map_err(|e| anyhow!("Invalid email address: {e}"))?)
-        .subject(subject)
-        .header(ContentType::TEXT_PLAIN)
-        .body(body.to_string())
-        .map_err(|e| anyhow!("Failed to build email message: {e}"))?;
+        .subject(subject))
+}
 
+/// Delivers a built message over SMTP.
+///
+/// # Errors
+/// Returns an error if connecting to the SMTP server or sending fails.
+async fn deliver(config: SmtpConfig, email: Message) -> Result<()> {
     let creds = Credentials::new(config.username.clone(), config.password.clone());
 
     let (host, port) = if let Some((h, p)) = config.server.split_once(':') {
@@ -111,6 +110,38 @@ async fn send_email(to_email: &str, subject: &str, body: &str) -> Result<()> {
     Ok(())
 }
 
+/// Sends a plain text email.
+///
+/// # Errors
+/// Returns an error if SMTP is not configured, email parsing fails, or sending fails.
+async fn send_email(to_email: &str, subject: &str, body: &str) -> Result<()> {
+    let config = SmtpConfig::load().map_err(|e| anyhow!("SMTP configuration error: {e}"))?;
+
+    let email = message_builder(&config, to_email, subject)?
+        .header(ContentType::TEXT_PLAIN)
+        .body(body.to_string())
+        .map_err(|e| anyhow!("Failed to build email message: {e}"))?;
+
+    deliver(config, email).await
+}
+
+/// Sends an email with plain text and HTML alternatives.
+///
+/// # Errors
+/// Returns an error if SMTP is not configured, email parsing fails, or sending fails.
+async fn send_html_email(to_email: &str, subject: &str, text: &str, html: &str) -> Result<()> {
+    let config = SmtpConfig::load().map_err(|e| anyhow!("SMTP configuration error: {e}"))?;
+
+    let email = message_builder(&config, to_email, subject)?
+        .multipart(MultiPart::alternative_plain_html(
+            text.to_string(),
+            html.to_string(),
+        ))
+        .map_err(|e| anyhow!("Failed to build email message: {e}"))?;
+
+    deliver(config, email).await
+}
+
 /// Sends an invitation email to a new user.
 ///
 /// # Errors
@@ -384,3 +415,299 @@ pub async fn send_user_company_deleted_notification(
     tracing::info!("Company deletion notification sent to user {}", user_email);
     Ok(())
 }
+
+/// A missed log period listed in alert emails.
+#[derive(Debug, Clone)]
+pub struct MissedLogSummary {
+    pub template_name: String,
+    pub branch_name: Option<String>,
+    pub period: String,
+    /// Set when the period is being escalated after the branch manager was alerted.
+    pub escalated: bool,
+}
+
+impl MissedLogSummary {
+    fn describe(&self) -> String {
+        let location = self
+            .branch_name
+            .as_deref()
+            .map_or_else(String::new, |branch| format!(" at {branch}"));
+        let escalated = if self.escalated { " (escalated)" } else { "" };
+        format!(
+            "'{}'{location} for period {}{escalated}",
+            self.template_name, self.period
+        )
+    }
+}
+
+/// Sends an alert that a scheduled log was not completed by its due time.
+///
+/// # Errors
+/// Returns an error if the email fails to send.
+pub async fn send_missed_log_alert(
+    to_email: &str,
+    company_name: &str,
+    missed: &MissedLogSummary,
+    logs_link: &str,
+) -> Result<()> {
+    let subject = if missed.escalated {
+        format!(
+            "Escalation: missed log '{}' - LogSmart",
+            missed.template_name
+        )
+    } else {
+        format!("Missed log '{}' - LogSmart", missed.template_name)
+    };
+    let escalation_note = if missed.escalated {
+        "The branch manager was alerted earlier and the log is still outstanding.\n\n"
+    } else {
+        ""
+    };
+    let body = format!(
+        "Hello,\n\n\
+        The log {} for '{company_name}' was not completed by its due time.\n\n\
+        {escalation_note}\
+        Please make sure it is completed as soon as possible:\n\
+        {logs_link}\n\n\
+        Best regards,\n\
+        The LogSmart Team",
+        missed.describe()
+    );
+
+    send_email(to_email, &subject, &body).await?;
+    tracing::info!("Missed log alert sent to {}", to_email);
+    Ok(())
+}
+
+/// Sends a single summary of missed logs, used when a company has digest alerts enabled.
+///
+/// # Errors
+/// Returns an error if the email fails to send.
+pub async fn send_missed_log_digest(
+    to_email: &str,
+    company_name: &str,
+    missed: &[MissedLogSummary],
+    logs_link: &str,
+) -> Result<()> {
+    let subject = format!("{} missed logs for {company_name} - LogSmart", missed.len());
+    let items: String = missed
+        .iter()
+        .map(|m| format!("- {}\n", m.describe()))
+        .collect();
+    let body = format!(
+        "Hello,\n\n\
+        The following logs for '{company_name}' were not completed by their due time:\n\n\
+        {items}\n\
+        Please make sure they are completed as soon as possible:\n\
+        {logs_link}\n\n\
+        Best regards,\n\
+        The LogSmart Team"
+    );
+
+    send_email(to_email, &subject, &body).await?;
+    tracing::info!("Missed log digest sent to {}", to_email);
+    Ok(())
+}
+
+/// Log completion figures for one template over a digest window.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct TemplateCompliance {
+    pub template_name: String,
+    /// Periods that fell due during the window.
+    pub due: usize,
+    /// Due periods with a submitted, reviewed or approved entry.
+    pub completed: usize,
+    /// Due periods with no entry at all.
+    pub missed: usize,
+    /// Draft entries currently open for the template, in any period.
+    pub open_drafts: usize,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BranchCompliance {
+    pub branch_name: String,
+    pub templates: Vec<TemplateCompliance>,
+}
+
+/// Scheduled compliance summary sent to users who opted in to digests.
+#[derive(Debug, Clone)]
+pub struct ComplianceDigest {
+    pub company_name: String,
+    /// Human readable window the figures cover, e.g. "Monday 2 March 2026".
+    pub window_label: String,
+    pub branches: Vec<BranchCompliance>,
+    /// Names of the users clocked in when the digest was generated.
+    pub clocked_in: Vec<String>,
+}
+
+impl ComplianceDigest {
+    fn subject(&self) -> String {
+        format!(
+            "Compliance digest for {} ({}) - LogSmart",
+            self.company_name, self.window_label
+        )
+    }
+
+    fn text_body(&self, dashboard_link: &str) -> String {
+        let mut body = format!(
+            "Hello,\n\nHere is the log compliance summary for '{}' covering {}.\n\n",
+            self.company_name, self.window_label
+        );
+
+        if self.branches.is_empty() {
+            body.push_str("No logs fell due during this period.\n\n");
+        }
+        for branch in &self.branches {
+            body.push_str(&format!("{}\n", branch.branch_name));
+            for t in &branch.templates {
+                body.push_str(&format!(
+                    "- {}: {} of {} completed, {} missed, {} open drafts\n",
+                    t.template_name, t.completed, t.due, t.missed, t.open_drafts
+                ));
+            }
+            body.push('\n');
+        }
+
+        if self.clocked_in.is_empty() {
+            body.push_str("Nobody is currently clocked in.\n\n");
+        } else {
+            body.push_str(&format!(
+                "Currently clocked in: {}\n\n",
+                self.clocked_in.join(", ")
+            ));
+        }
+
+        body.push_str(&format!(
+            "View your logs:\n{dashboard_link}\n\n\
+            You are receiving this email because you opted in to compliance digests. \
+            You can turn them off from your account settings.\n\n\
+            Best regards,\n\
+            The LogSmart Team"
+        ));
+        body
+    }
+
+    fn html_body(&self, dashboard_link: &str) -> String {
+        let mut body = format!(
+            "<p>Hello,</p><p>Here is the log compliance summary for <strong>{}</strong> covering {}.</p>",
+            html_escape(&self.company_name),
+            html_escape(&self.window_label)
+        );
+
+        if self.branches.is_empty() {
+            body.push_str("<p>No logs fell due during this period.</p>");
+        }
+        for branch in &self.branches {
+            body.push_str(&format!(
+                "<h3>{}</h3>\
+                <table cellpadding=\"4\" style=\"border-collapse:collapse\">\
+                <tr><th align=\"left\">Log</th><th>Completed</th><th>Due</th><th>Missed</th><th>Open drafts</th></tr>",
+                html_escape(&branch.branch_name)
+            ));
+            for t in &branch.templates {
+                let missed_style = if t.missed > 0 {
+                    " style=\"color:#b91c1c\""
+                } else {
+                    ""
+                };
+                body.push_str(&format!(
+                    "<tr><td>{}</td><td align=\"center\">{}</td><td align=\"center\">{}</td>\
+                    <td align=\"center\"{missed_style}>{}</td><td align=\"center\">{}</td></tr>",
+                    html_escape(&t.template_name),
+                    t.completed,
+                    t.due,
+                    t.missed,
+                    t.open_drafts
+                ));
+            }
+            body.push_str("</table>");
+        }
+
+        if self.clocked_in.is_empty() {
+            body.push_str("<p>Nobody is currently clocked in.</p>");
+        } else {
+            let names: Vec<String> = self.clocked_in.iter().map(|n| html_escape(n)).collect();
+            body.push_str(&format!(
+                "<p>Currently clocked in: {}</p>",
+                names.join(", ")
+            ));
+        }
+
+        body.push_str(&format!(
+            "<p><a href=\"{}\">View your logs</a></p>\
+            <p style=\"color:#6b7280;font-size:12px\">You are receiving this email because you opted in to \
+            compliance digests. You can turn them off from your account settings.</p>\
+            <p>Best regards,<br>The LogSmart Team</p>",
+            html_escape(dashboard_link)
+        ));
+        body
+    }
+}
+
+fn html_escape(value: &str) -> String {
+    value
+        .replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+        .replace('\'', "&#39;")
+}
+
+/// Sends a scheduled compliance digest as plain text with an HTML alternative.
+///
+/// # Errors
+/// Returns an error if the email fails to send.
+pub async fn send_compliance_digest(
+    to_email: &str,
+    digest: &ComplianceDigest,
+    dashboard_link: &str,
+) -> Result<()> {
+    send_html_email(
+        to_email,
+        &digest.subject(),
+        &digest.text_body(dashboard_link),
+        &digest.html_body(dashboard_link),
+    )
+    .await?;
+    tracing::info!("Compliance digest sent to {}", to_email);
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn digest() -> ComplianceDigest {
+        ComplianceDigest {
+            company_name: "Fish & Chips <Ltd>".to_string(),
+            window_label: "Monday 2 March 2026".to_string(),
+            branches: vec![BranchCompliance {
+                branch_name: "Plymouth".to_string(),
+                templates: vec![TemplateCompliance {
+                    template_name: "Fridge".to_string(),
+                    due: 3,
+                    completed: 2,
+                    missed: 1,
+                    open_drafts: 0,
+                }],
+            }],
+            clocked_in: vec!["Alex Smith".to_string()],
+        }
+    }
+
+    #[test]
+    fn test_compliance_digest_text_body() {
+        let body = digest().text_body("https://example.com/logs");
+        assert!(body.contains("- Fridge: 2 of 3 completed, 1 missed, 0 open drafts"));
+        assert!(body.contains("Currently clocked in: Alex Smith"));
+        assert!(body.contains("https://example.com/logs"));
+    }
+
+    #[test]
+    fn test_compliance_digest_html_body_escapes_names() {
+        let body = digest().html_body("https://example.com/logs");
+        assert!(body.contains("Fish &amp; Chips &lt;Ltd&gt;"));
+        assert!(!body.contains("<Ltd>"));
+        assert!(body.contains("<h3>Plymouth</h3>"));
+    }
+}
@@ -45,18 +45,13 @@ pub async fn init_exports_dir() -> Result<()> {
     Ok(())
 }
 
-pub async fn save_export(company_id: &str, data: &[u8]) -> Result<String> {
+/// Picks the filename for a new company export and the path to write it to.
+pub fn new_export_path(company_id: &str) -> Result<(String, PathBuf)> {
     let dir = exports_dir();
     let timestamp = chrono::Utc::now().timestamp_millis();
     let filename = format!("{company_id}_{timestamp}.zip");
     let file_path = safe_path(&dir, &filename)?;
-
-    fs::write(&file_path, data)
-        .await
-        .with_context(|| format!("Failed to write export file: {}", file_path.display()))?;
-
-    tracing::info!("Export saved: {} ({} bytes)", filename, data.len());
-    Ok(filename)
+    Ok((filename, file_path))
 }
 
 pub async fn get_export(_company_id: &str, filename: &str) -> Result<Option<Vec<u8>>> {
@@ -0,0 +1,665 @@
+//! A small expression language for calculated template fields.
+//!
+//! Expressions read other fields' values and combine them with arithmetic, comparisons,
+//! `and`/`or`/`not` and a fixed set of pure functions. There are no variables, loops or
+//! side effects, and the expression length and nesting depth are capped, so evaluation is
+//! bounded by the size of the expression.
+//!
+//! ```text
+//! avg({probe-1}, {probe-2}, {probe-3})
+//! {delivery} - {storage}
+//! if({core} >= 75 and {held} <= 2, "Pass", "Fail")
+//! ```
+//!
+//! Fields are referenced by id in braces. Supported functions are `avg`, `sum`, `min`,
+//! `max`, `abs`, `round(x)`/`round(x, digits)` and `if(condition, then, else)`.
+
+use std::fmt;
+
+/// Maximum length of an expression, in characters.
+pub const MAX_EXPRESSION_LENGTH: usize = 500;
+
+/// Maximum nesting depth of parentheses, operators and function calls.
+const MAX_DEPTH: usize = 32;
+
+/// Maximum number of decimal places accepted by `round`.
+const MAX_ROUND_DIGITS: f64 = 6.0;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ExpressionError(pub String);
+
+impl fmt::Display for ExpressionError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+impl std::error::Error for ExpressionError {}
+
+fn error<T>(message: impl Into<String>) -> Result<T, ExpressionError> {
+    Err(ExpressionError(message.into()))
+}
+
+/// A value produced by, or read into, an expression.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Value {
+    Number(f64),
+    Bool(bool),
+    Text(String),
+}
+
+impl Value {
+    #[must_use]
+    pub fn to_json(&self) -> serde_json::Value {
+        match self {
+            Self::Number(n) => serde_json::json!(n),
+            Self::Bool(b) => serde_json::Value::Bool(*b),
+            Self::Text(s) => serde_json::Value::String(s.clone()),
+        }
+    }
+
+    fn type_name(&self) -> &'static str {
+        match self {
+            Self::Number(_) => "number",
+            Self::Bool(_) => "boolean",
+            Self::Text(_) => "text",
+        }
+    }
+
+    fn as_number(&self) -> Result<f64, ExpressionError> {
+        match self {
+            Self::Number(n) => Ok(*n),
+            other => error(format!("Expected a number but found {}", other.type_name())),
+        }
+    }
+
+    fn as_bool(&self) -> Result<bool, ExpressionError> {
+        match self {
+            Self::Bool(b) => Ok(*b),
+            other => error(format!(
+                "Expected true or false but found {}",
+                other.type_name()
+            )),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum UnaryOp {
+    Neg,
+    Not,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BinaryOp {
+    Add,
+    Sub,
+    Mul,
+    Div,
+    Lt,
+    Le,
+    Gt,
+    Ge,
+    Eq,
+    Ne,
+    And,
+    Or,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Function {
+    Avg,
+    Sum,
+    Min,
+    Max,
+    Abs,
+    Round,
+    If,
+}
+
+impl Function {
+    fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "avg" => Some(Self::Avg),
+            "sum" => Some(Self::Sum),
+            "min" => Some(Self::Min),
+            "max" => Some(Self::Max),
+            "abs" => Some(Self::Abs),
+            "round" => Some(Self::Round),
+            "if" => Some(Self::If),
+            _ => None,
+        }
+    }
+
+    fn accepts(self, arg_count: usize) -> bool {
+        match self {
+            Self::Avg | Self::Sum | Self::Min | Self::Max => arg_count >= 1,
+            Self::Abs => arg_count == 1,
+            Self::Round => matches!(arg_count, 1 | 2),
+            Self::If => arg_count == 3,
+        }
+    }
+}
+
+/// A parsed expression.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Expr {
+    Literal(Value),
+    Field(String),
+    Unary(UnaryOp, Box<Expr>),
+    Binary(BinaryOp, Box<Expr>, Box<Expr>),
+    Call(Function, Vec<Expr>),
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Token {
+    Number(f64),
+    Text(String),
+    Field(String),
+    Ident(String),
+    Symbol(&'static str),
+}
+
+/// Splits an expression into tokens, each with its byte range in `source`.
+fn tokenize(source: &str) -> Result<Vec<(Token, std::ops::Range<usize>)>, ExpressionError> {
+    const SYMBOLS: [&str; 13] = [
+        "<=", ">=", "==", "!=", "+", "-", "*", "/", "(", ")", ",", "<", ">",
+    ];
+
+    let mut tokens = Vec::new();
+    let mut chars = source.char_indices().peekable();
+
+    while let Some(&(start, c)) = chars.peek() {
+        if c.is_whitespace() {
+            chars.next();
+        } else if c.is_ascii_digit() || c == '.' {
+            let mut end = start;
+            while let Some(&(i, c)) = chars.peek() {
+                if !(c.is_ascii_digit() || c == '.') {
+                    break;
+                }
+                end = i + c.len_utf8();
+                chars.next();
+            }
+            let number = source[start..end].parse::<f64>().map_err(|_| {
+                ExpressionError(format!("Invalid number '{}'", &source[start..end]))
+            })?;
+            tokens.push((Token::Number(number), start..end));
+        } else if c == '"' {
+            chars.next();
+            let mut text = String::new();
+            let end = loop {
+                match chars.next() {
+                    Some((i, '"')) => break i + 1,
+                    Some((_, c)) => text.push(c),
+                    None => return error("Unterminated text in expression"),
+                }
+            };
+            tokens.push((Token::Text(text), start..end));
+        } else if c == '{' {
+            chars.next();
+            let mut reference = String::new();
+            let end = loop {
+                match chars.next() {
+                    Some((i, '}')) => break i + 1,
+                    Some((_, c)) if c != '{' => reference.push(c),
+                    _ => return error("Unterminated field reference in expression"),
+                }
+            };
+            let reference = reference.trim();
+            if reference.is_empty() {
+                return error("Empty field reference in expression");
+            }
+            tokens.push((Token::Field(reference.to_string()), start..end));
+        } else if c.is_ascii_alphabetic() || c == '_' {
+            let mut end = start;
+            while let Some(&(i, c)) = chars.peek() {
+                if !(c.is_ascii_alphanumeric() || c == '_') {
+                    break;
+                }
+                end = i + c.len_utf8();
+                chars.next();
+            }
+            tokens.push((Token::Ident(source[start..end].to_string()), start..end));
+        } else {
+            let Some(symbol) = SYMBOLS.iter().find(|s| source[start..].starts_with(**s)) else {
+                return error(format!("Unexpected character '{c}' in expression"));
+            };
+            for _ in 0..symbol.len() {
+                chars.next();
+            }
+            tokens.push((Token::Symbol(symbol), start..start + symbol.len()));
+        }
+    }
+
+    Ok(tokens)
+}
+
+struct Parser {
+    tokens: Vec<Token>,
+    position: usize,
+    depth: usize,
+}
+
+impl Parser {
+    fn peek(&self) -> Option<&Token> {
+        self.tokens.get(self.position)
+    }
+
+    fn next(&mut self) -> Option<Token> {
+        let token = self.tokens.get(self.position).cloned();
+        self.position += 1;
+        token
+    }
+
+    fn eat_symbol(&mut self, symbol: &'static str) -> bool {
+        if self.peek() == Some(&Token::Symbol(symbol)) {
+            self.position += 1;
+            true
+        } else {
+            false
+        }
+    }
+
+    fn eat_keyword(&mut self, keyword: &str) -> bool {
+        if matches!(self.peek(), Some(Token::Ident(ident)) if ident == keyword) {
+            self.position += 1;
+            true
+        } else {
+            false
+        }
+    }
+
+    fn nested<T>(
+        &mut self,
+        parse: impl FnOnce(&mut Self) -> Result<T, ExpressionError>,
+    ) -> Result<T, ExpressionError> {
+        self.depth += 1;
+        if self.depth > MAX_DEPTH {
+            return error("Expression is nested too deeply");
+        }
+        let result = parse(self);
+        self.depth -= 1;
+        result
+    }
+
+    fn expression(&mut self) -> Result<Expr, ExpressionError> {
+        self.nested(Self::or)
+    }
+
+    fn or(&mut self) -> Result<Expr, ExpressionError> {
+        let mut left = self.and()?;
+        while self.eat_keyword("or") {
+            let right = self.and()?;
+            left = Expr::Binary(BinaryOp::Or, Box::new(left), Box::new(right));
+        }
+        Ok(left)
+    }
+
+    fn and(&mut self) -> Result<Expr, ExpressionError> {
+        let mut left = self.not()?;
+        while self.eat_keyword("and") {
+            let right = self.not()?;
+            left = Expr::Binary(BinaryOp::And, Box::new(left), Box::new(right));
+        }
+        Ok(left)
+    }
+
+    fn not(&mut self) -> Result<Expr, ExpressionError> {
+        if self.eat_keyword("not") {
+            let operand = self.nested(Self::not)?;
+            return Ok(Expr::Unary(UnaryOp::Not, Box::new(operand)));
+        }
+        self.comparison()
+    }
+
+    fn comparison(&mut self) -> Result<Expr, ExpressionError> {
+        let left = self.additive()?;
+        let op = match self.peek() {
+            Some(Token::Symbol("<")) => BinaryOp::Lt,
+            Some(Token::Symbol("<=")) => BinaryOp::Le,
+            Some(Token::Symbol(">")) => BinaryOp::Gt,
+            Some(Token::Symbol(">=")) => BinaryOp::Ge,
+            Some(Token::Symbol("==")) => BinaryOp::Eq,
+            Some(Token::Symbol("!=")) => BinaryOp::Ne,
+            _ => return Ok(left),
+        };
+        self.position += 1;
+        let right = self.additive()?;
+        Ok(Expr::Binary(op, Box::new(left), Box::new(right)))
+    }
+
+    fn additive(&mut self) -> Result<Expr, ExpressionError> {
+        let mut left = self.multiplicative()?;
+        loop {
+            let op = if self.eat_symbol("+") {
+                BinaryOp::Add
+            } else if self.eat_symbol("-") {
+                BinaryOp::Sub
+            } else {
+                return Ok(left);
+            };
+            let right = self.multiplicative()?;
+            left = Expr::Binary(op, Box::new(left), Box::new(right));
+        }
+    }
+
+    fn multiplicative(&mut self) -> Result<Expr, ExpressionError> {
+        let mut left = self.unary()?;
+        loop {
+            let op = if self.eat_symbol("*") {
+                BinaryOp::Mul
+            } else if self.eat_symbol("/") {
+                BinaryOp::Div
+            } else {
+                return Ok(left);
+            };
+            let right = self.unary()?;
+            left = Expr::Binary(op, Box::new(left), Box::new(right));
+        }
+    }
+
+    fn unary(&mut self) -> Result<Expr, ExpressionError> {
+        if self.eat_symbol("-") {
+            let operand = self.nested(Self::unary)?;
+            return Ok(Expr::Unary(UnaryOp::Neg, Box::new(operand)));
+        }
+        self.primary()
+    }
+
+    fn primary(&mut self) -> Result<Expr, ExpressionError> {
+        match self.next() {
+            Some(Token::Number(n)) => Ok(Expr::Literal(Value::Number(n))),
+            Some(Token::Text(s)) => Ok(Expr::Literal(Value::Text(s))),
+            Some(Token::Field(reference)) => Ok(Expr::Field(reference)),
+            Some(Token::Symbol("(")) => {
+                let inner = self.expression()?;
+                if !self.eat_symbol(")") {
+                    return error("Expected ')' in expression");
+                }
+                Ok(inner)
+            }
+            Some(Token::Ident(ident)) => match ident.as_str() {
+                "true" => Ok(Expr::Literal(Value::Bool(true))),
+                "false" => Ok(Expr::Literal(Value::Bool(false))),
+                name => {
+                    let Some(function) = Function::from_name(name) else {
+                        return error(format!("Unknown function '{name}'"));
+                    };
+                    if !self.eat_symbol("(") {
+                        return error(format!("Expected '(' after '{name}'"));
+                    }
+                    let mut args = Vec::new();
+                    if !self.eat_symbol(")") {
+                        loop {
+                            args.push(self.expression()?);
+                            if self.eat_symbol(")") {
+                                break;
+                            }
+                            if !self.eat_symbol(",") {
+                                return error(format!("Expected ',' or ')' in call to '{name}'"));
+                            }
+                        }
+                    }
+                    if !function.accepts(args.len()) {
+                        return error(format!("Wrong number of arguments in call to '{name}'"));
+                    }
+                    Ok(Expr::Call(function, args))
+                }
+            },
+            Some(Token::Symbol(symbol)) => error(format!("Unexpected '{symbol}' in expression")),
+            None => error("Unexpected end of expression"),
+        }
+    }
+}
+
+/// Parses an expression.
+///
+/// # Errors
+/// Returns an error if the expression is empty, too long, nested too deeply, or malformed.
+pub fn parse(source: &str) -> Result<Expr, ExpressionError> {
+    if source.trim().is_empty() {
+        return error("Expression cannot be empty");
+    }
+    if source.chars().count() > MAX_EXPRESSION_LENGTH {
+        return error(format!(
+            "Expression must not exceed {MAX_EXPRESSION_LENGTH} characters"
+        ));
+    }
+
+    let tokens = tokenize(source)?
+        .into_iter()
+        .map(|(token, _)| token)
+        .collect();
+    let mut parser = Parser {
+        tokens,
+        position: 0,
+        depth: 0,
+    };
+    let expr = parser.expression()?;
+    if parser.peek().is_some() {
+        return error("Expected an operator between values in expression");
+    }
+    Ok(expr)
+}
+
+/// Replaces field references in `source` for which `replace` returns a new reference.
+/// The expression is returned unchanged if it cannot be tokenized.
+#[must_use]
+pub fn rewrite_references(source: &str, replace: impl Fn(&str) -> Option<String>) -> String {
+    let Ok(tokens) = tokenize(source) else {
+        return source.to_string();
+    };
+
+    let mut rewritten = String::with_capacity(source.len());
+    let mut last = 0;
+    for (token, range) in tokens {
+        if let Token::Field(reference) = token
+            && let Some(replacement) = replace(&reference)
+        {
+            rewritten.push_str(&source[last..range.start]);
+            rewritten.push('{');
+            rewritten.push_str(&replacement);
+            rewritten.push('}');
+            last = range.end;
+        }
+    }
+    rewritten.push_str(&source[last..]);
+    rewritten
+}
+
+impl Expr {
+    /// The field references in the expression, in order of appearance.
+    #[must_use]
+    pub fn references(&self) -> Vec<&str> {
+        let mut references = Vec::new();
+        self.collect_references(&mut references);
+        references
+    }
+
+    fn collect_references<'a>(&'a self, references: &mut Vec<&'a str>) {
+        match self {
+            Self::Literal(_) => {}
+            Self::Field(reference) => references.push(reference),
+            Self::Unary(_, operand) => operand.collect_references(references),
+            Self::Binary(_, left, right) => {
+                left.collect_references(references);
+                right.collect_references(references);
+            }
+            Self::Call(_, args) => {
+                for arg in args {
+                    arg.collect_references(references);
+                }
+            }
+        }
+    }
+
+    /// Evaluates the expression, reading field values through `lookup`.
+    ///
+    /// # Errors
+    /// Returns an error if a referenced field has no value, an operand has the wrong type,
+    /// or the result is not a finite number (e.g. division by zero).
+    pub fn evaluate(
+        &self,
+        lookup: &dyn Fn(&str) -> Option<Value>,
+    ) -> Result<Value, ExpressionError> {
+        let value = match self {
+            Self::Literal(value) => value.clone(),
+            Self::Field(reference) => lookup(reference)
+                .ok_or_else(|| ExpressionError(format!("Field '{reference}' has no value")))?,
+            Self::Unary(UnaryOp::Neg, operand) => {
+                Value::Number(-operand.evaluate(lookup)?.as_number()?)
+            }
+            Self::Unary(UnaryOp::Not, operand) => {
+                Value::Bool(!operand.evaluate(lookup)?.as_bool()?)
+            }
+            Self::Binary(BinaryOp::And, left, right) => {
+                Value::Bool(left.evaluate(lookup)?.as_bool()? && right.evaluate(lookup)?.as_bool()?)
+            }
+            Self::Binary(BinaryOp::Or, left, right) => {
+                Value::Bool(left.evaluate(lookup)?.as_bool()? || right.evaluate(lookup)?.as_bool()?)
+            }
+            Self::Binary(op, left, right) => {
+                let left = left.evaluate(lookup)?;
+                let right = right.evaluate(lookup)?;
+                match op {
+                    BinaryOp::Eq => Value::Bool(left == right),
+                    BinaryOp::Ne => Value::Bool(left != right),
+                    BinaryOp::Add => match (&left, &right) {
+                        (Value::Text(a), Value::Text(b)) => Value::Text(format!("{a}{b}")),
+                        _ => Value::Number(left.as_number()? + right.as_number()?),
+                    },
+                    BinaryOp::Sub => Value::Number(left.as_number()? - right.as_number()?),
+                    BinaryOp::Mul => Value::Number(left.as_number()? * right.as_number()?),
+                    BinaryOp::Div => {
+                        let divisor = right.as_number()?;
+                        if divisor == 0.0 {
+                            return error("Division by zero");
+                        }
+                        Value::Number(left.as_number()? / divisor)
+                    }
+                    BinaryOp::Lt => Value::Bool(left.as_number()? < right.as_number()?),
+                    BinaryOp::Le => Value::Bool(left.as_number()? <= right.as_number()?),
+                    BinaryOp::Gt => Value::Bool(left.as_number()? > right.as_number()?),
+                    BinaryOp::Ge => Value::Bool(left.as_number()? >= right.as_number()?),
+                    BinaryOp::And | BinaryOp::Or => unreachable!("handled above"),
+                }
+            }
+            Self::Call(Function::If, args) => {
+                if args[0].evaluate(lookup)?.as_bool()? {
+                    args[1].evaluate(lookup)?
+                } else {
+                    args[2].evaluate(lookup)?
+                }
+            }
+            Self::Call(function, args) => {
+                let numbers = args
+                    .iter()
+                    .map(|arg| arg.evaluate(lookup)?.as_number())
+                    .collect::<Result<Vec<f64>, _>>()?;
+                Value::Number(match function {
+                    Function::Avg => numbers.iter().sum::<f64>() / numbers.len() as f64,
+                    Function::Sum => numbers.iter().sum(),
+                    Function::Min => numbers.iter().copied().fold(f64::INFINITY, f64::min),
+                    Function::Max => numbers.iter().copied().fold(f64::NEG_INFINITY, f64::max),
+                    Function::Abs => numbers[0].abs(),
+                    Function::Round => {
+                        let digits = numbers.get(1).copied().unwrap_or(0.0);
+                        if digits.fract() != 0.0 || !(0.0..=MAX_ROUND_DIGITS).contains(&digits) {
+                            return error("round() digits must be a whole number from 0 to 6");
+                        }
+                        let factor = 10f64.powf(digits);
+                        (numbers[0] * factor).round() / factor
+                    }
+                    Function::If => unreachable!("handled above"),
+                })
+            }
+        };
+
+        if let Value::Number(n) = value
+            && !n.is_finite()
+        {
+            return error("Result is not a finite number");
+        }
+        Ok(value)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn evaluate(source: &str, fields: &[(&str, Value)]) -> Result<Value, ExpressionError> {
+        let lookup = |reference: &str| {
+            fields
+                .iter()
+                .find(|(key, _)| *key == reference)
+                .map(|(_, value)| value.clone())
+        };
+        parse(source)?.evaluate(&lookup)
+    }
+
+    #[test]
+    fn test_arithmetic_and_precedence() {
+        assert_eq!(evaluate("1 + 2 * 3", &[]), Ok(Value::Number(7.0)));
+        assert_eq!(evaluate("(1 + 2) * 3", &[]), Ok(Value::Number(9.0)));
+        assert_eq!(evaluate("-2 - -3", &[]), Ok(Value::Number(1.0)));
+        assert_eq!(evaluate("round(10 / 3, 2)", &[]), Ok(Value::Number(3.33)));
+        assert!(evaluate("1 / 0", &[]).is_err());
+    }
+
+    #[test]
+    fn test_field_references() {
+        let fields = [
+            ("a", Value::Number(3.0)),
+            ("b", Value::Number(4.5)),
+            ("c", Value::Number(6.0)),
+        ];
+        assert_eq!(
+            evaluate("avg({a}, {b}, {c})", &fields),
+            Ok(Value::Number(4.5))
+        );
+        assert_eq!(evaluate("{c} - {a}", &fields), Ok(Value::Number(3.0)));
+        assert_eq!(evaluate("max({a}, {c})", &fields), Ok(Value::Number(6.0)));
+        assert!(evaluate("{a} + {missing}", &fields).is_err());
+
+        let expr = parse("sum({a}, { b }) * {a}").unwrap();
+        assert_eq!(expr.references(), vec!["a", "b", "a"]);
+    }
+
+    #[test]
+    fn test_conditions() {
+        let fields = [("core", Value::Number(78.0)), ("ok", Value::Bool(true))];
+        assert_eq!(
+            evaluate(r#"if({core} >= 75 and {ok}, "Pass", "Fail")"#, &fields),
+            Ok(Value::Text("Pass".to_string()))
+        );
+        assert_eq!(
+            evaluate("not {ok} or {core} < 75", &fields),
+            Ok(Value::Bool(false))
+        );
+        assert_eq!(evaluate(r#""a" == "a""#, &[]), Ok(Value::Bool(true)));
+        assert!(evaluate(r#"{core} > "hot""#, &fields).is_err());
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        assert!(parse("").is_err());
+        assert!(parse("1 +").is_err());
+        assert!(parse("foo(1)").is_err());
+        assert!(parse("abs(1, 2)").is_err());
+        assert!(parse("{a} {b}").is_err());
+        assert!(parse(r#""unterminated"#).is_err());
+        assert!(parse("1 < 2 < 3").is_err());
+        assert!(parse(&format!("{}1{}", "(".repeat(40), ")".repeat(40))).is_err());
+        assert!(parse(&"1+".repeat(300)).is_err());
+    }
+
+    #[test]
+    fn test_rewrite_references() {
+        let rewritten = rewrite_references(r#"{0} + {b} + "{0}""#, |reference| {
+            (reference == "0").then(|| "probe-1".to_string())
+        });
+        assert_eq!(rewritten, r#"{probe-1} + {b} + "{0}""#);
+    }
+}
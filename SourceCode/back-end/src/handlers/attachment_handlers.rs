@@ -0,0 +1,192 @@
+use crate::{
+    AppState,
+    dto::{AttachmentResponse, ErrorResponse, ListAttachmentsResponse, UploadAttachmentQuery},
+    middleware::AnyAuthUser,
+    services::{AttachmentService, attachment_service},
+};
+use axum::{
+    Json,
+    body::Bytes,
+    extract::{Path, Query, State, rejection::BytesRejection},
+    http::{StatusCode, header},
+};
+use serde_json::json;
+
+#[utoipa::path(
+    post,
+    path = "/logs/entries/{entry_id}/attachments",
+    params(
+        ("entry_id" = String, Path, description = "Log entry ID"),
+        UploadAttachmentQuery,
+    ),
+    request_body = Vec<u8>,
+    responses(
+        (status = 201, description = "Attachment uploaded", body = AttachmentResponse),
+        (status = 400, description = "Empty file or unsupported file type", body = ErrorResponse),
+        (status = 401, description = "Unauthorized - invalid or missing token", body = ErrorResponse),
+        (status = 403, description = "Forbidden", body = ErrorResponse),
+        (status = 404, description = "Entry not found", body = ErrorResponse),
+        (status = 409, description = "Entry is no longer a draft", body = ErrorResponse),
+        (status = 413, description = "File too large or company storage quota exceeded", body = ErrorResponse),
+        (status = 500, description = "Server error", body = ErrorResponse),
+    ),
+    security(("bearer_auth" = [])),
+    tag = "Log Entries"
+)]
+/// Attaches a photo or PDF to a log entry. The raw file is sent as the request body.
+///
+/// # Errors
+/// Returns an error if the entry is not found, the user cannot change it, or the file is rejected.
+pub async fn upload_entry_attachment(
+    AnyAuthUser(_claims, user): AnyAuthUser,
+    State(state): State<AppState>,
+    Path(entry_id): Path<String>,
+    Query(query): Query<UploadAttachmentQuery>,
+    body: Result<Bytes, BytesRejection>,
+) -> Result<(StatusCode, Json<AttachmentResponse>), (StatusCode, Json<serde_json::Value>)> {
+    // Bodies over the request limit are rejected before the size check, so answer in JSON too
+    let body = body.map_err(|rejection| {
+        let status = rejection.status();
+        let message = if status == StatusCode::PAYLOAD_TOO_LARGE {
+            "File too large. Maximum size is 20MB".to_string()
+        } else {
+            rejection.body_text()
+        };
+        (status, Json(json!({ "error": message })))
+    })?;
+    let attachment = AttachmentService::upload_attachment(
+        &state,
+        &user,
+        &entry_id,
+        query.filename.as_deref(),
+        &body,
+    )
+    .await
+    .map_err(|(status, err)| (status, Json(err)))?;
+
+    Ok((StatusCode::CREATED, Json(attachment.into())))
+}
+
+#[utoipa::path(
+    get,
+    path = "/logs/entries/{entry_id}/attachments",
+    params(("entry_id" = String, Path, description = "Log entry ID")),
+    responses(
+        (status = 200, description = "Attachments of the entry", body = ListAttachmentsResponse),
+        (status = 401, description = "Unauthorized - invalid or missing token", body = ErrorResponse),
+        (status = 403, description = "Forbidden", body = ErrorResponse),
+        (status = 404, description = "Entry not found", body = ErrorResponse),
+        (status = 500, description = "Server error", body = ErrorResponse),
+    ),
+    security(("bearer_auth" = [])),
+    tag = "Log Entries"
+)]
+/// Lists the attachments of a log entry along with the company's storage usage.
+///
+/// # Errors
+/// Returns an error if the entry is not found or the user cannot view it.
+pub async fn list_entry_attachments(
+    AnyAuthUser(_claims, user): AnyAuthUser,
+    State(state): State<AppState>,
+    Path(entry_id): Path<String>,
+) -> Result<Json<ListAttachmentsResponse>, (StatusCode, Json<serde_json::Value>)> {
+    let (attachments, used_bytes) = AttachmentService::list_attachments(&state, &user, &entry_id)
+        .await
+        .map_err(|(status, err)| (status, Json(err)))?;
+
+    Ok(Json(ListAttachmentsResponse {
+        attachments: attachments.into_iter().map(Into::into).collect(),
+        used_bytes,
+        quota_bytes: attachment_service::company_quota_bytes(),
+    }))
+}
+
+#[utoipa::path(
+    get,
+    path = "/logs/entries/{entry_id}/attachments/{attachment_id}",
+    params(
+        ("entry_id" = String, Path, description = "Log entry ID"),
+        ("attachment_id" = String, Path, description = "Attachment ID"),
+    ),
+    responses(
+        (status = 200, description = "Attachment contents", content_type = "application/octet-stream"),
+        (status = 401, description = "Unauthorized - invalid or missing token", body = ErrorResponse),
+        (status = 403, description = "Forbidden", body = ErrorResponse),
+        (status = 404, description = "Entry or attachment not found", body = ErrorResponse),
+        (status = 500, description = "Server error", body = ErrorResponse),
+    ),
+    security(("bearer_auth" = [])),
+    tag = "Log Entries"
+)]
+/// Downloads an attachment of a log entry.
+///
+/// # Errors
+/// Returns an error if the entry or attachment is not found or the user cannot view the entry.
+pub async fn download_entry_attachment(
+    AnyAuthUser(_claims, user): AnyAuthUser,
+    State(state): State<AppState>,
+    Path((entry_id, attachment_id)): Path<(String, String)>,
+) -> Result<
+    (
+        StatusCode,
+        [(header::HeaderName, header::HeaderValue); 2],
+        Vec<u8>,
+    ),
+    (StatusCode, Json<serde_json::Value>),
+> {
+    let (attachment, data) =
+        AttachmentService::download_attachment(&state, &user, &entry_id, &attachment_id)
+            .await
+            .map_err(|(status, err)| (status, Json(err)))?;
+
+    let content_type = header::HeaderValue::from_str(&attachment.content_type)
+        .unwrap_or(header::HeaderValue::from_static("application/octet-stream"));
+    let disposition =
+        header::HeaderValue::from_str(&format!("inline; filename=\"{}\"", attachment.filename))
+            .unwrap_or(header::HeaderValue::from_static("inline"));
+
+    Ok((
+        StatusCode::OK,
+        [
+            (header::CONTENT_TYPE, content_type),
+            (header::CONTENT_DISPOSITION, disposition),
+        ],
+        data,
+    ))
+}
+
+#[utoipa::path(
+    delete,
+    path = "/logs/entries/{entry_id}/attachments/{attachment_id}",
+    params(
+        ("entry_id" = String, Path, description = "Log entry ID"),
+        ("attachment_id" = String, Path, description = "Attachment ID"),
+    ),
+    responses(
+        (status = 200, description = "Attachment deleted", body = serde_json::Value),
+        (status = 401, description = "Unauthorized - invalid or missing token", body = ErrorResponse),
+        (status = 403, description = "Forbidden", body = ErrorResponse),
+        (status = 404, description = "Entry or attachment not found", body = ErrorResponse),
+        (status = 409, description = "Entry is no longer a draft", body = ErrorResponse),
+        (status = 500, description = "Server error", body = ErrorResponse),
+    ),
+    security(("bearer_auth" = [])),
+    tag = "Log Entries"
+)]
+/// Removes an attachment from a log entry.
+///
+/// # Errors
+/// Returns an error if the entry or attachment is not found or the user cannot change the entry.
+pub async fn delete_entry_attachment(
+    AnyAuthUser(_claims, user): AnyAuthUser,
+    State(state): State<AppState>,
+    Path((entry_id, attachment_id)): Path<(String, String)>,
+) -> Result<Json<serde_json::Value>, (StatusCode, Json<serde_json::Value>)> {
+    AttachmentService::delete_attachment(&state, &user, &entry_id, &attachment_id)
+        .await
+        .map_err(|(status, err)| (status, Json(err)))?;
+
+    Ok(Json(
+        json!({ "message": "Attachment deleted successfully" }),
+    ))
+}
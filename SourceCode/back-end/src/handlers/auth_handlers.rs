@@ -1,7 +1,7 @@
 use crate::middleware::{AnyAuthUser, AuditRequestContext};
 use crate::services::UserService;
 use crate::utils::{
-    err_bad_request, err_conflict, err_internal, err_not_found, err_unauthorized,
+    err_bad_request, err_conflict, err_forbidden, err_internal, err_not_found, err_unauthorized,
     extract_ip_from_headers_and_addr, extract_user_agent,
 };
 use crate::{
@@ -9,9 +9,9 @@ use crate::{
     auth::{validate_email, validate_password_policy},
     db,
     dto::{
-        AuthResponse, ErrorResponse, JwtVerifyResponse, LoginRequest, PasswordResetResponse,
-        RegisterRequest, RequestPasswordResetRequest, ResetPasswordRequest, UpdateProfileRequest,
-        UserResponse, VerifyTokenRequest,
+        AuthResponse, DigestPreferenceResponse, ErrorResponse, JwtVerifyResponse, LoginRequest,
+        PasswordResetResponse, RegisterRequest, RequestPasswordResetRequest, ResetPasswordRequest,
+        UpdateDigestPreferenceRequest, UpdateProfileRequest, UserResponse, VerifyTokenRequest,
     },
     jwt_manager::JwtManager,
     services,
@@ -382,6 +382,87 @@ pub async fn update_profile(
     Ok(Json(user.into()))
 }
 
+#[utoipa::path(
+    get,
+    path = "/auth/digest",
+    responses(
+        (status = 200, description = "Compliance digest preference", body = DigestPreferenceResponse),
+        (status = 401, description = "Unauthorized", body = ErrorResponse),
+        (status = 500, description = "Server error", body = ErrorResponse),
+    ),
+    security(("bearer_auth" = [])),
+    tag = "Authentication"
+)]
+/// Retrieves the current user's compliance digest preference.
+///
+/// # Errors
+/// Returns an error if the database query fails.
+pub async fn get_digest_preference(
+    AnyAuthUser(claims, _user): AnyAuthUser,
+    State(state): State<AppState>,
+) -> Result<Json<DigestPreferenceResponse>, (StatusCode, Json<serde_json::Value>)> {
+    let subscription = db::get_digest_subscription(&state.postgres, &claims.user_id)
+        .await
+        .map_err(|e| {
+            tracing::error!("Failed to fetch digest subscription: {:?}", e);
+            err_internal("Database error")
+        })?;
+
+    Ok(Json(subscription.into()))
+}
+
+#[utoipa::path(
+    put,
+    path = "/auth/digest",
+    request_body = UpdateDigestPreferenceRequest,
+    responses(
+        (status = 200, description = "Compliance digest preference updated", body = DigestPreferenceResponse),
+        (status = 401, description = "Unauthorized", body = ErrorResponse),
+        (status = 403, description = "Only managers and head office users can receive digests", body = ErrorResponse),
+        (status = 500, description = "Server error", body = ErrorResponse),
+    ),
+    security(("bearer_auth" = [])),
+    tag = "Authentication"
+)]
+/// Opts the current user in to daily or weekly compliance digests, or out of them.
+///
+/// # Errors
+/// Returns an error if the user cannot receive digests or the update fails.
+pub async fn update_digest_preference(
+    AnyAuthUser(claims, user): AnyAuthUser,
+    State(state): State<AppState>,
+    Json(payload): Json<UpdateDigestPreferenceRequest>,
+) -> Result<Json<DigestPreferenceResponse>, (StatusCode, Json<serde_json::Value>)> {
+    let subscription = match payload.frequency {
+        Some(frequency) => {
+            if user.company_id.is_none() || !user.can_read_manage_branch() {
+                return Err(err_forbidden(
+                    "Only managers and head office users can receive compliance digests",
+                ));
+            }
+            let subscription =
+                db::upsert_digest_subscription(&state.postgres, &claims.user_id, frequency)
+                    .await
+                    .map_err(|e| {
+                        tracing::error!("Failed to save digest subscription: {:?}", e);
+                        err_internal("Database error")
+                    })?;
+            Some(subscription)
+        }
+        None => {
+            db::delete_digest_subscription(&state.postgres, &claims.user_id)
+                .await
+                .map_err(|e| {
+                    tracing::error!("Failed to delete digest subscription: {:?}", e);
+                    err_internal("Database error")
+                })?;
+            None
+        }
+    };
+
+    Ok(Json(subscription.into()))
+}
+
 #[utoipa::path(
     post,
     path = "/auth/password/request-reset",
@@ -3,15 +3,24 @@ use crate::{
     auth::generate_uuid6_token,
     db,
     dto::{
-        BranchDto, ConfirmBranchDeletionRequest, ConfirmBranchDeletionResponse,
-        CreateBranchRequest, ErrorResponse, ListBranchesResponse, RequestBranchDeletionRequest,
-        RequestBranchDeletionResponse, UpdateBranchRequest,
+        BranchCalendarResponse, BranchClosureDto, BranchDto, ConfirmBranchDeletionRequest,
+        ConfirmBranchDeletionResponse, CreateBranchClosureRequest, CreateBranchRequest,
+        ErrorResponse, ImportBranchClosuresResponse, ListBranchesResponse,
+        RequestBranchDeletionRequest, RequestBranchDeletionResponse, UpdateBranchRequest,
+        UpdateOpeningHoursRequest,
     },
     email,
-    middleware::{AuditRequestContext, ManageCompanyUser, ReadCompanyUser},
+    middleware::{
+        AnyAuthUser, AuditRequestContext, BranchManagerUser, ManageCompanyUser, ReadCompanyUser,
+    },
+    services::BranchCalendarService,
     utils::AuditLogger,
 };
-use axum::{Json, extract::State, http::StatusCode};
+use axum::{
+    Json,
+    extract::{Path, State},
+    http::StatusCode,
+};
 use serde_json::json;
 
 #[utoipa::path(
@@ -39,11 +48,22 @@ pub async fn create_branch(
         Json(json!({ "error": "User is not associated with a company" })),
     ))?;
 
+    let timezone = payload.timezone.filter(|tz| !tz.trim().is_empty());
+    if let Some(tz) = &timezone
+        && tz.parse::<chrono_tz::Tz>().is_err()
+    {
+        return Err((
+            StatusCode::BAD_REQUEST,
+            Json(json!({ "error": "Unknown timezone" })),
+        ));
+    }
+
     let branch = db::create_branch(
         &state.postgres,
         company_id.clone(),
         payload.name,
         payload.address,
+        timezone,
     )
     .await
     .map_err(|e| {
@@ -173,11 +193,26 @@ pub async fn update_branch(
         ));
     }
 
+    let timezone = match payload.timezone.as_deref().map(str::trim) {
+        None => branch.timezone.clone(),
+        Some("") => None,
+        Some(tz) => {
+            if tz.parse::<chrono_tz::Tz>().is_err() {
+                return Err((
+                    StatusCode::BAD_REQUEST,
+                    Json(json!({ "error": "Unknown timezone" })),
+                ));
+            }
+            Some(tz.to_string())
+        }
+    };
+
     let updated_branch = db::update_branch(
         &state.postgres,
         &payload.branch_id,
         &payload.name,
         &payload.address,
+        timezone.as_deref(),
     )
     .await
     .map_err(|e| {
@@ -406,3 +441,179 @@ pub async fn confirm_branch_deletion(
         message: format!("Branch '{branch_name}' has been successfully deleted."),
     }))
 }
+
+#[utoipa::path(
+    get,
+    path = "/auth/company/branches/{branch_id}/calendar",
+    params(("branch_id" = String, Path, description = "Branch ID")),
+    responses(
+        (status = 200, description = "Opening hours and closures of the branch", body = BranchCalendarResponse),
+        (status = 401, description = "Unauthorized", body = ErrorResponse),
+        (status = 404, description = "Branch not found", body = ErrorResponse),
+        (status = 500, description = "Server error", body = ErrorResponse),
+    ),
+    security(("bearer_auth" = [])),
+    tag = "Company Management"
+)]
+/// Retrieves the weekly opening hours and closure calendar of a branch.
+///
+/// # Errors
+/// Returns an error if the branch is not found.
+pub async fn get_branch_calendar(
+    AnyAuthUser(_claims, user): AnyAuthUser,
+    State(state): State<AppState>,
+    Path(branch_id): Path<String>,
+) -> Result<Json<BranchCalendarResponse>, (StatusCode, Json<serde_json::Value>)> {
+    let calendar = BranchCalendarService::get_calendar(&state, &user, &branch_id)
+        .await
+        .map_err(|(status, err)| (status, Json(err)))?;
+
+    Ok(Json(calendar.into()))
+}
+
+#[utoipa::path(
+    put,
+    path = "/auth/company/branches/{branch_id}/opening-hours",
+    params(("branch_id" = String, Path, description = "Branch ID")),
+    request_body = UpdateOpeningHoursRequest,
+    responses(
+        (status = 200, description = "Opening hours updated", body = BranchCalendarResponse),
+        (status = 400, description = "Invalid opening hours", body = ErrorResponse),
+        (status = 401, description = "Unauthorized", body = ErrorResponse),
+        (status = 403, description = "Forbidden", body = ErrorResponse),
+        (status = 404, description = "Branch not found", body = ErrorResponse),
+        (status = 500, description = "Server error", body = ErrorResponse),
+    ),
+    security(("bearer_auth" = [])),
+    tag = "Company Management"
+)]
+/// Replaces the weekly opening hours of a branch. Forms are not due on days without hours.
+///
+/// # Errors
+/// Returns an error if the branch is not found, the user cannot manage it, or the hours are invalid.
+pub async fn update_branch_opening_hours(
+    BranchManagerUser(_claims, user): BranchManagerUser,
+    State(state): State<AppState>,
+    Path(branch_id): Path<String>,
+    Json(payload): Json<UpdateOpeningHoursRequest>,
+) -> Result<Json<BranchCalendarResponse>, (StatusCode, Json<serde_json::Value>)> {
+    let days: Vec<(u8, &str, &str)> = payload
+        .opening_hours
+        .iter()
+        .map(|h| (h.day_of_week, h.opens_at.as_str(), h.closes_at.as_str()))
+        .collect();
+    let calendar = BranchCalendarService::set_opening_hours(&state, &user, &branch_id, &days)
+        .await
+        .map_err(|(status, err)| (status, Json(err)))?;
+
+    Ok(Json(calendar.into()))
+}
+
+#[utoipa::path(
+    post,
+    path = "/auth/company/branches/{branch_id}/closures",
+    params(("branch_id" = String, Path, description = "Branch ID")),
+    request_body = CreateBranchClosureRequest,
+    responses(
+        (status = 201, description = "Closure added", body = BranchClosureDto),
+        (status = 400, description = "Invalid closure", body = ErrorResponse),
+        (status = 401, description = "Unauthorized", body = ErrorResponse),
+        (status = 403, description = "Forbidden", body = ErrorResponse),
+        (status = 404, description = "Branch not found", body = ErrorResponse),
+        (status = 500, description = "Server error", body = ErrorResponse),
+    ),
+    security(("bearer_auth" = [])),
+    tag = "Company Management"
+)]
+/// Closes a branch for a holiday or other closure. Forms are not due while it is closed.
+///
+/// # Errors
+/// Returns an error if the branch is not found, the user cannot manage it, or the closure is invalid.
+pub async fn create_branch_closure(
+    BranchManagerUser(_claims, user): BranchManagerUser,
+    State(state): State<AppState>,
+    Path(branch_id): Path<String>,
+    Json(payload): Json<CreateBranchClosureRequest>,
+) -> Result<(StatusCode, Json<BranchClosureDto>), (StatusCode, Json<serde_json::Value>)> {
+    let closure = BranchCalendarService::add_closure(
+        &state,
+        &user,
+        &branch_id,
+        payload.starts_on,
+        payload.ends_on,
+        &payload.reason,
+    )
+    .await
+    .map_err(|(status, err)| (status, Json(err)))?;
+
+    Ok((StatusCode::CREATED, Json(closure.into())))
+}
+
+#[utoipa::path(
+    post,
+    path = "/auth/company/branches/{branch_id}/closures/import",
+    params(("branch_id" = String, Path, description = "Branch ID")),
+    request_body(content = String, content_type = "text/calendar"),
+    responses(
+        (status = 200, description = "Closures imported", body = ImportBranchClosuresResponse),
+        (status = 400, description = "Not an iCalendar file", body = ErrorResponse),
+        (status = 401, description = "Unauthorized", body = ErrorResponse),
+        (status = 403, description = "Forbidden", body = ErrorResponse),
+        (status = 404, description = "Branch not found", body = ErrorResponse),
+        (status = 413, description = "Calendar file too large", body = ErrorResponse),
+        (status = 500, description = "Server error", body = ErrorResponse),
+    ),
+    security(("bearer_auth" = [])),
+    tag = "Company Management"
+)]
+/// Imports holidays and closures from the all-day events of an iCalendar (`.ics`) file sent
+/// as the request body. Re-importing the same file updates the closures it created instead of duplicating them.
+///
+/// # Errors
+/// Returns an error if the branch is not found, the user cannot manage it, or the file is rejected.
+pub async fn import_branch_closures(
+    BranchManagerUser(_claims, user): BranchManagerUser,
+    State(state): State<AppState>,
+    Path(branch_id): Path<String>,
+    body: String,
+) -> Result<Json<ImportBranchClosuresResponse>, (StatusCode, Json<serde_json::Value>)> {
+    let (imported, skipped) =
+        BranchCalendarService::import_closures(&state, &user, &branch_id, &body)
+            .await
+            .map_err(|(status, err)| (status, Json(err)))?;
+
+    Ok(Json(ImportBranchClosuresResponse { imported, skipped }))
+}
+
+#[utoipa::path(
+    delete,
+    path = "/auth/company/branches/{branch_id}/closures/{closure_id}",
+    params(
+        ("branch_id" = String, Path, description = "Branch ID"),
+        ("closure_id" = String, Path, description = "Closure ID"),
+    ),
+    responses(
+        (status = 200, description = "Closure deleted", body = serde_json::Value),
+        (status = 401, description = "Unauthorized", body = ErrorResponse),
+        (status = 403, description = "Forbidden", body = ErrorResponse),
+        (status = 404, description = "Branch or closure not found", body = ErrorResponse),
+        (status = 500, description = "Server error", body = ErrorResponse),
+    ),
+    security(("bearer_auth" = [])),
+    tag = "Company Management"
+)]
+/// Removes a closure from a branch.
+///
+/// # Errors
+/// Returns an error if the branch or closure is not found or the user cannot manage the branch.
+pub async fn delete_branch_closure(
+    BranchManagerUser(_claims, user): BranchManagerUser,
+    State(state): State<AppState>,
+    Path((branch_id, closure_id)): Path<(String, String)>,
+) -> Result<Json<serde_json::Value>, (StatusCode, Json<serde_json::Value>)> {
+    BranchCalendarService::delete_closure(&state, &user, &branch_id, &closure_id)
+        .await
+        .map_err(|(status, err)| (status, Json(err)))?;
+
+    Ok(Json(json!({ "message": "Closure deleted successfully" })))
+}
@@ -1,6 +1,9 @@
 use crate::{
-    AppState, db,
-    dto::{CompanyResponse, ErrorResponse, ExportResponse, UpdateCompanyRequest},
+    AppState, attachments_db, db,
+    dto::{
+        CompanyResponse, ErrorResponse, ExportResponse, MissedLogAlertSettingsResponse,
+        UpdateCompanyRequest, UpdateMissedLogAlertSettingsRequest,
+    },
     exports_db, images_db, logs_db,
     middleware::{AuditRequestContext, ManageCompanyUser},
     services::company_service::{CompanyService, CompanyServiceError},
@@ -223,6 +226,96 @@ pub async fn get_company(
     Ok(Json(CompanyResponse::from(company)))
 }
 
+/// Maximum escalation delay for missed-log alerts (one week).
+const MAX_ESCALATION_HOURS: i32 = 168;
+
+#[utoipa::path(
+    get,
+    path = "/companies/{company_id}/missed-log-alerts",
+    responses(
+        (status = 200, description = "Missed log alert settings", body = MissedLogAlertSettingsResponse),
+        (status = 403, description = "Forbidden", body = ErrorResponse),
+        (status = 500, description = "Server error", body = ErrorResponse),
+    ),
+    security(("bearer_auth" = [])),
+    tag = "Company"
+)]
+pub async fn get_missed_log_alert_settings(
+    ManageCompanyUser(_claims, user): ManageCompanyUser,
+    State(state): State<AppState>,
+    axum::extract::Path(company_id): axum::extract::Path<String>,
+) -> Result<Json<MissedLogAlertSettingsResponse>, (StatusCode, Json<serde_json::Value>)> {
+    if user.company_id.as_ref() != Some(&company_id) {
+        return Err(err_forbidden("You can only view your own company"));
+    }
+
+    let settings = db::get_missed_log_alert_settings(&state.postgres, &company_id)
+        .await
+        .map_err(|e| {
+            tracing::error!("Failed to fetch missed log alert settings: {:?}", e);
+            err_internal("Database error")
+        })?;
+
+    Ok(Json(MissedLogAlertSettingsResponse::from(settings)))
+}
+
+#[utoipa::path(
+    put,
+    path = "/companies/{company_id}/missed-log-alerts",
+    request_body = UpdateMissedLogAlertSettingsRequest,
+    responses(
+        (status = 200, description = "Missed log alert settings updated", body = MissedLogAlertSettingsResponse),
+        (status = 400, description = "Invalid request", body = ErrorResponse),
+        (status = 403, description = "Forbidden", body = ErrorResponse),
+        (status = 500, description = "Server error", body = ErrorResponse),
+    ),
+    security(("bearer_auth" = [])),
+    tag = "Company"
+)]
+pub async fn update_missed_log_alert_settings(
+    ManageCompanyUser(_claims, user): ManageCompanyUser,
+    AuditRequestContext(audit_ctx): AuditRequestContext,
+    State(state): State<AppState>,
+    axum::extract::Path(company_id): axum::extract::Path<String>,
+    Json(payload): Json<UpdateMissedLogAlertSettingsRequest>,
+) -> Result<Json<MissedLogAlertSettingsResponse>, (StatusCode, Json<serde_json::Value>)> {
+    if user.company_id.as_ref() != Some(&company_id) {
+        return Err(err_forbidden("You can only update your own company"));
+    }
+
+    if !(0..=MAX_ESCALATION_HOURS).contains(&payload.escalation_hours) {
+        return Err(err_bad_request(&format!(
+            "escalation_hours must be between 0 and {MAX_ESCALATION_HOURS}"
+        )));
+    }
+
+    let settings = db::upsert_missed_log_alert_settings(
+        &state.postgres,
+        &company_id,
+        payload.enabled,
+        payload.escalation_hours,
+        payload.digest,
+    )
+    .await
+    .map_err(|e| {
+        tracing::error!("Failed to update missed log alert settings: {:?}", e);
+        err_internal("Database error")
+    })?;
+
+    AuditLogger::log_admin_action(
+        &state.postgres,
+        user.id.clone(),
+        format!(
+            "Updated missed log alert settings for company {} (enabled: {}, escalation_hours: {}, digest: {})",
+            company_id, settings.enabled, settings.escalation_hours, settings.digest
+        ),
+        crate::audit_ctx!(&audit_ctx, actor: &user),
+    )
+    .await;
+
+    Ok(Json(MissedLogAlertSettingsResponse::from(settings)))
+}
+
 #[utoipa::path(
     put,
     path = "/companies/{company_id}",
@@ -255,11 +348,31 @@ pub async fn update_company(
         return Err(err_bad_request("Company address cannot be empty"));
     }
 
+    let timezone = match payload.timezone.as_deref().map(str::trim) {
+        Some(tz) => {
+            if tz.parse::<chrono_tz::Tz>().is_err() {
+                return Err(err_bad_request("Unknown timezone"));
+            }
+            tz.to_string()
+        }
+        None => {
+            db::get_company_by_id(&state.postgres, &company_id)
+                .await
+                .map_err(|e| {
+                    tracing::error!("Failed to fetch company: {:?}", e);
+                    err_internal("Database error")
+                })?
+                .ok_or_else(|| err_not_found("Company not found"))?
+                .timezone
+        }
+    };
+
     let company = db::update_company(
         &state.postgres,
         &company_id,
         &payload.name,
         &payload.address,
+        &timezone,
     )
     .await
     .map_err(|e| {
@@ -333,12 +446,13 @@ pub async fn export_company_data(
     let company_email = user.email.clone();
     let company_name = company.name.clone();
 
-    let log_templates = logs_db::get_templates_by_company(&state.mongodb, &company_id)
-        .await
-        .map_err(|e| {
-            tracing::error!("Failed to fetch log templates for export: {:?}", e);
-            err_internal("Failed to fetch log templates")
-        })?;
+    let log_templates =
+        logs_db::get_templates_by_company_including_archived(&state.mongodb, &company_id)
+            .await
+            .map_err(|e| {
+                tracing::error!("Failed to fetch log templates for export: {:?}", e);
+                err_internal("Failed to fetch log templates")
+            })?;
 
     let log_entries = logs_db::get_company_log_entries(&state.mongodb, &company_id)
         .await
@@ -347,45 +461,42 @@ pub async fn export_company_data(
             err_internal("Failed to fetch log entries")
         })?;
 
+    let attachments = attachments_db::get_company_attachments(&state.mongodb, &company_id)
+        .await
+        .map_err(|e| {
+            tracing::error!("Failed to fetch attachments for export: {:?}", e);
+            err_internal("Failed to fetch attachments")
+        })?;
+
     let exported_at = chrono::Utc::now();
 
     let templates_json = serde_json::to_string_pretty(&log_templates).unwrap_or_default();
     let entries_json = serde_json::to_string_pretty(&log_entries).unwrap_or_default();
+    let attachments_json = serde_json::to_string_pretty(&attachments).unwrap_or_default();
 
-    let zip_data = tokio::task::spawn_blocking(move || -> anyhow::Result<Vec<u8>> {
-        use std::io::Write;
-        let mut buf = Vec::new();
-        {
-            let mut zip = zip::ZipWriter::new(std::io::Cursor::new(&mut buf));
-            let options = zip::write::SimpleFileOptions::default()
-                .compression_method(zip::CompressionMethod::Deflated);
-
-            zip.start_file("templates.json", options)?;
-            zip.write_all(templates_json.as_bytes())?;
-
-            zip.start_file("log_entries.json", options)?;
-            zip.write_all(entries_json.as_bytes())?;
-
-            zip.finish()?;
-        }
-        Ok(buf)
-    })
-    .await
-    .map_err(|e| {
-        tracing::error!("ZIP generation failed: {:?}", e);
-        err_internal("Failed to generate export")
-    })?
-    .map_err(|e| {
-        tracing::error!("ZIP generation error: {:?}", e);
-        err_internal("Failed to generate export")
+    let (filename, file_path) = exports_db::new_export_path(&company_id).map_err(|e| {
+        tracing::error!("Failed to create export file: {:?}", e);
+        err_internal("Failed to save export")
     })?;
-
-    let filename = exports_db::save_export(&company_id, &zip_data)
-        .await
-        .map_err(|e| {
-            tracing::error!("Failed to save export file: {:?}", e);
-            err_internal("Failed to save export")
-        })?;
+    let written = write_export_zip(
+        &state,
+        &file_path,
+        vec![
+            ("templates.json", templates_json),
+            ("log_entries.json", entries_json),
+            ("attachments.json", attachments_json),
+        ],
+        &attachments,
+    )
+    .await;
+    if let Err(e) = written {
+        tracing::error!("ZIP generation failed: {:?}", e);
+        if let Err(e) = exports_db::delete_export(&filename).await {
+            tracing::warn!("Failed to delete incomplete export: {:?}", e);
+        }
+        return Err(err_internal("Failed to generate export"));
+    }
+    tracing::info!("Export saved: {}", filename);
 
     db::mark_company_data_exported(&state.postgres, &company_id)
         .await
@@ -425,6 +536,72 @@ pub async fn export_company_data(
     }))
 }
 
+enum ExportPart {
+    File(String),
+    Data(Vec<u8>),
+}
+
+/// Writes a company export to `path`. Attachments are streamed into the archive chunk by
+/// chunk, so no attachment is held in memory whole.
+async fn write_export_zip(
+    state: &AppState,
+    path: &std::path::Path,
+    documents: Vec<(&'static str, String)>,
+    attachments: &[attachments_db::AttachmentDocument],
+) -> anyhow::Result<()> {
+    let (tx, mut rx) = tokio::sync::mpsc::channel::<ExportPart>(8);
+    let path = path.to_path_buf();
+    let writer = tokio::task::spawn_blocking(move || -> anyhow::Result<()> {
+        use std::io::Write;
+        let file = std::io::BufWriter::new(std::fs::File::create(&path)?);
+        let mut zip = zip::ZipWriter::new(file);
+        let options = zip::write::SimpleFileOptions::default()
+            .compression_method(zip::CompressionMethod::Deflated);
+
+        for (name, contents) in documents {
+            zip.start_file(name, options)?;
+            zip.write_all(contents.as_bytes())?;
+        }
+        while let Some(part) = rx.blocking_recv() {
+            match part {
+                ExportPart::File(name) => zip.start_file(name, options)?,
+                ExportPart::Data(data) => zip.write_all(&data)?,
+            }
+        }
+
+        zip.finish()?.flush()?;
+        Ok(())
+    });
+
+    let mut read = Ok(());
+    for attachment in attachments {
+        let name = format!(
+            "attachments/{}/{}_{}",
+            attachment.entry_id, attachment.attachment_id, attachment.filename
+        );
+        // A closed channel means the writer failed; its error is reported below
+        if tx.send(ExportPart::File(name)).await.is_err() {
+            break;
+        }
+        read = attachments_db::read_attachment_chunks(&state.mongodb, attachment, |chunk| {
+            let tx = tx.clone();
+            async move {
+                tx.send(ExportPart::Data(chunk))
+                    .await
+                    .map_err(|_| anyhow::anyhow!("Export writer stopped"))
+            }
+        })
+        .await;
+        if read.is_err() {
+            break;
+        }
+    }
+    drop(tx);
+
+    writer.await??;
+    read
+}
+
 #[utoipa::path(
     get,
     path = "/companies/{company_id}/export/download/{filename}",
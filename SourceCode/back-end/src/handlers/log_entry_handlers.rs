@@ -1,12 +1,15 @@
 use std::collections::{HashMap, HashSet};
 
 use crate::{
-    AppState,
+    AppState, db,
     dto::{
-        CreateLogEntryRequest, CreateLogEntryResponse, CreateReportRunRequest,
-        CreateReportRunResponse, DeleteReportRunResponse, DueFormInfo, DueFormsResponse,
-        ErrorResponse, ListLogEntriesResponse, ListReportRunsResponse, LogEntryResponse,
-        ReportRunResponse, SubmitLogEntryResponse, UpdateLogEntryRequest, UseReportRunResponse,
+        CloseCorrectiveActionIssueRequest, CorrectiveActionIssueResponse, CreateLogEntryRequest,
+        CreateLogEntryResponse, CreateReportRunRequest, CreateReportRunResponse,
+        DeleteReportRunResponse, DueFormInfo, DueFormsResponse, EntryHistoryResponse,
+        ErrorResponse, ExportLogEntriesQuery, ListCorrectiveActionIssuesResponse,
+        ListLogEntriesResponse, ListReportRunsResponse, LogEntryResponse, ReportRunParams,
+        ReportRunResponse, ReviewLogEntryRequest, ReviewLogEntryResponse, StartableFormInfo,
+        SubmitLogEntryRequest, SubmitLogEntryResponse, UpdateLogEntryRequest, UseReportRunResponse,
     },
     logs_db::{self, LogStatus},
     middleware::{AnyAuthUser, BranchManagerUser, ReadBranchUser},
@@ -15,11 +18,64 @@ use crate::{
 use axum::{
     Json,
     extract::{Path, Query, State},
-    http::StatusCode,
+    http::{StatusCode, header},
 };
+use serde::Deserialize;
 use serde_json::json;
 use uuid::Uuid;
 
+/// Loads the timezones a company's schedules are evaluated in.
+async fn company_timezones(
+    state: &AppState,
+    company_id: &str,
+) -> Result<db::CompanyTimezones, (StatusCode, Json<serde_json::Value>)> {
+    db::get_company_timezones(&state.postgres, company_id)
+        .await
+        .map_err(|e| {
+            tracing::error!("Failed to get company timezones: {:?}", e);
+            (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                Json(json!({ "error": "Failed to get company timezone" })),
+            )
+        })
+}
+
+/// Loads the opening hours and closures that suppress due periods at a company's branches.
+async fn company_calendars(
+    state: &AppState,
+    company_id: &str,
+) -> Result<db::CompanyCalendars, (StatusCode, Json<serde_json::Value>)> {
+    db::get_company_calendars(&state.postgres, company_id)
+        .await
+        .map_err(|e| {
+            tracing::error!("Failed to get branch calendars: {:?}", e);
+            (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                Json(json!({ "error": "Failed to get branch calendars" })),
+            )
+        })
+}
+
+/// Resolves the layout an entry was captured against, so listings only fetch each
+/// template version once.
+async fn entry_template_layout(
+    state: &AppState,
+    template: &logs_db::TemplateDocument,
+    template_version: Option<u16>,
+    archived_layouts: &mut logs_db::TemplateLayoutCache,
+) -> Result<logs_db::TemplateLayout, (StatusCode, Json<serde_json::Value>)> {
+    archived_layouts
+        .entry_layout(&state.mongodb, template, template_version)
+        .await
+        .map_err(|e| {
+            tracing::error!("Failed to get template version: {:?}", e);
+            (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                Json(json!({ "error": "Failed to get template version" })),
+            )
+        })
+}
+
 #[utoipa::path(
     get,
     path = "/logs/entries/due",
@@ -34,6 +90,8 @@ use uuid::Uuid;
 )]
 /// Lists all log forms that are due today for the current user's company.
 ///
+/// Repeatable and unscheduled templates are never due and are listed in `startable` instead.
+///
 /// # Errors
 /// Returns an error if the user is not authorized or if the query fails.
 pub async fn list_due_forms_today(
@@ -51,22 +109,47 @@ pub async fn list_due_forms_today(
         Json(json!({ "error": "User is not associated with a company" })),
     ))?;
 
-    let templates =
+    let (templates, startable_templates) =
         services::LogEntryService::list_due_forms(&state, &company_id, user.branch_id.as_deref())
             .await
             .map_err(|(status, err)| (status, Json(err)))?;
 
     let now = chrono::Utc::now();
+    let timezones = company_timezones(&state, &company_id).await?;
+    let template_timezone =
+        |template: &logs_db::TemplateDocument| timezones.for_branch(template.branch_id.as_deref());
+    let calendars = company_calendars(&state, &company_id).await?;
+    let open_missed_periods = |template: &logs_db::TemplateDocument, last_period: Option<&str>| {
+        let created_at = template.created_at.to_rfc3339();
+        let mut periods = logs_db::get_missed_periods(
+            &template.schedule,
+            last_period,
+            Some(&created_at),
+            template_timezone(template),
+        );
+        periods.retain(|period| {
+            calendars.is_period_open(template.branch_id.as_deref(), &template.schedule, period)
+        });
+        periods
+    };
+    let is_due_today = |template: &logs_db::TemplateDocument| {
+        let tz = template_timezone(template);
+        logs_db::is_form_due_today(&template.schedule, tz)
+            && calendars.is_period_open(
+                template.branch_id.as_deref(),
+                &template.schedule,
+                &logs_db::format_period_for_schedule(&template.schedule, tz),
+            )
+    };
 
     // First, fetch latest submitted entries for ALL templates to get actual last periods
-    let all_template_names: Vec<String> =
-        templates.iter().map(|t| t.template_name.clone()).collect();
+    let all_template_ids: Vec<String> = templates.iter().map(|t| t.template_id.clone()).collect();
 
     let latest_submitted_entries = logs_db::get_latest_submitted_entries_batch(
         &state.mongodb,
         &user.id,
         &company_id,
-        &all_template_names,
+        &all_template_ids,
     )
     .await
     .map_err(|e| {
@@ -83,49 +166,40 @@ pub async fn list_due_forms_today(
     let mut accumulated = 0usize;
 
     for template in &templates {
-        let last_submitted = latest_submitted_entries.get(&template.template_name);
+        let last_submitted = latest_submitted_entries.get(&template.template_id);
         let last_period = last_submitted.as_ref().map(|e| e.period.as_str());
-        let created_at = template.created_at.to_rfc3339();
-        let missed_periods =
-            logs_db::get_missed_periods(&template.schedule, last_period, Some(&created_at));
-        let missed_count = missed_periods.len();
-        let due_today = if logs_db::is_form_due_today(&template.schedule) {
-            1
-        } else {
-            0
-        };
+        let missed_count = open_missed_periods(template, last_period).len();
+        let due_today = usize::from(is_due_today(template));
         let total_due = missed_count + due_today;
 
         if accumulated < max as usize {
             needed_templates.push(template);
-            template_due_counts.insert(template.template_name.clone(), total_due);
+            template_due_counts.insert(template.template_id.clone(), total_due);
             accumulated += total_due;
         }
     }
 
-    let needed_template_names: Vec<String> = needed_templates
+    let needed_template_ids: Vec<String> = needed_templates
         .iter()
-        .map(|t| t.template_name.clone())
+        .map(|t| t.template_id.clone())
         .collect();
 
     let mut all_missed_periods: Vec<String> = Vec::new();
     let mut template_missed_periods: HashMap<String, Vec<String>> = HashMap::new();
 
     for template in &needed_templates {
-        let last_submitted = latest_submitted_entries.get(&template.template_name);
+        let last_submitted = latest_submitted_entries.get(&template.template_id);
         let last_period = last_submitted.as_ref().map(|e| e.period.as_str());
-        let created_at = template.created_at.to_rfc3339();
-        let mut missed_periods =
-            logs_db::get_missed_periods(&template.schedule, last_period, Some(&created_at));
+        let mut missed_periods = open_missed_periods(template, last_period);
         missed_periods.reverse();
         all_missed_periods.extend(missed_periods.clone());
-        template_missed_periods.insert(template.template_name.clone(), missed_periods);
+        template_missed_periods.insert(template.template_id.clone(), missed_periods);
     }
 
     let periods_with_entries = logs_db::get_periods_with_entries_batch(
         &state.mongodb,
         &company_id,
-        &needed_template_names,
+        &needed_template_ids,
         &all_missed_periods,
     )
     .await
@@ -141,11 +215,10 @@ pub async fn list_due_forms_today(
     let mut seen_forms: HashSet<(String, String)> = HashSet::new();
 
     for template in needed_templates {
-        let last_submitted = latest_submitted_entries
-            .get(&template.template_name)
-            .cloned();
-        let missed_periods = template_missed_periods.get(&template.template_name);
-        let periods_with_entries_map = periods_with_entries.get(&template.template_name);
+        let last_submitted = latest_submitted_entries.get(&template.template_id).cloned();
+        let missed_periods = template_missed_periods.get(&template.template_id);
+        let periods_with_entries_map = periods_with_entries.get(&template.template_id);
+        let tz = template_timezone(template);
 
         if let Some(missed_periods) = missed_periods {
             for period in missed_periods {
@@ -155,7 +228,7 @@ pub async fn list_due_forms_today(
                     continue;
                 }
 
-                let form_key = (template.template_name.clone(), period.clone());
+                let form_key = (template.template_id.clone(), period.clone());
                 if seen_forms.contains(&form_key) {
                     continue;
                 }
@@ -165,8 +238,12 @@ pub async fn list_due_forms_today(
                     break;
                 }
 
-                let status =
-                    logs_db::get_availability_status_for_period(&template.schedule, period, now);
+                let status = logs_db::get_availability_status_for_period(
+                    &template.schedule,
+                    period,
+                    now,
+                    tz,
+                );
 
                 let processed_layout = logs_db::process_template_layout_with_period_string(
                     &template.template_layout,
@@ -174,10 +251,11 @@ pub async fn list_due_forms_today(
                 );
 
                 let available_from =
-                    logs_db::get_available_from_datetime(&template.schedule, period);
-                let due_at = logs_db::get_due_at_datetime(&template.schedule, period);
+                    logs_db::get_available_from_datetime(&template.schedule, period, tz);
+                let due_at = logs_db::get_due_at_datetime(&template.schedule, period, tz);
 
                 due_forms.push(DueFormInfo {
+                    template_id: template.template_id.clone(),
                     template_name: template.template_name.clone(),
                     template_layout: processed_layout,
                     last_submitted: last_submitted
@@ -196,13 +274,14 @@ pub async fn list_due_forms_today(
             break;
         }
 
-        if logs_db::is_form_due_today(&template.schedule) {
+        if is_due_today(template) {
             let has_submitted = logs_db::has_submitted_entry_for_current_period(
                 &state.mongodb,
                 &user.id,
                 &company_id,
-                &template.template_name,
+                &template.template_id,
                 &template.schedule.frequency,
+                tz,
             )
             .await
             .map_err(|e| {
@@ -218,40 +297,46 @@ pub async fn list_due_forms_today(
                     &state.mongodb,
                     &user.id,
                     &company_id,
-                    &template.template_name,
+                    &template.template_id,
                     &template.schedule.frequency,
+                    tz,
                 )
                 .await
                 .ok()
                 .flatten();
 
-                let processed_layout = logs_db::process_template_layout_with_period(
+                let period = logs_db::format_period_for_schedule(&template.schedule, tz);
+                let processed_layout = logs_db::process_template_layout_with_period_string(
                     &template.template_layout,
-                    &template.schedule.frequency,
+                    &period,
                 );
 
-                let period = logs_db::format_period_for_frequency(&template.schedule.frequency);
                 let available_from =
-                    logs_db::get_available_from_datetime(&template.schedule, &period);
-                let due_at = logs_db::get_due_at_datetime(&template.schedule, &period);
+                    logs_db::get_available_from_datetime(&template.schedule, &period, tz);
+                let due_at = logs_db::get_due_at_datetime(&template.schedule, &period, tz);
 
-                let form_key = (template.template_name.clone(), period.clone());
+                let form_key = (template.template_id.clone(), period.clone());
                 if seen_forms.contains(&form_key) {
                     continue;
                 }
                 seen_forms.insert(form_key);
 
-                let status =
-                    logs_db::get_availability_status_for_period(&template.schedule, &period, now);
+                let status = logs_db::get_availability_status_for_period(
+                    &template.schedule,
+                    &period,
+                    now,
+                    tz,
+                );
 
                 let derived_draft_status = draft_entry.as_ref().map(|e| {
-                    logs_db::derive_log_status(e.status, &template.schedule, &period, now)
+                    logs_db::derive_log_status(e.status, &template.schedule, &period, now, tz)
                         .0
                         .as_str()
                         .to_string()
                 });
 
                 due_forms.push(DueFormInfo {
+                    template_id: template.template_id.clone(),
                     template_name: template.template_name.clone(),
                     template_layout: processed_layout,
                     last_submitted: last_submitted
@@ -267,8 +352,16 @@ pub async fn list_due_forms_today(
     }
 
     let forms = due_forms;
+    let startable = startable_templates
+        .into_iter()
+        .map(|template| StartableFormInfo {
+            template_id: template.template_id,
+            template_name: template.template_name,
+            entry_mode: template.entry_mode,
+        })
+        .collect();
 
-    Ok(Json(DueFormsResponse { forms }))
+    Ok(Json(DueFormsResponse { forms, startable }))
 }
 
 #[utoipa::path(
@@ -288,23 +381,22 @@ pub async fn list_due_forms_today(
 /// Creates a new log entry draft.
 ///
 /// # Errors
-/// Returns an error if the template name is empty or if entry creation fails.
+/// Returns an error if neither a template id nor name is given or if entry creation fails.
 pub async fn create_log_entry(
     AnyAuthUser(_claims, user): AnyAuthUser,
     State(state): State<AppState>,
     Json(payload): Json<CreateLogEntryRequest>,
 ) -> Result<(StatusCode, Json<CreateLogEntryResponse>), (StatusCode, Json<serde_json::Value>)> {
-    if payload.template_name.is_empty() {
-        return Err((
-            StatusCode::BAD_REQUEST,
-            Json(json!({ "error": "Template name is required" })),
-        ));
-    }
+    let template_key = services::TemplateService::template_key(
+        payload.template_id.as_deref(),
+        payload.template_name.as_deref(),
+    )
+    .map_err(|(status, err)| (status, Json(err)))?;
 
     let entry_id = services::LogEntryService::create_log_entry(
         &state,
         &user,
-        &payload.template_name,
+        template_key,
         payload.period.as_deref(),
     )
     .await
@@ -350,7 +442,7 @@ pub async fn get_log_entry(
         Json(json!({ "error": "User is not associated with a company" })),
     ))?;
 
-    let template = logs_db::get_template_by_name(&state.mongodb, &entry.template_name, &company_id)
+    let template = logs_db::get_template_by_id(&state.mongodb, &entry.template_id, &company_id)
         .await
         .map_err(|e| {
             tracing::error!("Failed to get template: {:?}", e);
@@ -364,19 +456,28 @@ pub async fn get_log_entry(
             Json(json!({ "error": "Template not found" })),
         ))?;
 
-    let processed_layout = logs_db::process_template_layout_with_period_string(
-        &template.template_layout,
-        &entry.period,
-    );
+    let template_layout =
+        logs_db::get_entry_template_layout(&state.mongodb, &template, entry.template_version)
+            .await
+            .map_err(|e| {
+                tracing::error!("Failed to get template version: {:?}", e);
+                (
+                    StatusCode::INTERNAL_SERVER_ERROR,
+                    Json(json!({ "error": "Failed to get template version" })),
+                )
+            })?;
 
-    let availability = logs_db::get_availability_status_for_period(
-        &template.schedule,
-        &entry.period,
-        chrono::Utc::now(),
-    );
+    let processed_layout =
+        logs_db::process_template_layout_with_period_string(&template_layout, &entry.period);
+
+    let tz = company_timezones(&state, &company_id)
+        .await?
+        .for_branch(entry.branch_id.as_deref());
+    let availability = template.availability_for_period(&entry.period, chrono::Utc::now(), tz);
 
     Ok(Json(LogEntryResponse {
         id: entry.entry_id,
+        template_id: entry.template_id,
         template_name: entry.template_name,
         template_layout: processed_layout,
         entry_data: entry.entry_data,
@@ -386,6 +487,10 @@ pub async fn get_log_entry(
         updated_at: entry.updated_at.to_rfc3339(),
         submitted_at: entry.submitted_at.map(|ts| ts.to_rfc3339()),
         period: entry.period,
+        template_version: entry.template_version,
+        flagged: entry.flagged,
+        corrective_action: entry.corrective_action,
+        review: entry.review,
     }))
 }
 
@@ -395,6 +500,7 @@ pub async fn get_log_entry(
     request_body = UpdateLogEntryRequest,
     responses(
         (status = 200, description = "Log entry updated successfully", body = LogEntryResponse),
+        (status = 400, description = "Entry data failed validation against the template fields", body = serde_json::Value),
         (status = 401, description = "Unauthorized - invalid or missing token", body = ErrorResponse),
         (status = 403, description = "Forbidden - entry does not belong to user", body = ErrorResponse),
         (status = 404, description = "Entry not found", body = ErrorResponse),
@@ -428,7 +534,7 @@ pub async fn update_log_entry(
     ))?;
 
     let template =
-        logs_db::get_template_by_name(&state.mongodb, &updated_entry.template_name, &company_id)
+        logs_db::get_template_by_id(&state.mongodb, &updated_entry.template_id, &company_id)
             .await
             .map_err(|e| {
                 tracing::error!("Failed to get template: {:?}", e);
@@ -442,19 +548,34 @@ pub async fn update_log_entry(
                 Json(json!({ "error": "Template not found" })),
             ))?;
 
+    let template_layout = logs_db::get_entry_template_layout(
+        &state.mongodb,
+        &template,
+        updated_entry.template_version,
+    )
+    .await
+    .map_err(|e| {
+        tracing::error!("Failed to get template version: {:?}", e);
+        (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(json!({ "error": "Failed to get template version" })),
+        )
+    })?;
+
     let processed_layout = logs_db::process_template_layout_with_period_string(
-        &template.template_layout,
+        &template_layout,
         &updated_entry.period,
     );
 
-    let availability = logs_db::get_availability_status_for_period(
-        &template.schedule,
-        &updated_entry.period,
-        chrono::Utc::now(),
-    );
+    let tz = company_timezones(&state, &company_id)
+        .await?
+        .for_branch(updated_entry.branch_id.as_deref());
+    let availability =
+        template.availability_for_period(&updated_entry.period, chrono::Utc::now(), tz);
 
     Ok(Json(LogEntryResponse {
         id: updated_entry.entry_id,
+        template_id: updated_entry.template_id,
         template_name: updated_entry.template_name,
         template_layout: processed_layout,
         entry_data: updated_entry.entry_data,
@@ -464,14 +585,20 @@ pub async fn update_log_entry(
         updated_at: updated_entry.updated_at.to_rfc3339(),
         submitted_at: updated_entry.submitted_at.map(|ts| ts.to_rfc3339()),
         period: updated_entry.period,
+        template_version: updated_entry.template_version,
+        flagged: updated_entry.flagged,
+        corrective_action: updated_entry.corrective_action,
+        review: updated_entry.review,
     }))
 }
 
 #[utoipa::path(
     post,
     path = "/logs/entries/{entry_id}/submit",
+    request_body(content = Option<SubmitLogEntryRequest>, description = "Optional corrective action note, required when a reading is out of range"),
     responses(
         (status = 200, description = "Log entry submitted successfully", body = SubmitLogEntryResponse),
+        (status = 400, description = "Entry data failed validation, or a corrective action note is required for out-of-range readings", body = serde_json::Value),
         (status = 401, description = "Unauthorized - invalid or missing token", body = ErrorResponse),
         (status = 403, description = "Forbidden - entry does not belong to user", body = ErrorResponse),
         (status = 404, description = "Entry not found", body = ErrorResponse),
@@ -488,13 +615,28 @@ pub async fn submit_log_entry(
     AnyAuthUser(_claims, user): AnyAuthUser,
     State(state): State<AppState>,
     axum::extract::Path(entry_id): axum::extract::Path<String>,
+    payload: Option<Json<SubmitLogEntryRequest>>,
 ) -> Result<Json<SubmitLogEntryResponse>, (StatusCode, Json<serde_json::Value>)> {
-    services::LogEntryService::submit_log_entry(&state, &user.id, &entry_id)
-        .await
-        .map_err(|(status, err)| (status, Json(err)))?;
+    let corrective_action = payload.and_then(|Json(p)| p.corrective_action);
+
+    let issue_id = services::LogEntryService::submit_log_entry(
+        &state,
+        &user.id,
+        &entry_id,
+        corrective_action.as_deref(),
+    )
+    .await
+    .map_err(|(status, err)| (status, Json(err)))?;
+
+    let message = if issue_id.is_some() {
+        "Log entry submitted and flagged for out-of-range readings."
+    } else {
+        "Log entry submitted successfully."
+    };
 
     Ok(Json(SubmitLogEntryResponse {
-        message: "Log entry submitted successfully.".to_string(),
+        message: message.to_string(),
+        issue_id,
     }))
 }
 
@@ -526,6 +668,7 @@ pub async fn unsubmit_log_entry(
 
     Ok(Json(SubmitLogEntryResponse {
         message: "Log entry returned to draft successfully.".to_string(),
+        issue_id: None,
     }))
 }
 
@@ -558,14 +701,219 @@ pub async fn delete_log_entry(
     Ok(Json(json!({ "message": "Log entry deleted successfully" })))
 }
 
+#[derive(Debug, Deserialize, utoipa::IntoParams)]
+pub struct CompanyLogEntriesQuery {
+    /// Comma-separated branch IDs to filter by (company managers and HQ only)
+    pub branch_ids: Option<String>,
+    /// Template id filter
+    pub template_id: Option<String>,
+    /// Template name filter, used when `template_id` is not given
+    pub template_name: Option<String>,
+    /// Only entries created by this user
+    pub user_id: Option<String>,
+    /// Exact period filter, e.g. `01/03/2026`
+    pub period: Option<String>,
+    /// Status filter: draft, submitted, reviewed, approved or overdue
+    pub status: Option<String>,
+    /// ISO 8601 datetime or YYYY-MM-DD lower bound on creation time (inclusive)
+    pub created_from: Option<String>,
+    /// ISO 8601 datetime or YYYY-MM-DD upper bound on creation time (inclusive)
+    pub created_to: Option<String>,
+    /// Sort field: `created_at` (default) or `updated_at`
+    pub sort: Option<String>,
+    /// Sort order: `desc` (default) or `asc`
+    pub order: Option<String>,
+    /// Number of results per page (default 50, max 200). Without `limit` or `cursor` all matching entries are returned.
+    pub limit: Option<i64>,
+    /// Cursor for pagination, as returned in `next_cursor`
+    pub cursor: Option<String>,
+}
+
+const DEFAULT_COMPANY_LOG_ENTRIES_LIMIT: i64 = 50;
+
+/// Parses an optional filter bound given as an RFC 3339 datetime or a plain date.
+/// Plain dates cover the whole day, so an upper bound resolves to the end of that day.
+fn parse_created_bound(
+    value: Option<&str>,
+    end_of_day: bool,
+) -> Result<Option<chrono::DateTime<chrono::Utc>>, (StatusCode, Json<serde_json::Value>)> {
+    let Some(raw) = value.map(str::trim).filter(|v| !v.is_empty()) else {
+        return Ok(None);
+    };
+
+    if let Ok(dt) = chrono::DateTime::parse_from_rfc3339(raw) {
+        return Ok(Some(dt.with_timezone(&chrono::Utc)));
+    }
+
+    let date = chrono::NaiveDate::parse_from_str(raw, "%Y-%m-%d").map_err(|_| {
+        (
+            StatusCode::BAD_REQUEST,
+            Json(json!({ "error": format!("Invalid datetime format: {raw}") })),
+        )
+    })?;
+    let time = if end_of_day {
+        chrono::NaiveTime::from_hms_nano_opt(23, 59, 59, 999_999_999)
+    } else {
+        chrono::NaiveTime::from_hms_opt(0, 0, 0)
+    };
+    Ok(time.map(|t| date.and_time(t).and_utc()))
+}
+
+/// Sort order and pagination of an entry listing.
+struct EntriesPageOptions {
+    sort: logs_db::LogEntrySort,
+    descending: bool,
+    limit: Option<i64>,
+    cursor: Option<(chrono::DateTime<chrono::Utc>, String)>,
+}
+
+impl EntriesPageOptions {
+    /// Without `limit` or `cursor` every matching entry is returned.
+    fn parse(
+        sort: Option<&str>,
+        order: Option<&str>,
+        limit: Option<i64>,
+        cursor: Option<&str>,
+    ) -> Result<Self, (StatusCode, Json<serde_json::Value>)> {
+        let sort = match sort {
+            None | Some("") => logs_db::LogEntrySort::default(),
+            Some(sort) => logs_db::LogEntrySort::from_param(sort).ok_or((
+                StatusCode::BAD_REQUEST,
+                Json(json!({ "error": "sort must be 'created_at' or 'updated_at'" })),
+            ))?,
+        };
+        let descending = match order {
+            None | Some("" | "desc") => true,
+            Some("asc") => false,
+            Some(_) => {
+                return Err((
+                    StatusCode::BAD_REQUEST,
+                    Json(json!({ "error": "order must be 'asc' or 'desc'" })),
+                ));
+            }
+        };
+
+        let cursor = match cursor.filter(|c| !c.is_empty()) {
+            Some(cursor) => Some(logs_db::parse_log_entries_cursor(cursor, sort).map_err(
+                |_| {
+                    (
+                        StatusCode::BAD_REQUEST,
+                        Json(json!({ "error": "Invalid cursor" })),
+                    )
+                },
+            )?),
+            None => None,
+        };
+        let limit = if limit.is_some() || cursor.is_some() {
+            Some(limit.unwrap_or(DEFAULT_COMPANY_LOG_ENTRIES_LIMIT))
+        } else {
+            None
+        };
+
+        Ok(Self {
+            sort,
+            descending,
+            limit,
+            cursor,
+        })
+    }
+
+    async fn fetch(
+        self,
+        state: &AppState,
+        company_id: &str,
+        filters: &logs_db::LogEntryFilters,
+    ) -> Result<logs_db::LogEntriesPage, (StatusCode, Json<serde_json::Value>)> {
+        logs_db::get_company_log_entries_page(
+            &state.mongodb,
+            company_id,
+            filters,
+            self.sort,
+            self.descending,
+            self.limit,
+            self.cursor,
+        )
+        .await
+        .map_err(|e| {
+            tracing::error!("Failed to get log entries: {:?}", e);
+            (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                Json(json!({ "error": "Failed to get log entries" })),
+            )
+        })
+    }
+}
+
+/// Query filter for entries deriving to `status`. Drafts derive to overdue once their period
+/// is past due, so the past-due periods of the matching drafts are worked out first.
+async fn entry_status_filter(
+    state: &AppState,
+    company_id: &str,
+    filters: &logs_db::LogEntryFilters,
+    status: LogStatus,
+    now: chrono::DateTime<chrono::Utc>,
+    timezones: &db::CompanyTimezones,
+) -> Result<logs_db::EntryStatusFilter, (StatusCode, Json<serde_json::Value>)> {
+    if !matches!(status, LogStatus::Draft | LogStatus::Overdue) {
+        return Ok(logs_db::EntryStatusFilter::Stored(status));
+    }
+
+    let drafts = logs_db::get_draft_periods(&state.mongodb, company_id, filters)
+        .await
+        .map_err(|e| {
+            tracing::error!("Failed to get draft periods: {:?}", e);
+            (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                Json(json!({ "error": "Failed to get log entries" })),
+            )
+        })?;
+    let template_ids: Vec<String> = drafts
+        .iter()
+        .map(|draft| draft.template_id.clone())
+        .collect::<HashSet<_>>()
+        .into_iter()
+        .collect();
+    let templates: HashMap<String, logs_db::TemplateDocument> =
+        logs_db::get_templates_by_ids(&state.mongodb, company_id, &template_ids)
+            .await
+            .map_err(|err| {
+                tracing::error!("Failed to get templates: {:?}", err);
+                (
+                    StatusCode::INTERNAL_SERVER_ERROR,
+                    Json(json!({ "error": "Failed to get template" })),
+                )
+            })?
+            .into_iter()
+            .map(|t| (t.template_id.clone(), t))
+            .collect();
+
+    let past_due = drafts
+        .into_iter()
+        .filter(|draft| {
+            templates.get(&draft.template_id).is_some_and(|template| {
+                let tz = timezones.for_branch(draft.branch_id.as_deref());
+                template
+                    .derive_entry_status(LogStatus::Draft, &draft.period, now, tz)
+                    .0
+                    == LogStatus::Overdue
+            })
+        })
+        .collect();
+
+    Ok(if status == LogStatus::Overdue {
+        logs_db::EntryStatusFilter::Overdue(past_due)
+    } else {
+        logs_db::EntryStatusFilter::OpenDrafts(past_due)
+    })
+}
+
 #[utoipa::path(
     get,
     path = "/logs/admin/entries",
-    params(
-        ("branch_ids" = String, Query, description = "Optional comma-separated list of branch IDs to filter by (managers only)"),
-    ),
+    params(CompanyLogEntriesQuery),
     responses(
         (status = 200, description = "Company log entries retrieved successfully", body = ListLogEntriesResponse),
+        (status = 400, description = "Invalid query parameters or unknown template", body = ErrorResponse),
         (status = 401, description = "Unauthorized", body = ErrorResponse),
         (status = 403, description = "Forbidden - only managers can view all entries", body = ErrorResponse),
         (status = 500, description = "Server error", body = ErrorResponse),
@@ -573,86 +921,153 @@ pub async fn delete_log_entry(
     security(("bearer_auth" = [])),
     tag = "Log Entries"
 )]
-/// Lists all log entries for the company or branch (managers only).
-pub async fn list_company_log_entries<S: ::std::hash::BuildHasher>(
+/// Lists log entries for the company or branch (managers only).
+///
+/// Filters are applied in the database. When `limit` or `cursor` is given the result is
+/// paginated and `next_cursor` is set while more entries remain. Status filtering uses the
+/// derived status, so drafts whose period is past due match `overdue` rather than `draft`.
+pub async fn list_company_log_entries(
     ReadBranchUser(_claims, user): ReadBranchUser,
     State(state): State<AppState>,
-    Query(params): Query<HashMap<String, String, S>>,
+    Query(params): Query<CompanyLogEntriesQuery>,
 ) -> Result<Json<ListLogEntriesResponse>, (StatusCode, Json<serde_json::Value>)> {
     let company_id = user.company_id.clone().ok_or((
         StatusCode::FORBIDDEN,
         Json(json!({ "error": "User is not associated with a company" })),
     ))?;
 
-    // Parse optional branch_ids parameter (comma-separated)
-    let branch_ids_param = params.get("branch_ids");
+    let page_options = EntriesPageOptions::parse(
+        params.sort.as_deref(),
+        params.order.as_deref(),
+        params.limit,
+        params.cursor.as_deref(),
+    )?;
+
+    let status_filter = match params.status.as_deref().filter(|s| !s.is_empty()) {
+        Some(status) => Some(LogStatus::from_str(status).ok_or((
+            StatusCode::BAD_REQUEST,
+            Json(json!({ "error": format!("Invalid status: {status}") })),
+        ))?),
+        None => None,
+    };
 
-    let entries = if user.can_manage_company() || user.is_readonly_hq() {
+    let branch_ids = if user.can_manage_company() || user.is_readonly_hq() {
         // Company manager/HQ - check if specific branches requested
-        if let Some(branch_ids_str) = branch_ids_param {
-            if branch_ids_str.is_empty() {
-                logs_db::get_company_log_entries(&state.mongodb, &company_id).await
-            } else {
-                let branch_ids: Vec<String> = branch_ids_str
-                    .split(',')
+        params
+            .branch_ids
+            .as_deref()
+            .filter(|ids| !ids.is_empty())
+            .map(|ids| {
+                ids.split(',')
                     .map(std::string::ToString::to_string)
-                    .collect();
-                logs_db::get_branches_log_entries(&state.mongodb, &company_id, &branch_ids).await
-            }
-        } else {
-            logs_db::get_company_log_entries(&state.mongodb, &company_id).await
-        }
+                    .collect()
+            })
     } else {
         // Branch manager - only their branch
-        let branch_id = user.branch_id.as_ref().ok_or((
+        let branch_id = user.branch_id.clone().ok_or((
             StatusCode::FORBIDDEN,
             Json(json!({ "error": "Branch manager has no branch assigned" })),
         ))?;
-        logs_db::get_branch_log_entries(&state.mongodb, &company_id, branch_id).await
-    }
-    .map_err(|e| {
-        tracing::error!("Failed to get log entries: {:?}", e);
-        (
-            StatusCode::INTERNAL_SERVER_ERROR,
-            Json(json!({ "error": "Failed to get log entries" })),
-        )
-    })?;
+        Some(vec![branch_id])
+    };
 
-    let mut response_entries = Vec::new();
-    for e in entries {
-        let template = logs_db::get_template_by_name(&state.mongodb, &e.template_name, &company_id)
+    let has_template_key = [&params.template_id, &params.template_name]
+        .iter()
+        .any(|key| key.as_deref().is_some_and(|key| !key.is_empty()));
+    let template_ids = if has_template_key {
+        let template_key = services::TemplateService::template_key(
+            params.template_id.as_deref(),
+            params.template_name.as_deref(),
+        )
+        .map_err(|(status, err)| (status, Json(err)))?;
+        let template = logs_db::get_template(&state.mongodb, template_key, &company_id)
             .await
-            .map_err(|err| {
-                tracing::error!("Failed to get template: {:?}", err);
+            .map_err(|e| {
+                tracing::error!("Failed to get template: {:?}", e);
                 (
                     StatusCode::INTERNAL_SERVER_ERROR,
                     Json(json!({ "error": "Failed to get template" })),
                 )
-            })?;
+            })?
+            .ok_or((
+                StatusCode::BAD_REQUEST,
+                Json(json!({ "error": "Template not found" })),
+            ))?;
+        Some(vec![template.template_id])
+    } else {
+        None
+    };
 
-        let (processed_layout, derived_status, availability_status) =
-            if let Some(template) = template {
-                let layout = logs_db::process_template_layout_with_period_string(
-                    &template.template_layout,
-                    &e.period,
-                );
-                let (status, availability) = logs_db::derive_log_status(
-                    e.status,
-                    &template.schedule,
-                    &e.period,
-                    chrono::Utc::now(),
-                );
-                (layout, status, availability)
-            } else {
+    let mut filters = logs_db::LogEntryFilters {
+        branch_ids,
+        template_ids,
+        user_id: params.user_id.filter(|id| !id.is_empty()),
+        period: params.period.filter(|period| !period.is_empty()),
+        status: None,
+        created_from: parse_created_bound(params.created_from.as_deref(), false)?,
+        created_to: parse_created_bound(params.created_to.as_deref(), true)?,
+    };
+
+    let now = chrono::Utc::now();
+    let timezones = company_timezones(&state, &company_id).await?;
+    if let Some(status) = status_filter {
+        filters.status = Some(
+            entry_status_filter(&state, &company_id, &filters, status, now, &timezones).await?,
+        );
+    }
+
+    let page = page_options.fetch(&state, &company_id, &filters).await?;
+
+    let template_ids: Vec<String> = page
+        .entries
+        .iter()
+        .map(|e| e.template_id.clone())
+        .collect::<HashSet<_>>()
+        .into_iter()
+        .collect();
+    let templates: HashMap<String, logs_db::TemplateDocument> =
+        logs_db::get_templates_by_ids(&state.mongodb, &company_id, &template_ids)
+            .await
+            .map_err(|err| {
+                tracing::error!("Failed to get templates: {:?}", err);
                 (
-                    Vec::new(),
-                    e.status,
-                    logs_db::AvailabilityStatus::NotAvailable,
+                    StatusCode::INTERNAL_SERVER_ERROR,
+                    Json(json!({ "error": "Failed to get template" })),
                 )
-            };
+            })?
+            .into_iter()
+            .map(|t| (t.template_id.clone(), t))
+            .collect();
+
+    let mut response_entries = Vec::new();
+    let mut archived_layouts = logs_db::TemplateLayoutCache::default();
+    for e in page.entries {
+        let (processed_layout, derived_status, availability_status) = if let Some(template) =
+            templates.get(&e.template_id)
+        {
+            let (status, availability) = template.derive_entry_status(
+                e.status,
+                &e.period,
+                now,
+                timezones.for_branch(e.branch_id.as_deref()),
+            );
+            let template_layout =
+                entry_template_layout(&state, template, e.template_version, &mut archived_layouts)
+                    .await?;
+            let layout =
+                logs_db::process_template_layout_with_period_string(&template_layout, &e.period);
+            (layout, status, availability)
+        } else {
+            (
+                Vec::new(),
+                e.status,
+                logs_db::AvailabilityStatus::NotAvailable,
+            )
+        };
 
         response_entries.push(LogEntryResponse {
             id: e.entry_id,
+            template_id: e.template_id,
             template_name: e.template_name,
             template_layout: processed_layout,
             entry_data: e.entry_data,
@@ -662,11 +1077,176 @@ pub async fn list_company_log_entries<S: ::std::hash::BuildHasher>(
             updated_at: e.updated_at.to_rfc3339(),
             submitted_at: e.submitted_at.map(|ts| ts.to_rfc3339()),
             period: e.period,
+            template_version: e.template_version,
+            flagged: e.flagged,
+            corrective_action: e.corrective_action,
+            review: e.review,
+        });
+    }
+
+    Ok(Json(ListLogEntriesResponse {
+        entries: response_entries,
+        next_cursor: page.next_cursor,
+    }))
+}
+
+#[derive(Debug, Deserialize, utoipa::IntoParams)]
+pub struct TemplateLogEntriesQuery {
+    /// Template id
+    pub template_id: Option<String>,
+    /// Template name, used when `template_id` is not given
+    pub template_name: Option<String>,
+    /// Comma-separated branch IDs to filter by (company managers and HQ only)
+    pub branch_ids: Option<String>,
+    /// Only entries created by this user (managers only; staff always see their own entries)
+    pub user_id: Option<String>,
+    /// Exact period filter, e.g. `01/03/2026`
+    pub period: Option<String>,
+    /// Status filter: draft, submitted, reviewed or approved
+    pub status: Option<String>,
+    /// ISO 8601 datetime or YYYY-MM-DD lower bound on creation time (inclusive)
+    pub created_from: Option<String>,
+    /// ISO 8601 datetime or YYYY-MM-DD upper bound on creation time (inclusive)
+    pub created_to: Option<String>,
+    /// Sort field: `created_at` (default) or `updated_at`
+    pub sort: Option<String>,
+    /// Sort order: `desc` (default) or `asc`
+    pub order: Option<String>,
+    /// Number of results per page (default 50, max 200). Without `limit` or `cursor` all matching entries are returned.
+    pub limit: Option<i64>,
+    /// Cursor for pagination, as returned in `next_cursor`
+    pub cursor: Option<String>,
+}
+
+#[utoipa::path(
+    get,
+    path = "/logs/templates/entries",
+    params(TemplateLogEntriesQuery),
+    responses(
+        (status = 200, description = "Template log entries retrieved successfully", body = ListLogEntriesResponse),
+        (status = 400, description = "Invalid query parameters or the template is scheduled", body = ErrorResponse),
+        (status = 401, description = "Unauthorized - invalid or missing token", body = ErrorResponse),
+        (status = 403, description = "Template is not available for your branch", body = ErrorResponse),
+        (status = 404, description = "Template not found", body = ErrorResponse),
+        (status = 500, description = "Server error", body = ErrorResponse),
+    ),
+    security(("bearer_auth" = [])),
+    tag = "Log Entries"
+)]
+/// Lists the entries of a repeatable or unscheduled template.
+///
+/// Staff see their own entries, branch managers their branch's entries and company managers
+/// and HQ every branch's. Pagination follows `/logs/admin/entries`.
+pub async fn list_template_log_entries(
+    AnyAuthUser(_claims, user): AnyAuthUser,
+    State(state): State<AppState>,
+    Query(params): Query<TemplateLogEntriesQuery>,
+) -> Result<Json<ListLogEntriesResponse>, (StatusCode, Json<serde_json::Value>)> {
+    let company_id = user.company_id.clone().ok_or((
+        StatusCode::FORBIDDEN,
+        Json(json!({ "error": "User is not associated with a company" })),
+    ))?;
+
+    let template_key = services::TemplateService::template_key(
+        params.template_id.as_deref(),
+        params.template_name.as_deref(),
+    )
+    .map_err(|(status, err)| (status, Json(err)))?;
+    let template = services::TemplateService::get_template(&state, &company_id, template_key)
+        .await
+        .map_err(|(status, err)| (status, Json(err)))?;
+    if template.is_scheduled() {
+        return Err((
+            StatusCode::BAD_REQUEST,
+            Json(
+                json!({ "error": "Scheduled templates have one entry per period; use /logs/admin/entries instead" }),
+            ),
+        ));
+    }
+
+    let sees_company = user.can_manage_company() || user.is_readonly_hq();
+    if !sees_company && template.branch_id != user.branch_id {
+        return Err((
+            StatusCode::FORBIDDEN,
+            Json(json!({ "error": "Template is not available for your branch" })),
+        ));
+    }
+
+    let page_options = EntriesPageOptions::parse(
+        params.sort.as_deref(),
+        params.order.as_deref(),
+        params.limit,
+        params.cursor.as_deref(),
+    )?;
+    let status_filter = match params.status.as_deref().filter(|s| !s.is_empty()) {
+        Some(status) => Some(LogStatus::from_str(status).ok_or((
+            StatusCode::BAD_REQUEST,
+            Json(json!({ "error": format!("Invalid status: {status}") })),
+        ))?),
+        None => None,
+    };
+
+    let branch_ids = if sees_company {
+        params
+            .branch_ids
+            .as_deref()
+            .filter(|ids| !ids.is_empty())
+            .map(|ids| {
+                ids.split(',')
+                    .map(std::string::ToString::to_string)
+                    .collect()
+            })
+    } else {
+        user.branch_id.clone().map(|branch_id| vec![branch_id])
+    };
+    let user_id = if user.can_read_manage_branch() {
+        params.user_id.filter(|id| !id.is_empty())
+    } else {
+        Some(user.id.clone())
+    };
+
+    let filters = logs_db::LogEntryFilters {
+        branch_ids,
+        template_ids: Some(vec![template.template_id.clone()]),
+        user_id,
+        period: params.period.filter(|period| !period.is_empty()),
+        status: status_filter.map(logs_db::EntryStatusFilter::Stored),
+        created_from: parse_created_bound(params.created_from.as_deref(), false)?,
+        created_to: parse_created_bound(params.created_to.as_deref(), true)?,
+    };
+    let page = page_options.fetch(&state, &company_id, &filters).await?;
+
+    let mut archived_layouts = logs_db::TemplateLayoutCache::default();
+    let mut response_entries = Vec::new();
+    for e in page.entries {
+        let template_layout =
+            entry_template_layout(&state, &template, e.template_version, &mut archived_layouts)
+                .await?;
+        response_entries.push(LogEntryResponse {
+            id: e.entry_id,
+            template_id: e.template_id,
+            template_name: e.template_name,
+            template_layout: logs_db::process_template_layout_with_period_string(
+                &template_layout,
+                &e.period,
+            ),
+            entry_data: e.entry_data,
+            status: e.status.as_str().to_string(),
+            availability_status: logs_db::AvailabilityStatus::Available.as_str().to_string(),
+            created_at: e.created_at.to_rfc3339(),
+            updated_at: e.updated_at.to_rfc3339(),
+            submitted_at: e.submitted_at.map(|ts| ts.to_rfc3339()),
+            period: e.period,
+            template_version: e.template_version,
+            flagged: e.flagged,
+            corrective_action: e.corrective_action,
+            review: e.review,
         });
     }
 
     Ok(Json(ListLogEntriesResponse {
         entries: response_entries,
+        next_cursor: page.next_cursor,
     }))
 }
 
@@ -701,13 +1281,17 @@ pub async fn list_user_log_entries(
             .await
             .map_err(|(status, err)| (status, Json(err)))?;
 
-    if let Some(template_name) = params.get("template_name") {
+    if let Some(template_id) = params.get("template_id") {
+        entries.retain(|e| e.template_id == *template_id);
+    } else if let Some(template_name) = params.get("template_name") {
         entries.retain(|e| e.template_name == *template_name);
     }
 
+    let timezones = company_timezones(&state, &company_id).await?;
     let mut response_entries = Vec::new();
+    let mut archived_layouts = logs_db::TemplateLayoutCache::default();
     for e in entries {
-        let template = logs_db::get_template_by_name(&state.mongodb, &e.template_name, &company_id)
+        let template = logs_db::get_template_by_id(&state.mongodb, &e.template_id, &company_id)
             .await
             .map_err(|err| {
                 tracing::error!("Failed to get template: {:?}", err);
@@ -717,29 +1301,32 @@ pub async fn list_user_log_entries(
                 )
             })?;
 
-        let (processed_layout, derived_status, availability_status) =
-            if let Some(template) = template {
-                let layout = logs_db::process_template_layout_with_period_string(
-                    &template.template_layout,
-                    &e.period,
-                );
-                let (status, availability) = logs_db::derive_log_status(
-                    e.status,
-                    &template.schedule,
-                    &e.period,
-                    chrono::Utc::now(),
-                );
-                (layout, status, availability)
-            } else {
-                (
-                    Vec::new(),
-                    e.status,
-                    logs_db::AvailabilityStatus::NotAvailable,
-                )
-            };
+        let (processed_layout, derived_status, availability_status) = if let Some(template) =
+            template
+        {
+            let template_layout =
+                entry_template_layout(&state, &template, e.template_version, &mut archived_layouts)
+                    .await?;
+            let layout =
+                logs_db::process_template_layout_with_period_string(&template_layout, &e.period);
+            let (status, availability) = template.derive_entry_status(
+                e.status,
+                &e.period,
+                chrono::Utc::now(),
+                timezones.for_branch(e.branch_id.as_deref()),
+            );
+            (layout, status, availability)
+        } else {
+            (
+                Vec::new(),
+                e.status,
+                logs_db::AvailabilityStatus::NotAvailable,
+            )
+        };
 
         response_entries.push(LogEntryResponse {
             id: e.entry_id,
+            template_id: e.template_id,
             template_name: e.template_name,
             template_layout: processed_layout,
             entry_data: e.entry_data,
@@ -749,6 +1336,10 @@ pub async fn list_user_log_entries(
             updated_at: e.updated_at.to_rfc3339(),
             submitted_at: e.submitted_at.map(|ts| ts.to_rfc3339()),
             period: e.period,
+            template_version: e.template_version,
+            flagged: e.flagged,
+            corrective_action: e.corrective_action,
+            review: e.review,
         });
 
         if let Some(status) = params.get("status")
@@ -761,6 +1352,7 @@ pub async fn list_user_log_entries(
 
     Ok(Json(ListLogEntriesResponse {
         entries: response_entries,
+        next_cursor: None,
     }))
 }
 
@@ -1034,3 +1626,420 @@ pub async fn delete_report_run(
         message: "Report run deleted".to_string(),
     }))
 }
+
+/// Wraps generated report bytes in a PDF download response.
+fn report_pdf_response(
+    data: Vec<u8>,
+    params: &crate::dto::ReportRunParams,
+) -> Result<axum::response::Response, (StatusCode, Json<serde_json::Value>)> {
+    let date_part = |value: &str| {
+        value
+            .get(..10)
+            .unwrap_or(value)
+            .chars()
+            .filter(|c| c.is_ascii_digit() || *c == '-')
+            .collect::<String>()
+    };
+    let filename = format!(
+        "compliance-report-{}-{}.pdf",
+        date_part(&params.date_from_iso),
+        date_part(&params.date_to_iso)
+    );
+
+    axum::response::Response::builder()
+        .status(StatusCode::OK)
+        .header(header::CONTENT_TYPE, "application/pdf")
+        .header(header::CONTENT_LENGTH, data.len().to_string())
+        .header(
+            header::CONTENT_DISPOSITION,
+            format!("attachment; filename=\"{filename}\""),
+        )
+        .body(axum::body::Body::from(data))
+        .map_err(|_| {
+            tracing::error!("Failed to build response");
+            (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                Json(json!({ "error": "Failed to build response" })),
+            )
+        })
+}
+
+#[utoipa::path(
+    post,
+    path = "/reports/pdf",
+    request_body = ReportRunParams,
+    responses(
+        (status = 200, description = "Compliance report PDF", content_type = "application/pdf"),
+        (status = 400, description = "Invalid report parameters", body = ErrorResponse),
+        (status = 401, description = "Unauthorized", body = ErrorResponse),
+        (status = 403, description = "Forbidden", body = ErrorResponse),
+        (status = 500, description = "Server error", body = ErrorResponse),
+    ),
+    security(("bearer_auth" = [])),
+    tag = "Log Entries"
+)]
+/// Generates a compliance report PDF for the given report parameters.
+pub async fn generate_report_pdf(
+    ReadBranchUser(_claims, user): ReadBranchUser,
+    State(state): State<AppState>,
+    Json(params): Json<ReportRunParams>,
+) -> Result<axum::response::Response, (StatusCode, Json<serde_json::Value>)> {
+    let params = logs_db::normalize_report_params(&params);
+
+    let data = services::ReportService::generate_report_pdf(&state, &user, &params)
+        .await
+        .map_err(|(status, err)| (status, Json(err)))?;
+
+    report_pdf_response(data, &params)
+}
+
+#[utoipa::path(
+    get,
+    path = "/reports/runs/{report_id}/pdf",
+    params(("report_id" = String, Path, description = "Saved report run ID")),
+    responses(
+        (status = 200, description = "Compliance report PDF", content_type = "application/pdf"),
+        (status = 400, description = "Invalid report parameters", body = ErrorResponse),
+        (status = 401, description = "Unauthorized", body = ErrorResponse),
+        (status = 403, description = "Forbidden", body = ErrorResponse),
+        (status = 404, description = "Report run not found", body = ErrorResponse),
+        (status = 500, description = "Server error", body = ErrorResponse),
+    ),
+    security(("bearer_auth" = [])),
+    tag = "Log Entries"
+)]
+/// Generates the compliance report PDF for a saved report run.
+pub async fn generate_report_run_pdf(
+    ReadBranchUser(_claims, user): ReadBranchUser,
+    State(state): State<AppState>,
+    Path(report_id): Path<String>,
+) -> Result<axum::response::Response, (StatusCode, Json<serde_json::Value>)> {
+    let company_id = user.company_id.clone().ok_or((
+        StatusCode::FORBIDDEN,
+        Json(json!({ "error": "User is not associated with a company" })),
+    ))?;
+
+    let run = logs_db::get_report_run(&state.mongodb, &report_id, &user.id, &company_id)
+        .await
+        .map_err(|e| {
+            tracing::error!("Failed to fetch report run: {:?}", e);
+            (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                Json(json!({ "error": "Failed to fetch report run" })),
+            )
+        })?
+        .ok_or((
+            StatusCode::NOT_FOUND,
+            Json(json!({ "error": "Report run not found" })),
+        ))?;
+
+    // Normalised like /reports/pdf so a saved run renders the same as the live report
+    let params = logs_db::normalize_report_params(&run.params);
+
+    let data = services::ReportService::generate_report_pdf(&state, &user, &params)
+        .await
+        .map_err(|(status, err)| (status, Json(err)))?;
+
+    report_pdf_response(data, &params)
+}
+
+#[utoipa::path(
+    get,
+    path = "/reports/entries/export",
+    params(ExportLogEntriesQuery),
+    responses(
+        (status = 200, description = "CSV export of the template's log entries", content_type = "text/csv"),
+        (status = 200, description = "XLSX export of the template's log entries", content_type = "application/vnd.openxmlformats-officedocument.spreadsheetml.sheet"),
+        (status = 400, description = "Invalid query parameters", body = ErrorResponse),
+        (status = 401, description = "Unauthorized", body = ErrorResponse),
+        (status = 403, description = "Forbidden", body = ErrorResponse),
+        (status = 404, description = "Template not found", body = ErrorResponse),
+        (status = 500, description = "Server error", body = ErrorResponse),
+    ),
+    security(("bearer_auth" = [])),
+    tag = "Log Entries"
+)]
+/// Exports a template's log entries as a CSV or XLSX spreadsheet, one row per entry.
+pub async fn export_log_entries(
+    ReadBranchUser(_claims, user): ReadBranchUser,
+    State(state): State<AppState>,
+    Query(query): Query<ExportLogEntriesQuery>,
+) -> Result<axum::response::Response, (StatusCode, Json<serde_json::Value>)> {
+    let export = services::ReportService::export_template_entries(&state, &user, &query)
+        .await
+        .map_err(|(status, err)| (status, Json(err)))?;
+
+    axum::response::Response::builder()
+        .status(StatusCode::OK)
+        .header(header::CONTENT_TYPE, export.content_type)
+        .header(header::CONTENT_LENGTH, export.data.len().to_string())
+        .header(
+            header::CONTENT_DISPOSITION,
+            format!("attachment; filename=\"{}\"", export.filename),
+        )
+        .body(axum::body::Body::from(export.data))
+        .map_err(|_| {
+            tracing::error!("Failed to build response");
+            (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                Json(json!({ "error": "Failed to build response" })),
+            )
+        })
+}
+
+#[utoipa::path(
+    post,
+    path = "/logs/entries/{entry_id}/review",
+    params(("entry_id" = String, Path, description = "Log entry ID")),
+    request_body = ReviewLogEntryRequest,
+    responses(
+        (status = 200, description = "Log entry approved or rejected", body = ReviewLogEntryResponse),
+        (status = 400, description = "Rejection without a comment, or comment too long", body = ErrorResponse),
+        (status = 401, description = "Unauthorized - invalid or missing token", body = ErrorResponse),
+        (status = 403, description = "Forbidden - not a manager of the entry's branch, or own entry", body = ErrorResponse),
+        (status = 404, description = "Entry not found", body = ErrorResponse),
+        (status = 409, description = "Entry is not awaiting review", body = ErrorResponse),
+        (status = 500, description = "Server error", body = ErrorResponse),
+    ),
+    security(("bearer_auth" = [])),
+    tag = "Log Entries"
+)]
+/// Approves or rejects a submitted log entry. Rejected entries return to draft.
+///
+/// # Errors
+/// Returns an error if the entry cannot be reviewed by the user or is not awaiting review.
+pub async fn review_log_entry(
+    BranchManagerUser(_claims, user): BranchManagerUser,
+    State(state): State<AppState>,
+    Path(entry_id): Path<String>,
+    Json(payload): Json<ReviewLogEntryRequest>,
+) -> Result<Json<ReviewLogEntryResponse>, (StatusCode, Json<serde_json::Value>)> {
+    let entry = services::LogEntryService::review_log_entry(
+        &state,
+        &user,
+        &entry_id,
+        payload.decision,
+        payload.comment.as_deref(),
+    )
+    .await
+    .map_err(|(status, err)| (status, Json(err)))?;
+
+    let message = match payload.decision {
+        logs_db::ReviewDecision::Approved => "Log entry approved.",
+        logs_db::ReviewDecision::Rejected => "Log entry rejected and returned to draft.",
+    };
+
+    Ok(Json(ReviewLogEntryResponse {
+        message: message.to_string(),
+        status: entry.status.as_str().to_string(),
+        review: entry.review,
+    }))
+}
+
+#[utoipa::path(
+    get,
+    path = "/logs/entries/{entry_id}/history",
+    params(("entry_id" = String, Path, description = "Log entry ID")),
+    responses(
+        (status = 200, description = "Status history of the entry, oldest first", body = EntryHistoryResponse),
+        (status = 401, description = "Unauthorized - invalid or missing token", body = ErrorResponse),
+        (status = 403, description = "Forbidden", body = ErrorResponse),
+        (status = 404, description = "Entry not found", body = ErrorResponse),
+        (status = 500, description = "Server error", body = ErrorResponse),
+    ),
+    security(("bearer_auth" = [])),
+    tag = "Log Entries"
+)]
+/// Retrieves the audit trail of status changes for a log entry.
+///
+/// # Errors
+/// Returns an error if the entry is not found or the user cannot view it.
+pub async fn get_log_entry_history(
+    AnyAuthUser(_claims, user): AnyAuthUser,
+    State(state): State<AppState>,
+    Path(entry_id): Path<String>,
+) -> Result<Json<EntryHistoryResponse>, (StatusCode, Json<serde_json::Value>)> {
+    let transitions = services::LogEntryService::get_entry_history(&state, &user, &entry_id)
+        .await
+        .map_err(|(status, err)| (status, Json(err)))?;
+
+    Ok(Json(EntryHistoryResponse {
+        transitions: transitions.into_iter().map(Into::into).collect(),
+    }))
+}
+
+#[utoipa::path(
+    get,
+    path = "/logs/entries/{entry_id}/signatures/{signature_id}",
+    params(
+        ("entry_id" = String, Path, description = "Log entry ID"),
+        ("signature_id" = String, Path, description = "Signature ID stored in the entry data"),
+    ),
+    responses(
+        (status = 200, description = "Signature image", content_type = "image/png"),
+        (status = 401, description = "Unauthorized - invalid or missing token", body = ErrorResponse),
+        (status = 403, description = "Forbidden", body = ErrorResponse),
+        (status = 404, description = "Entry or signature not found", body = ErrorResponse),
+        (status = 500, description = "Server error", body = ErrorResponse),
+    ),
+    security(("bearer_auth" = [])),
+    tag = "Log Entries"
+)]
+/// Retrieves a signature captured in a log entry.
+///
+/// # Errors
+/// Returns an error if the entry or signature is not found or the user cannot view the entry.
+pub async fn get_entry_signature(
+    AnyAuthUser(_claims, user): AnyAuthUser,
+    State(state): State<AppState>,
+    Path((entry_id, signature_id)): Path<(String, String)>,
+) -> Result<
+    (
+        StatusCode,
+        [(header::HeaderName, header::HeaderValue); 1],
+        Vec<u8>,
+    ),
+    (StatusCode, Json<serde_json::Value>),
+> {
+    let (content_type, data) =
+        services::LogEntryService::get_entry_signature(&state, &user, &entry_id, &signature_id)
+            .await
+            .map_err(|(status, err)| (status, Json(err)))?;
+
+    let content_type = header::HeaderValue::from_str(&content_type)
+        .unwrap_or(header::HeaderValue::from_static("application/octet-stream"));
+    Ok((StatusCode::OK, [(header::CONTENT_TYPE, content_type)], data))
+}
+
+#[derive(Debug, Deserialize, utoipa::IntoParams)]
+pub struct ListCorrectiveActionIssuesQuery {
+    /// Restrict to one branch. Branch managers always see their own branch only.
+    pub branch_id: Option<String>,
+    /// Comma-separated statuses (`open`, `acknowledged`, `closed`). Defaults to `open,acknowledged`.
+    pub status: Option<String>,
+}
+
+#[utoipa::path(
+    get,
+    path = "/logs/issues",
+    params(ListCorrectiveActionIssuesQuery),
+    responses(
+        (status = 200, description = "Corrective action issues", body = ListCorrectiveActionIssuesResponse),
+        (status = 400, description = "Invalid status filter", body = ErrorResponse),
+        (status = 401, description = "Unauthorized - invalid or missing token", body = ErrorResponse),
+        (status = 403, description = "Forbidden", body = ErrorResponse),
+        (status = 500, description = "Server error", body = ErrorResponse),
+    ),
+    security(("bearer_auth" = [])),
+    tag = "Log Entries"
+)]
+/// Lists corrective-action issues raised for out-of-range readings, newest first.
+///
+/// # Errors
+/// Returns an error if the status filter is invalid, the user cannot view the branch, or the query fails.
+pub async fn list_corrective_action_issues(
+    ReadBranchUser(_claims, user): ReadBranchUser,
+    State(state): State<AppState>,
+    Query(query): Query<ListCorrectiveActionIssuesQuery>,
+) -> Result<Json<ListCorrectiveActionIssuesResponse>, (StatusCode, Json<serde_json::Value>)> {
+    let statuses = match query.status.as_deref().filter(|s| !s.is_empty()) {
+        None => vec![
+            logs_db::IssueStatus::Open,
+            logs_db::IssueStatus::Acknowledged,
+        ],
+        Some(raw) => raw
+            .split(',')
+            .map(|s| {
+                logs_db::IssueStatus::from_param(s.trim()).ok_or_else(|| {
+                    (
+                        StatusCode::BAD_REQUEST,
+                        Json(json!({ "error": format!("Invalid status: {}", s.trim()) })),
+                    )
+                })
+            })
+            .collect::<Result<Vec<_>, _>>()?,
+    };
+
+    let issues = services::CorrectiveActionService::list_issues(
+        &state,
+        &user,
+        query.branch_id.as_deref().filter(|b| !b.is_empty()),
+        &statuses,
+    )
+    .await
+    .map_err(|(status, err)| (status, Json(err)))?;
+
+    Ok(Json(ListCorrectiveActionIssuesResponse {
+        issues: issues.into_iter().map(Into::into).collect(),
+    }))
+}
+
+#[utoipa::path(
+    post,
+    path = "/logs/issues/{issue_id}/acknowledge",
+    params(("issue_id" = String, Path, description = "Issue ID")),
+    responses(
+        (status = 200, description = "Issue acknowledged", body = CorrectiveActionIssueResponse),
+        (status = 401, description = "Unauthorized - invalid or missing token", body = ErrorResponse),
+        (status = 403, description = "Forbidden - not responsible for the issue's branch", body = ErrorResponse),
+        (status = 404, description = "Issue not found", body = ErrorResponse),
+        (status = 409, description = "Issue is not open", body = ErrorResponse),
+        (status = 500, description = "Server error", body = ErrorResponse),
+    ),
+    security(("bearer_auth" = [])),
+    tag = "Log Entries"
+)]
+/// Acknowledges an open corrective-action issue.
+///
+/// # Errors
+/// Returns an error if the issue is not found, not open, or the user cannot resolve it.
+pub async fn acknowledge_corrective_action_issue(
+    BranchManagerUser(_claims, user): BranchManagerUser,
+    State(state): State<AppState>,
+    Path(issue_id): Path<String>,
+) -> Result<Json<CorrectiveActionIssueResponse>, (StatusCode, Json<serde_json::Value>)> {
+    let issue = services::CorrectiveActionService::acknowledge_issue(&state, &user, &issue_id)
+        .await
+        .map_err(|(status, err)| (status, Json(err)))?;
+
+    Ok(Json(issue.into()))
+}
+
+#[utoipa::path(
+    post,
+    path = "/logs/issues/{issue_id}/close",
+    params(("issue_id" = String, Path, description = "Issue ID")),
+    request_body = CloseCorrectiveActionIssueRequest,
+    responses(
+        (status = 200, description = "Issue closed", body = CorrectiveActionIssueResponse),
+        (status = 400, description = "Invalid resolution note", body = ErrorResponse),
+        (status = 401, description = "Unauthorized - invalid or missing token", body = ErrorResponse),
+        (status = 403, description = "Forbidden - not responsible for the issue's branch", body = ErrorResponse),
+        (status = 404, description = "Issue not found", body = ErrorResponse),
+        (status = 409, description = "Issue has not been acknowledged", body = ErrorResponse),
+        (status = 500, description = "Server error", body = ErrorResponse),
+    ),
+    security(("bearer_auth" = [])),
+    tag = "Log Entries"
+)]
+/// Closes an acknowledged corrective-action issue.
+///
+/// # Errors
+/// Returns an error if the issue is not found, not acknowledged, or the user cannot resolve it.
+pub async fn close_corrective_action_issue(
+    BranchManagerUser(_claims, user): BranchManagerUser,
+    State(state): State<AppState>,
+    Path(issue_id): Path<String>,
+    Json(payload): Json<CloseCorrectiveActionIssueRequest>,
+) -> Result<Json<CorrectiveActionIssueResponse>, (StatusCode, Json<serde_json::Value>)> {
+    let issue = services::CorrectiveActionService::close_issue(
+        &state,
+        &user,
+        &issue_id,
+        payload.resolution_note.as_deref(),
+    )
+    .await
+    .map_err(|(status, err)| (status, Json(err)))?;
+
+    Ok(Json(issue.into()))
+}
@@ -1,3 +1,4 @@
+pub mod attachment_handlers;
 pub mod auth_handlers;
 pub mod branch_handlers;
 pub mod clock_handlers;
@@ -12,6 +13,7 @@ pub mod security_handlers;
 pub mod template_handlers;
 pub mod user_handlers;
 
+pub use attachment_handlers::*;
 pub use auth_handlers::*;
 pub use branch_handlers::*;
 pub use clock_handlers::*;
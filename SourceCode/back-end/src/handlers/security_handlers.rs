@@ -2,6 +2,7 @@ use crate::{
     AppState, db,
     dto::{ErrorResponse, SecurityLogDto, SecurityLogsQuery, SecurityLogsResponse},
     middleware::LogSmartAdminUser,
+    utils::csv_escape,
 };
 use axum::{
     Json,
@@ -244,25 +245,6 @@ fn parse_optional_utc_datetime(
     }
 }
 
-fn csv_escape(value: &str) -> String {
-    let sanitized = csv_sanitize_cell(value);
-    let escaped = sanitized.replace('"', "\"\"");
-    format!("\"{escaped}\"")
-}
-
-fn csv_sanitize_cell(value: &str) -> String {
-    let starts_with_formula_symbol = matches!(
-        value.chars().find(|c| !c.is_whitespace()),
-        Some('=' | '+' | '-' | '@')
-    );
-
-    if starts_with_formula_symbol {
-        format!("'{value}")
-    } else {
-        value.to_string()
-    }
-}
-
 #[must_use]
 pub fn role_label(role: &crate::db::UserRole) -> String {
     role.to_string()
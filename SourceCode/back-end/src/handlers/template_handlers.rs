@@ -1,17 +1,27 @@
 use crate::{
     AppState,
     dto::{
-        AddTemplateRequest, AddTemplateResponse, DeleteTemplateRequest, DeleteTemplateResponse,
-        ErrorResponse, GetAllTemplatesResponse, GetTemplateRequest, GetTemplateResponse,
-        GetTemplateVersionsResponse, RenameTemplateRequest, RenameTemplateResponse,
-        RestoreTemplateVersionRequest, TemplateInfo, UpdateTemplateRequest, UpdateTemplateResponse,
+        AddTemplateRequest, AddTemplateResponse, CloneLibraryTemplateRequest,
+        DeleteTemplateRequest, DeleteTemplateResponse, DistributeTemplateRequest,
+        DistributeTemplateResponse, ErrorResponse, ExportTemplateBundleRequest,
+        GetAllTemplatesQuery, GetAllTemplatesResponse, GetTemplateRequest, GetTemplateResponse,
+        GetTemplateVersionsResponse, ImportTemplateBundleRequest, ImportTemplateBundleResponse,
+        LibraryTemplateRequest, LibraryTemplateResponse, ListLibraryTemplatesQuery,
+        ListLibraryTemplatesResponse, PropagateTemplateRequest, PropagateTemplateResponse,
+        PropagationPreviewResponse, RenameTemplateRequest, RenameTemplateResponse,
+        RestoreTemplateVersionRequest, SchedulePreviewRequest, SchedulePreviewResponse,
+        TemplateBundle, TemplateInfo, TemplateVersionDiffQuery, TemplateVersionDiffResponse,
+        UnarchiveTemplateRequest, UnarchiveTemplateResponse, UnlinkTemplateRequest,
+        UpdateTemplateRequest, UpdateTemplateResponse,
+    },
+    middleware::{
+        AnyAuthUser, BranchManagerUser, LogSmartAdminUser, ManageCompanyUser, ReadBranchUser,
     },
-    middleware::{AnyAuthUser, BranchManagerUser, ReadBranchUser},
     services,
 };
 use axum::{
     Json,
-    extract::{Query, State},
+    extract::{Path, Query, State},
     http::StatusCode,
 };
 use serde_json::json;
@@ -58,29 +68,21 @@ pub async fn add_template(
         Json(json!({ "error": "User is not associated with a company" })),
     ))?;
 
-    services::TemplateService::create_template(
-        &state,
-        &company_id,
-        payload.template_name,
-        payload.template_layout,
-        payload.schedule,
-        &user.id,
-        payload.branch_id,
-    )
-    .await
-    .map_err(|(status, err)| (status, Json(err)))?;
+    let template_id =
+        services::TemplateService::create_template(&state, &company_id, payload.into(), &user.id)
+            .await
+            .map_err(|(status, err)| (status, Json(err)))?;
 
     Ok(Json(AddTemplateResponse {
         message: "Template added successfully.".to_string(),
+        template_id,
     }))
 }
 
 #[utoipa::path(
     get,
     path = "/logs/templates",
-    params(
-        ("template_name"=String, Query, description = "Name of the template to retrieve", example = "ErrorLog" )
-    ),
+    params(GetTemplateRequest),
     responses(
         (status = 200, description = "Template retrieved successfully", body = GetTemplateResponse),
         (status = 401, description = "Invalid or expired token", body = ErrorResponse),
@@ -90,7 +92,7 @@ pub async fn add_template(
     security(("bearer_auth" = [])),
     tag = "Templates"
 )]
-/// Retrieves a specific log template by name.
+/// Retrieves a specific log template by id or name.
 pub async fn get_template(
     AnyAuthUser(_claims, user): AnyAuthUser,
     State(state): State<AppState>,
@@ -101,12 +103,16 @@ pub async fn get_template(
         Json(json!({ "error": "User is not associated with a company" })),
     ))?;
 
-    let (template_name, template_layout, version, version_name, branch_id) =
-        services::TemplateService::get_template(&state, company_id, &payload.template_name)
-            .await
-            .map_err(|(status, err)| (status, Json(err)))?;
+    let template_key = services::TemplateService::template_key(
+        payload.template_id.as_deref(),
+        payload.template_name.as_deref(),
+    )
+    .map_err(|(status, err)| (status, Json(err)))?;
+    let template = services::TemplateService::get_template(&state, company_id, template_key)
+        .await
+        .map_err(|(status, err)| (status, Json(err)))?;
 
-    if let Some(branch) = branch_id.clone() {
+    if let Some(branch) = template.branch_id.clone() {
         // If the template is branch-specific, check if the user has access to that branch
         if let Some(user_branch_id) = &user.branch_id {
             if &branch != user_branch_id && !user.can_manage_company() {
@@ -125,17 +131,22 @@ pub async fn get_template(
     }
 
     Ok(Json(GetTemplateResponse {
-        template_name,
-        template_layout,
-        version,
-        version_name,
-        branch_id,
+        template_id: template.template_id,
+        template_name: template.template_name,
+        template_layout: template.template_layout,
+        version: template.version,
+        version_name: template.version_name,
+        branch_id: template.branch_id,
+        archived_at: template.archived_at.map(|t| t.to_string()),
+        linked_from: template.linked_from,
+        entry_mode: template.entry_mode,
     }))
 }
 
 #[utoipa::path(
     get,
     path = "/logs/templates/all",
+    params(GetAllTemplatesQuery),
     responses(
         (status = 200, description = "All templates retrieved successfully", body = GetAllTemplatesResponse),
         (status = 401, description = "Unauthorized", body = ErrorResponse),
@@ -145,10 +156,11 @@ pub async fn get_template(
     security(("bearer_auth" = [])),
     tag = "Templates"
 )]
-/// Retrieves all log templates for the current company/branch.
+/// Retrieves all active (or archived) log templates for the current company/branch.
 pub async fn get_all_templates(
     ReadBranchUser(_claims, user): ReadBranchUser,
     State(state): State<AppState>,
+    Query(query): Query<GetAllTemplatesQuery>,
 ) -> Result<Json<GetAllTemplatesResponse>, (StatusCode, Json<serde_json::Value>)> {
     let company_id = user.company_id.as_deref().ok_or((
         StatusCode::FORBIDDEN,
@@ -163,6 +175,7 @@ pub async fn get_all_templates(
         } else {
             None
         },
+        query.archived,
     )
     .await
     .map_err(|(status, err)| (status, Json(err)))?;
@@ -170,12 +183,26 @@ pub async fn get_all_templates(
     let response_templates = templates
         .into_iter()
         .map(
-            |(name, created_at, updated_at, user_id, schedule)| TemplateInfo {
-                template_name: name,
-                created_at: created_at.to_string(),
-                updated_at: updated_at.to_string(),
-                created_by: user_id,
+            |(
+                template_id,
+                name,
+                created_at,
+                updated_at,
+                user_id,
                 schedule,
+                entry_mode,
+                archived_at,
+            )| {
+                TemplateInfo {
+                    template_id,
+                    template_name: name,
+                    created_at: created_at.to_string(),
+                    updated_at: updated_at.to_string(),
+                    created_by: user_id,
+                    schedule,
+                    entry_mode,
+                    archived_at: archived_at.map(|t| t.to_string()),
+                }
             },
         )
         .collect();
@@ -204,9 +231,14 @@ pub async fn update_template(
     State(state): State<AppState>,
     Json(payload): Json<UpdateTemplateRequest>,
 ) -> Result<Json<UpdateTemplateResponse>, (StatusCode, Json<serde_json::Value>)> {
+    let template_key = services::TemplateService::template_key(
+        payload.template_id.as_deref(),
+        payload.template_name.as_deref(),
+    )
+    .map_err(|(status, err)| (status, Json(err)))?;
     services::TemplateService::update_template(
         &state,
-        &payload.template_name,
+        template_key,
         payload.template_layout.as_ref(),
         payload.schedule.as_ref(),
         &user,
@@ -226,12 +258,50 @@ pub async fn update_template(
     }))
 }
 
+#[utoipa::path(
+    post,
+    path = "/logs/templates/schedule/preview",
+    request_body = SchedulePreviewRequest,
+    responses(
+        (status = 200, description = "Upcoming periods of the schedule and any problems with it", body = SchedulePreviewResponse),
+        (status = 400, description = "Bad request", body = ErrorResponse),
+        (status = 401, description = "Unauthorized", body = ErrorResponse),
+        (status = 403, description = "Forbidden", body = ErrorResponse),
+        (status = 500, description = "Server error", body = ErrorResponse),
+    ),
+    security(("bearer_auth" = [])),
+    tag = "Templates"
+)]
+/// Previews the next periods a schedule produces, with their opening and due times, so it can
+/// be checked in the template designer before saving.
+pub async fn preview_schedule(
+    BranchManagerUser(_claims, user): BranchManagerUser,
+    State(state): State<AppState>,
+    Json(payload): Json<SchedulePreviewRequest>,
+) -> Result<Json<SchedulePreviewResponse>, (StatusCode, Json<serde_json::Value>)> {
+    let company_id = user.company_id.as_deref().ok_or((
+        StatusCode::FORBIDDEN,
+        Json(json!({ "error": "User is not associated with a company" })),
+    ))?;
+
+    let preview = services::TemplateService::preview_schedule(
+        &state,
+        company_id,
+        payload.branch_id.as_deref().or(user.branch_id.as_deref()),
+        &payload.schedule,
+        payload.created_at,
+        payload.count,
+    )
+    .await
+    .map_err(|(status, err)| (status, Json(err)))?;
+
+    Ok(Json(preview))
+}
+
 #[utoipa::path(
     get,
     path = "/logs/templates/versions",
-    params(
-        ("template_name"=String, Query, description = "Name of the template to retrieve versions for", example = "ErrorLog")
-    ),
+    params(GetTemplateRequest),
     responses(
         (status = 200, description = "Versions retrieved successfully", body = GetTemplateVersionsResponse),
         (status = 401, description = "Unauthorized", body = ErrorResponse),
@@ -246,7 +316,12 @@ pub async fn get_template_versions(
     State(state): State<AppState>,
     Query(payload): Query<GetTemplateRequest>,
 ) -> Result<Json<crate::dto::GetTemplateVersionsResponse>, (StatusCode, Json<serde_json::Value>)> {
-    let versions = services::TemplateService::get_versions(&state, &payload.template_name, &user)
+    let template_key = services::TemplateService::template_key(
+        payload.template_id.as_deref(),
+        payload.template_name.as_deref(),
+    )
+    .map_err(|(status, err)| (status, Json(err)))?;
+    let versions = services::TemplateService::get_versions(&state, template_key, &user)
         .await
         .map_err(|(status, err)| (status, Json(err)))?;
 
@@ -265,12 +340,44 @@ pub async fn get_template_versions(
     }))
 }
 
+#[utoipa::path(
+    get,
+    path = "/logs/templates/versions/diff",
+    params(TemplateVersionDiffQuery),
+    responses(
+        (status = 200, description = "Changes between the two versions", body = TemplateVersionDiffResponse),
+        (status = 400, description = "Bad request", body = ErrorResponse),
+        (status = 401, description = "Unauthorized", body = ErrorResponse),
+        (status = 403, description = "Forbidden", body = ErrorResponse),
+        (status = 404, description = "Template or version not found", body = ErrorResponse),
+        (status = 500, description = "Server error", body = ErrorResponse),
+    ),
+    security(("bearer_auth" = [])),
+    tag = "Templates"
+)]
+/// Compares two versions of a log template field by field, or a version with the current template.
+pub async fn diff_template_versions(
+    BranchManagerUser(_claims, user): BranchManagerUser,
+    State(state): State<AppState>,
+    Query(query): Query<TemplateVersionDiffQuery>,
+) -> Result<Json<TemplateVersionDiffResponse>, (StatusCode, Json<serde_json::Value>)> {
+    let template_key = services::TemplateService::template_key(
+        query.template_id.as_deref(),
+        query.template_name.as_deref(),
+    )
+    .map_err(|(status, err)| (status, Json(err)))?;
+    let diff =
+        services::TemplateService::diff_versions(&state, template_key, query.from, query.to, &user)
+            .await
+            .map_err(|(status, err)| (status, Json(err)))?;
+
+    Ok(Json(diff))
+}
+
 #[utoipa::path(
     post,
     path = "/logs/templates/versions/restore",
-    params(
-        ("template_name"=String, Query, description = "Name of the template to restore", example = "ErrorLog")
-    ),
+    params(GetTemplateRequest),
     request_body = RestoreTemplateVersionRequest,
     responses(
         (status = 200, description = "Template restored successfully", body = UpdateTemplateResponse),
@@ -294,10 +401,15 @@ pub async fn restore_template_version(
         Json(json!({ "error": "User is not associated with a company" })),
     ))?;
 
+    let template_key = services::TemplateService::template_key(
+        query.template_id.as_deref(),
+        query.template_name.as_deref(),
+    )
+    .map_err(|(status, err)| (status, Json(err)))?;
     services::TemplateService::restore_version(
         &state,
         company_id,
-        &query.template_name,
+        template_key,
         payload.version,
         &user,
     )
@@ -333,10 +445,15 @@ pub async fn rename_template(
         Json(json!({ "error": "User is not associated with a company" })),
     ))?;
 
+    let template_key = services::TemplateService::template_key(
+        payload.template_id.as_deref(),
+        payload.old_template_name.as_deref(),
+    )
+    .map_err(|(status, err)| (status, Json(err)))?;
     services::TemplateService::rename_template(
         &state,
         company_id,
-        &payload.old_template_name,
+        template_key,
         &payload.new_template_name,
         user.branch_id.as_deref(),
         &user.role,
@@ -355,15 +472,17 @@ pub async fn rename_template(
         DeleteTemplateRequest
     ),
     responses(
-        (status = 200, description = "Template deleted successfully", body = DeleteTemplateResponse),
+        (status = 200, description = "Template archived successfully", body = DeleteTemplateResponse),
         (status = 401, description = "Unauthorized", body = ErrorResponse),
         (status = 403, description = "Forbidden", body = ErrorResponse),
+        (status = 404, description = "Template not found", body = ErrorResponse),
+        (status = 409, description = "Template is already archived", body = ErrorResponse),
         (status = 500, description = "Server error", body = ErrorResponse),
     ),
     security(("bearer_auth" = [])),
     tag = "Templates"
 )]
-/// Deletes a specific log template.
+/// Archives a specific log template. Its entries and versions are kept.
 pub async fn delete_template(
     BranchManagerUser(_claims, user): BranchManagerUser,
     State(state): State<AppState>,
@@ -374,10 +493,107 @@ pub async fn delete_template(
         Json(json!({ "error": "User is not associated with a company" })),
     ))?;
 
-    services::TemplateService::delete_template(
+    let template_key = services::TemplateService::template_key(
+        payload.template_id.as_deref(),
+        payload.template_name.as_deref(),
+    )
+    .map_err(|(status, err)| (status, Json(err)))?;
+    services::TemplateService::archive_template(
+        &state,
+        company_id,
+        template_key,
+        user.branch_id.as_deref(),
+        &user.role,
+    )
+    .await
+    .map_err(|(status, err)| (status, Json(err)))?;
+    Ok(Json(DeleteTemplateResponse {
+        message: "Template archived successfully.".to_string(),
+    }))
+}
+
+#[utoipa::path(
+    post,
+    path = "/logs/templates/unarchive",
+    request_body = UnarchiveTemplateRequest,
+    responses(
+        (status = 200, description = "Template restored successfully", body = UnarchiveTemplateResponse),
+        (status = 401, description = "Unauthorized", body = ErrorResponse),
+        (status = 403, description = "Forbidden", body = ErrorResponse),
+        (status = 404, description = "Template not found", body = ErrorResponse),
+        (status = 409, description = "Template is not archived", body = ErrorResponse),
+        (status = 500, description = "Server error", body = ErrorResponse),
+    ),
+    security(("bearer_auth" = [])),
+    tag = "Templates"
+)]
+/// Restores an archived log template.
+pub async fn unarchive_template(
+    BranchManagerUser(_claims, user): BranchManagerUser,
+    State(state): State<AppState>,
+    Json(payload): Json<UnarchiveTemplateRequest>,
+) -> Result<Json<UnarchiveTemplateResponse>, (StatusCode, Json<serde_json::Value>)> {
+    let company_id = user.company_id.as_ref().ok_or((
+        StatusCode::FORBIDDEN,
+        Json(json!({ "error": "User is not associated with a company" })),
+    ))?;
+
+    let template_key = services::TemplateService::template_key(
+        payload.template_id.as_deref(),
+        payload.template_name.as_deref(),
+    )
+    .map_err(|(status, err)| (status, Json(err)))?;
+    services::TemplateService::unarchive_template(
+        &state,
+        company_id,
+        template_key,
+        user.branch_id.as_deref(),
+        &user.role,
+    )
+    .await
+    .map_err(|(status, err)| (status, Json(err)))?;
+    Ok(Json(UnarchiveTemplateResponse {
+        message: "Template restored successfully.".to_string(),
+    }))
+}
+
+#[utoipa::path(
+    delete,
+    path = "/logs/templates/purge",
+    params(
+        DeleteTemplateRequest
+    ),
+    responses(
+        (status = 200, description = "Template deleted successfully", body = DeleteTemplateResponse),
+        (status = 401, description = "Unauthorized", body = ErrorResponse),
+        (status = 403, description = "Forbidden", body = ErrorResponse),
+        (status = 404, description = "Template not found", body = ErrorResponse),
+        (status = 409, description = "Template has log entries", body = ErrorResponse),
+        (status = 500, description = "Server error", body = ErrorResponse),
+    ),
+    security(("bearer_auth" = [])),
+    tag = "Templates"
+)]
+/// Permanently deletes a log template that has no entries.
+pub async fn purge_template(
+    BranchManagerUser(_claims, user): BranchManagerUser,
+    State(state): State<AppState>,
+    Query(payload): Query<DeleteTemplateRequest>,
+) -> Result<Json<DeleteTemplateResponse>, (StatusCode, Json<serde_json::Value>)> {
+    let company_id = user.company_id.as_ref().ok_or((
+        StatusCode::FORBIDDEN,
+        Json(json!({ "error": "User is not associated with a company" })),
+    ))?;
+
+    let template_key = services::TemplateService::template_key(
+        payload.template_id.as_deref(),
+        payload.template_name.as_deref(),
+    )
+    .map_err(|(status, err)| (status, Json(err)))?;
+    services::TemplateService::purge_template(
         &state,
         company_id,
-        &payload.template_name,
+        template_key,
         user.branch_id.as_deref(),
         &user.role,
     )
@@ -387,3 +603,414 @@ pub async fn delete_template(
         message: "Template deleted successfully.".to_string(),
     }))
 }
+
+impl From<AddTemplateRequest> for services::NewTemplate {
+    fn from(request: AddTemplateRequest) -> Self {
+        Self {
+            template_name: request.template_name,
+            template_layout: request.template_layout,
+            schedule: request.schedule,
+            entry_mode: request.entry_mode,
+            branch_id: request.branch_id,
+        }
+    }
+}
+
+impl From<LibraryTemplateRequest> for services::LibraryTemplateDetails {
+    fn from(request: LibraryTemplateRequest) -> Self {
+        Self {
+            template_name: request.template_name,
+            category: request.category,
+            description: request.description,
+            template_layout: request.template_layout,
+            schedule: request.schedule,
+        }
+    }
+}
+
+#[utoipa::path(
+    get,
+    path = "/logs/templates/library",
+    params(ListLibraryTemplatesQuery),
+    responses(
+        (status = 200, description = "Template library retrieved successfully", body = ListLibraryTemplatesResponse),
+        (status = 401, description = "Unauthorized", body = ErrorResponse),
+        (status = 403, description = "Forbidden", body = ErrorResponse),
+        (status = 500, description = "Server error", body = ErrorResponse),
+    ),
+    security(("bearer_auth" = [])),
+    tag = "Templates"
+)]
+/// Lists the LogSmart starter template library.
+pub async fn list_library_templates(
+    ManageCompanyUser(_claims, _user): ManageCompanyUser,
+    State(state): State<AppState>,
+    Query(query): Query<ListLibraryTemplatesQuery>,
+) -> Result<Json<ListLibraryTemplatesResponse>, (StatusCode, Json<serde_json::Value>)> {
+    let (templates, categories) = services::TemplateLibraryService::list_templates(
+        &state,
+        query.category.as_deref().filter(|c| !c.is_empty()),
+    )
+    .await
+    .map_err(|(status, err)| (status, Json(err)))?;
+
+    Ok(Json(ListLibraryTemplatesResponse {
+        templates: templates.into_iter().map(Into::into).collect(),
+        categories,
+    }))
+}
+
+#[utoipa::path(
+    post,
+    path = "/logs/templates/library",
+    request_body = LibraryTemplateRequest,
+    responses(
+        (status = 200, description = "Library template added successfully", body = LibraryTemplateResponse),
+        (status = 400, description = "Invalid template", body = ErrorResponse),
+        (status = 401, description = "Unauthorized", body = ErrorResponse),
+        (status = 403, description = "Forbidden - LogSmart admin only", body = ErrorResponse),
+        (status = 500, description = "Server error", body = ErrorResponse),
+    ),
+    security(("bearer_auth" = [])),
+    tag = "Templates"
+)]
+/// Adds a template to the starter library.
+pub async fn add_library_template(
+    LogSmartAdminUser(_claims, _user): LogSmartAdminUser,
+    State(state): State<AppState>,
+    Json(payload): Json<LibraryTemplateRequest>,
+) -> Result<Json<LibraryTemplateResponse>, (StatusCode, Json<serde_json::Value>)> {
+    let template = services::TemplateLibraryService::create_template(&state, payload.into())
+        .await
+        .map_err(|(status, err)| (status, Json(err)))?;
+
+    Ok(Json(template.into()))
+}
+
+#[utoipa::path(
+    put,
+    path = "/logs/templates/library/{library_template_id}",
+    params(("library_template_id" = String, Path, description = "Library template ID")),
+    request_body = LibraryTemplateRequest,
+    responses(
+        (status = 200, description = "Library template updated successfully", body = LibraryTemplateResponse),
+        (status = 400, description = "Invalid template", body = ErrorResponse),
+        (status = 401, description = "Unauthorized", body = ErrorResponse),
+        (status = 403, description = "Forbidden - LogSmart admin only", body = ErrorResponse),
+        (status = 404, description = "Library template not found", body = ErrorResponse),
+        (status = 500, description = "Server error", body = ErrorResponse),
+    ),
+    security(("bearer_auth" = [])),
+    tag = "Templates"
+)]
+/// Updates a template in the starter library.
+pub async fn update_library_template(
+    LogSmartAdminUser(_claims, _user): LogSmartAdminUser,
+    State(state): State<AppState>,
+    Path(library_template_id): Path<String>,
+    Json(payload): Json<LibraryTemplateRequest>,
+) -> Result<Json<LibraryTemplateResponse>, (StatusCode, Json<serde_json::Value>)> {
+    let template = services::TemplateLibraryService::update_template(
+        &state,
+        &library_template_id,
+        payload.into(),
+    )
+    .await
+    .map_err(|(status, err)| (status, Json(err)))?;
+
+    Ok(Json(template.into()))
+}
+
+#[utoipa::path(
+    delete,
+    path = "/logs/templates/library/{library_template_id}",
+    params(("library_template_id" = String, Path, description = "Library template ID")),
+    responses(
+        (status = 200, description = "Library template deleted successfully", body = DeleteTemplateResponse),
+        (status = 401, description = "Unauthorized", body = ErrorResponse),
+        (status = 403, description = "Forbidden - LogSmart admin only", body = ErrorResponse),
+        (status = 404, description = "Library template not found", body = ErrorResponse),
+        (status = 500, description = "Server error", body = ErrorResponse),
+    ),
+    security(("bearer_auth" = [])),
+    tag = "Templates"
+)]
+/// Removes a template from the starter library. Existing copies are kept.
+pub async fn delete_library_template(
+    LogSmartAdminUser(_claims, _user): LogSmartAdminUser,
+    State(state): State<AppState>,
+    Path(library_template_id): Path<String>,
+) -> Result<Json<DeleteTemplateResponse>, (StatusCode, Json<serde_json::Value>)> {
+    services::TemplateLibraryService::delete_template(&state, &library_template_id)
+        .await
+        .map_err(|(status, err)| (status, Json(err)))?;
+
+    Ok(Json(DeleteTemplateResponse {
+        message: "Library template deleted successfully.".to_string(),
+    }))
+}
+
+#[utoipa::path(
+    post,
+    path = "/logs/templates/library/{library_template_id}/clone",
+    params(("library_template_id" = String, Path, description = "Library template ID")),
+    request_body = CloneLibraryTemplateRequest,
+    responses(
+        (status = 200, description = "Template cloned successfully", body = AddTemplateResponse),
+        (status = 401, description = "Unauthorized", body = ErrorResponse),
+        (status = 403, description = "Forbidden", body = ErrorResponse),
+        (status = 404, description = "Library template or branch not found", body = ErrorResponse),
+        (status = 409, description = "A template with this name already exists", body = ErrorResponse),
+        (status = 500, description = "Server error", body = ErrorResponse),
+    ),
+    security(("bearer_auth" = [])),
+    tag = "Templates"
+)]
+/// Copies a starter library template into the company, optionally for a single branch.
+pub async fn clone_library_template(
+    ManageCompanyUser(_claims, user): ManageCompanyUser,
+    State(state): State<AppState>,
+    Path(library_template_id): Path<String>,
+    Json(payload): Json<CloneLibraryTemplateRequest>,
+) -> Result<Json<AddTemplateResponse>, (StatusCode, Json<serde_json::Value>)> {
+    let template_id = services::TemplateLibraryService::clone_template(
+        &state,
+        &user,
+        &library_template_id,
+        payload.template_name,
+        payload.branch_id.filter(|b| !b.is_empty()),
+    )
+    .await
+    .map_err(|(status, err)| (status, Json(err)))?;
+
+    Ok(Json(AddTemplateResponse {
+        message: "Template cloned successfully.".to_string(),
+        template_id,
+    }))
+}
+
+#[utoipa::path(
+    post,
+    path = "/logs/templates/bundle/export",
+    request_body = ExportTemplateBundleRequest,
+    responses(
+        (status = 200, description = "Signed template bundle", body = TemplateBundle),
+        (status = 400, description = "No templates requested", body = ErrorResponse),
+        (status = 401, description = "Unauthorized", body = ErrorResponse),
+        (status = 403, description = "Forbidden", body = ErrorResponse),
+        (status = 404, description = "Template not found", body = ErrorResponse),
+        (status = 500, description = "Server error", body = ErrorResponse),
+    ),
+    security(("bearer_auth" = [])),
+    tag = "Templates"
+)]
+/// Exports one or more templates as a signed JSON bundle.
+pub async fn export_template_bundle(
+    BranchManagerUser(_claims, user): BranchManagerUser,
+    State(state): State<AppState>,
+    Json(payload): Json<ExportTemplateBundleRequest>,
+) -> Result<Json<TemplateBundle>, (StatusCode, Json<serde_json::Value>)> {
+    let bundle = services::TemplateBundleService::export_bundle(
+        &state,
+        &user,
+        &payload.template_ids,
+        payload.include_versions,
+    )
+    .await
+    .map_err(|(status, err)| (status, Json(err)))?;
+
+    Ok(Json(bundle))
+}
+
+#[utoipa::path(
+    post,
+    path = "/logs/templates/bundle/import",
+    request_body = ImportTemplateBundleRequest,
+    responses(
+        (status = 200, description = "Bundle imported", body = ImportTemplateBundleResponse),
+        (status = 400, description = "Invalid, unsigned or tampered bundle", body = ErrorResponse),
+        (status = 401, description = "Unauthorized", body = ErrorResponse),
+        (status = 403, description = "Forbidden", body = ErrorResponse),
+        (status = 404, description = "Branch not found", body = ErrorResponse),
+        (status = 500, description = "Server error", body = ErrorResponse),
+    ),
+    security(("bearer_auth" = [])),
+    tag = "Templates"
+)]
+/// Imports a signed template bundle into the current company or a branch.
+pub async fn import_template_bundle(
+    BranchManagerUser(_claims, user): BranchManagerUser,
+    State(state): State<AppState>,
+    Json(payload): Json<ImportTemplateBundleRequest>,
+) -> Result<Json<ImportTemplateBundleResponse>, (StatusCode, Json<serde_json::Value>)> {
+    let response = services::TemplateBundleService::import_bundle(
+        &state,
+        &user,
+        payload.bundle,
+        payload.branch_id.filter(|b| !b.is_empty()),
+        payload.on_name_conflict,
+    )
+    .await
+    .map_err(|(status, err)| (status, Json(err)))?;
+
+    Ok(Json(response))
+}
+
+#[utoipa::path(
+    post,
+    path = "/logs/templates/distribute",
+    request_body = DistributeTemplateRequest,
+    responses(
+        (status = 200, description = "Template copied to the branches", body = DistributeTemplateResponse),
+        (status = 400, description = "No branches given", body = ErrorResponse),
+        (status = 401, description = "Unauthorized", body = ErrorResponse),
+        (status = 403, description = "Forbidden", body = ErrorResponse),
+        (status = 404, description = "Template or branch not found", body = ErrorResponse),
+        (status = 409, description = "Template is archived", body = ErrorResponse),
+        (status = 500, description = "Server error", body = ErrorResponse),
+    ),
+    security(("bearer_auth" = [])),
+    tag = "Templates"
+)]
+/// Copies a template to a set of branches, optionally keeping the copies linked to it.
+pub async fn distribute_template(
+    ManageCompanyUser(_claims, user): ManageCompanyUser,
+    State(state): State<AppState>,
+    Json(payload): Json<DistributeTemplateRequest>,
+) -> Result<Json<DistributeTemplateResponse>, (StatusCode, Json<serde_json::Value>)> {
+    let template_key = services::TemplateService::template_key(
+        payload.template_id.as_deref(),
+        payload.template_name.as_deref(),
+    )
+    .map_err(|(status, err)| (status, Json(err)))?;
+    let response = services::TemplateService::distribute_template(
+        &state,
+        &user,
+        template_key,
+        &payload.branch_ids,
+        payload.linked,
+    )
+    .await
+    .map_err(|(status, err)| (status, Json(err)))?;
+
+    Ok(Json(response))
+}
+
+#[utoipa::path(
+    get,
+    path = "/logs/templates/propagate/preview",
+    params(GetTemplateRequest),
+    responses(
+        (status = 200, description = "Changes each linked copy would receive", body = PropagationPreviewResponse),
+        (status = 401, description = "Unauthorized", body = ErrorResponse),
+        (status = 403, description = "Forbidden", body = ErrorResponse),
+        (status = 404, description = "Template not found", body = ErrorResponse),
+        (status = 500, description = "Server error", body = ErrorResponse),
+    ),
+    security(("bearer_auth" = [])),
+    tag = "Templates"
+)]
+/// Previews pushing a template's current layout and schedule to its linked copies.
+pub async fn preview_template_propagation(
+    ManageCompanyUser(_claims, user): ManageCompanyUser,
+    State(state): State<AppState>,
+    Query(payload): Query<GetTemplateRequest>,
+) -> Result<Json<PropagationPreviewResponse>, (StatusCode, Json<serde_json::Value>)> {
+    let company_id = user.company_id.as_deref().ok_or((
+        StatusCode::FORBIDDEN,
+        Json(json!({ "error": "User is not associated with a company" })),
+    ))?;
+
+    let template_key = services::TemplateService::template_key(
+        payload.template_id.as_deref(),
+        payload.template_name.as_deref(),
+    )
+    .map_err(|(status, err)| (status, Json(err)))?;
+    let preview = services::TemplateService::preview_propagation(&state, company_id, template_key)
+        .await
+        .map_err(|(status, err)| (status, Json(err)))?;
+
+    Ok(Json(preview))
+}
+
+#[utoipa::path(
+    post,
+    path = "/logs/templates/propagate",
+    request_body = PropagateTemplateRequest,
+    responses(
+        (status = 200, description = "Linked copies updated, with any copies that failed", body = PropagateTemplateResponse),
+        (status = 401, description = "Unauthorized", body = ErrorResponse),
+        (status = 403, description = "Forbidden", body = ErrorResponse),
+        (status = 404, description = "Template or linked copy not found", body = ErrorResponse),
+        (status = 500, description = "Server error", body = ErrorResponse),
+    ),
+    security(("bearer_auth" = [])),
+    tag = "Templates"
+)]
+/// Pushes a template's current layout and schedule to its linked copies.
+pub async fn propagate_template(
+    ManageCompanyUser(_claims, user): ManageCompanyUser,
+    State(state): State<AppState>,
+    Json(payload): Json<PropagateTemplateRequest>,
+) -> Result<Json<PropagateTemplateResponse>, (StatusCode, Json<serde_json::Value>)> {
+    let template_key = services::TemplateService::template_key(
+        payload.template_id.as_deref(),
+        payload.template_name.as_deref(),
+    )
+    .map_err(|(status, err)| (status, Json(err)))?;
+    let response = services::TemplateService::propagate_template(
+        &state,
+        &user,
+        template_key,
+        payload.copy_ids.as_deref(),
+    )
+    .await
+    .map_err(|(status, err)| (status, Json(err)))?;
+
+    Ok(Json(response))
+}
+
+#[utoipa::path(
+    post,
+    path = "/logs/templates/unlink",
+    request_body = UnlinkTemplateRequest,
+    responses(
+        (status = 200, description = "Template unlinked successfully", body = UnarchiveTemplateResponse),
+        (status = 401, description = "Unauthorized", body = ErrorResponse),
+        (status = 403, description = "Forbidden", body = ErrorResponse),
+        (status = 404, description = "Template not found", body = ErrorResponse),
+        (status = 409, description = "Template is not linked", body = ErrorResponse),
+        (status = 500, description = "Server error", body = ErrorResponse),
+    ),
+    security(("bearer_auth" = [])),
+    tag = "Templates"
+)]
+/// Detaches a distributed copy from the template it was copied from.
+pub async fn unlink_template(
+    BranchManagerUser(_claims, user): BranchManagerUser,
+    State(state): State<AppState>,
+    Json(payload): Json<UnlinkTemplateRequest>,
+) -> Result<Json<UnarchiveTemplateResponse>, (StatusCode, Json<serde_json::Value>)> {
+    let company_id = user.company_id.as_ref().ok_or((
+        StatusCode::FORBIDDEN,
+        Json(json!({ "error": "User is not associated with a company" })),
+    ))?;
+
+    let template_key = services::TemplateService::template_key(
+        payload.template_id.as_deref(),
+        payload.template_name.as_deref(),
+    )
+    .map_err(|(status, err)| (status, Json(err)))?;
+    services::TemplateService::unlink_template(
+        &state,
+        company_id,
+        template_key,
+        user.branch_id.as_deref(),
+        &user.role,
+    )
+    .await
+    .map_err(|(status, err)| (status, Json(err)))?;
+    Ok(Json(UnarchiveTemplateResponse {
+        message: "Template unlinked successfully.".to_string(),
+    }))
+}
@@ -2,6 +2,7 @@ use anyhow::Result;
 
 const PROFILE_PICTURES_COLLECTION: &str = "profile_pictures";
 const COMPANY_LOGOS_COLLECTION: &str = "company_logos";
+const ENTRY_SIGNATURES_COLLECTION: &str = "entry_signatures";
 
 #[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
 struct ProfilePictureDoc {
@@ -21,6 +22,15 @@ struct CompanyLogoDoc {
     created_at: chrono::DateTime<chrono::Utc>,
 }
 
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+struct EntrySignatureDoc {
+    _id: mongodb::bson::Uuid,
+    entry_id: String,
+    data: Vec<u8>,
+    content_type: String,
+    created_at: chrono::DateTime<chrono::Utc>,
+}
+
 pub async fn upload_profile_picture(
     client: &mongodb::Client,
     data: Vec<u8>,
@@ -137,3 +147,70 @@ pub async fn delete_company_logo(client: &mongodb::Client, file_id: &str) -> Res
 
     Ok(())
 }
+
+pub async fn upload_entry_signature(
+    client: &mongodb::Client,
+    data: Vec<u8>,
+    entry_id: &str,
+    content_type: &str,
+) -> Result<String> {
+    let db = client.database("logs_db");
+    let collection: mongodb::Collection<EntrySignatureDoc> =
+        db.collection(ENTRY_SIGNATURES_COLLECTION);
+
+    let file_id = mongodb::bson::Uuid::new();
+
+    let doc = EntrySignatureDoc {
+        _id: file_id,
+        entry_id: entry_id.to_string(),
+        data,
+        content_type: content_type.to_string(),
+        created_at: chrono::Utc::now(),
+    };
+
+    collection.insert_one(doc).await?;
+
+    Ok(file_id.to_string())
+}
+
+/// Returns the content type and image data of a signature captured for `entry_id`.
+pub async fn get_entry_signature(
+    client: &mongodb::Client,
+    entry_id: &str,
+    file_id: &str,
+) -> Result<Option<(String, Vec<u8>)>> {
+    let db = client.database("logs_db");
+    let collection: mongodb::Collection<EntrySignatureDoc> =
+        db.collection(ENTRY_SIGNATURES_COLLECTION);
+
+    let Ok(oid) = mongodb::bson::Uuid::parse_str(file_id) else {
+        return Ok(None);
+    };
+
+    let filter = mongodb::bson::doc! { "_id": oid, "entry_id": entry_id };
+
+    let doc = collection.find_one(filter).await?;
+
+    Ok(doc.map(|signature| (signature.content_type, signature.data)))
+}
+
+/// Deletes the signatures captured for `entry_id`, except those listed in `keep`.
+pub async fn delete_entry_signatures(
+    client: &mongodb::Client,
+    entry_id: &str,
+    keep: &[String],
+) -> Result<()> {
+    let db = client.database("logs_db");
+    let collection: mongodb::Collection<EntrySignatureDoc> =
+        db.collection(ENTRY_SIGNATURES_COLLECTION);
+
+    let keep: Vec<mongodb::bson::Uuid> = keep
+        .iter()
+        .filter_map(|id| mongodb::bson::Uuid::parse_str(id).ok())
+        .collect();
+
+    let filter = mongodb::bson::doc! { "entry_id": entry_id, "_id": { "$nin": keep } };
+    collection.delete_many(filter).await?;
+
+    Ok(())
+}
@@ -1,9 +1,11 @@
 pub mod api_docs;
+pub mod attachments_db;
 pub mod auth;
 pub mod db;
 pub mod dto;
 pub mod email;
 pub mod exports_db;
+pub mod expression;
 pub mod handlers;
 pub mod images_db;
 pub mod jwt_manager;
@@ -11,10 +13,13 @@ pub mod llm;
 pub mod logs_db;
 pub mod metrics;
 pub mod middleware;
+pub mod pdf;
 pub mod rate_limit;
 pub mod security;
 pub mod services;
+pub mod template_library;
 pub mod utils;
+pub mod xlsx;
 
 #[path = "../tests/common/mod.rs"]
 pub mod common;
@@ -20,7 +20,7 @@ Return a JSON object for a form layout in this exact shape:
 {
   "template_layout": [
     {
-      "field_type": "text_input | checkbox | temperature | dropdown | label",
+      "field_type": "text_input | checkbox | temperature | dropdown | label | number | date | time | datetime | multi_select | signature",
       "position": { "x": number, "y": number },
       "props": { ... }
     }
@@ -38,6 +38,12 @@ Rules you must follow:
    - temperature
    - dropdown
    - label
+   - number
+   - date
+   - time
+   - datetime
+   - multi_select
+   - signature
 
 2) Positioning:
    - Use absolute coordinates in pixels via position.x and position.y.
@@ -51,6 +57,10 @@ Rules you must follow:
    - checkbox: use text and optional required.
    - temperature: include min/max as numbers, optional value, label, and unit.
    - dropdown: include options (non-empty array), optional selected.
+   - number: use text, optional min/max as numbers, unit (e.g. "kg", "ppm") and decimals (0-6).
+   - date, time, datetime: use text and optional required.
+   - multi_select: include options (non-empty array of unique strings); use for checklists where several items may apply.
+   - signature: use text (e.g. "Signed off by") and optional required.
    - label: use text; optional style props (size, weight, font_family, text_decoration, color).
 
 4) General props guidance:
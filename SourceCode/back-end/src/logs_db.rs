@@ -1,5 +1,6 @@
 use anyhow::Result;
 use chrono::Datelike;
+use chrono::TimeZone;
 use chrono::Timelike;
 use futures_util::TryStreamExt;
 use mongodb::bson::Uuid;
@@ -7,7 +8,7 @@ use mongodb::options::ReturnDocument;
 use schemars::JsonSchema;
 use utoipa::ToSchema;
 
-#[derive(Debug, Clone, serde::Deserialize, serde::Serialize, ToSchema, JsonSchema)]
+#[derive(Debug, Clone, PartialEq, serde::Deserialize, serde::Serialize, ToSchema, JsonSchema)]
 pub struct Position {
     pub x: f64,
     pub y: f64,
@@ -33,6 +34,19 @@ pub struct TemplateFieldProps {
     pub max_length: Option<i32>,
     pub min_length: Option<i32>,
     pub input_type: Option<String>,
+    /// Maximum number of decimal places accepted by a `number` field.
+    #[serde(default)]
+    pub decimals: Option<u8>,
+    /// Only show the field while this condition holds. Hidden fields are ignored on submit.
+    #[serde(default)]
+    pub visible_if: Option<FieldCondition>,
+    /// Treat the field as required while this condition holds, in addition to `required`.
+    #[serde(default)]
+    pub required_if: Option<FieldCondition>,
+    /// Formula of a `calculated` field, referencing other fields as `{field_id}`.
+    /// See [`crate::expression`] for the syntax.
+    #[serde(default)]
+    pub expression: Option<String>,
 }
 
 impl Default for TemplateFieldProps {
@@ -56,18 +70,686 @@ impl Default for TemplateFieldProps {
             max_length: None,
             min_length: None,
             input_type: Some("text".to_string()),
+            decimals: None,
+            visible_if: None,
+            required_if: None,
+            expression: None,
+        }
+    }
+}
+
+/// How a [`FieldCondition`] tests the value of the field it references.
+#[derive(
+    Debug, Clone, Copy, PartialEq, Eq, serde::Deserialize, serde::Serialize, ToSchema, JsonSchema,
+)]
+#[serde(rename_all = "snake_case")]
+pub enum ConditionOperator {
+    Equals,
+    NotEquals,
+    GreaterThan,
+    GreaterThanOrEqual,
+    LessThan,
+    LessThanOrEqual,
+    Checked,
+    Unchecked,
+    Empty,
+    NotEmpty,
+    /// The reading is above the referenced field's `max`.
+    AboveMax,
+    /// The reading is below the referenced field's `min`.
+    BelowMin,
+    /// The reading is outside the referenced field's `min`/`max`.
+    OutOfRange,
+}
+
+impl ConditionOperator {
+    /// Whether the operator compares against [`FieldCondition::value`].
+    #[must_use]
+    pub fn takes_value(self) -> bool {
+        matches!(
+            self,
+            Self::Equals
+                | Self::NotEquals
+                | Self::GreaterThan
+                | Self::GreaterThanOrEqual
+                | Self::LessThan
+                | Self::LessThanOrEqual
+        )
+    }
+
+    /// Whether the operator can be applied to the value of `field`.
+    #[must_use]
+    pub fn applies_to(self, field: &TemplateField) -> bool {
+        let numeric = matches!(
+            field.field_type.as_str(),
+            "temperature" | "number" | "calculated"
+        ) || (matches!(field.field_type.as_str(), "text" | "text_input")
+            && matches!(field.props.input_type.as_deref(), Some("int" | "float")));
+        match self {
+            Self::Equals | Self::NotEquals | Self::Empty | Self::NotEmpty => true,
+            Self::GreaterThan
+            | Self::GreaterThanOrEqual
+            | Self::LessThan
+            | Self::LessThanOrEqual => numeric,
+            Self::Checked | Self::Unchecked => {
+                matches!(field.field_type.as_str(), "checkbox" | "calculated")
+            }
+            Self::AboveMax => field.field_type == "temperature" && field.props.max.is_some(),
+            Self::BelowMin => field.field_type == "temperature" && field.props.min.is_some(),
+            Self::OutOfRange => {
+                field.field_type == "temperature"
+                    && (field.props.min.is_some() || field.props.max.is_some())
+            }
+        }
+    }
+}
+
+/// A test on another field's value, used by the `visible_if` and `required_if` field rules.
+#[derive(Debug, Clone, serde::Deserialize, serde::Serialize, ToSchema, JsonSchema)]
+pub struct FieldCondition {
+    /// Id of the field whose value is tested, or its index for fields that have no id yet.
+    /// Indices are replaced with the field's id when the layout is saved.
+    pub field_id: String,
+    pub operator: ConditionOperator,
+    /// The value compared against by `equals`, `not_equals` and the numeric comparisons.
+    #[serde(default)]
+    pub value: Option<serde_json::Value>,
+}
+
+/// Whether a value counts as empty: blank text or an empty multi-select.
+fn is_blank(value: &serde_json::Value) -> bool {
+    match value {
+        serde_json::Value::String(s) => s.trim().is_empty(),
+        serde_json::Value::Array(items) => items.is_empty(),
+        _ => false,
+    }
+}
+
+/// Reads a number from a JSON number or a numeric string, as entered in `int`/`float` inputs.
+fn condition_number(value: &serde_json::Value) -> Option<f64> {
+    value
+        .as_f64()
+        .or_else(|| value.as_str().and_then(|s| s.trim().parse::<f64>().ok()))
+        .filter(|n| n.is_finite())
+}
+
+impl FieldCondition {
+    /// Tests the condition against `value`, the current value of the referenced `field`.
+    /// `None` means the field has no value or is itself hidden.
+    #[must_use]
+    pub fn is_met(&self, field: &TemplateField, value: Option<&serde_json::Value>) -> bool {
+        let value = value.filter(|v| !v.is_null());
+        let number = value.and_then(condition_number);
+        let expected = self.value.as_ref().and_then(condition_number);
+        let compare = |f: fn(f64, f64) -> bool| number.zip(expected).is_some_and(|(a, b)| f(a, b));
+
+        match self.operator {
+            ConditionOperator::Equals => self.value_equals(value),
+            ConditionOperator::NotEquals => !self.value_equals(value),
+            ConditionOperator::GreaterThan => compare(|a, b| a > b),
+            ConditionOperator::GreaterThanOrEqual => compare(|a, b| a >= b),
+            ConditionOperator::LessThan => compare(|a, b| a < b),
+            ConditionOperator::LessThanOrEqual => compare(|a, b| a <= b),
+            ConditionOperator::Checked => value.and_then(serde_json::Value::as_bool) == Some(true),
+            ConditionOperator::Unchecked => {
+                value.and_then(serde_json::Value::as_bool) != Some(true)
+            }
+            ConditionOperator::Empty => value.is_none_or(is_blank),
+            ConditionOperator::NotEmpty => value.is_some_and(|v| !is_blank(v)),
+            ConditionOperator::AboveMax => number
+                .zip(field.props.max)
+                .is_some_and(|(n, max)| n > f64::from(max)),
+            ConditionOperator::BelowMin => number
+                .zip(field.props.min)
+                .is_some_and(|(n, min)| n < f64::from(min)),
+            ConditionOperator::OutOfRange => number.is_some_and(|n| {
+                field.props.min.is_some_and(|min| n < f64::from(min))
+                    || field.props.max.is_some_and(|max| n > f64::from(max))
+            }),
+        }
+    }
+
+    /// Numbers are compared by value so `"12"` from an `int` input equals `12`.
+    /// An unticked checkbox has no stored value but counts as `false`.
+    fn value_equals(&self, value: Option<&serde_json::Value>) -> bool {
+        let Some(expected) = &self.value else {
+            return false;
+        };
+        let Some(value) = value else {
+            return *expected == serde_json::Value::Bool(false);
+        };
+        // A multi-select equals a value when that option is among those selected
+        if let Some(selected) = value.as_array() {
+            return selected.contains(expected);
+        }
+        match (condition_number(value), condition_number(expected)) {
+            (Some(a), Some(b)) => (a - b).abs() < f64::EPSILON,
+            _ => value == expected,
+        }
+    }
+}
+
+/// Evaluates the `visible_if` and `required_if` rules of a layout against entry data.
+pub struct FieldRules<'a> {
+    fields: std::collections::HashMap<String, &'a TemplateField>,
+    entry_data: &'a serde_json::Value,
+    hidden: std::collections::HashSet<String>,
+}
+
+impl<'a> FieldRules<'a> {
+    #[must_use]
+    pub fn new(layout: &'a TemplateLayout, entry_data: &'a serde_json::Value) -> Self {
+        let fields: std::collections::HashMap<String, &TemplateField> = layout
+            .iter()
+            .enumerate()
+            .map(|(field_index, field)| (field.entry_key(field_index), field))
+            .collect();
+
+        let mut visibility = std::collections::HashMap::new();
+        for key in fields.keys() {
+            Self::resolve_visibility(key, &fields, entry_data, &mut visibility, 0);
+        }
+        let hidden = visibility
+            .into_iter()
+            .filter(|(_, visible)| !visible)
+            .map(|(key, _)| key)
+            .collect();
+
+        Self {
+            fields,
+            entry_data,
+            hidden,
+        }
+    }
+
+    /// A field is hidden when its `visible_if` condition does not hold. Fields that depend on
+    /// a hidden field see it as having no value.
+    fn resolve_visibility(
+        key: &str,
+        fields: &std::collections::HashMap<String, &TemplateField>,
+        entry_data: &serde_json::Value,
+        visibility: &mut std::collections::HashMap<String, bool>,
+        depth: usize,
+    ) -> bool {
+        if let Some(&visible) = visibility.get(key) {
+            return visible;
+        }
+        let Some(condition) = fields.get(key).and_then(|f| f.props.visible_if.as_ref()) else {
+            return true;
+        };
+        // Cycles are rejected when a layout is saved; the depth limit only guards stored data
+        if depth > fields.len() {
+            return true;
         }
+
+        let visible = fields.get(&condition.field_id).is_some_and(|source| {
+            let source_visible = Self::resolve_visibility(
+                &condition.field_id,
+                fields,
+                entry_data,
+                visibility,
+                depth + 1,
+            );
+            let value = entry_data
+                .get(&condition.field_id)
+                .filter(|_| source_visible);
+            condition.is_met(source, value)
+        });
+        visibility.insert(key.to_string(), visible);
+        visible
+    }
+
+    /// Whether the field stored under `key` is currently hidden.
+    #[must_use]
+    pub fn is_hidden(&self, key: &str) -> bool {
+        self.hidden.contains(key)
+    }
+
+    /// Whether `field` must have a value on submit, from its `required` flag or `required_if` rule.
+    #[must_use]
+    pub fn is_required(&self, field: &TemplateField) -> bool {
+        field.props.required.unwrap_or(false)
+            || field.props.required_if.as_ref().is_some_and(|condition| {
+                self.fields.get(&condition.field_id).is_some_and(|source| {
+                    let value = self
+                        .entry_data
+                        .get(&condition.field_id)
+                        .filter(|_| !self.is_hidden(&condition.field_id));
+                    condition.is_met(source, value)
+                })
+            })
     }
 }
 
 #[derive(Debug, Clone, serde::Deserialize, serde::Serialize, ToSchema, JsonSchema)]
 pub struct TemplateField {
+    /// Stable identifier used as the key for this field's value in `LogEntry.entry_data`.
+    /// Generated when the field is first saved and preserved across layout edits.
+    #[serde(default)]
+    pub field_id: String,
     pub field_type: String,
     pub position: Position,
     pub props: TemplateFieldProps,
 }
 
-#[derive(Debug, serde::Deserialize, serde::Serialize, ToSchema, JsonSchema)]
+impl TemplateField {
+    /// Returns the key this field's value is stored under in `entry_data`.
+    /// Falls back to the field index for layouts that predate field ids.
+    #[must_use]
+    pub fn entry_key(&self, field_index: usize) -> String {
+        if self.field_id.is_empty() {
+            field_index.to_string()
+        } else {
+            self.field_id.clone()
+        }
+    }
+}
+
+/// Reads a stored field value as an expression input. Unticked checkboxes count as `false`;
+/// numeric text inputs are read as numbers.
+fn expression_input(
+    field: &TemplateField,
+    value: Option<&serde_json::Value>,
+) -> Option<crate::expression::Value> {
+    use crate::expression::Value;
+
+    let value = value.filter(|v| !v.is_null());
+    match field.field_type.as_str() {
+        "checkbox" => Some(Value::Bool(
+            value.and_then(serde_json::Value::as_bool).unwrap_or(false),
+        )),
+        "temperature" | "number" => value.and_then(condition_number).map(Value::Number),
+        "multi_select" | "signature" => None,
+        "text" | "text_input"
+            if matches!(field.props.input_type.as_deref(), Some("int" | "float")) =>
+        {
+            value.and_then(condition_number).map(Value::Number)
+        }
+        _ => match value? {
+            serde_json::Value::Bool(b) => Some(Value::Bool(*b)),
+            serde_json::Value::Number(n) => n.as_f64().map(Value::Number),
+            serde_json::Value::String(s) if !s.trim().is_empty() => Some(Value::Text(s.clone())),
+            _ => None,
+        },
+    }
+}
+
+/// Returns `entry_data` with the value of every `calculated` field recomputed from its
+/// expression. Values sent for calculated fields are discarded, and a field whose inputs
+/// are missing or whose expression cannot be evaluated is left without a value.
+#[must_use]
+pub fn calculate_fields(
+    layout: &TemplateLayout,
+    entry_data: &serde_json::Value,
+) -> serde_json::Value {
+    let Some(values) = entry_data.as_object() else {
+        return entry_data.clone();
+    };
+
+    let fields: std::collections::HashMap<String, &TemplateField> = layout
+        .iter()
+        .enumerate()
+        .map(|(field_index, field)| (field.entry_key(field_index), field))
+        .collect();
+    let mut values = values.clone();
+    for (key, field) in &fields {
+        if field.field_type == "calculated" {
+            values.remove(key);
+        }
+    }
+
+    let mut results = std::collections::HashMap::new();
+    for (key, field) in &fields {
+        if field.field_type == "calculated" {
+            calculate_field(key, &fields, &values, &mut results, 0);
+        }
+    }
+    for (key, value) in results {
+        if let Some(value) = value {
+            values.insert(key, value.to_json());
+        }
+    }
+    serde_json::Value::Object(values)
+}
+
+fn calculate_field(
+    key: &str,
+    fields: &std::collections::HashMap<String, &TemplateField>,
+    values: &serde_json::Map<String, serde_json::Value>,
+    results: &mut std::collections::HashMap<String, Option<crate::expression::Value>>,
+    depth: usize,
+) -> Option<crate::expression::Value> {
+    if let Some(result) = results.get(key) {
+        return result.clone();
+    }
+    let field = fields.get(key)?;
+    if field.field_type != "calculated" {
+        return expression_input(field, values.get(key));
+    }
+    // Reference cycles are rejected when a layout is saved; the depth limit only guards stored data
+    if depth > fields.len() {
+        return None;
+    }
+
+    let expr = crate::expression::parse(field.props.expression.as_deref()?).ok()?;
+    let mut inputs = std::collections::HashMap::new();
+    for reference in expr.references() {
+        if !inputs.contains_key(reference) {
+            let input = calculate_field(reference, fields, values, results, depth + 1);
+            inputs.insert(reference.to_string(), input);
+        }
+    }
+    let result = expr
+        .evaluate(&|reference| inputs.get(reference).cloned().flatten())
+        .ok();
+    results.insert(key.to_string(), result.clone());
+    result
+}
+
+/// A field as shown in a layout comparison.
+#[derive(Debug, Clone, PartialEq, serde::Serialize, ToSchema)]
+pub struct LayoutFieldSummary {
+    pub field_id: String,
+    pub field_type: String,
+    pub label: Option<String>,
+}
+
+impl LayoutFieldSummary {
+    fn new(field: &TemplateField, field_index: usize) -> Self {
+        Self {
+            field_id: field.entry_key(field_index),
+            field_type: field.field_type.clone(),
+            label: field.props.text.clone().filter(|t| !t.trim().is_empty()),
+        }
+    }
+}
+
+/// A single prop or setting that differs between two versions. `old` is `None` when the
+/// value was added and `new` is `None` when it was removed.
+#[derive(Debug, Clone, PartialEq, serde::Serialize, ToSchema)]
+pub struct PropertyChange {
+    pub name: String,
+    pub old: Option<serde_json::Value>,
+    pub new: Option<serde_json::Value>,
+}
+
+/// Lists the top-level keys whose values differ between two serialized values. Missing keys
+/// and `null` are treated alike.
+fn diff_properties<T: serde::Serialize>(old: &T, new: &T) -> Vec<PropertyChange> {
+    let as_object = |value: &T| match serde_json::to_value(value) {
+        Ok(serde_json::Value::Object(map)) => map,
+        _ => serde_json::Map::new(),
+    };
+    let (old, new) = (as_object(old), as_object(new));
+    let present = |map: &serde_json::Map<String, serde_json::Value>, key: &str| {
+        map.get(key).filter(|v| !v.is_null()).cloned()
+    };
+
+    let names: std::collections::BTreeSet<&String> = old.keys().chain(new.keys()).collect();
+    names
+        .into_iter()
+        .filter_map(|name| {
+            let (old_value, new_value) = (present(&old, name), present(&new, name));
+            (old_value != new_value).then(|| PropertyChange {
+                name: name.clone(),
+                old: old_value,
+                new: new_value,
+            })
+        })
+        .collect()
+}
+
+/// Compares two schedules setting by setting.
+#[must_use]
+pub fn diff_schedules(old: &Schedule, new: &Schedule) -> Vec<PropertyChange> {
+    diff_properties(old, new)
+}
+
+/// A field whose type or props differ between two layouts.
+#[derive(Debug, Clone, PartialEq, serde::Serialize, ToSchema)]
+pub struct FieldChange {
+    #[serde(flatten)]
+    pub field: LayoutFieldSummary,
+    /// The props that changed. A type change is reported as `field_type`.
+    pub changes: Vec<PropertyChange>,
+}
+
+/// A field that was moved on the form.
+#[derive(Debug, Clone, PartialEq, serde::Serialize, ToSchema)]
+pub struct FieldMove {
+    #[serde(flatten)]
+    pub field: LayoutFieldSummary,
+    pub from: Position,
+    pub to: Position,
+}
+
+/// Fields added, removed, modified or moved between two layouts, matched by field id.
+#[derive(Debug, Default, Clone, PartialEq, serde::Serialize, ToSchema)]
+pub struct LayoutDiff {
+    pub added: Vec<LayoutFieldSummary>,
+    pub removed: Vec<LayoutFieldSummary>,
+    pub changed: Vec<FieldChange>,
+    pub moved: Vec<FieldMove>,
+}
+
+impl LayoutDiff {
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.added.is_empty()
+            && self.removed.is_empty()
+            && self.changed.is_empty()
+            && self.moved.is_empty()
+    }
+}
+
+/// Compares two layouts field by field. A field counts as changed when its type or any of
+/// its props differ, and as moved when its position differs.
+#[must_use]
+pub fn diff_layouts(old: &TemplateLayout, new: &TemplateLayout) -> LayoutDiff {
+    let old_fields: std::collections::HashMap<String, &TemplateField> = old
+        .iter()
+        .enumerate()
+        .map(|(field_index, field)| (field.entry_key(field_index), field))
+        .collect();
+    let new_keys: std::collections::HashSet<String> = new
+        .iter()
+        .enumerate()
+        .map(|(field_index, field)| field.entry_key(field_index))
+        .collect();
+
+    let mut diff = LayoutDiff::default();
+    for (field_index, field) in new.iter().enumerate() {
+        let Some(previous) = old_fields.get(&field.entry_key(field_index)) else {
+            diff.added.push(LayoutFieldSummary::new(field, field_index));
+            continue;
+        };
+
+        let mut changes = Vec::new();
+        if previous.field_type != field.field_type {
+            changes.push(PropertyChange {
+                name: "field_type".to_string(),
+                old: Some(serde_json::Value::from(previous.field_type.as_str())),
+                new: Some(serde_json::Value::from(field.field_type.as_str())),
+            });
+        }
+        changes.extend(diff_properties(&previous.props, &field.props));
+        if !changes.is_empty() {
+            diff.changed.push(FieldChange {
+                field: LayoutFieldSummary::new(field, field_index),
+                changes,
+            });
+        }
+
+        if previous.position != field.position {
+            diff.moved.push(FieldMove {
+                field: LayoutFieldSummary::new(field, field_index),
+                from: previous.position.clone(),
+                to: field.position.clone(),
+            });
+        }
+    }
+    for (field_index, field) in old.iter().enumerate() {
+        if !new_keys.contains(&field.entry_key(field_index)) {
+            diff.removed
+                .push(LayoutFieldSummary::new(field, field_index));
+        }
+    }
+    diff
+}
+
+/// Assigns a field id to every field in `layout` that does not have one.
+///
+/// When `previous` is provided and no field in `layout` has an id, a field reuses the id of
+/// the field at the same index in the previous layout if it has the same type. This keeps ids
+/// stable for legacy layouts saved by clients that never sent ids. Once a client sends ids,
+/// fields without one are new and always get a fresh id, so deleting a field cannot hand its
+/// id to the field that moved into its place.
+/// Field rules and expressions that reference a field by index are updated to its id.
+#[must_use]
+pub fn assign_field_ids(
+    layout: &TemplateLayout,
+    previous: Option<&TemplateLayout>,
+) -> TemplateLayout {
+    let mut used: std::collections::HashSet<String> = layout
+        .iter()
+        .filter(|f| !f.field_id.is_empty())
+        .map(|f| f.field_id.clone())
+        .collect();
+    let previous = previous.filter(|_| used.is_empty());
+
+    let mut assigned: TemplateLayout = layout
+        .iter()
+        .enumerate()
+        .map(|(field_index, field)| {
+            let mut field = field.clone();
+            if field.field_id.is_empty() {
+                let reused = previous
+                    .and_then(|prev| prev.get(field_index))
+                    .filter(|prev| {
+                        prev.field_type == field.field_type
+                            && !prev.field_id.is_empty()
+                            && !used.contains(&prev.field_id)
+                    })
+                    .map(|prev| prev.field_id.clone());
+                field.field_id = reused.unwrap_or_else(|| uuid::Uuid::new_v4().to_string());
+                used.insert(field.field_id.clone());
+            }
+            field
+        })
+        .collect();
+
+    // Rules and expressions written against fields that had no id yet refer to them by index
+    let ids: Vec<String> = assigned.iter().map(|f| f.field_id.clone()).collect();
+    for field in &mut assigned {
+        if let Some(expression) = &mut field.props.expression {
+            *expression = crate::expression::rewrite_references(expression, |reference| {
+                reference
+                    .parse::<usize>()
+                    .ok()
+                    .and_then(|index| ids.get(index).cloned())
+            });
+        }
+        let rules = [&mut field.props.visible_if, &mut field.props.required_if];
+        for condition in rules.into_iter().flatten() {
+            if let Some(id) = condition
+                .field_id
+                .parse::<usize>()
+                .ok()
+                .and_then(|index| ids.get(index))
+            {
+                condition.field_id.clone_from(id);
+            }
+        }
+    }
+    assigned
+}
+
+/// Assigns ids to a legacy `layout` by matching its fields to `reference` on type and label.
+/// A field takes the id of the one reference field with the same type and label; fields
+/// without exactly one match in either layout get a fresh id, so a field that was reordered
+/// or removed never inherits the id of another field.
+#[must_use]
+pub fn match_legacy_field_ids(
+    layout: &TemplateLayout,
+    reference: &TemplateLayout,
+) -> TemplateLayout {
+    fn match_key(field: &TemplateField) -> (&str, Option<&str>) {
+        (
+            field.field_type.as_str(),
+            field.props.text.as_deref().map(str::trim),
+        )
+    }
+
+    let mut counts: std::collections::HashMap<(&str, Option<&str>), (usize, usize)> =
+        std::collections::HashMap::new();
+    for field in layout {
+        counts.entry(match_key(field)).or_default().0 += 1;
+    }
+    for field in reference.iter().filter(|f| !f.field_id.is_empty()) {
+        counts.entry(match_key(field)).or_default().1 += 1;
+    }
+
+    let matched: TemplateLayout = layout
+        .iter()
+        .map(|field| {
+            let mut field = field.clone();
+            if field.field_id.is_empty()
+                && counts.get(&match_key(&field)) == Some(&(1, 1))
+                && let Some(found) = reference
+                    .iter()
+                    .find(|r| !r.field_id.is_empty() && match_key(r) == match_key(&field))
+            {
+                field.field_id.clone_from(&found.field_id);
+            }
+            field
+        })
+        .collect();
+    assign_field_ids(&matched, None)
+}
+
+/// Returns the layout a legacy entry last saved at `saved_at` was filled against: the
+/// earliest of `archived` (version layouts with the time they were replaced, oldest first)
+/// replaced after that save, or `current` if the template has not changed since.
+#[must_use]
+pub fn legacy_entry_layout<'a>(
+    archived: &'a [(chrono::DateTime<chrono::Utc>, TemplateLayout)],
+    current: &'a TemplateLayout,
+    saved_at: chrono::DateTime<chrono::Utc>,
+) -> &'a TemplateLayout {
+    archived
+        .iter()
+        .find(|(replaced_at, _)| *replaced_at > saved_at)
+        .map_or(current, |(_, layout)| layout)
+}
+
+/// Rewrites index-keyed `entry_data` (e.g. `"0"`, `"1"`) to the field ids of `layout`.
+/// Keys that are not field indices, or whose field has no id, are left unchanged.
+#[must_use]
+pub fn normalize_entry_data_keys(
+    layout: &TemplateLayout,
+    entry_data: &serde_json::Value,
+) -> serde_json::Value {
+    let Some(values) = entry_data.as_object() else {
+        return entry_data.clone();
+    };
+
+    let normalized = values
+        .iter()
+        .map(|(key, value)| {
+            let new_key = key
+                .parse::<usize>()
+                .ok()
+                .and_then(|index| layout.get(index))
+                .filter(|field| !field.field_id.is_empty())
+                .map_or_else(|| key.clone(), |field| field.field_id.clone());
+            (new_key, value.clone())
+        })
+        .collect();
+
+    serde_json::Value::Object(normalized)
+}
+
+#[derive(Debug, Clone, serde::Deserialize, serde::Serialize, ToSchema, JsonSchema)]
 pub enum Frequency {
     Daily,
     Weekly,
@@ -91,7 +773,16 @@ fn get_month_last_day(year: i32, month: u32) -> u32 {
     }
 }
 
-#[derive(Debug, serde::Deserialize, serde::Serialize, ToSchema)]
+/// One occurrence within the day of a sub-daily schedule.
+#[derive(Debug, Clone, PartialEq, serde::Deserialize, serde::Serialize, ToSchema)]
+pub struct TimeSlot {
+    /// Time the slot opens, as `HH:MM`. Also identifies the slot in period strings.
+    pub available_from_time: String,
+    /// Time the slot is due, as `HH:MM`.
+    pub due_at_time: String,
+}
+
+#[derive(Debug, Clone, serde::Deserialize, serde::Serialize, ToSchema)]
 pub struct Schedule {
     pub frequency: Frequency,
     pub days_of_week: Option<Vec<u8>>,
@@ -102,12 +793,98 @@ pub struct Schedule {
     pub available_from_time: Option<String>,
     #[serde(default)]
     pub due_at_time: Option<String>,
+    /// Repeat every N days for daily schedules or every N weeks for weekly ones, counted
+    /// from `starts_on`. Defaults to 1.
+    #[serde(default)]
+    pub interval: Option<u16>,
+    /// First day of a daily or weekly schedule. Required when `interval` is greater than 1.
+    #[serde(default)]
+    pub starts_on: Option<chrono::NaiveDate>,
+    /// Several occurrences per day for daily schedules, each its own `DD/MM/YYYY HH:MM`
+    /// period. Replaces `available_from_time` and `due_at_time`.
+    #[serde(default)]
+    pub time_slots: Option<Vec<TimeSlot>>,
+}
+
+impl Schedule {
+    /// Opening and due times of a daily schedule's time slots, in minutes after midnight,
+    /// ordered by opening time. Empty when the schedule has no valid slots.
+    #[must_use]
+    pub fn daily_slots(&self) -> Vec<(u32, u32)> {
+        if !matches!(self.frequency, Frequency::Daily) {
+            return Vec::new();
+        }
+        let mut slots: Vec<(u32, u32)> = self
+            .time_slots
+            .iter()
+            .flatten()
+            .filter_map(|slot| {
+                let (from_h, from_m) = parse_time_string(&slot.available_from_time)?;
+                let (due_h, due_m) = parse_time_string(&slot.due_at_time)?;
+                Some((from_h * 60 + from_m, due_h * 60 + due_m))
+            })
+            .collect();
+        slots.sort_unstable();
+        slots
+    }
+
+    fn interval_length(&self) -> i64 {
+        i64::from(self.interval.unwrap_or(1).max(1))
+    }
+
+    /// Whether the day (daily) or Sunday-to-Saturday week (weekly) containing `date` is on
+    /// the schedule's interval and not before `starts_on`. Always true for other frequencies.
+    #[must_use]
+    pub fn matches_interval(&self, date: chrono::NaiveDate) -> bool {
+        let Some(starts_on) = self.starts_on else {
+            return true;
+        };
+        let elapsed = match self.frequency {
+            Frequency::Daily => (date - starts_on).num_days(),
+            Frequency::Weekly => (week_start(date) - week_start(starts_on)).num_days() / 7,
+            Frequency::Monthly | Frequency::Quarterly | Frequency::Yearly => return true,
+        };
+        elapsed >= 0 && elapsed % self.interval_length() == 0
+    }
+
+    /// Whether a daily schedule has an occurrence on `date`, honouring both `days_of_week`
+    /// and the interval.
+    fn is_scheduled_day(&self, date: chrono::NaiveDate) -> bool {
+        let weekday = u8::try_from(date.weekday().num_days_from_sunday()).unwrap_or_default();
+        self.days_of_week
+            .as_ref()
+            .is_none_or(|days| days.contains(&weekday))
+            && self.matches_interval(date)
+    }
+}
+
+fn week_start(date: chrono::NaiveDate) -> chrono::NaiveDate {
+    date - chrono::Duration::days(i64::from(date.weekday().num_days_from_sunday()))
 }
 
 pub type TemplateLayout = Vec<TemplateField>;
 
+/// How a template's entries relate to its schedule. Set when the template is created.
+#[derive(
+    Debug, Clone, Copy, Default, PartialEq, Eq, serde::Deserialize, serde::Serialize, ToSchema,
+)]
+#[serde(rename_all = "lowercase")]
+pub enum EntryMode {
+    /// One entry per scheduled period, with due times and missed periods.
+    #[default]
+    Scheduled,
+    /// Any number of entries per period of the schedule, for logs such as deliveries
+    /// received. Periods are never due or missed.
+    Repeatable,
+    /// Any number of entries at any time, each labelled with the day it was started.
+    Unscheduled,
+}
+
 #[derive(Debug, serde::Deserialize, serde::Serialize, ToSchema)]
 pub struct TemplateDocument {
+    /// Immutable identifier that entries, versions and issues refer to. The name is display-only.
+    #[serde(default)]
+    pub template_id: String,
     pub template_name: String,
     pub template_layout: TemplateLayout,
     pub company_id: String,
@@ -119,6 +896,15 @@ pub struct TemplateDocument {
     #[serde(default = "default_version")]
     pub version: u16,
     pub version_name: Option<String>,
+    /// Set while the template is archived. Archived templates are no longer scheduled but
+    /// their entries and versions are kept.
+    #[serde(default)]
+    pub archived_at: Option<chrono::DateTime<chrono::Utc>>,
+    /// Id of the template this one was distributed from while the copy stays linked to it.
+    #[serde(default)]
+    pub linked_from: Option<String>,
+    #[serde(default)]
+    pub entry_mode: EntryMode,
 }
 
 impl TemplateDocument {
@@ -126,14 +912,71 @@ impl TemplateDocument {
     pub fn is_company_wide(&self) -> bool {
         self.branch_id.is_none()
     }
+
+    #[must_use]
+    pub fn is_archived(&self) -> bool {
+        self.archived_at.is_some()
+    }
+
+    /// Whether entries are due once per period. Repeatable and unscheduled templates have no
+    /// due, missed or overdue periods.
+    #[must_use]
+    pub fn is_scheduled(&self) -> bool {
+        self.entry_mode == EntryMode::Scheduled
+    }
+
+    /// Status and availability of an entry of this template. Drafts of repeatable and
+    /// unscheduled templates never become overdue.
+    #[must_use]
+    pub fn derive_entry_status(
+        &self,
+        status: LogStatus,
+        period: &str,
+        now: chrono::DateTime<chrono::Utc>,
+        tz: chrono_tz::Tz,
+    ) -> (LogStatus, AvailabilityStatus) {
+        if self.is_scheduled() {
+            derive_log_status(status, &self.schedule, period, now, tz)
+        } else {
+            (status, AvailabilityStatus::Available)
+        }
+    }
+
+    /// Availability of a period of this template. Entries of repeatable and unscheduled
+    /// templates are always available.
+    #[must_use]
+    pub fn availability_for_period(
+        &self,
+        period: &str,
+        now: chrono::DateTime<chrono::Utc>,
+        tz: chrono_tz::Tz,
+    ) -> AvailabilityStatus {
+        self.derive_entry_status(LogStatus::Draft, period, now, tz)
+            .1
+    }
 }
 
 fn default_version() -> u16 {
     1
 }
 
+/// A LogSmart-curated starter template that companies can clone into their own templates.
+#[derive(Debug, serde::Deserialize, serde::Serialize, ToSchema)]
+pub struct LibraryTemplateDocument {
+    pub library_template_id: String,
+    pub template_name: String,
+    pub category: String,
+    pub description: String,
+    pub template_layout: TemplateLayout,
+    pub schedule: Schedule,
+    pub created_at: chrono::DateTime<chrono::Utc>,
+    pub updated_at: chrono::DateTime<chrono::Utc>,
+}
+
 #[derive(Debug, serde::Deserialize, serde::Serialize, ToSchema)]
 pub struct TemplateVersionDocument {
+    #[serde(default)]
+    pub template_id: String,
     pub template_name: String,
     pub company_id: String,
     pub branch_id: Option<String>,
@@ -159,15 +1002,414 @@ pub async fn init_mongodb() -> Result<mongodb::Client> {
         .map_err(anyhow::Error::from)?;
 
     ensure_report_run_indexes(&client).await?;
+    ensure_log_entry_indexes(&client).await?;
+    ensure_corrective_action_issue_indexes(&client).await?;
+    ensure_entry_transition_indexes(&client).await?;
+    crate::attachments_db::ensure_attachment_indexes(&client).await?;
+    migrate_template_field_ids(&client).await?;
+    migrate_template_ids(&client).await?;
+    migrate_template_has_entries(&client).await?;
+    ensure_template_indexes(&client).await?;
+    seed_template_library(&client).await?;
 
     Ok(client)
 }
 
-async fn ensure_report_run_indexes(client: &mongodb::Client) -> Result<()> {
+const TEMPLATE_FIELD_IDS_MIGRATION: &str = "20260601_template_field_ids";
+
+/// Backfills `field_id` on existing templates and template versions, and rewrites
+/// index-keyed entry data to use those ids. Version fields are matched to the current
+/// layout by type and label, and each entry is remapped against the layout it was last
+/// saved with. Runs once; completion is recorded in the `migrations` collection.
+/// Each step is idempotent so an interrupted run is safe to repeat.
+async fn migrate_template_field_ids(client: &mongodb::Client) -> Result<()> {
     let db = client.database("logs_db");
-    let collection: mongodb::Collection<ReportRunDocument> = db.collection("report_runs");
+    let migrations: mongodb::Collection<mongodb::bson::Document> = db.collection("migrations");
 
-    let index = mongodb::IndexModel::builder()
+    if migrations
+        .find_one(mongodb::bson::doc! { "_id": TEMPLATE_FIELD_IDS_MIGRATION })
+        .await?
+        .is_some()
+    {
+        return Ok(());
+    }
+
+    tracing::info!("Running migration {}", TEMPLATE_FIELD_IDS_MIGRATION);
+
+    let templates: mongodb::Collection<TemplateDocument> = db.collection("templates");
+    let mut layouts: std::collections::HashMap<(String, String), TemplateLayout> =
+        std::collections::HashMap::new();
+
+    let mut cursor = templates.find(mongodb::bson::doc! {}).await?;
+    while let Some(template) = cursor.try_next().await? {
+        let layout = assign_field_ids(&template.template_layout, None);
+        if template
+            .template_layout
+            .iter()
+            .any(|field| field.field_id.is_empty())
+        {
+            templates
+                .update_one(
+                    mongodb::bson::doc! {
+                        "template_name": &template.template_name,
+                        "company_id": &template.company_id,
+                    },
+                    mongodb::bson::doc! {
+                        "$set": { "template_layout": mongodb::bson::to_bson(&layout)? }
+                    },
+                )
+                .await?;
+        }
+        layouts.insert((template.company_id, template.template_name), layout);
+    }
+
+    // Archived layouts keyed like `layouts`, in the order they were replaced
+    type ArchivedLayouts = Vec<(chrono::DateTime<chrono::Utc>, TemplateLayout)>;
+    let mut archived: std::collections::HashMap<(String, String), ArchivedLayouts> =
+        std::collections::HashMap::new();
+    let versions: mongodb::Collection<TemplateVersionDocument> = db.collection("template_versions");
+    let mut cursor = versions
+        .find(mongodb::bson::doc! {})
+        .sort(mongodb::bson::doc! { "created_at": 1 })
+        .await?;
+    while let Some(version) = cursor.try_next().await? {
+        let key = (version.company_id.clone(), version.template_name.clone());
+        let layout = if version
+            .template_layout
+            .iter()
+            .any(|field| field.field_id.is_empty())
+        {
+            let empty = TemplateLayout::new();
+            let layout = match_legacy_field_ids(
+                &version.template_layout,
+                layouts.get(&key).unwrap_or(&empty),
+            );
+            versions
+                .update_one(
+                    mongodb::bson::doc! {
+                        "template_name": &version.template_name,
+                        "company_id": &version.company_id,
+                        "version": u32::from(version.version),
+                    },
+                    mongodb::bson::doc! {
+                        "$set": { "template_layout": mongodb::bson::to_bson(&layout)? }
+                    },
+                )
+                .await?;
+            layout
+        } else {
+            version.template_layout
+        };
+        archived
+            .entry(key)
+            .or_default()
+            .push((version.created_at, layout));
+    }
+
+    let entries: mongodb::Collection<LogEntry> = db.collection("log_entries");
+    let mut cursor = entries.find(mongodb::bson::doc! {}).await?;
+    while let Some(entry) = cursor.try_next().await? {
+        let key = (entry.company_id.clone(), entry.template_name.clone());
+        let Some(current) = layouts.get(&key) else {
+            continue;
+        };
+        let layout = legacy_entry_layout(
+            archived.get(&key).map_or(&[], Vec::as_slice),
+            current,
+            entry.updated_at,
+        );
+        let unmatched = entry.entry_data.as_object().is_some_and(|values| {
+            values.keys().any(|key| {
+                key.parse::<usize>()
+                    .is_ok_and(|index| layout.get(index).is_none())
+            })
+        });
+        if unmatched {
+            tracing::warn!(
+                "Skipping entry {} whose data does not match the layout it was saved with",
+                entry.entry_id
+            );
+            continue;
+        }
+        let normalized = normalize_entry_data_keys(layout, &entry.entry_data);
+        if normalized != entry.entry_data {
+            entries
+                .update_one(
+                    mongodb::bson::doc! { "entry_id": &entry.entry_id },
+                    mongodb::bson::doc! {
+                        "$set": { "entry_data": mongodb::bson::to_bson(&normalized)? }
+                    },
+                )
+                .await?;
+        }
+    }
+
+    migrations
+        .insert_one(mongodb::bson::doc! {
+            "_id": TEMPLATE_FIELD_IDS_MIGRATION,
+            "applied_at": mongodb::bson::DateTime::now(),
+        })
+        .await?;
+
+    Ok(())
+}
+
+const TEMPLATE_IDS_MIGRATION: &str = "20260610_template_ids";
+
+/// Assigns an immutable `template_id` to every template and backfills it on the template's
+/// versions, entries and corrective-action issues, matched by company and current name.
+/// Runs once; each step only touches documents still missing an id so a rerun is safe.
+async fn migrate_template_ids(client: &mongodb::Client) -> Result<()> {
+    let db = client.database("logs_db");
+    let migrations: mongodb::Collection<mongodb::bson::Document> = db.collection("migrations");
+
+    if migrations
+        .find_one(mongodb::bson::doc! { "_id": TEMPLATE_IDS_MIGRATION })
+        .await?
+        .is_some()
+    {
+        return Ok(());
+    }
+
+    tracing::info!("Running migration {}", TEMPLATE_IDS_MIGRATION);
+
+    let templates: mongodb::Collection<TemplateDocument> = db.collection("templates");
+    let mut cursor = templates.find(mongodb::bson::doc! {}).await?;
+    let mut assigned = Vec::new();
+    while let Some(template) = cursor.try_next().await? {
+        let template_id = if template.template_id.is_empty() {
+            let template_id = uuid::Uuid::new_v4().to_string();
+            templates
+                .update_one(
+                    mongodb::bson::doc! {
+                        "template_name": &template.template_name,
+                        "company_id": &template.company_id,
+                    },
+                    mongodb::bson::doc! { "$set": { "template_id": &template_id } },
+                )
+                .await?;
+            template_id
+        } else {
+            template.template_id
+        };
+        assigned.push((template.company_id, template.template_name, template_id));
+    }
+
+    for collection in [
+        "template_versions",
+        "log_entries",
+        "corrective_action_issues",
+    ] {
+        let collection: mongodb::Collection<mongodb::bson::Document> = db.collection(collection);
+        for (company_id, template_name, template_id) in &assigned {
+            collection
+                .update_many(
+                    mongodb::bson::doc! {
+                        "company_id": company_id,
+                        "template_name": template_name,
+                        "template_id": { "$exists": false },
+                    },
+                    mongodb::bson::doc! { "$set": { "template_id": template_id } },
+                )
+                .await?;
+        }
+    }
+
+    migrations
+        .insert_one(mongodb::bson::doc! {
+            "_id": TEMPLATE_IDS_MIGRATION,
+            "applied_at": mongodb::bson::DateTime::now(),
+        })
+        .await?;
+
+    Ok(())
+}
+
+const MISSED_LOG_ALERT_TEMPLATE_IDS_MIGRATION: &str = "20260610_missed_log_alert_template_ids";
+
+/// Sets the template id on missed log alerts recorded before templates had ids, matching
+/// them by company and template name. The alerts live in Postgres, so this runs at startup
+/// once both databases are connected. Runs once; only alerts still missing an id are touched.
+///
+/// # Errors
+/// Returns an error if a database query fails.
+pub async fn migrate_missed_log_alert_template_ids(
+    client: &mongodb::Client,
+    pool: &sqlx::PgPool,
+) -> Result<()> {
+    let db = client.database("logs_db");
+    let migrations: mongodb::Collection<mongodb::bson::Document> = db.collection("migrations");
+
+    if migrations
+        .find_one(mongodb::bson::doc! { "_id": MISSED_LOG_ALERT_TEMPLATE_IDS_MIGRATION })
+        .await?
+        .is_some()
+    {
+        return Ok(());
+    }
+
+    tracing::info!(
+        "Running migration {}",
+        MISSED_LOG_ALERT_TEMPLATE_IDS_MIGRATION
+    );
+
+    let templates: mongodb::Collection<TemplateDocument> = db.collection("templates");
+    let mut cursor = templates.find(mongodb::bson::doc! {}).await?;
+    let mut by_company: std::collections::HashMap<String, Vec<(String, String)>> =
+        std::collections::HashMap::new();
+    while let Some(template) = cursor.try_next().await? {
+        by_company
+            .entry(template.company_id)
+            .or_default()
+            .push((template.template_name, template.template_id));
+    }
+
+    for (company_id, templates) in &by_company {
+        crate::db::backfill_missed_log_alert_template_ids(pool, company_id, templates).await?;
+    }
+
+    migrations
+        .insert_one(mongodb::bson::doc! {
+            "_id": MISSED_LOG_ALERT_TEMPLATE_IDS_MIGRATION,
+            "applied_at": mongodb::bson::DateTime::now(),
+        })
+        .await?;
+
+    Ok(())
+}
+
+const TEMPLATE_HAS_ENTRIES_MIGRATION: &str = "20260625_template_has_entries";
+
+/// Sets `has_entries` on templates that entries were created for before the flag existed,
+/// so they cannot be purged. Runs once; completion is recorded in the `migrations` collection.
+async fn migrate_template_has_entries(client: &mongodb::Client) -> Result<()> {
+    let db = client.database("logs_db");
+    let migrations: mongodb::Collection<mongodb::bson::Document> = db.collection("migrations");
+
+    if migrations
+        .find_one(mongodb::bson::doc! { "_id": TEMPLATE_HAS_ENTRIES_MIGRATION })
+        .await?
+        .is_some()
+    {
+        return Ok(());
+    }
+
+    tracing::info!("Running migration {}", TEMPLATE_HAS_ENTRIES_MIGRATION);
+
+    let entries: mongodb::Collection<LogEntry> = db.collection("log_entries");
+    let mut used = entries
+        .aggregate(vec![mongodb::bson::doc! {
+            "$group": { "_id": { "company_id": "$company_id", "template_id": "$template_id" } }
+        }])
+        .await?;
+    let templates: mongodb::Collection<TemplateDocument> = db.collection("templates");
+    while let Some(group) = used.try_next().await? {
+        let key = group.get_document("_id")?;
+        templates
+            .update_one(
+                mongodb::bson::doc! {
+                    "company_id": key.get_str("company_id")?,
+                    "template_id": key.get_str("template_id")?,
+                },
+                mongodb::bson::doc! { "$set": { "has_entries": true } },
+            )
+            .await?;
+    }
+
+    migrations
+        .insert_one(mongodb::bson::doc! {
+            "_id": TEMPLATE_HAS_ENTRIES_MIGRATION,
+            "applied_at": mongodb::bson::DateTime::now(),
+        })
+        .await?;
+
+    Ok(())
+}
+
+async fn ensure_template_indexes(client: &mongodb::Client) -> Result<()> {
+    let db = client.database("logs_db");
+    let templates: mongodb::Collection<TemplateDocument> = db.collection("templates");
+    let index = mongodb::IndexModel::builder()
+        .keys(mongodb::bson::doc! { "company_id": 1, "template_id": 1 })
+        .options(
+            mongodb::options::IndexOptions::builder()
+                .name(Some("templates_company_template_id_idx".to_string()))
+                .unique(Some(true))
+                .build(),
+        )
+        .build();
+    templates.create_index(index).await?;
+
+    let entries: mongodb::Collection<LogEntry> = db.collection("log_entries");
+    let index = mongodb::IndexModel::builder()
+        .keys(mongodb::bson::doc! { "company_id": 1, "template_id": 1, "period": 1 })
+        .options(
+            mongodb::options::IndexOptions::builder()
+                .name(Some("log_entries_company_template_period_idx".to_string()))
+                .build(),
+        )
+        .build();
+    entries.create_index(index).await?;
+
+    Ok(())
+}
+
+const TEMPLATE_LIBRARY_SEED: &str = "20260612_template_library_seed";
+
+/// Loads the starter templates into the global template library. Runs once so that
+/// templates LogSmart admins have since edited or removed are not brought back.
+async fn seed_template_library(client: &mongodb::Client) -> Result<()> {
+    let db = client.database("logs_db");
+    let library: mongodb::Collection<LibraryTemplateDocument> = db.collection("library_templates");
+    let index = mongodb::IndexModel::builder()
+        .keys(mongodb::bson::doc! { "library_template_id": 1 })
+        .options(
+            mongodb::options::IndexOptions::builder()
+                .name(Some("library_templates_id_idx".to_string()))
+                .unique(Some(true))
+                .build(),
+        )
+        .build();
+    library.create_index(index).await?;
+
+    let migrations: mongodb::Collection<mongodb::bson::Document> = db.collection("migrations");
+    if migrations
+        .find_one(mongodb::bson::doc! { "_id": TEMPLATE_LIBRARY_SEED })
+        .await?
+        .is_some()
+    {
+        return Ok(());
+    }
+
+    tracing::info!("Running migration {}", TEMPLATE_LIBRARY_SEED);
+
+    for template in crate::template_library::starter_templates() {
+        let exists = library
+            .find_one(mongodb::bson::doc! {
+                "library_template_id": &template.library_template_id,
+            })
+            .await?
+            .is_some();
+        if !exists {
+            library.insert_one(&template).await?;
+        }
+    }
+
+    migrations
+        .insert_one(mongodb::bson::doc! {
+            "_id": TEMPLATE_LIBRARY_SEED,
+            "applied_at": mongodb::bson::DateTime::now(),
+        })
+        .await?;
+
+    Ok(())
+}
+
+async fn ensure_report_run_indexes(client: &mongodb::Client) -> Result<()> {
+    let db = client.database("logs_db");
+    let collection: mongodb::Collection<ReportRunDocument> = db.collection("report_runs");
+
+    let index = mongodb::IndexModel::builder()
         .keys(mongodb::bson::doc! {
             "user_id": 1,
             "company_id": 1,
@@ -185,6 +1427,79 @@ async fn ensure_report_run_indexes(client: &mongodb::Client) -> Result<()> {
     Ok(())
 }
 
+async fn ensure_log_entry_indexes(client: &mongodb::Client) -> Result<()> {
+    let db = client.database("logs_db");
+    let collection: mongodb::Collection<LogEntry> = db.collection("log_entries");
+
+    for sort in [LogEntrySort::CreatedAt, LogEntrySort::UpdatedAt] {
+        let field = sort.field_name();
+        let index = mongodb::IndexModel::builder()
+            .keys(mongodb::bson::doc! {
+                "company_id": 1,
+                field: -1,
+                "entry_id": -1,
+            })
+            .options(
+                mongodb::options::IndexOptions::builder()
+                    .name(Some(format!("log_entries_company_{field}_idx")))
+                    .build(),
+            )
+            .build();
+
+        collection.create_index(index).await?;
+    }
+
+    Ok(())
+}
+
+async fn ensure_corrective_action_issue_indexes(client: &mongodb::Client) -> Result<()> {
+    let db = client.database("logs_db");
+    let collection: mongodb::Collection<CorrectiveActionIssue> =
+        db.collection("corrective_action_issues");
+
+    for (keys, name) in [
+        (
+            mongodb::bson::doc! { "company_id": 1, "status": 1, "raised_at": -1 },
+            "corrective_action_issues_company_status_idx",
+        ),
+        (
+            mongodb::bson::doc! { "entry_id": 1 },
+            "corrective_action_issues_entry_idx",
+        ),
+    ] {
+        let index = mongodb::IndexModel::builder()
+            .keys(keys)
+            .options(
+                mongodb::options::IndexOptions::builder()
+                    .name(Some(name.to_string()))
+                    .build(),
+            )
+            .build();
+
+        collection.create_index(index).await?;
+    }
+
+    Ok(())
+}
+
+async fn ensure_entry_transition_indexes(client: &mongodb::Client) -> Result<()> {
+    let db = client.database("logs_db");
+    let collection: mongodb::Collection<EntryTransition> = db.collection("log_entry_transitions");
+
+    let index = mongodb::IndexModel::builder()
+        .keys(mongodb::bson::doc! { "entry_id": 1, "at": 1 })
+        .options(
+            mongodb::options::IndexOptions::builder()
+                .name(Some("log_entry_transitions_entry_idx".to_string()))
+                .build(),
+        )
+        .build();
+
+    collection.create_index(index).await?;
+
+    Ok(())
+}
+
 async fn backfill_missing_report_params_keys(
     collection: &mongodb::Collection<ReportRunDocument>,
     user_id: &str,
@@ -262,7 +1577,7 @@ pub async fn add_template_version(
 pub async fn get_template_versions(
     client: &mongodb::Client,
     company_id: &str,
-    template_name: &str,
+    template_id: &str,
 ) -> Result<Vec<TemplateVersionDocument>> {
     let db = client.database("logs_db");
     let collection: mongodb::Collection<TemplateVersionDocument> =
@@ -270,7 +1585,7 @@ pub async fn get_template_versions(
 
     let filter = mongodb::bson::doc! {
         "company_id": company_id,
-        "template_name": template_name,
+        "template_id": template_id,
     };
 
     let find_options = mongodb::options::FindOptions::builder()
@@ -294,7 +1609,7 @@ pub async fn get_template_versions(
 pub async fn get_template_version(
     client: &mongodb::Client,
     company_id: &str,
-    template_name: &str,
+    template_id: &str,
     version: u16,
 ) -> Result<Option<TemplateVersionDocument>> {
     let db = client.database("logs_db");
@@ -303,7 +1618,7 @@ pub async fn get_template_version(
 
     let filter = mongodb::bson::doc! {
         "company_id": company_id,
-        "template_name": template_name,
+        "template_id": template_id,
         "version": u32::from(version),
     };
 
@@ -311,6 +1626,63 @@ pub async fn get_template_version(
     Ok(result)
 }
 
+/// Retrieves the layout a log entry was captured against.
+///
+/// Returns the archived layout for the entry's pinned version, or the template's current
+/// layout if the entry is on the current version, has no pinned version, or the version
+/// snapshot no longer exists.
+///
+/// # Errors
+/// Returns an error if the database query fails.
+pub async fn get_entry_template_layout(
+    client: &mongodb::Client,
+    template: &TemplateDocument,
+    template_version: Option<u16>,
+) -> Result<TemplateLayout> {
+    match template_version {
+        Some(version) if version != template.version => {
+            let archived =
+                get_template_version(client, &template.company_id, &template.template_id, version)
+                    .await?;
+            Ok(archived.map_or_else(|| template.template_layout.clone(), |v| v.template_layout))
+        }
+        _ => Ok(template.template_layout.clone()),
+    }
+}
+
+/// Layouts of archived template versions, fetched once per version while resolving the
+/// layouts of many entries.
+#[derive(Debug, Default)]
+pub struct TemplateLayoutCache {
+    archived: std::collections::HashMap<(String, u16), TemplateLayout>,
+}
+
+impl TemplateLayoutCache {
+    /// Resolves the layout an entry was captured against, as [`get_entry_template_layout`].
+    ///
+    /// # Errors
+    /// Returns an error if the database query fails.
+    pub async fn entry_layout(
+        &mut self,
+        client: &mongodb::Client,
+        template: &TemplateDocument,
+        template_version: Option<u16>,
+    ) -> Result<TemplateLayout> {
+        let Some(version) = template_version.filter(|v| *v != template.version) else {
+            return Ok(template.template_layout.clone());
+        };
+
+        let key = (template.template_id.clone(), version);
+        if let Some(layout) = self.archived.get(&key) {
+            return Ok(layout.clone());
+        }
+
+        let layout = get_entry_template_layout(client, template, Some(version)).await?;
+        self.archived.insert(key, layout.clone());
+        Ok(layout)
+    }
+}
+
 /// Retrieves a log template by its name and company ID.
 ///
 /// # Errors
@@ -332,6 +1704,52 @@ pub async fn get_template_by_name(
     Ok(result)
 }
 
+/// Retrieves a log template by its id and company ID.
+///
+/// # Errors
+/// Returns an error if the database query fails.
+pub async fn get_template_by_id(
+    client: &mongodb::Client,
+    template_id: &str,
+    company_id: &str,
+) -> Result<Option<TemplateDocument>> {
+    let db = client.database("logs_db");
+    let collection: mongodb::Collection<TemplateDocument> = db.collection("templates");
+
+    let filter = mongodb::bson::doc! {
+        "template_id": template_id,
+        "company_id": company_id,
+    };
+
+    let result = collection.find_one(filter).await?;
+    Ok(result)
+}
+
+/// How a request identifies a template. Clients should send the id; the name is accepted
+/// for compatibility and resolves to whichever template currently has that name.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TemplateKey<'a> {
+    Id(&'a str),
+    Name(&'a str),
+}
+
+/// Retrieves a log template by id or name.
+///
+/// # Errors
+/// Returns an error if the database query fails.
+pub async fn get_template(
+    client: &mongodb::Client,
+    key: TemplateKey<'_>,
+    company_id: &str,
+) -> Result<Option<TemplateDocument>> {
+    match key {
+        TemplateKey::Id(template_id) => get_template_by_id(client, template_id, company_id).await,
+        TemplateKey::Name(template_name) => {
+            get_template_by_name(client, template_name, company_id).await
+        }
+    }
+}
+
 pub async fn get_templates_by_company(
     client: &mongodb::Client,
     company_id: &str,
@@ -339,7 +1757,54 @@ pub async fn get_templates_by_company(
     get_templates_by_company_and_branch(client, company_id, None).await
 }
 
-/// Retrieves all log templates for a specific company, optionally filtered by branch.
+/// Retrieves every log template of a company, including archived ones.
+///
+/// # Errors
+/// Returns an error if the database query fails.
+pub async fn get_templates_by_company_including_archived(
+    client: &mongodb::Client,
+    company_id: &str,
+) -> Result<Vec<TemplateDocument>> {
+    let db = client.database("logs_db");
+    let collection: mongodb::Collection<TemplateDocument> = db.collection("templates");
+
+    let cursor = collection
+        .find(mongodb::bson::doc! { "company_id": company_id })
+        .await?;
+    Ok(cursor.try_collect().await?)
+}
+
+/// Retrieves a company's archived log templates, optionally filtered by branch.
+///
+/// # Errors
+/// Returns an error if the database query fails.
+pub async fn get_archived_templates(
+    client: &mongodb::Client,
+    company_id: &str,
+    branch_id: Option<&str>,
+) -> Result<Vec<TemplateDocument>> {
+    let db = client.database("logs_db");
+    let collection: mongodb::Collection<TemplateDocument> = db.collection("templates");
+
+    let mut filter = mongodb::bson::doc! {
+        "company_id": company_id,
+        "archived_at": { "$ne": null },
+    };
+    if let Some(bid) = branch_id {
+        filter.insert(
+            "$or",
+            vec![
+                mongodb::bson::doc! { "branch_id": bid },
+                mongodb::bson::doc! { "branch_id": null },
+            ],
+        );
+    }
+
+    let cursor = collection.find(filter).await?;
+    Ok(cursor.try_collect().await?)
+}
+
+/// Retrieves the active (non-archived) log templates of a company, optionally filtered by branch.
 ///
 /// # Errors
 /// Returns an error if the database query fails.
@@ -354,6 +1819,7 @@ pub async fn get_templates_by_company_and_branch(
     let filter = if let Some(bid) = branch_id {
         mongodb::bson::doc! {
             "company_id": company_id,
+            "archived_at": null,
             "$or": [
                 { "branch_id": bid },
                 { "branch_id": null }
@@ -362,96 +1828,475 @@ pub async fn get_templates_by_company_and_branch(
     } else {
         mongodb::bson::doc! {
             "company_id": company_id,
+            "archived_at": null,
         }
     };
 
-    let mut cursor = collection.find(filter).await?;
-    let mut templates = Vec::new();
-
-    while let Some(template) = cursor.try_next().await? {
-        templates.push(template);
+    let mut cursor = collection.find(filter).await?;
+    let mut templates = Vec::new();
+
+    while let Some(template) = cursor.try_next().await? {
+        templates.push(template);
+    }
+
+    Ok(templates)
+}
+
+/// Retrieves all log entries for a branch.
+///
+/// # Errors
+/// Returns an error if the database query fails.
+pub async fn get_branch_log_entries(
+    client: &mongodb::Client,
+    company_id: &str,
+    branch_id: &str,
+) -> Result<Vec<LogEntry>> {
+    let db = client.database("logs_db");
+    let collection: mongodb::Collection<LogEntry> = db.collection("log_entries");
+
+    let filter = mongodb::bson::doc! {
+        "company_id": company_id,
+        "branch_id": branch_id,
+    };
+
+    let mut cursor = collection.find(filter).await?;
+    let mut entries = Vec::new();
+
+    while let Some(entry) = cursor.try_next().await? {
+        entries.push(entry);
+    }
+
+    Ok(entries)
+}
+
+/// Retrieves all log entries for a company.
+///
+/// # Errors
+/// Returns an error if the database query fails.
+pub async fn get_company_log_entries(
+    client: &mongodb::Client,
+    company_id: &str,
+) -> Result<Vec<LogEntry>> {
+    let db = client.database("logs_db");
+    let collection: mongodb::Collection<LogEntry> = db.collection("log_entries");
+
+    let filter = mongodb::bson::doc! {
+        "company_id": company_id,
+    };
+
+    let mut cursor = collection.find(filter).await?;
+    let mut entries = Vec::new();
+
+    while let Some(entry) = cursor.try_next().await? {
+        entries.push(entry);
+    }
+
+    Ok(entries)
+}
+
+/// Retrieves log entries for specific branches in a company.
+///
+/// # Errors
+/// Returns an error if the database query fails.
+pub async fn get_branches_log_entries(
+    client: &mongodb::Client,
+    company_id: &str,
+    branch_ids: &[String],
+) -> Result<Vec<LogEntry>> {
+    let db = client.database("logs_db");
+    let collection: mongodb::Collection<LogEntry> = db.collection("log_entries");
+
+    let filter = mongodb::bson::doc! {
+        "company_id": company_id,
+        "branch_id": mongodb::bson::doc! { "$in": branch_ids }
+    };
+
+    let mut cursor = collection.find(filter).await?;
+    let mut entries = Vec::new();
+
+    while let Some(entry) = cursor.try_next().await? {
+        entries.push(entry);
+    }
+
+    Ok(entries)
+}
+
+/// Retrieves the template and period of every non-draft entry created since `created_from`,
+/// optionally limited to some branches. Used to tell which scheduled periods were completed.
+///
+/// # Errors
+/// Returns an error if the database query fails.
+pub async fn get_completed_entry_periods(
+    client: &mongodb::Client,
+    company_id: &str,
+    branch_ids: Option<&[String]>,
+    created_from: chrono::DateTime<chrono::Utc>,
+) -> Result<Vec<(String, String)>> {
+    let db = client.database("logs_db");
+    let collection: mongodb::Collection<mongodb::bson::Document> = db.collection("log_entries");
+
+    let mut filter = mongodb::bson::doc! {
+        "company_id": company_id,
+        "status": { "$ne": LogStatus::Draft.as_str() },
+        "created_at": { "$gte": mongodb::bson::to_bson(&created_from)? },
+    };
+    if let Some(branch_ids) = branch_ids {
+        filter.insert("branch_id", mongodb::bson::doc! { "$in": branch_ids });
+    }
+
+    let mut cursor = collection
+        .find(filter)
+        .projection(mongodb::bson::doc! { "_id": 0, "template_id": 1, "period": 1 })
+        .await?;
+    let mut periods = Vec::new();
+    while let Some(doc) = cursor.try_next().await? {
+        if let (Ok(template_id), Ok(period)) = (doc.get_str("template_id"), doc.get_str("period")) {
+            periods.push((template_id.to_string(), period.to_string()));
+        }
+    }
+
+    Ok(periods)
+}
+
+/// Field a page of log entries is ordered by.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum LogEntrySort {
+    #[default]
+    CreatedAt,
+    UpdatedAt,
+}
+
+impl LogEntrySort {
+    #[must_use]
+    pub fn field_name(self) -> &'static str {
+        match self {
+            LogEntrySort::CreatedAt => "created_at",
+            LogEntrySort::UpdatedAt => "updated_at",
+        }
+    }
+
+    #[must_use]
+    pub fn from_param(s: &str) -> Option<Self> {
+        match s {
+            "created_at" => Some(LogEntrySort::CreatedAt),
+            "updated_at" => Some(LogEntrySort::UpdatedAt),
+            _ => None,
+        }
+    }
+
+    fn value(self, entry: &LogEntry) -> chrono::DateTime<chrono::Utc> {
+        match self {
+            LogEntrySort::CreatedAt => entry.created_at,
+            LogEntrySort::UpdatedAt => entry.updated_at,
+        }
+    }
+}
+
+/// The template, branch and period of a draft. Drafts are stored as `draft` and derive to
+/// overdue once their period is past due, so status filters list those periods explicitly.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct DraftPeriod {
+    pub template_id: String,
+    pub branch_id: Option<String>,
+    pub period: String,
+}
+
+impl DraftPeriod {
+    fn to_filter(&self) -> mongodb::bson::Document {
+        mongodb::bson::doc! {
+            "template_id": self.template_id.as_str(),
+            "branch_id": self.branch_id.clone(),
+            "period": self.period.as_str(),
+        }
+    }
+}
+
+/// Filter on the status an entry derives to.
+#[derive(Debug, Clone)]
+pub enum EntryStatusFilter {
+    /// Entries stored with this status.
+    Stored(LogStatus),
+    /// Drafts outside the given past-due periods.
+    OpenDrafts(Vec<DraftPeriod>),
+    /// Entries stored as overdue and drafts in the given past-due periods.
+    Overdue(Vec<DraftPeriod>),
+}
+
+impl EntryStatusFilter {
+    fn to_filter(&self) -> mongodb::bson::Document {
+        let draft = LogStatus::Draft.as_str();
+        match self {
+            EntryStatusFilter::Stored(status) => mongodb::bson::doc! { "status": status.as_str() },
+            EntryStatusFilter::OpenDrafts(past_due) if past_due.is_empty() => {
+                mongodb::bson::doc! { "status": draft }
+            }
+            EntryStatusFilter::OpenDrafts(past_due) => mongodb::bson::doc! {
+                "status": draft,
+                "$nor": past_due.iter().map(DraftPeriod::to_filter).collect::<Vec<_>>(),
+            },
+            EntryStatusFilter::Overdue(past_due) if past_due.is_empty() => {
+                mongodb::bson::doc! { "status": LogStatus::Overdue.as_str() }
+            }
+            EntryStatusFilter::Overdue(past_due) => mongodb::bson::doc! {
+                "$or": [
+                    { "status": LogStatus::Overdue.as_str() },
+                    {
+                        "status": draft,
+                        "$or": past_due.iter().map(DraftPeriod::to_filter).collect::<Vec<_>>(),
+                    },
+                ],
+            },
+        }
+    }
+}
+
+/// Filters applied when paging through a company's log entries. `None` means no filter.
+#[derive(Debug, Clone, Default)]
+pub struct LogEntryFilters {
+    pub branch_ids: Option<Vec<String>>,
+    pub template_ids: Option<Vec<String>>,
+    pub user_id: Option<String>,
+    pub period: Option<String>,
+    pub status: Option<EntryStatusFilter>,
+    pub created_from: Option<chrono::DateTime<chrono::Utc>>,
+    pub created_to: Option<chrono::DateTime<chrono::Utc>>,
+}
+
+#[derive(Debug, Clone)]
+pub struct LogEntriesPage {
+    pub entries: Vec<LogEntry>,
+    pub next_cursor: Option<String>,
+}
+
+/// Builds the query for a page of a company's log entries. The status filter and the cursor
+/// are combined under `$and` so neither replaces the other.
+///
+/// # Errors
+/// Returns an error if a timestamp cannot be converted to BSON.
+pub fn log_entries_page_filter(
+    company_id: &str,
+    filters: &LogEntryFilters,
+    sort: LogEntrySort,
+    descending: bool,
+    cursor: Option<&(chrono::DateTime<chrono::Utc>, String)>,
+) -> Result<mongodb::bson::Document> {
+    let mut filter = mongodb::bson::doc! { "company_id": company_id };
+    let mut clauses = Vec::new();
+
+    if let Some(branch_ids) = &filters.branch_ids {
+        filter.insert("branch_id", mongodb::bson::doc! { "$in": branch_ids });
+    }
+    if let Some(template_ids) = &filters.template_ids {
+        filter.insert("template_id", mongodb::bson::doc! { "$in": template_ids });
+    }
+    if let Some(user_id) = &filters.user_id {
+        filter.insert("user_id", user_id);
+    }
+    if let Some(period) = &filters.period {
+        filter.insert("period", period);
+    }
+    if let Some(status) = &filters.status {
+        clauses.push(status.to_filter());
+    }
+
+    let mut created_range = mongodb::bson::Document::new();
+    if let Some(created_from) = &filters.created_from {
+        created_range.insert("$gte", mongodb::bson::to_bson(created_from)?);
+    }
+    if let Some(created_to) = &filters.created_to {
+        created_range.insert("$lte", mongodb::bson::to_bson(created_to)?);
+    }
+    if !created_range.is_empty() {
+        filter.insert("created_at", created_range);
+    }
+
+    if let Some((cursor_value, cursor_id)) = cursor {
+        let field = sort.field_name();
+        let op = if descending { "$lt" } else { "$gt" };
+        let cursor_value = mongodb::bson::to_bson(cursor_value)?;
+        clauses.push(mongodb::bson::doc! {
+            "$or": [
+                { field: { op: cursor_value.clone() } },
+                { field: cursor_value, "entry_id": { op: cursor_id } },
+            ],
+        });
+    }
+
+    if !clauses.is_empty() {
+        filter.insert("$and", clauses);
+    }
+    Ok(filter)
+}
+
+/// Cuts entries fetched with one row beyond `limit` down to a page, setting `next_cursor`
+/// when the extra row shows more entries remain. Without a limit every entry is kept.
+#[must_use]
+pub fn split_log_entries_page(
+    mut entries: Vec<LogEntry>,
+    sort: LogEntrySort,
+    limit: Option<i64>,
+) -> LogEntriesPage {
+    let next_cursor = match limit {
+        #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+        Some(limit) if entries.len() > limit as usize => {
+            entries.truncate(limit as usize);
+            entries
+                .last()
+                .map(|entry| format_log_entries_cursor(sort, sort.value(entry), &entry.entry_id))
+        }
+        _ => None,
+    };
+
+    LogEntriesPage {
+        entries,
+        next_cursor,
     }
-
-    Ok(templates)
 }
 
-/// Retrieves all log entries for a branch.
+/// Retrieves a page of a company's log entries, ordered by the sort field and then entry id.
+/// Without a limit every matching entry is returned.
 ///
 /// # Errors
 /// Returns an error if the database query fails.
-pub async fn get_branch_log_entries(
+pub async fn get_company_log_entries_page(
     client: &mongodb::Client,
     company_id: &str,
-    branch_id: &str,
-) -> Result<Vec<LogEntry>> {
+    filters: &LogEntryFilters,
+    sort: LogEntrySort,
+    descending: bool,
+    limit: Option<i64>,
+    cursor: Option<(chrono::DateTime<chrono::Utc>, String)>,
+) -> Result<LogEntriesPage> {
     let db = client.database("logs_db");
     let collection: mongodb::Collection<LogEntry> = db.collection("log_entries");
 
-    let filter = mongodb::bson::doc! {
-        "company_id": company_id,
-        "branch_id": branch_id,
-    };
+    let filter = log_entries_page_filter(company_id, filters, sort, descending, cursor.as_ref())?;
+    let field = sort.field_name();
+    let direction = if descending { -1 } else { 1 };
 
-    let mut cursor = collection.find(filter).await?;
-    let mut entries = Vec::new();
+    let safe_limit = limit.map(|l| l.clamp(1, 200));
+    let mut find = collection
+        .find(filter)
+        .sort(mongodb::bson::doc! { field: direction, "entry_id": direction });
+    if let Some(safe_limit) = safe_limit {
+        find = find.limit(safe_limit + 1);
+    }
 
-    while let Some(entry) = cursor.try_next().await? {
+    let mut results = find.await?;
+    let mut entries = Vec::new();
+    while let Some(entry) = results.try_next().await? {
         entries.push(entry);
     }
 
-    Ok(entries)
+    Ok(split_log_entries_page(entries, sort, safe_limit))
 }
 
-/// Retrieves all log entries for a company.
+/// Lists the distinct template, branch and period of the company's drafts that match
+/// `filters`, ignoring its status filter.
 ///
 /// # Errors
 /// Returns an error if the database query fails.
-pub async fn get_company_log_entries(
+pub async fn get_draft_periods(
     client: &mongodb::Client,
     company_id: &str,
-) -> Result<Vec<LogEntry>> {
+    filters: &LogEntryFilters,
+) -> Result<Vec<DraftPeriod>> {
     let db = client.database("logs_db");
-    let collection: mongodb::Collection<LogEntry> = db.collection("log_entries");
+    let collection: mongodb::Collection<mongodb::bson::Document> = db.collection("log_entries");
 
-    let filter = mongodb::bson::doc! {
-        "company_id": company_id,
+    let filters = LogEntryFilters {
+        status: Some(EntryStatusFilter::Stored(LogStatus::Draft)),
+        ..filters.clone()
     };
+    let filter =
+        log_entries_page_filter(company_id, &filters, LogEntrySort::default(), true, None)?;
+    let mut results = collection
+        .find(filter)
+        .projection(mongodb::bson::doc! { "_id": 0, "template_id": 1, "branch_id": 1, "period": 1 })
+        .await?;
 
-    let mut cursor = collection.find(filter).await?;
-    let mut entries = Vec::new();
+    let mut periods = std::collections::HashSet::new();
+    while let Some(doc) = results.try_next().await? {
+        periods.insert(DraftPeriod {
+            template_id: doc.get_str("template_id").unwrap_or_default().to_string(),
+            branch_id: doc.get_str("branch_id").ok().map(str::to_string),
+            period: doc.get_str("period").unwrap_or_default().to_string(),
+        });
+    }
+    Ok(periods.into_iter().collect())
+}
 
-    while let Some(entry) = cursor.try_next().await? {
-        entries.push(entry);
+#[must_use]
+pub fn format_log_entries_cursor(
+    sort: LogEntrySort,
+    value: chrono::DateTime<chrono::Utc>,
+    entry_id: &str,
+) -> String {
+    use base64::Engine as _;
+    base64::engine::general_purpose::URL_SAFE_NO_PAD.encode(format!(
+        "{}|{}|{}",
+        sort.field_name(),
+        value.to_rfc3339_opts(chrono::SecondsFormat::Nanos, true),
+        entry_id
+    ))
+}
+
+/// Parses a log entries cursor, rejecting cursors issued for a different sort field.
+///
+/// # Errors
+/// Returns an error if the cursor is malformed or was issued for another sort field.
+pub fn parse_log_entries_cursor(
+    cursor: &str,
+    sort: LogEntrySort,
+) -> Result<(chrono::DateTime<chrono::Utc>, String)> {
+    use base64::Engine as _;
+
+    let decoded = base64::engine::general_purpose::URL_SAFE_NO_PAD
+        .decode(cursor)
+        .map_err(|_| anyhow::anyhow!("Invalid cursor encoding"))?;
+    let decoded = String::from_utf8(decoded).map_err(|_| anyhow::anyhow!("Invalid cursor"))?;
+
+    let mut parts = decoded.splitn(3, '|');
+    let (Some(field), Some(value), Some(entry_id)) = (parts.next(), parts.next(), parts.next())
+    else {
+        return Err(anyhow::anyhow!("Invalid cursor format"));
+    };
+    if field != sort.field_name() {
+        return Err(anyhow::anyhow!("Cursor does not match sort field"));
     }
 
-    Ok(entries)
+    let value = chrono::DateTime::parse_from_rfc3339(value)
+        .map_err(|_| anyhow::anyhow!("Invalid cursor timestamp"))?
+        .with_timezone(&chrono::Utc);
+
+    Ok((value, entry_id.to_string()))
 }
 
-/// Retrieves log entries for specific branches in a company.
+/// Retrieves the given templates of a company in a single query.
 ///
 /// # Errors
 /// Returns an error if the database query fails.
-pub async fn get_branches_log_entries(
+pub async fn get_templates_by_ids(
     client: &mongodb::Client,
     company_id: &str,
-    branch_ids: &[String],
-) -> Result<Vec<LogEntry>> {
+    template_ids: &[String],
+) -> Result<Vec<TemplateDocument>> {
     let db = client.database("logs_db");
-    let collection: mongodb::Collection<LogEntry> = db.collection("log_entries");
+    let collection: mongodb::Collection<TemplateDocument> = db.collection("templates");
 
     let filter = mongodb::bson::doc! {
         "company_id": company_id,
-        "branch_id": mongodb::bson::doc! { "$in": branch_ids }
+        "template_id": { "$in": template_ids },
     };
 
     let mut cursor = collection.find(filter).await?;
-    let mut entries = Vec::new();
+    let mut templates = Vec::new();
 
-    while let Some(entry) = cursor.try_next().await? {
-        entries.push(entry);
+    while let Some(template) = cursor.try_next().await? {
+        templates.push(template);
     }
 
-    Ok(entries)
+    Ok(templates)
 }
 
 /// Updates an existing log template.
@@ -460,7 +2305,7 @@ pub async fn get_branches_log_entries(
 /// Returns an error if the database update fails.
 pub async fn update_template(
     client: &mongodb::Client,
-    template_name: &str,
+    template_id: &str,
     company_id: &str,
     schedule: Option<&Schedule>,
     layout: Option<&TemplateLayout>,
@@ -474,7 +2319,7 @@ pub async fn update_template(
     let collection: mongodb::Collection<TemplateDocument> = db.collection("templates");
 
     let filter = mongodb::bson::doc! {
-        "template_name": template_name,
+        "template_id": template_id,
         "company_id": company_id,
     };
 
@@ -514,7 +2359,7 @@ pub async fn update_template(
 /// Returns an error if the database update fails.
 pub async fn update_template_with_version(
     client: &mongodb::Client,
-    template_name: &str,
+    template_id: &str,
     company_id: &str,
     schedule: Option<&Schedule>,
     layout: Option<&TemplateLayout>,
@@ -523,7 +2368,7 @@ pub async fn update_template_with_version(
     // but we can keep the original signature compatible by forwarding
     update_template(
         client,
-        template_name,
+        template_id,
         company_id,
         schedule,
         layout,
@@ -533,13 +2378,14 @@ pub async fn update_template_with_version(
     .await
 }
 
-/// Renames a log template.
+/// Renames a log template. Entries, versions and issues refer to the template by id, so
+/// only their denormalized display names are updated.
 ///
 /// # Errors
-/// Returns an error if a template with the new name already exists or if the database update fails.
+/// Returns an error if another template already has the new name or if the database update fails.
 pub async fn rename_template(
     client: &mongodb::Client,
-    old_name: &str,
+    template_id: &str,
     new_name: &str,
     company_id: &str,
 ) -> Result<()> {
@@ -550,6 +2396,7 @@ pub async fn rename_template(
         .find_one(mongodb::bson::doc! {
             "template_name": new_name,
             "company_id": company_id,
+            "template_id": { "$ne": template_id },
         })
         .await?
         .is_some();
@@ -559,7 +2406,7 @@ pub async fn rename_template(
     }
 
     let filter = mongodb::bson::doc! {
-        "template_name": old_name,
+        "template_id": template_id,
         "company_id": company_id,
     };
 
@@ -570,44 +2417,419 @@ pub async fn rename_template(
         }
     };
 
-    collection.update_one(filter, update).await?;
+    collection.update_one(filter.clone(), update).await?;
+
+    let relabel = mongodb::bson::doc! { "$set": { "template_name": new_name } };
+    for name in [
+        "template_versions",
+        "log_entries",
+        "corrective_action_issues",
+    ] {
+        db.collection::<mongodb::bson::Document>(name)
+            .update_many(filter.clone(), relabel.clone())
+            .await?;
+    }
+
     Ok(())
 }
 
-/// Deletes a log template.
+/// Archives a log template. Returns `false` if it is already archived.
+///
+/// # Errors
+/// Returns an error if the database update fails.
+pub async fn archive_template(
+    client: &mongodb::Client,
+    template_id: &str,
+    company_id: &str,
+) -> Result<bool> {
+    set_template_archived(client, template_id, company_id, true).await
+}
+
+/// Restores an archived log template. Returns `false` if it is not archived.
+///
+/// # Errors
+/// Returns an error if the database update fails.
+pub async fn unarchive_template(
+    client: &mongodb::Client,
+    template_id: &str,
+    company_id: &str,
+) -> Result<bool> {
+    set_template_archived(client, template_id, company_id, false).await
+}
+
+async fn set_template_archived(
+    client: &mongodb::Client,
+    template_id: &str,
+    company_id: &str,
+    archived: bool,
+) -> Result<bool> {
+    let db = client.database("logs_db");
+    let collection: mongodb::Collection<TemplateDocument> = db.collection("templates");
+
+    let now = mongodb::bson::to_bson(&chrono::Utc::now())?;
+    let (current, archived_at) = if archived {
+        (mongodb::bson::Bson::Null, now.clone())
+    } else {
+        (
+            mongodb::bson::bson!({ "$ne": null }),
+            mongodb::bson::Bson::Null,
+        )
+    };
+
+    let result = collection
+        .update_one(
+            mongodb::bson::doc! {
+                "template_id": template_id,
+                "company_id": company_id,
+                "archived_at": current,
+            },
+            mongodb::bson::doc! {
+                "$set": { "archived_at": archived_at, "updated_at": now }
+            },
+        )
+        .await?;
+    Ok(result.modified_count > 0)
+}
+
+/// Retrieves the active templates that are still linked to `template_id`.
+///
+/// # Errors
+/// Returns an error if the database query fails.
+pub async fn get_linked_templates(
+    client: &mongodb::Client,
+    company_id: &str,
+    template_id: &str,
+) -> Result<Vec<TemplateDocument>> {
+    let db = client.database("logs_db");
+    let collection: mongodb::Collection<TemplateDocument> = db.collection("templates");
+
+    let cursor = collection
+        .find(mongodb::bson::doc! {
+            "company_id": company_id,
+            "linked_from": template_id,
+            "archived_at": null,
+        })
+        .await?;
+    Ok(cursor.try_collect().await?)
+}
+
+/// Detaches a distributed copy from its master. Returns `false` if it was not linked.
+///
+/// # Errors
+/// Returns an error if the database update fails.
+pub async fn unlink_template(
+    client: &mongodb::Client,
+    template_id: &str,
+    company_id: &str,
+) -> Result<bool> {
+    let db = client.database("logs_db");
+    let collection: mongodb::Collection<TemplateDocument> = db.collection("templates");
+
+    let result = collection
+        .update_one(
+            mongodb::bson::doc! {
+                "template_id": template_id,
+                "company_id": company_id,
+                "linked_from": { "$ne": null },
+            },
+            mongodb::bson::doc! {
+                "$set": {
+                    "linked_from": null,
+                    "updated_at": mongodb::bson::to_bson(&chrono::Utc::now())?,
+                }
+            },
+        )
+        .await?;
+    Ok(result.modified_count > 0)
+}
+
+/// Records that an entry is about to be created for a template, so it can no longer be
+/// purged. Returns `false` if the template is archived or no longer exists, in which case
+/// no entry may be created.
+///
+/// # Errors
+/// Returns an error if the database update fails.
+pub async fn mark_template_has_entries(
+    client: &mongodb::Client,
+    template_id: &str,
+    company_id: &str,
+) -> Result<bool> {
+    let db = client.database("logs_db");
+    let collection: mongodb::Collection<TemplateDocument> = db.collection("templates");
+
+    let result = collection
+        .update_one(
+            mongodb::bson::doc! {
+                "template_id": template_id,
+                "company_id": company_id,
+                "archived_at": mongodb::bson::Bson::Null,
+            },
+            mongodb::bson::doc! { "$set": { "has_entries": true } },
+        )
+        .await?;
+    Ok(result.matched_count > 0)
+}
+
+/// Permanently deletes a log template and its version history, provided no entry was ever
+/// created for it. The check and the delete are one operation, so an entry created
+/// concurrently cannot be orphaned. Returns `false`, deleting nothing, otherwise.
 ///
 /// # Errors
 /// Returns an error if the database deletion fails.
-pub async fn delete_template(
+pub async fn purge_template(
     client: &mongodb::Client,
-    template_name: &str,
+    template_id: &str,
     company_id: &str,
+) -> Result<bool> {
+    let db = client.database("logs_db");
+    let filter = mongodb::bson::doc! {
+        "template_id": template_id,
+        "company_id": company_id,
+    };
+
+    let mut unused = filter.clone();
+    unused.insert("has_entries", mongodb::bson::doc! { "$ne": true });
+    let deleted = db
+        .collection::<TemplateDocument>("templates")
+        .delete_one(unused)
+        .await?;
+    if deleted.deleted_count == 0 {
+        return Ok(false);
+    }
+
+    db.collection::<TemplateVersionDocument>("template_versions")
+        .delete_many(filter)
+        .await?;
+    Ok(true)
+}
+
+/// Lists the global template library, optionally limited to one category.
+///
+/// # Errors
+/// Returns an error if the database query fails.
+pub async fn get_library_templates(
+    client: &mongodb::Client,
+    category: Option<&str>,
+) -> Result<Vec<LibraryTemplateDocument>> {
+    let db = client.database("logs_db");
+    let collection: mongodb::Collection<LibraryTemplateDocument> =
+        db.collection("library_templates");
+
+    let filter = match category {
+        Some(category) => mongodb::bson::doc! { "category": category },
+        None => mongodb::bson::doc! {},
+    };
+    let cursor = collection
+        .find(filter)
+        .sort(mongodb::bson::doc! { "category": 1, "template_name": 1 })
+        .await?;
+    Ok(cursor.try_collect().await?)
+}
+
+/// Retrieves a single library template.
+///
+/// # Errors
+/// Returns an error if the database query fails.
+pub async fn get_library_template(
+    client: &mongodb::Client,
+    library_template_id: &str,
+) -> Result<Option<LibraryTemplateDocument>> {
+    let db = client.database("logs_db");
+    let collection: mongodb::Collection<LibraryTemplateDocument> =
+        db.collection("library_templates");
+
+    Ok(collection
+        .find_one(mongodb::bson::doc! { "library_template_id": library_template_id })
+        .await?)
+}
+
+/// Adds a template to the global library.
+///
+/// # Errors
+/// Returns an error if the database insertion fails.
+pub async fn add_library_template(
+    client: &mongodb::Client,
+    template: &LibraryTemplateDocument,
 ) -> Result<()> {
     let db = client.database("logs_db");
-    let collection: mongodb::Collection<TemplateDocument> = db.collection("templates");
+    let collection: mongodb::Collection<LibraryTemplateDocument> =
+        db.collection("library_templates");
+
+    collection.insert_one(template).await?;
+    Ok(())
+}
+
+/// Replaces a library template. Returns `false` if it does not exist.
+///
+/// # Errors
+/// Returns an error if the database update fails.
+pub async fn replace_library_template(
+    client: &mongodb::Client,
+    template: &LibraryTemplateDocument,
+) -> Result<bool> {
+    let db = client.database("logs_db");
+    let collection: mongodb::Collection<LibraryTemplateDocument> =
+        db.collection("library_templates");
+
+    let result = collection
+        .replace_one(
+            mongodb::bson::doc! { "library_template_id": &template.library_template_id },
+            template,
+        )
+        .await?;
+    Ok(result.matched_count > 0)
+}
+
+/// Removes a template from the global library. Templates already cloned from it are unaffected.
+///
+/// # Errors
+/// Returns an error if the database deletion fails.
+pub async fn delete_library_template(
+    client: &mongodb::Client,
+    library_template_id: &str,
+) -> Result<bool> {
+    let db = client.database("logs_db");
+    let collection: mongodb::Collection<LibraryTemplateDocument> =
+        db.collection("library_templates");
+
+    let result = collection
+        .delete_one(mongodb::bson::doc! { "library_template_id": library_template_id })
+        .await?;
+    Ok(result.deleted_count > 0)
+}
+
+#[derive(Debug, Clone, serde::Deserialize, serde::Serialize, ToSchema)]
+pub struct LogEntry {
+    pub entry_id: String,
+    #[serde(default)]
+    pub template_id: String,
+    /// Template name at the time of the last rename, kept for display.
+    pub template_name: String,
+    pub company_id: String,
+    pub branch_id: Option<String>,
+    pub user_id: String,
+    pub entry_data: serde_json::Value,
+    pub created_at: chrono::DateTime<chrono::Utc>,
+    pub updated_at: chrono::DateTime<chrono::Utc>,
+    pub submitted_at: Option<chrono::DateTime<chrono::Utc>>,
+    pub status: LogStatus,
+    pub period: String,
+    /// Template version the entry was created against. `None` for entries that predate version pinning.
+    #[serde(default)]
+    pub template_version: Option<u16>,
+    /// Set on submit when a reading falls outside its field's `min`/`max`.
+    #[serde(default)]
+    pub flagged: bool,
+    /// Action taken in response to out-of-range readings, recorded on submit.
+    #[serde(default)]
+    pub corrective_action: Option<String>,
+    /// Latest manager review. Rejected entries are returned to draft with the review kept
+    /// so the author can see why.
+    #[serde(default)]
+    pub review: Option<EntryReview>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Deserialize, serde::Serialize, ToSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum ReviewDecision {
+    Approved,
+    Rejected,
+}
+
+#[derive(Debug, Clone, PartialEq, serde::Deserialize, serde::Serialize, ToSchema)]
+pub struct EntryReview {
+    pub decision: ReviewDecision,
+    pub reviewer_id: String,
+    pub reviewed_at: chrono::DateTime<chrono::Utc>,
+    pub comment: Option<String>,
+}
+
+/// What caused a log entry to change status.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Deserialize, serde::Serialize, ToSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum EntryAction {
+    Created,
+    Submitted,
+    Unsubmitted,
+    Approved,
+    Rejected,
+}
+
+/// One status change of a log entry, kept as an audit trail.
+#[derive(Debug, Clone, serde::Deserialize, serde::Serialize)]
+pub struct EntryTransition {
+    pub entry_id: String,
+    pub company_id: String,
+    pub action: EntryAction,
+    pub from_status: Option<LogStatus>,
+    pub to_status: LogStatus,
+    pub actor_id: String,
+    pub comment: Option<String>,
+    pub at: chrono::DateTime<chrono::Utc>,
+}
+
+/// A temperature reading outside the range configured on its template field.
+#[derive(Debug, Clone, PartialEq, serde::Deserialize, serde::Serialize, ToSchema)]
+pub struct OutOfRangeReading {
+    pub field_id: String,
+    pub label: Option<String>,
+    pub value: f64,
+    pub min: Option<f32>,
+    pub max: Option<f32>,
+    pub unit: Option<String>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Deserialize, serde::Serialize, ToSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum IssueStatus {
+    Open,
+    Acknowledged,
+    Closed,
+}
 
-    let filter = mongodb::bson::doc! {
-        "template_name": template_name,
-        "company_id": company_id,
-    };
+impl IssueStatus {
+    #[must_use]
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Open => "open",
+            Self::Acknowledged => "acknowledged",
+            Self::Closed => "closed",
+        }
+    }
 
-    collection.delete_one(filter).await?;
-    Ok(())
+    #[must_use]
+    pub fn from_param(value: &str) -> Option<Self> {
+        match value {
+            "open" => Some(Self::Open),
+            "acknowledged" => Some(Self::Acknowledged),
+            "closed" => Some(Self::Closed),
+            _ => None,
+        }
+    }
 }
 
-#[derive(Debug, Clone, serde::Deserialize, serde::Serialize, ToSchema)]
-pub struct LogEntry {
-    pub entry_id: String,
-    pub template_name: String,
+/// Raised when an entry is submitted with out-of-range readings. A manager acknowledges
+/// the issue and closes it once the corrective action has been verified.
+#[derive(Debug, Clone, serde::Deserialize, serde::Serialize)]
+pub struct CorrectiveActionIssue {
+    pub issue_id: String,
     pub company_id: String,
     pub branch_id: Option<String>,
-    pub user_id: String,
-    pub entry_data: serde_json::Value,
-    pub created_at: chrono::DateTime<chrono::Utc>,
-    pub updated_at: chrono::DateTime<chrono::Utc>,
-    pub submitted_at: Option<chrono::DateTime<chrono::Utc>>,
-    pub status: LogStatus,
+    pub entry_id: String,
+    #[serde(default)]
+    pub template_id: String,
+    pub template_name: String,
     pub period: String,
+    pub readings: Vec<OutOfRangeReading>,
+    pub corrective_action: Option<String>,
+    pub status: IssueStatus,
+    pub raised_by: String,
+    pub raised_at: chrono::DateTime<chrono::Utc>,
+    pub acknowledged_by: Option<String>,
+    pub acknowledged_at: Option<chrono::DateTime<chrono::Utc>>,
+    pub closed_by: Option<String>,
+    pub closed_at: Option<chrono::DateTime<chrono::Utc>>,
+    pub resolution_note: Option<String>,
 }
 
 #[derive(Debug, Clone, serde::Deserialize, serde::Serialize)]
@@ -740,6 +2962,29 @@ pub async fn list_report_runs(
     Ok(runs)
 }
 
+/// Retrieves a saved report run for a user.
+///
+/// # Errors
+/// Returns an error if query fails.
+pub async fn get_report_run(
+    client: &mongodb::Client,
+    report_id: &str,
+    user_id: &str,
+    company_id: &str,
+) -> Result<Option<ReportRunDocument>> {
+    let db = client.database("logs_db");
+    let collection: mongodb::Collection<ReportRunDocument> = db.collection("report_runs");
+
+    let filter = mongodb::bson::doc! {
+        "report_id": report_id,
+        "user_id": user_id,
+        "company_id": company_id,
+    };
+
+    let result = collection.find_one(filter).await?;
+    Ok(result)
+}
+
 /// Marks a saved report run as used.
 ///
 /// # Errors
@@ -899,7 +3144,7 @@ pub async fn get_user_log_entries_by_template(
     client: &mongodb::Client,
     user_id: &str,
     company_id: &str,
-    template_name: &str,
+    template_id: &str,
 ) -> Result<Vec<LogEntry>> {
     let db = client.database("logs_db");
     let collection: mongodb::Collection<LogEntry> = db.collection("log_entries");
@@ -907,7 +3152,7 @@ pub async fn get_user_log_entries_by_template(
     let filter = mongodb::bson::doc! {
         "user_id": user_id,
         "company_id": company_id,
-        "template_name": template_name,
+        "template_id": template_id,
     };
 
     let mut cursor = collection.find(filter).await?;
@@ -928,7 +3173,7 @@ pub async fn get_latest_submitted_entry(
     client: &mongodb::Client,
     user_id: &str,
     company_id: &str,
-    template_name: &str,
+    template_id: &str,
 ) -> Result<Option<LogEntry>> {
     let db = client.database("logs_db");
     let collection: mongodb::Collection<LogEntry> = db.collection("log_entries");
@@ -936,7 +3181,7 @@ pub async fn get_latest_submitted_entry(
     let filter = mongodb::bson::doc! {
         "user_id": user_id,
         "company_id": company_id,
-        "template_name": template_name,
+        "template_id": template_id,
         "status": mongodb::bson::to_bson(&LogStatus::Submitted)?,
     };
 
@@ -955,9 +3200,9 @@ pub async fn get_latest_submitted_entries_batch(
     client: &mongodb::Client,
     user_id: &str,
     company_id: &str,
-    template_names: &[String],
+    template_ids: &[String],
 ) -> Result<std::collections::HashMap<String, LogEntry>> {
-    if template_names.is_empty() {
+    if template_ids.is_empty() {
         return Ok(std::collections::HashMap::new());
     }
 
@@ -967,7 +3212,7 @@ pub async fn get_latest_submitted_entries_batch(
     let filter = mongodb::bson::doc! {
         "user_id": user_id,
         "company_id": company_id,
-        "template_name": { "$in": template_names },
+        "template_id": { "$in": template_ids },
         "status": mongodb::bson::to_bson(&LogStatus::Submitted)?,
     };
 
@@ -978,7 +3223,7 @@ pub async fn get_latest_submitted_entries_batch(
     let mut results: std::collections::HashMap<String, LogEntry> = std::collections::HashMap::new();
 
     while let Some(entry) = cursor.try_next().await? {
-        results.entry(entry.template_name.clone()).or_insert(entry);
+        results.entry(entry.template_id.clone()).or_insert(entry);
     }
 
     Ok(results)
@@ -991,10 +3236,10 @@ pub async fn get_latest_submitted_entries_batch(
 pub async fn get_periods_with_entries_batch(
     client: &mongodb::Client,
     company_id: &str,
-    template_names: &[String],
+    template_ids: &[String],
     all_periods: &[String],
 ) -> Result<std::collections::HashMap<String, std::collections::HashSet<String>>> {
-    if template_names.is_empty() || all_periods.is_empty() {
+    if template_ids.is_empty() || all_periods.is_empty() {
         return Ok(std::collections::HashMap::new());
     }
 
@@ -1003,7 +3248,7 @@ pub async fn get_periods_with_entries_batch(
 
     let filter = mongodb::bson::doc! {
         "company_id": company_id,
-        "template_name": { "$in": template_names },
+        "template_id": { "$in": template_ids },
         "period": { "$in": all_periods }
     };
 
@@ -1014,7 +3259,7 @@ pub async fn get_periods_with_entries_batch(
         std::collections::HashMap::new();
     for entry in entries {
         results
-            .entry(entry.template_name)
+            .entry(entry.template_id)
             .or_default()
             .insert(entry.period);
     }
@@ -1044,14 +3289,14 @@ pub async fn has_submitted_entries_batch(
     let db = client.database("logs_db");
     let collection: mongodb::Collection<LogEntry> = db.collection("log_entries");
 
-    let template_names: Vec<&str> = templates.iter().map(|(n, _)| *n).collect();
+    let template_ids: Vec<&str> = templates.iter().map(|(id, _)| *id).collect();
     let now = chrono::Utc::now();
     let period_start = now - chrono::Duration::days(400);
     let period_end = now;
 
     let filter = mongodb::bson::doc! {
         "company_id": company_id,
-        "template_name": { "$in": template_names },
+        "template_id": { "$in": template_ids },
         "submitted_at": {
             "$gte": mongodb::bson::DateTime::from_system_time(std::time::SystemTime::from(period_start)),
             "$lte": mongodb::bson::DateTime::from_system_time(std::time::SystemTime::from(period_end))
@@ -1063,11 +3308,11 @@ pub async fn has_submitted_entries_batch(
     let entries: Vec<LogEntry> = cursor.try_collect().await?;
 
     let mut results: std::collections::HashMap<String, bool> = std::collections::HashMap::new();
-    for (name, _) in templates {
-        let has_submitted = entries.iter().any(|e| {
-            e.template_name == *name && is_in_current_period(&e.submitted_at, &e.period, now)
-        });
-        results.insert(name.to_string(), has_submitted);
+    for (id, _) in templates {
+        let has_submitted = entries
+            .iter()
+            .any(|e| e.template_id == *id && is_in_current_period(&e.submitted_at, &e.period, now));
+        results.insert(id.to_string(), has_submitted);
     }
 
     Ok(results)
@@ -1093,9 +3338,9 @@ pub async fn get_draft_entries_batch(
     client: &mongodb::Client,
     user_id: &str,
     company_id: &str,
-    template_names: &[String],
+    template_ids: &[String],
 ) -> Result<std::collections::HashMap<String, Option<LogEntry>>> {
-    if template_names.is_empty() {
+    if template_ids.is_empty() {
         return Ok(std::collections::HashMap::new());
     }
 
@@ -1105,7 +3350,7 @@ pub async fn get_draft_entries_batch(
     let filter = mongodb::bson::doc! {
         "user_id": user_id,
         "company_id": company_id,
-        "template_name": { "$in": template_names },
+        "template_id": { "$in": template_ids },
         "status": mongodb::bson::to_bson(&LogStatus::Draft)?,
     };
 
@@ -1114,133 +3359,77 @@ pub async fn get_draft_entries_batch(
 
     let mut results: std::collections::HashMap<String, Option<LogEntry>> =
         std::collections::HashMap::new();
-    for name in template_names {
-        results.insert(name.clone(), None);
+    for id in template_ids {
+        results.insert(id.clone(), None);
     }
     for entry in entries {
-        let name = entry.template_name.clone();
-        results.insert(name, Some(entry));
+        let id = entry.template_id.clone();
+        results.insert(id, Some(entry));
     }
 
     Ok(results)
 }
 
+/// Start and end, in UTC, of the calendar period containing today in `tz`.
+fn current_period_bounds(
+    frequency: &Frequency,
+    tz: chrono_tz::Tz,
+) -> Option<(chrono::DateTime<chrono::Utc>, chrono::DateTime<chrono::Utc>)> {
+    let today = chrono::Utc::now().with_timezone(&tz).date_naive();
+    let (first_day, last_day) = match frequency {
+        Frequency::Daily => (today, today),
+        Frequency::Weekly => {
+            let days_since_sunday = today.weekday().num_days_from_sunday();
+            let start = today - chrono::Duration::days(i64::from(days_since_sunday));
+            (start, start + chrono::Duration::days(6))
+        }
+        Frequency::Monthly => (
+            today.with_day(1)?,
+            today.with_day(get_month_last_day(today.year(), today.month()))?,
+        ),
+        Frequency::Quarterly => {
+            let start_month = ((today.month() - 1) / 3) * 3 + 1;
+            let end_month = start_month + 2;
+            (
+                chrono::NaiveDate::from_ymd_opt(today.year(), start_month, 1)?,
+                chrono::NaiveDate::from_ymd_opt(
+                    today.year(),
+                    end_month,
+                    get_month_last_day(today.year(), end_month),
+                )?,
+            )
+        }
+        Frequency::Yearly => (
+            chrono::NaiveDate::from_ymd_opt(today.year(), 1, 1)?,
+            chrono::NaiveDate::from_ymd_opt(today.year(), 12, 31)?,
+        ),
+    };
+
+    let start = local_to_utc(first_day.and_hms_opt(0, 0, 0)?, tz)?;
+    let next_start = local_to_utc(last_day.succ_opt()?.and_hms_opt(0, 0, 0)?, tz)?;
+    Some((start, next_start - chrono::Duration::seconds(1)))
+}
+
 /// Checks if a log entry exists for the current period and template.
 ///
 /// # Errors
 /// Returns an error if the database query fails.
-///
-/// # Panics
-/// Panics if period boundary calculations fail.
 pub async fn has_entry_for_current_period(
     client: &mongodb::Client,
     company_id: &str,
-    template_name: &str,
+    template_id: &str,
     frequency: &Frequency,
+    tz: chrono_tz::Tz,
 ) -> Result<bool> {
     let db = client.database("logs_db");
     let collection: mongodb::Collection<LogEntry> = db.collection("log_entries");
 
-    let now = chrono::Utc::now();
-    let (period_start, period_end) = match frequency {
-        Frequency::Daily => {
-            let start = now
-                .date_naive()
-                .and_hms_opt(0, 0, 0)
-                .unwrap_or_else(|| panic!("Error finding day start: {now}"))
-                .and_utc();
-            let end = now
-                .date_naive()
-                .and_hms_opt(23, 59, 59)
-                .unwrap_or_else(|| panic!("Error finding day end: {now}"))
-                .and_utc();
-            (start, end)
-        }
-        Frequency::Weekly => {
-            let days_since_monday = now.weekday().num_days_from_sunday();
-            let start = (now.date_naive() - chrono::Duration::days(i64::from(days_since_monday)))
-                .and_hms_opt(0, 0, 0)
-                .unwrap()
-                .and_utc();
-            let end = (now.date_naive() + chrono::Duration::days(6 - i64::from(days_since_monday)))
-                .and_hms_opt(23, 59, 59)
-                .unwrap()
-                .and_utc();
-            (start, end)
-        }
-        Frequency::Monthly => {
-            let start = now
-                .date_naive()
-                .with_day(1)
-                .unwrap()
-                .and_hms_opt(0, 0, 0)
-                .unwrap()
-                .and_utc();
-            let next_month = if now.month() == 12 {
-                now.date_naive()
-                    .with_year(now.year() + 1)
-                    .unwrap()
-                    .with_month(1)
-                    .unwrap()
-            } else {
-                now.date_naive().with_month(now.month() + 1).unwrap()
-            };
-            let end = (next_month - chrono::Duration::days(1))
-                .and_hms_opt(23, 59, 59)
-                .unwrap()
-                .and_utc();
-            (start, end)
-        }
-        Frequency::Quarterly => {
-            let quarter_start_month = ((now.date_naive().month() - 1) / 3) * 3 + 1;
-            let start = now
-                .date_naive()
-                .with_month(quarter_start_month)
-                .unwrap()
-                .with_day(1)
-                .unwrap()
-                .and_hms_opt(0, 0, 0)
-                .unwrap()
-                .and_utc();
-            let quarter_end_month = quarter_start_month + 2;
-            let last_day = get_month_last_day(now.date_naive().year(), quarter_end_month);
-            let end = now
-                .date_naive()
-                .with_month(quarter_end_month)
-                .unwrap()
-                .with_day(last_day)
-                .unwrap()
-                .and_hms_opt(23, 59, 59)
-                .unwrap()
-                .and_utc();
-            (start, end)
-        }
-        Frequency::Yearly => {
-            let start = now
-                .date_naive()
-                .with_month(1)
-                .unwrap()
-                .with_day(1)
-                .unwrap()
-                .and_hms_opt(0, 0, 0)
-                .unwrap()
-                .and_utc();
-            let end = now
-                .date_naive()
-                .with_month(12)
-                .unwrap()
-                .with_day(31)
-                .unwrap()
-                .and_hms_opt(23, 59, 59)
-                .unwrap()
-                .and_utc();
-            (start, end)
-        }
-    };
+    let (period_start, period_end) = current_period_bounds(frequency, tz)
+        .ok_or_else(|| anyhow::anyhow!("Failed to calculate current period bounds"))?;
 
     let filter = mongodb::bson::doc! {
         "company_id": company_id,
-        "template_name": template_name,
+        "template_id": template_id,
         "created_at": {
             "$gte": mongodb::bson::to_bson(&period_start)?,
             "$lte": mongodb::bson::to_bson(&period_end)?,
@@ -1255,120 +3444,24 @@ pub async fn has_entry_for_current_period(
 ///
 /// # Errors
 /// Returns an error if the database query fails.
-///
-/// # Panics
-/// Panics if period boundary calculations fail.
 pub async fn has_submitted_entry_for_current_period(
     client: &mongodb::Client,
     user_id: &str,
     company_id: &str,
-    template_name: &str,
+    template_id: &str,
     frequency: &Frequency,
+    tz: chrono_tz::Tz,
 ) -> Result<bool> {
     let db = client.database("logs_db");
     let collection: mongodb::Collection<LogEntry> = db.collection("log_entries");
 
-    let now = chrono::Utc::now();
-    let (period_start, period_end) = match frequency {
-        Frequency::Daily => {
-            let start = now
-                .date_naive()
-                .and_hms_opt(0, 0, 0)
-                .unwrap_or_else(|| panic!("Error finding day start: {now}"))
-                .and_utc();
-            let end = now
-                .date_naive()
-                .and_hms_opt(23, 59, 59)
-                .unwrap_or_else(|| panic!("Error finding day end: {now}"))
-                .and_utc();
-            (start, end)
-        }
-        Frequency::Weekly => {
-            let days_since_monday = now.weekday().num_days_from_sunday();
-            let start = (now.date_naive() - chrono::Duration::days(i64::from(days_since_monday)))
-                .and_hms_opt(0, 0, 0)
-                .unwrap()
-                .and_utc();
-            let end = (start.date_naive() + chrono::Duration::days(6))
-                .and_hms_opt(23, 59, 59)
-                .unwrap()
-                .and_utc();
-            (start, end)
-        }
-        Frequency::Monthly => {
-            let start = now
-                .date_naive()
-                .with_day(1)
-                .unwrap()
-                .and_hms_opt(0, 0, 0)
-                .unwrap()
-                .and_utc();
-            let next_month = if now.month() == 12 {
-                now.date_naive()
-                    .with_year(now.year() + 1)
-                    .unwrap()
-                    .with_month(1)
-                    .unwrap()
-            } else {
-                now.date_naive().with_month(now.month() + 1).unwrap()
-            };
-            let end = (next_month - chrono::Duration::days(1))
-                .and_hms_opt(23, 59, 59)
-                .unwrap()
-                .and_utc();
-            (start, end)
-        }
-        Frequency::Quarterly => {
-            let quarter_start_month = ((now.date_naive().month() - 1) / 3) * 3 + 1;
-            let start = now
-                .date_naive()
-                .with_month(quarter_start_month)
-                .unwrap()
-                .with_day(1)
-                .unwrap()
-                .and_hms_opt(0, 0, 0)
-                .unwrap()
-                .and_utc();
-            let quarter_end_month = quarter_start_month + 2;
-            let last_day = get_month_last_day(now.date_naive().year(), quarter_end_month);
-            let end = now
-                .date_naive()
-                .with_month(quarter_end_month)
-                .unwrap()
-                .with_day(last_day)
-                .unwrap()
-                .and_hms_opt(23, 59, 59)
-                .unwrap()
-                .and_utc();
-            (start, end)
-        }
-        Frequency::Yearly => {
-            let start = now
-                .date_naive()
-                .with_month(1)
-                .unwrap()
-                .with_day(1)
-                .unwrap()
-                .and_hms_opt(0, 0, 0)
-                .unwrap()
-                .and_utc();
-            let end = now
-                .date_naive()
-                .with_month(12)
-                .unwrap()
-                .with_day(31)
-                .unwrap()
-                .and_hms_opt(23, 59, 59)
-                .unwrap()
-                .and_utc();
-            (start, end)
-        }
-    };
+    let (period_start, period_end) = current_period_bounds(frequency, tz)
+        .ok_or_else(|| anyhow::anyhow!("Failed to calculate current period bounds"))?;
 
     let filter = mongodb::bson::doc! {
         "user_id": user_id,
         "company_id": company_id,
-        "template_name": template_name,
+        "template_id": template_id,
         "status": mongodb::bson::to_bson(&LogStatus::Submitted)?,
         "created_at": {
             "$gte": mongodb::bson::to_bson(&period_start)?,
@@ -1376,179 +3469,334 @@ pub async fn has_submitted_entry_for_current_period(
         },
     };
 
-    let result = collection.find_one(filter).await?;
-    Ok(result.is_some())
+    let result = collection.find_one(filter).await?;
+    Ok(result.is_some())
+}
+
+/// Checks if any log entry (draft or submitted) exists for a specific period.
+///
+/// # Errors
+/// Returns an error if the database query fails.
+pub async fn has_entry_for_period(
+    client: &mongodb::Client,
+    company_id: &str,
+    template_id: &str,
+    period: &str,
+) -> Result<bool> {
+    let db = client.database("logs_db");
+    let collection: mongodb::Collection<LogEntry> = db.collection("log_entries");
+
+    let filter = mongodb::bson::doc! {
+        "company_id": company_id,
+        "template_id": template_id,
+        "period": period,
+    };
+
+    let result = collection.find_one(filter).await?;
+    Ok(result.is_some())
+}
+
+/// Returns periods that have entries, for batch checking.
+///
+/// # Errors
+/// Returns an error if the database query fails.
+pub async fn get_periods_with_entries(
+    client: &mongodb::Client,
+    company_id: &str,
+    template_id: &str,
+    periods: &[String],
+) -> Result<std::collections::HashSet<String>> {
+    if periods.is_empty() {
+        return Ok(std::collections::HashSet::new());
+    }
+
+    let db = client.database("logs_db");
+    let collection: mongodb::Collection<LogEntry> = db.collection("log_entries");
+
+    let filter = mongodb::bson::doc! {
+        "company_id": company_id,
+        "template_id": template_id,
+        "period": { "$in": periods }
+    };
+
+    let cursor = collection.find(filter).await?;
+    let entries: Vec<LogEntry> = cursor.try_collect().await?;
+    Ok(entries.into_iter().map(|e| e.period).collect())
+}
+
+/// Returns the periods of a template that have a submitted, reviewed or approved entry.
+///
+/// # Errors
+/// Returns an error if the database query fails.
+pub async fn get_completed_periods(
+    client: &mongodb::Client,
+    company_id: &str,
+    template_id: &str,
+    periods: &[String],
+) -> Result<std::collections::HashSet<String>> {
+    if periods.is_empty() {
+        return Ok(std::collections::HashSet::new());
+    }
+
+    let db = client.database("logs_db");
+    let collection: mongodb::Collection<LogEntry> = db.collection("log_entries");
+
+    let filter = mongodb::bson::doc! {
+        "company_id": company_id,
+        "template_id": template_id,
+        "period": { "$in": periods },
+        "status": { "$in": ["submitted", "reviewed", "approved"] },
+    };
+
+    let cursor = collection.find(filter).await?;
+    let entries: Vec<LogEntry> = cursor.try_collect().await?;
+    Ok(entries.into_iter().map(|e| e.period).collect())
+}
+
+/// Readings on fields that no closed issue of the entry covers. Each field of an entry
+/// raises at most one issue, so resubmitting a rejected entry does not raise it again.
+#[must_use]
+pub fn readings_without_closed_issue(
+    readings: &[OutOfRangeReading],
+    issues: &[CorrectiveActionIssue],
+) -> Vec<OutOfRangeReading> {
+    let closed: std::collections::HashSet<&str> = issues
+        .iter()
+        .filter(|issue| issue.status == IssueStatus::Closed)
+        .flat_map(|issue| issue.readings.iter().map(|r| r.field_id.as_str()))
+        .collect();
+    readings
+        .iter()
+        .filter(|reading| !closed.contains(reading.field_id.as_str()))
+        .cloned()
+        .collect()
+}
+
+/// Raises a corrective-action issue for an entry's readings on fields without a closed
+/// issue. If the entry already has an issue that is not closed (it was returned to draft
+/// and resubmitted), that issue's readings and note are updated instead. Returns the id of
+/// the issue, or `None` when every reading's field already has a closed issue.
+///
+/// # Errors
+/// Returns an error if the database operation fails.
+pub async fn raise_corrective_action_issue(
+    client: &mongodb::Client,
+    issue: &CorrectiveActionIssue,
+) -> Result<Option<String>> {
+    let db = client.database("logs_db");
+    let collection: mongodb::Collection<CorrectiveActionIssue> =
+        db.collection("corrective_action_issues");
+
+    let existing: Vec<CorrectiveActionIssue> = collection
+        .find(mongodb::bson::doc! { "entry_id": &issue.entry_id })
+        .await?
+        .try_collect()
+        .await?;
+    let readings = readings_without_closed_issue(&issue.readings, &existing);
+    if readings.is_empty() {
+        return Ok(None);
+    }
+
+    let filter = mongodb::bson::doc! {
+        "entry_id": &issue.entry_id,
+        "status": { "$ne": mongodb::bson::to_bson(&IssueStatus::Closed)? },
+    };
+    let update = mongodb::bson::doc! {
+        "$set": {
+            "readings": mongodb::bson::to_bson(&readings)?,
+            "corrective_action": mongodb::bson::to_bson(&issue.corrective_action)?,
+        }
+    };
+
+    if let Some(open) = collection.find_one_and_update(filter, update).await? {
+        return Ok(Some(open.issue_id));
+    }
+
+    collection
+        .insert_one(CorrectiveActionIssue {
+            readings,
+            ..issue.clone()
+        })
+        .await?;
+    Ok(Some(issue.issue_id.clone()))
+}
+
+/// Deletes every corrective-action issue raised for an entry.
+///
+/// # Errors
+/// Returns an error if the deletion fails.
+pub async fn delete_entry_issues(client: &mongodb::Client, entry_id: &str) -> Result<()> {
+    let db = client.database("logs_db");
+    let collection: mongodb::Collection<CorrectiveActionIssue> =
+        db.collection("corrective_action_issues");
+
+    collection
+        .delete_many(mongodb::bson::doc! { "entry_id": entry_id })
+        .await?;
+    Ok(())
+}
+
+/// Retrieves a corrective-action issue by id within a company.
+///
+/// # Errors
+/// Returns an error if the database query fails.
+pub async fn get_corrective_action_issue(
+    client: &mongodb::Client,
+    issue_id: &str,
+    company_id: &str,
+) -> Result<Option<CorrectiveActionIssue>> {
+    let db = client.database("logs_db");
+    let collection: mongodb::Collection<CorrectiveActionIssue> =
+        db.collection("corrective_action_issues");
+
+    let filter = mongodb::bson::doc! {
+        "issue_id": issue_id,
+        "company_id": company_id,
+    };
+
+    Ok(collection.find_one(filter).await?)
+}
+
+/// Lists a company's corrective-action issues with the given statuses, newest first.
+/// `branch_ids` restricts the result to issues raised against those branches.
+///
+/// # Errors
+/// Returns an error if the database query fails.
+pub async fn list_corrective_action_issues(
+    client: &mongodb::Client,
+    company_id: &str,
+    branch_ids: Option<&[String]>,
+    statuses: &[IssueStatus],
+) -> Result<Vec<CorrectiveActionIssue>> {
+    let db = client.database("logs_db");
+    let collection: mongodb::Collection<CorrectiveActionIssue> =
+        db.collection("corrective_action_issues");
+
+    let mut filter = mongodb::bson::doc! {
+        "company_id": company_id,
+        "status": { "$in": mongodb::bson::to_bson(statuses)? },
+    };
+    if let Some(branch_ids) = branch_ids {
+        filter.insert("branch_id", mongodb::bson::doc! { "$in": branch_ids });
+    }
+
+    let cursor = collection
+        .find(filter)
+        .sort(mongodb::bson::doc! { "raised_at": -1 })
+        .await?;
+    let issues: Vec<CorrectiveActionIssue> = cursor.try_collect().await?;
+    Ok(issues)
+}
+
+/// Marks an open issue as acknowledged. Returns `false` if the issue was not open.
+///
+/// # Errors
+/// Returns an error if the database update fails.
+pub async fn acknowledge_corrective_action_issue(
+    client: &mongodb::Client,
+    issue_id: &str,
+    user_id: &str,
+) -> Result<bool> {
+    let db = client.database("logs_db");
+    let collection: mongodb::Collection<CorrectiveActionIssue> =
+        db.collection("corrective_action_issues");
+
+    let filter = mongodb::bson::doc! {
+        "issue_id": issue_id,
+        "status": mongodb::bson::to_bson(&IssueStatus::Open)?,
+    };
+    let update = mongodb::bson::doc! {
+        "$set": {
+            "status": mongodb::bson::to_bson(&IssueStatus::Acknowledged)?,
+            "acknowledged_by": user_id,
+            "acknowledged_at": mongodb::bson::to_bson(&chrono::Utc::now())?,
+        }
+    };
+
+    let result = collection.update_one(filter, update).await?;
+    Ok(result.modified_count > 0)
 }
 
-/// Checks if any log entry (draft or submitted) exists for a specific period.
+/// Closes an acknowledged issue. Returns `false` if the issue was not acknowledged.
 ///
 /// # Errors
-/// Returns an error if the database query fails.
-pub async fn has_entry_for_period(
+/// Returns an error if the database update fails.
+pub async fn close_corrective_action_issue(
     client: &mongodb::Client,
-    company_id: &str,
-    template_name: &str,
-    period: &str,
+    issue_id: &str,
+    user_id: &str,
+    resolution_note: Option<&str>,
 ) -> Result<bool> {
     let db = client.database("logs_db");
-    let collection: mongodb::Collection<LogEntry> = db.collection("log_entries");
+    let collection: mongodb::Collection<CorrectiveActionIssue> =
+        db.collection("corrective_action_issues");
 
     let filter = mongodb::bson::doc! {
-        "company_id": company_id,
-        "template_name": template_name,
-        "period": period,
+        "issue_id": issue_id,
+        "status": mongodb::bson::to_bson(&IssueStatus::Acknowledged)?,
+    };
+    let update = mongodb::bson::doc! {
+        "$set": {
+            "status": mongodb::bson::to_bson(&IssueStatus::Closed)?,
+            "closed_by": user_id,
+            "closed_at": mongodb::bson::to_bson(&chrono::Utc::now())?,
+            "resolution_note": resolution_note,
+        }
     };
 
-    let result = collection.find_one(filter).await?;
-    Ok(result.is_some())
+    let result = collection.update_one(filter, update).await?;
+    Ok(result.modified_count > 0)
 }
 
-/// Returns periods that have entries, for batch checking.
+/// Counts a company's open draft entries per template.
 ///
 /// # Errors
 /// Returns an error if the database query fails.
-pub async fn get_periods_with_entries(
+pub async fn count_draft_entries_by_template(
     client: &mongodb::Client,
     company_id: &str,
-    template_name: &str,
-    periods: &[String],
-) -> Result<std::collections::HashSet<String>> {
-    if periods.is_empty() {
-        return Ok(std::collections::HashSet::new());
-    }
-
+) -> Result<std::collections::HashMap<String, usize>> {
     let db = client.database("logs_db");
     let collection: mongodb::Collection<LogEntry> = db.collection("log_entries");
 
     let filter = mongodb::bson::doc! {
         "company_id": company_id,
-        "template_name": template_name,
-        "period": { "$in": periods }
+        "status": mongodb::bson::to_bson(&LogStatus::Draft)?,
     };
 
     let cursor = collection.find(filter).await?;
     let entries: Vec<LogEntry> = cursor.try_collect().await?;
-    Ok(entries.into_iter().map(|e| e.period).collect())
+
+    let mut counts: std::collections::HashMap<String, usize> = std::collections::HashMap::new();
+    for entry in entries {
+        *counts.entry(entry.template_id).or_default() += 1;
+    }
+
+    Ok(counts)
 }
 
 /// Retrieves a draft log entry for the current period and template.
 ///
 /// # Errors
 /// Returns an error if the database query fails.
-///
-/// # Panics
-/// Panics if period boundary calculations fail.
 pub async fn get_draft_entry_for_current_period(
     client: &mongodb::Client,
     user_id: &str,
     company_id: &str,
-    template_name: &str,
+    template_id: &str,
     frequency: &Frequency,
+    tz: chrono_tz::Tz,
 ) -> Result<Option<LogEntry>> {
     let db = client.database("logs_db");
     let collection: mongodb::Collection<LogEntry> = db.collection("log_entries");
 
-    let now = chrono::Utc::now();
-    let (period_start, period_end) = match frequency {
-        Frequency::Daily => {
-            let start = now
-                .date_naive()
-                .and_hms_opt(0, 0, 0)
-                .unwrap_or_else(|| panic!("Error finding day start: {now}"))
-                .and_utc();
-            let end = now
-                .date_naive()
-                .and_hms_opt(23, 59, 59)
-                .unwrap_or_else(|| panic!("Error finding day end: {now}"))
-                .and_utc();
-            (start, end)
-        }
-        Frequency::Weekly => {
-            let days_since_monday = now.weekday().num_days_from_sunday();
-            let start = (now.date_naive() - chrono::Duration::days(i64::from(days_since_monday)))
-                .and_hms_opt(0, 0, 0)
-                .unwrap()
-                .and_utc();
-            let end = (start.date_naive() + chrono::Duration::days(6))
-                .and_hms_opt(23, 59, 59)
-                .unwrap()
-                .and_utc();
-            (start, end)
-        }
-        Frequency::Monthly => {
-            let start = now
-                .date_naive()
-                .with_day(1)
-                .unwrap()
-                .and_hms_opt(0, 0, 0)
-                .unwrap()
-                .and_utc();
-            let next_month = if now.month() == 12 {
-                now.date_naive()
-                    .with_year(now.year() + 1)
-                    .unwrap()
-                    .with_month(1)
-                    .unwrap()
-            } else {
-                now.date_naive().with_month(now.month() + 1).unwrap()
-            };
-            let end = (next_month - chrono::Duration::days(1))
-                .and_hms_opt(23, 59, 59)
-                .unwrap()
-                .and_utc();
-            (start, end)
-        }
-        Frequency::Yearly => {
-            let start = now
-                .date_naive()
-                .with_month(1)
-                .unwrap()
-                .with_day(1)
-                .unwrap()
-                .and_hms_opt(0, 0, 0)
-                .unwrap()
-                .and_utc();
-            let end = now
-                .date_naive()
-                .with_month(12)
-                .unwrap()
-                .with_day(31)
-                .unwrap()
-                .and_hms_opt(23, 59, 59)
-                .unwrap()
-                .and_utc();
-            (start, end)
-        }
-        Frequency::Quarterly => {
-            let quarter_start_month = ((now.date_naive().month() - 1) / 3) * 3 + 1;
-            let start = now
-                .date_naive()
-                .with_month(quarter_start_month)
-                .unwrap()
-                .with_day(1)
-                .unwrap()
-                .and_hms_opt(0, 0, 0)
-                .unwrap()
-                .and_utc();
-            let quarter_end_month = quarter_start_month + 2;
-            let last_day = get_month_last_day(now.date_naive().year(), quarter_end_month);
-            let end = now
-                .date_naive()
-                .with_month(quarter_end_month)
-                .unwrap()
-                .with_day(last_day)
-                .unwrap()
-                .and_hms_opt(23, 59, 59)
-                .unwrap()
-                .and_utc();
-            (start, end)
-        }
-    };
+    let (period_start, period_end) = current_period_bounds(frequency, tz)
+        .ok_or_else(|| anyhow::anyhow!("Failed to calculate current period bounds"))?;
 
     let filter = mongodb::bson::doc! {
         "user_id": user_id,
         "company_id": company_id,
-        "template_name": template_name,
+        "template_id": template_id,
         "status": mongodb::bson::to_bson(&LogStatus::Draft)?,
         "created_at": {
             "$gte": mongodb::bson::to_bson(&period_start)?,
@@ -1587,27 +3835,124 @@ pub async fn update_log_entry(
     Ok(())
 }
 
-/// Submits a log entry, marking it as final.
+/// Submits a draft log entry with its final entry data, recording whether it was flagged
+/// for out-of-range readings. Returns `false` if the entry is not a draft.
 ///
 /// # Errors
 /// Returns an error if the database update fails.
-pub async fn submit_log_entry(client: &mongodb::Client, entry_id: &str) -> Result<()> {
+pub async fn submit_log_entry(
+    client: &mongodb::Client,
+    entry_id: &str,
+    entry_data: &serde_json::Value,
+    flagged: bool,
+    corrective_action: Option<&str>,
+) -> Result<bool> {
     let db = client.database("logs_db");
     let collection: mongodb::Collection<LogEntry> = db.collection("log_entries");
 
     let filter = mongodb::bson::doc! {
         "entry_id": entry_id,
+        "status": mongodb::bson::to_bson(&LogStatus::Draft)?,
     };
 
     let update = mongodb::bson::doc! {
         "$set": {
             "status": mongodb::bson::to_bson(&LogStatus::Submitted)?,
+            "entry_data": mongodb::bson::to_bson(entry_data)?,
             "submitted_at": mongodb::bson::to_bson(&chrono::Utc::now())?,
             "updated_at": mongodb::bson::to_bson(&chrono::Utc::now())?,
+            "flagged": flagged,
+            "corrective_action": corrective_action,
         }
     };
 
-    collection.update_one(filter, update).await?;
+    let result = collection.update_one(filter, update).await?;
+    Ok(result.modified_count > 0)
+}
+
+/// Applies a manager review to a submitted entry: approved entries become final, rejected
+/// entries return to draft. Returns `false` if the entry is not awaiting review.
+///
+/// # Errors
+/// Returns an error if the database update fails.
+pub async fn review_log_entry(
+    client: &mongodb::Client,
+    entry_id: &str,
+    review: &EntryReview,
+) -> Result<bool> {
+    let db = client.database("logs_db");
+    let collection: mongodb::Collection<LogEntry> = db.collection("log_entries");
+
+    let filter = mongodb::bson::doc! {
+        "entry_id": entry_id,
+        "status": mongodb::bson::to_bson(&LogStatus::Submitted)?,
+    };
+
+    let mut set = mongodb::bson::doc! {
+        "review": mongodb::bson::to_bson(review)?,
+        "updated_at": mongodb::bson::to_bson(&chrono::Utc::now())?,
+    };
+    match review.decision {
+        ReviewDecision::Approved => {
+            set.insert("status", mongodb::bson::to_bson(&LogStatus::Approved)?);
+        }
+        ReviewDecision::Rejected => {
+            set.insert("status", mongodb::bson::to_bson(&LogStatus::Draft)?);
+            set.insert("submitted_at", mongodb::bson::Bson::Null);
+        }
+    }
+
+    let result = collection
+        .update_one(filter, mongodb::bson::doc! { "$set": set })
+        .await?;
+    Ok(result.modified_count > 0)
+}
+
+/// Records a status change of a log entry in its audit trail.
+///
+/// # Errors
+/// Returns an error if the insertion fails.
+pub async fn record_entry_transition(
+    client: &mongodb::Client,
+    transition: &EntryTransition,
+) -> Result<()> {
+    let db = client.database("logs_db");
+    let collection: mongodb::Collection<EntryTransition> = db.collection("log_entry_transitions");
+
+    collection.insert_one(transition).await?;
+    Ok(())
+}
+
+/// Retrieves the status changes of a log entry, oldest first.
+///
+/// # Errors
+/// Returns an error if the database query fails.
+pub async fn get_entry_transitions(
+    client: &mongodb::Client,
+    entry_id: &str,
+) -> Result<Vec<EntryTransition>> {
+    let db = client.database("logs_db");
+    let collection: mongodb::Collection<EntryTransition> = db.collection("log_entry_transitions");
+
+    let cursor = collection
+        .find(mongodb::bson::doc! { "entry_id": entry_id })
+        .sort(mongodb::bson::doc! { "at": 1 })
+        .await?;
+    let transitions: Vec<EntryTransition> = cursor.try_collect().await?;
+    Ok(transitions)
+}
+
+/// Deletes the status history of an entry.
+///
+/// # Errors
+/// Returns an error if the deletion fails.
+pub async fn delete_entry_transitions(client: &mongodb::Client, entry_id: &str) -> Result<()> {
+    let db = client.database("logs_db");
+    let collection: mongodb::Collection<EntryTransition> = db.collection("log_entry_transitions");
+
+    collection
+        .delete_many(mongodb::bson::doc! { "entry_id": entry_id })
+        .await?;
     Ok(())
 }
 
@@ -1784,36 +4129,130 @@ pub fn compute_due_date_for_period(schedule: &Schedule, period: &str) -> Option<
             }
             let year: i32 = parts[0].parse().ok()?;
             let month = u32::from(schedule.month_of_year.unwrap_or(1));
-            let target_day = u32::from(schedule.day_of_month.unwrap_or(1));
-            let last_day_of_month = chrono::NaiveDate::from_ymd_opt(
-                if month == 12 { year + 1 } else { year },
-                if month == 12 { 1 } else { month + 1 },
-                1,
-            )? - chrono::Duration::days(1);
-            let day = target_day.min(last_day_of_month.day());
-            chrono::NaiveDate::from_ymd_opt(year, month, day)
+            day_or_last_of_month(year, month, schedule.day_of_month.unwrap_or(1))
         }
         Frequency::Quarterly => {
-            let parts: Vec<&str> = period.split('/').collect();
             if parts.len() != 2 {
                 return None;
             }
             let quarter: u32 = parts[0].parse().ok()?;
             let year: i32 = parts[1].parse().ok()?;
+            if !(1..=4).contains(&quarter) {
+                return None;
+            }
+            // Quarters are due in their first month: January, April, July and October
             let month = (quarter - 1) * 3 + 1;
-            chrono::NaiveDate::from_ymd_opt(year, month, 1)
+            day_or_last_of_month(
+                normalize_year(year)?,
+                month,
+                schedule.day_of_month.unwrap_or(1),
+            )
+        }
+    }
+}
+
+/// The given day of a month, or the month's last day when it has fewer days. Yearly and
+/// quarterly schedules fall due on this date, like monthly ones.
+fn day_or_last_of_month(year: i32, month: u32, day: u8) -> Option<chrono::NaiveDate> {
+    let next_month = if month == 12 {
+        chrono::NaiveDate::from_ymd_opt(year + 1, 1, 1)
+    } else {
+        chrono::NaiveDate::from_ymd_opt(year, month + 1, 1)
+    }?;
+    let last_day = next_month.pred_opt()?.day();
+    chrono::NaiveDate::from_ymd_opt(year, month, u32::from(day).min(last_day))
+}
+
+/// First and last day, inclusive, that a period of a schedule covers.
+#[must_use]
+pub fn period_date_range(
+    schedule: &Schedule,
+    period: &str,
+) -> Option<(chrono::NaiveDate, chrono::NaiveDate)> {
+    let first_of_month = |year: i32, month: u32| chrono::NaiveDate::from_ymd_opt(year, month, 1);
+    match schedule.frequency {
+        Frequency::Daily => parse_period_to_date(period).map(|date| (date, date)),
+        Frequency::Weekly => {
+            // Weekly periods parse to the last day of their week
+            let end = parse_period_to_date(period)?;
+            Some((end - chrono::Duration::days(6), end))
+        }
+        Frequency::Monthly => {
+            let start = parse_period_to_date(period)?;
+            let next = start.checked_add_months(chrono::Months::new(1))?;
+            Some((start, next.pred_opt()?))
+        }
+        Frequency::Quarterly => {
+            let (quarter, year) = period.split_once('/')?;
+            let quarter: u32 = quarter.parse().ok()?;
+            if !(1..=4).contains(&quarter) {
+                return None;
+            }
+            let start = first_of_month(year.parse().ok()?, (quarter - 1) * 3 + 1)?;
+            let next = start.checked_add_months(chrono::Months::new(3))?;
+            Some((start, next.pred_opt()?))
+        }
+        Frequency::Yearly => {
+            let start = parse_period_to_date(period)?;
+            let next = start.checked_add_months(chrono::Months::new(12))?;
+            Some((start, next.pred_opt()?))
         }
     }
 }
 
+/// Furthest ahead `upcoming_periods` looks for due periods, in days.
+const UPCOMING_PERIODS_HORIZON_DAYS: i64 = 366 * 25;
+
+/// The first `count` periods of a schedule due on or after `from`, in order of due date.
+/// Looks at most 25 years ahead, so schedules that are never due return no periods.
+#[must_use]
+pub fn upcoming_periods(schedule: &Schedule, from: chrono::NaiveDate, count: usize) -> Vec<String> {
+    let horizon = from + chrono::Duration::days(UPCOMING_PERIODS_HORIZON_DAYS);
+    let slots = schedule.daily_slots();
+    let mut periods = Vec::new();
+
+    let mut date = from;
+    while periods.len() < count && date <= horizon {
+        let candidates = match schedule.frequency {
+            Frequency::Daily if !schedule.is_scheduled_day(date) => Vec::new(),
+            Frequency::Daily if slots.is_empty() => vec![format_period_for_date(date)],
+            Frequency::Daily => slots
+                .iter()
+                .map(|(from_minutes, _)| format_period_for_slot(date, *from_minutes))
+                .collect(),
+            Frequency::Weekly if !schedule.matches_interval(date) => Vec::new(),
+            Frequency::Weekly => vec![format_period_for_weekly(date)],
+            Frequency::Monthly => vec![format_period_for_monthly(date)],
+            Frequency::Quarterly => {
+                vec![format!("{}/{:04}", (date.month() - 1) / 3 + 1, date.year())]
+            }
+            Frequency::Yearly => vec![date.year().to_string()],
+        };
+        periods.extend(
+            candidates
+                .into_iter()
+                .filter(|period| compute_due_date_for_period(schedule, period) == Some(date)),
+        );
+        let Some(next) = date.succ_opt() else {
+            break;
+        };
+        date = next;
+    }
+
+    periods.truncate(count);
+    periods
+}
+
 #[must_use]
 pub fn get_availability_status_for_period(
     schedule: &Schedule,
     period: &str,
     current_datetime: chrono::DateTime<chrono::Utc>,
+    tz: chrono_tz::Tz,
 ) -> AvailabilityStatus {
     let target_date = compute_due_date_for_period(schedule, period);
     if let Some(target) = target_date {
+        let current_datetime = current_datetime.with_timezone(&tz);
         let today = current_datetime.date_naive();
 
         if target < today {
@@ -1827,6 +4266,15 @@ pub fn get_availability_status_for_period(
 
         match schedule.frequency {
             Frequency::Daily => {
+                if let Some((from_mins, due_mins)) = slot_for_period(schedule, period) {
+                    if current_time < from_mins {
+                        return AvailabilityStatus::NotAvailable;
+                    }
+                    if current_time >= due_mins {
+                        return AvailabilityStatus::Overdue;
+                    }
+                    return AvailabilityStatus::Available;
+                }
                 if let (Some(from_str), Some(due_str)) =
                     (&schedule.available_from_time, &schedule.due_at_time)
                     && let (Some((from_h, from_m)), Some((due_h, due_m))) =
@@ -1872,22 +4320,70 @@ pub fn derive_log_status(
     schedule: &Schedule,
     period: &str,
     current_datetime: chrono::DateTime<chrono::Utc>,
+    tz: chrono_tz::Tz,
 ) -> (LogStatus, AvailabilityStatus) {
-    let availability = get_availability_status_for_period(schedule, period, current_datetime);
+    let availability = get_availability_status_for_period(schedule, period, current_datetime, tz);
 
     if stored_status == LogStatus::Overdue {
         return (stored_status, availability);
     }
 
-    if availability == AvailabilityStatus::Overdue && stored_status != LogStatus::Submitted {
+    // Only unfinished entries become overdue; submitted and signed-off entries keep their status
+    if availability == AvailabilityStatus::Overdue && stored_status == LogStatus::Draft {
         return (LogStatus::Overdue, availability);
     }
 
     (stored_status, availability)
 }
 
+/// Splits a sub-daily period (`DD/MM/YYYY HH:MM`) into its date and slot time.
+fn split_period_slot(period: &str) -> (&str, Option<&str>) {
+    match period.trim().split_once(' ') {
+        Some((date, time)) => (date, Some(time.trim())),
+        None => (period.trim(), None),
+    }
+}
+
+/// The opening time, in minutes after midnight, of the slot a sub-daily period refers to.
+#[must_use]
+pub fn period_slot_minutes(period: &str) -> Option<u32> {
+    let (hour, minute) = parse_time_string(split_period_slot(period).1?)?;
+    Some(hour * 60 + minute)
+}
+
+/// The opening and due times of the schedule slot a sub-daily period refers to.
+fn slot_for_period(schedule: &Schedule, period: &str) -> Option<(u32, u32)> {
+    let from = period_slot_minutes(period)?;
+    schedule
+        .daily_slots()
+        .into_iter()
+        .find(|(slot_from, _)| *slot_from == from)
+}
+
+/// Converts a local wall-clock time to UTC. A time repeated when clocks go back resolves to
+/// its first occurrence, and a time skipped when clocks go forward to the hour after it.
+fn local_to_utc(
+    datetime: chrono::NaiveDateTime,
+    tz: chrono_tz::Tz,
+) -> Option<chrono::DateTime<chrono::Utc>> {
+    let local = match tz.from_local_datetime(&datetime) {
+        chrono::LocalResult::Single(dt) | chrono::LocalResult::Ambiguous(dt, _) => dt,
+        chrono::LocalResult::None => tz
+            .from_local_datetime(&(datetime + chrono::Duration::hours(1)))
+            .earliest()?,
+    };
+    Some(local.with_timezone(&chrono::Utc))
+}
+
+/// The UTC instant, as RFC 3339, of a local time on a date.
+fn local_datetime_at(date: chrono::NaiveDate, minutes: u32, tz: chrono_tz::Tz) -> Option<String> {
+    let datetime = local_to_utc(date.and_hms_opt(minutes / 60, minutes % 60, 0)?, tz)?;
+    Some(datetime.to_rfc3339())
+}
+
 #[must_use]
 pub fn parse_period_to_date(period: &str) -> Option<chrono::NaiveDate> {
+    let (period, _) = split_period_slot(period);
     let parts: Vec<&str> = period.split('/').collect();
 
     fn normalize_year(year: i32) -> Option<i32> {
@@ -1935,12 +4431,24 @@ pub fn validate_and_normalize_period(schedule: &Schedule, period: &str) -> Optio
     let period = period.trim();
     match schedule.frequency {
         Frequency::Daily => {
-            let parts: Vec<&str> = period.split('/').collect();
+            let (date_part, slot) = split_period_slot(period);
+            let parts: Vec<&str> = date_part.split('/').collect();
             if parts.len() != 3 || parts[0].contains('-') {
                 return None;
             }
-            let date = parse_period_to_date(period)?;
-            Some(format_period_for_date(date))
+            let date = parse_period_to_date(date_part)?;
+            let slots = schedule.daily_slots();
+            match slot {
+                None if slots.is_empty() => Some(format_period_for_date(date)),
+                Some(_) => {
+                    let from = period_slot_minutes(period)?;
+                    slots
+                        .iter()
+                        .any(|(slot_from, _)| *slot_from == from)
+                        .then(|| format_period_for_slot(date, from))
+                }
+                None => None,
+            }
         }
         Frequency::Weekly => {
             let parts: Vec<&str> = period.split('/').collect();
@@ -1989,6 +4497,7 @@ pub enum PeriodValidationError {
     FormatInvalid,
     DueDateInFuture,
     WeekdayNotAllowed,
+    NotScheduled,
     BeforeTemplateCreation,
 }
 
@@ -1997,6 +4506,7 @@ pub fn validate_period_business_rules(
     period: &str,
     template_created_at: Option<chrono::DateTime<chrono::Utc>>,
     current_datetime: chrono::DateTime<chrono::Utc>,
+    tz: chrono_tz::Tz,
 ) -> Result<String, PeriodValidationError> {
     let normalized = validate_and_normalize_period(schedule, period)
         .ok_or(PeriodValidationError::FormatInvalid)?;
@@ -2004,7 +4514,7 @@ pub fn validate_period_business_rules(
     let due_date = compute_due_date_for_period(schedule, &normalized)
         .ok_or(PeriodValidationError::FormatInvalid)?;
 
-    let today = current_datetime.date_naive();
+    let today = current_datetime.with_timezone(&tz).date_naive();
     if due_date > today {
         return Err(PeriodValidationError::DueDateInFuture);
     }
@@ -2026,8 +4536,12 @@ pub fn validate_period_business_rules(
         }
     }
 
+    if !schedule.matches_interval(due_date) {
+        return Err(PeriodValidationError::NotScheduled);
+    }
+
     if let Some(created_at) = template_created_at {
-        let created_date = created_at.date_naive();
+        let created_date = created_at.with_timezone(&tz).date_naive();
         if due_date < created_date {
             return Err(PeriodValidationError::BeforeTemplateCreation);
         }
@@ -2037,28 +4551,16 @@ pub fn validate_period_business_rules(
 }
 
 #[must_use]
-pub fn is_form_due_today(schedule: &Schedule) -> bool {
-    let today = chrono::Utc::now();
+pub fn is_form_due_today(schedule: &Schedule, tz: chrono_tz::Tz) -> bool {
+    let today = chrono::Utc::now().with_timezone(&tz);
     let weekday = today.weekday();
 
     match schedule.frequency {
-        Frequency::Daily => {
-            if let Some(days) = &schedule.days_of_week {
-                let day_num = match weekday {
-                    chrono::Weekday::Sun => 0,
-                    chrono::Weekday::Mon => 1,
-                    chrono::Weekday::Tue => 2,
-                    chrono::Weekday::Wed => 3,
-                    chrono::Weekday::Thu => 4,
-                    chrono::Weekday::Fri => 5,
-                    chrono::Weekday::Sat => 6,
-                };
-                days.contains(&day_num)
-            } else {
-                true
-            }
-        }
+        Frequency::Daily => schedule.is_scheduled_day(today.date_naive()),
         Frequency::Weekly => {
+            if !schedule.matches_interval(today.date_naive()) {
+                return false;
+            }
             if let Some(day) = schedule.day_of_week {
                 let day_num = match weekday {
                     chrono::Weekday::Sun => 0,
@@ -2119,14 +4621,16 @@ pub fn get_missed_periods(
     schedule: &Schedule,
     last_submitted_period: Option<&str>,
     created_at: Option<&str>,
+    tz: chrono_tz::Tz,
 ) -> Vec<String> {
-    let today = chrono::Utc::now().date_naive();
+    let now = chrono::Utc::now().with_timezone(&tz);
+    let today = now.date_naive();
     let last_period = last_submitted_period.and_then(parse_period_to_date);
 
     let start_from = created_at.and_then(|c| {
         chrono::DateTime::parse_from_rfc3339(c)
             .ok()
-            .map(|dt| dt.date_naive())
+            .map(|dt| dt.with_timezone(&tz).date_naive())
     });
     let created_date = start_from;
 
@@ -2134,13 +4638,19 @@ pub fn get_missed_periods(
 
     match schedule.frequency {
         Frequency::Daily => {
-            let days_of_week = schedule
-                .days_of_week
-                .clone()
-                .unwrap_or_else(|| vec![0, 1, 2, 3, 4, 5, 6]);
+            let slots = schedule.daily_slots();
+            // A sub-daily period only covers one slot, so later slots that day are still open
+            let last_slot = last_submitted_period.and_then(period_slot_minutes);
+            let now_minutes = now.hour() * 60 + now.minute();
 
             let start_date = last_period
-                .map(|d| d + chrono::Duration::days(1))
+                .map(|d| {
+                    if !slots.is_empty() && last_slot.is_some() {
+                        d
+                    } else {
+                        d + chrono::Duration::days(1)
+                    }
+                })
                 .unwrap_or_else(|| {
                     start_from.unwrap_or_else(|| {
                         tracing::warn!("Failed to determine start date for missed periods");
@@ -2150,18 +4660,18 @@ pub fn get_missed_periods(
 
             let mut current = start_date;
             while current <= today {
-                let day_num = match current.weekday() {
-                    chrono::Weekday::Sun => 0,
-                    chrono::Weekday::Mon => 1,
-                    chrono::Weekday::Tue => 2,
-                    chrono::Weekday::Wed => 3,
-                    chrono::Weekday::Thu => 4,
-                    chrono::Weekday::Fri => 5,
-                    chrono::Weekday::Sat => 6,
-                };
-
-                if days_of_week.contains(&day_num) {
-                    missed.push(format_period_for_date(current));
+                if schedule.is_scheduled_day(current) {
+                    if slots.is_empty() {
+                        missed.push(format_period_for_date(current));
+                    }
+                    for (from, _) in &slots {
+                        let already_covered = last_period == Some(current)
+                            && last_slot.is_some_and(|last| *from <= last);
+                        let opened = current < today || *from <= now_minutes;
+                        if !already_covered && opened {
+                            missed.push(format_period_for_slot(current, *from));
+                        }
+                    }
                 }
                 current += chrono::Duration::days(1);
             }
@@ -2201,7 +4711,9 @@ pub fn get_missed_periods(
 
             let mut current = start_date;
             while current <= today {
-                missed.push(format_period_for_weekly(current));
+                if schedule.matches_interval(current) {
+                    missed.push(format_period_for_weekly(current));
+                }
                 current += chrono::Duration::weeks(1);
             }
         }
@@ -2309,11 +4821,8 @@ pub fn get_missed_periods(
 
             let mut current_year = last_year + 1;
             while current_year <= today.year() {
-                let check_date = chrono::NaiveDate::from_ymd_opt(
-                    current_year,
-                    u32::from(target_month),
-                    u32::from(target_day),
-                );
+                let check_date =
+                    day_or_last_of_month(current_year, u32::from(target_month), target_day);
 
                 if let Some(d) = check_date
                     && d <= today
@@ -2337,11 +4846,7 @@ pub fn get_missed_periods(
                     && current_quarter <= ((today.month() - 1) / 3 + 1))
             {
                 let quarter_month = (current_quarter - 1) * 3 + 1;
-                let check_date = chrono::NaiveDate::from_ymd_opt(
-                    current_year,
-                    quarter_month,
-                    u32::from(target_day),
-                );
+                let check_date = day_or_last_of_month(current_year, quarter_month, target_day);
 
                 if let Some(d) = check_date
                     && d <= today
@@ -2368,6 +4873,16 @@ fn format_period_for_date(date: chrono::NaiveDate) -> String {
     format!("{:02}/{:02}/{:04}", date.day(), date.month(), date.year())
 }
 
+#[must_use]
+fn format_period_for_slot(date: chrono::NaiveDate, from_minutes: u32) -> String {
+    format!(
+        "{} {:02}:{:02}",
+        format_period_for_date(date),
+        from_minutes / 60,
+        from_minutes % 60
+    )
+}
+
 #[must_use]
 fn format_period_for_weekly(date: chrono::NaiveDate) -> String {
     let days_since_monday = date.weekday().num_days_from_sunday();
@@ -2388,32 +4903,27 @@ fn format_period_for_monthly(date: chrono::NaiveDate) -> String {
 }
 
 #[must_use]
-pub fn get_available_from_datetime(schedule: &Schedule, period: &str) -> Option<String> {
+pub fn get_available_from_datetime(
+    schedule: &Schedule,
+    period: &str,
+    tz: chrono_tz::Tz,
+) -> Option<String> {
     let target_date = compute_due_date_for_period(schedule, period)?;
 
     match schedule.frequency {
         Frequency::Daily => {
+            if let Some((from, _)) = slot_for_period(schedule, period) {
+                return local_datetime_at(target_date, from, tz);
+            }
             if let Some(from_str) = &schedule.available_from_time
                 && let Some((hour, minute)) = parse_time_string(from_str)
             {
-                let datetime = chrono::DateTime::<chrono::Utc>::from_naive_utc_and_offset(
-                    target_date.and_hms_opt(hour, minute, 0)?,
-                    chrono::Utc,
-                );
-                return Some(datetime.to_rfc3339());
+                return local_datetime_at(target_date, hour * 60 + minute, tz);
             }
-            let datetime = chrono::DateTime::<chrono::Utc>::from_naive_utc_and_offset(
-                target_date.and_hms_opt(8, 0, 0)?,
-                chrono::Utc,
-            );
-            Some(datetime.to_rfc3339())
+            local_datetime_at(target_date, 8 * 60, tz)
         }
         Frequency::Weekly | Frequency::Monthly | Frequency::Yearly => {
-            let datetime = chrono::DateTime::<chrono::Utc>::from_naive_utc_and_offset(
-                target_date.and_hms_opt(0, 0, 0)?,
-                chrono::Utc,
-            );
-            Some(datetime.to_rfc3339())
+            local_datetime_at(target_date, 0, tz)
         }
         Frequency::Quarterly => {
             let from_hour = schedule
@@ -2421,42 +4931,29 @@ pub fn get_available_from_datetime(schedule: &Schedule, period: &str) -> Option<
                 .as_ref()
                 .and_then(|s| parse_time_string(s).map(|(h, _)| h))
                 .unwrap_or(8);
-            let datetime = chrono::DateTime::<chrono::Utc>::from_naive_utc_and_offset(
-                target_date.and_hms_opt(from_hour, 0, 0)?,
-                chrono::Utc,
-            );
-            Some(datetime.to_rfc3339())
+            local_datetime_at(target_date, from_hour * 60, tz)
         }
     }
 }
 
 #[must_use]
-pub fn get_due_at_datetime(schedule: &Schedule, period: &str) -> Option<String> {
+pub fn get_due_at_datetime(schedule: &Schedule, period: &str, tz: chrono_tz::Tz) -> Option<String> {
     let target_date = compute_due_date_for_period(schedule, period)?;
 
     match schedule.frequency {
         Frequency::Daily => {
+            if let Some((_, due)) = slot_for_period(schedule, period) {
+                return local_datetime_at(target_date, due, tz);
+            }
             if let Some(due_str) = &schedule.due_at_time
                 && let Some((hour, minute)) = parse_time_string(due_str)
             {
-                let datetime = chrono::DateTime::<chrono::Utc>::from_naive_utc_and_offset(
-                    target_date.and_hms_opt(hour, minute, 0)?,
-                    chrono::Utc,
-                );
-                return Some(datetime.to_rfc3339());
+                return local_datetime_at(target_date, hour * 60 + minute, tz);
             }
-            let datetime = chrono::DateTime::<chrono::Utc>::from_naive_utc_and_offset(
-                target_date.and_hms_opt(17, 0, 0)?,
-                chrono::Utc,
-            );
-            Some(datetime.to_rfc3339())
+            local_datetime_at(target_date, 17 * 60, tz)
         }
         Frequency::Weekly | Frequency::Monthly | Frequency::Yearly => {
-            let datetime = chrono::DateTime::<chrono::Utc>::from_naive_utc_and_offset(
-                target_date.and_hms_opt(23, 59, 0)?,
-                chrono::Utc,
-            );
-            Some(datetime.to_rfc3339())
+            local_datetime_at(target_date, 23 * 60 + 59, tz)
         }
         Frequency::Quarterly => {
             let due_hour = schedule
@@ -2464,61 +4961,66 @@ pub fn get_due_at_datetime(schedule: &Schedule, period: &str) -> Option<String>
                 .as_ref()
                 .and_then(|s| parse_time_string(s).map(|(h, _)| h))
                 .unwrap_or(17);
-            let datetime = chrono::DateTime::<chrono::Utc>::from_naive_utc_and_offset(
-                target_date.and_hms_opt(due_hour, 59, 0)?,
-                chrono::Utc,
-            );
-            Some(datetime.to_rfc3339())
+            local_datetime_at(target_date, due_hour * 60 + 59, tz)
         }
     }
 }
 
-/// Formats the period string for a given frequency.
-///
-/// # Panics
-/// Panics if week boundary calculations fail.
+/// Formats the period string for a given frequency, using today's date in `tz`.
 #[must_use]
-pub fn format_period_for_frequency(frequency: &Frequency) -> String {
-    let now = chrono::Utc::now();
+pub fn format_period_for_frequency(frequency: &Frequency, tz: chrono_tz::Tz) -> String {
+    let now = chrono::Utc::now().with_timezone(&tz);
 
     match frequency {
-        Frequency::Daily => now.format("%d/%m/%Y").to_string(),
-        Frequency::Weekly => {
-            let days_since_monday = now.weekday().num_days_from_sunday();
-            let week_start = if let Some(dt) = (now.date_naive()
-                - chrono::Duration::days(i64::from(days_since_monday)))
-            .and_hms_opt(0, 0, 0)
-            {
-                dt.and_utc()
-            } else {
-                tracing::warn!("Error finding week start date: {now}");
-                // Fall back to a simple date format
-                return now.format("%d/%m/%Y").to_string();
-            };
-            let week_end = if let Some(dt) =
-                (week_start.date_naive() + chrono::Duration::days(6)).and_hms_opt(23, 59, 59)
-            {
-                dt.and_utc()
+        Frequency::Daily => format_period_for_date(now.date_naive()),
+        Frequency::Weekly => format_period_for_weekly(now.date_naive()),
+        Frequency::Monthly => format_period_for_monthly(now.date_naive()),
+        Frequency::Quarterly => {
+            let quarter = ((now.month() - 1) / 3) + 1;
+            format!("{}/{:04}", quarter, now.year())
+        }
+        Frequency::Yearly => now.year().to_string(),
+    }
+}
+
+/// Formats the current period of a schedule. Interval schedules use their most recent
+/// scheduled day or week, and sub-daily schedules the slot that opened most recently, or
+/// the first slot of the day before any has opened.
+#[must_use]
+pub fn format_period_for_schedule(schedule: &Schedule, tz: chrono_tz::Tz) -> String {
+    let now = chrono::Utc::now().with_timezone(&tz);
+    let today = now.date_naive();
+
+    match schedule.frequency {
+        Frequency::Daily => {
+            let date = (0..schedule.interval_length())
+                .map(|days_back| today - chrono::Duration::days(days_back))
+                .find(|date| schedule.matches_interval(*date))
+                .unwrap_or(today);
+            let slots = schedule.daily_slots();
+            let now_minutes = now.hour() * 60 + now.minute();
+            let slot = if date == today {
+                slots
+                    .iter()
+                    .rev()
+                    .find(|(from, _)| *from <= now_minutes)
+                    .or(slots.first())
             } else {
-                tracing::warn!("Error finding week end date: {now}");
-                // Fall back to a simple date format
-                return now.format("%d/%m/%Y").to_string();
+                slots.last()
             };
-
-            format!(
-                "{}-{}/{}/{}",
-                week_start.day(),
-                week_end.day(),
-                week_end.month(),
-                week_end.format("%Y")
-            )
+            match slot {
+                Some((from, _)) => format_period_for_slot(date, *from),
+                None => format_period_for_date(date),
+            }
         }
-        Frequency::Monthly => now.format("%m/%Y").to_string(),
-        Frequency::Quarterly => {
-            let quarter = ((now.month() - 1) / 3) + 1;
-            format!("{}/{}", quarter, now.format("%Y"))
+        Frequency::Weekly if schedule.interval_length() > 1 => {
+            let date = (0..schedule.interval_length())
+                .map(|weeks_back| today - chrono::Duration::weeks(weeks_back))
+                .find(|date| schedule.matches_interval(*date))
+                .unwrap_or(today);
+            format_period_for_weekly(date)
         }
-        Frequency::Yearly => now.format("%Y").to_string(),
+        _ => format_period_for_frequency(&schedule.frequency, tz),
     }
 }
 
@@ -2526,8 +5028,9 @@ pub fn format_period_for_frequency(frequency: &Frequency) -> String {
 pub fn process_template_layout_with_period(
     layout: &TemplateLayout,
     frequency: &Frequency,
+    tz: chrono_tz::Tz,
 ) -> TemplateLayout {
-    let period = format_period_for_frequency(frequency);
+    let period = format_period_for_frequency(frequency, tz);
 
     layout
         .iter()
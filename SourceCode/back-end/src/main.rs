@@ -1,9 +1,9 @@
 use anyhow::Context;
 use axum::routing::{delete, get, post, put};
-use axum::{Router, middleware};
+use axum::{Router, extract::DefaultBodyLimit, middleware};
 use back_end::exports_db;
 use back_end::logs_db;
-use back_end::{AppState, api_docs::ApiDoc, db, handlers, rate_limit};
+use back_end::{AppState, api_docs::ApiDoc, db, handlers, rate_limit, services};
 use sqlx::postgres::{PgConnectOptions, PgPoolOptions};
 use url::Url;
 use utoipa::OpenApi;
@@ -131,13 +131,18 @@ async fn main() {
         .time_to_live(std::time::Duration::from_secs(900)) // 15 minutes
         .build();
 
+    let mongodb = logs_db::init_mongodb()
+        .await
+        .expect("Failed to initialize MongoDB");
+    logs_db::migrate_missed_log_alert_template_ids(&mongodb, &auth_db_postgres_pool)
+        .await
+        .expect("Failed to migrate missed log alerts");
+
     let state = AppState {
         postgres: auth_db_postgres_pool,
         rate_limit: rate_limit_state.clone(),
         metrics,
-        mongodb: logs_db::init_mongodb()
-            .await
-            .expect("Failed to initialize MongoDB"),
+        mongodb,
         webauthn: std::sync::Arc::new(
             webauthn_rs::WebauthnBuilder::new(
                 &std::env::var("RP_ID").unwrap_or_else(|_| "localhost".to_string()),
@@ -157,6 +162,9 @@ async fn main() {
         user_cache,
     };
 
+    back_end::services::MissedLogAlertService::spawn_alert_task(state.clone());
+    back_end::services::ComplianceDigestService::spawn_digest_task(state.clone());
+
     let api_routes = Router::new()
         .route("/health", get(handlers::basic_health_check))
         .route("/auth/register", post(handlers::register_company_admin))
@@ -221,6 +229,8 @@ async fn main() {
             delete(handlers::delete_passkey),
         )
         .route("/auth/profile", put(handlers::update_profile))
+        .route("/auth/digest", get(handlers::get_digest_preference))
+        .route("/auth/digest", put(handlers::update_digest_preference))
         .route("/auth/invitations/send", post(handlers::invite_user))
         .route(
             "/auth/invitations/pending",
@@ -238,6 +248,26 @@ async fn main() {
             "/auth/company/branches/confirm-deletion",
             post(handlers::confirm_branch_deletion),
         )
+        .route(
+            "/auth/company/branches/{branch_id}/calendar",
+            get(handlers::get_branch_calendar),
+        )
+        .route(
+            "/auth/company/branches/{branch_id}/opening-hours",
+            put(handlers::update_branch_opening_hours),
+        )
+        .route(
+            "/auth/company/branches/{branch_id}/closures",
+            post(handlers::create_branch_closure),
+        )
+        .route(
+            "/auth/company/branches/{branch_id}/closures/import",
+            post(handlers::import_branch_closures),
+        )
+        .route(
+            "/auth/company/branches/{branch_id}/closures/{closure_id}",
+            delete(handlers::delete_branch_closure),
+        )
         .route(
             "/auth/admin/update-member",
             put(handlers::admin_update_member_profile),
@@ -272,6 +302,14 @@ async fn main() {
         )
         .route("/companies/{company_id}", get(handlers::get_company))
         .route("/companies/{company_id}", put(handlers::update_company))
+        .route(
+            "/companies/{company_id}/missed-log-alerts",
+            get(handlers::get_missed_log_alert_settings),
+        )
+        .route(
+            "/companies/{company_id}/missed-log-alerts",
+            put(handlers::update_missed_log_alert_settings),
+        )
         .route(
             "/companies/{company_id}/export",
             post(handlers::export_company_data),
@@ -292,23 +330,81 @@ async fn main() {
         .route("/logs/templates", post(handlers::add_template))
         .route("/logs/templates", get(handlers::get_template))
         .route("/logs/templates/all", get(handlers::get_all_templates))
+        .route(
+            "/logs/templates/schedule/preview",
+            post(handlers::preview_schedule),
+        )
         .route("/logs/templates/update", put(handlers::update_template))
         .route("/logs/templates/rename", put(handlers::rename_template))
         .route("/logs/templates", delete(handlers::delete_template))
+        .route(
+            "/logs/templates/unarchive",
+            post(handlers::unarchive_template),
+        )
+        .route("/logs/templates/purge", delete(handlers::purge_template))
+        .route(
+            "/logs/templates/distribute",
+            post(handlers::distribute_template),
+        )
+        .route(
+            "/logs/templates/propagate/preview",
+            get(handlers::preview_template_propagation),
+        )
+        .route(
+            "/logs/templates/propagate",
+            post(handlers::propagate_template),
+        )
+        .route("/logs/templates/unlink", post(handlers::unlink_template))
+        .route(
+            "/logs/templates/bundle/export",
+            post(handlers::export_template_bundle),
+        )
+        .route(
+            "/logs/templates/bundle/import",
+            post(handlers::import_template_bundle),
+        )
+        .route(
+            "/logs/templates/library",
+            get(handlers::list_library_templates),
+        )
+        .route(
+            "/logs/templates/library",
+            post(handlers::add_library_template),
+        )
+        .route(
+            "/logs/templates/library/{library_template_id}",
+            put(handlers::update_library_template),
+        )
+        .route(
+            "/logs/templates/library/{library_template_id}",
+            delete(handlers::delete_library_template),
+        )
+        .route(
+            "/logs/templates/library/{library_template_id}/clone",
+            post(handlers::clone_library_template),
+        )
         .route(
             "/logs/templates/versions",
             get(handlers::get_template_versions),
         )
+        .route(
+            "/logs/templates/versions/diff",
+            get(handlers::diff_template_versions),
+        )
         .route(
             "/logs/templates/versions/restore",
             post(handlers::restore_template_version),
         )
+        .route(
+            "/logs/templates/entries",
+            get(handlers::list_template_log_entries),
+        )
         .route("/logs/entries/due", get(handlers::list_due_forms_today))
         .route("/logs/entries", post(handlers::create_log_entry))
         .route("/logs/entries", get(handlers::list_user_log_entries))
         .route(
             "/logs/admin/entries",
-            get(handlers::list_company_log_entries::<std::hash::RandomState>),
+            get(handlers::list_company_log_entries),
         )
         .route("/logs/entries/{entry_id}", get(handlers::get_log_entry))
         .route("/logs/entries/{entry_id}", put(handlers::update_log_entry))
@@ -316,6 +412,45 @@ async fn main() {
             "/logs/entries/{entry_id}",
             delete(handlers::delete_log_entry),
         )
+        .route(
+            "/logs/entries/{entry_id}/review",
+            post(handlers::review_log_entry),
+        )
+        .route(
+            "/logs/entries/{entry_id}/history",
+            get(handlers::get_log_entry_history),
+        )
+        .route(
+            "/logs/entries/{entry_id}/signatures/{signature_id}",
+            get(handlers::get_entry_signature),
+        )
+        .route(
+            "/logs/entries/{entry_id}/attachments",
+            post(handlers::upload_entry_attachment).layer(DefaultBodyLimit::max(
+                services::attachment_service::MAX_ATTACHMENT_REQUEST_BYTES,
+            )),
+        )
+        .route(
+            "/logs/entries/{entry_id}/attachments",
+            get(handlers::list_entry_attachments),
+        )
+        .route(
+            "/logs/entries/{entry_id}/attachments/{attachment_id}",
+            get(handlers::download_entry_attachment),
+        )
+        .route(
+            "/logs/entries/{entry_id}/attachments/{attachment_id}",
+            delete(handlers::delete_entry_attachment),
+        )
+        .route("/logs/issues", get(handlers::list_corrective_action_issues))
+        .route(
+            "/logs/issues/{issue_id}/acknowledge",
+            post(handlers::acknowledge_corrective_action_issue),
+        )
+        .route(
+            "/logs/issues/{issue_id}/close",
+            post(handlers::close_corrective_action_issue),
+        )
         .route("/reports/runs", post(handlers::create_report_run))
         .route("/reports/runs", get(handlers::list_report_runs))
         .route(
@@ -326,6 +461,12 @@ async fn main() {
             "/reports/runs/{report_id}",
             delete(handlers::delete_report_run),
         )
+        .route(
+            "/reports/runs/{report_id}/pdf",
+            get(handlers::generate_report_run_pdf),
+        )
+        .route("/reports/pdf", post(handlers::generate_report_pdf))
+        .route("/reports/entries/export", get(handlers::export_log_entries))
         .route(
             "/logs/entries/{entry_id}/submit",
             post(handlers::submit_log_entry),
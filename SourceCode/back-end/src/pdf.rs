@@ -0,0 +1,440 @@
+//! Minimal PDF writer used for server-rendered reports.
+//!
+//! Supports the standard Helvetica fonts, lines, rectangles and JPEG images on A4 pages.
+//! Text is encoded as `WinAnsiEncoding`; characters outside it are replaced with `?`.
+
+use std::fmt::Write;
+
+/// A4 page width in points.
+pub const PAGE_WIDTH: f32 = 595.0;
+/// A4 page height in points.
+pub const PAGE_HEIGHT: f32 = 842.0;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Font {
+    Regular,
+    Bold,
+}
+
+impl Font {
+    fn resource_name(self) -> &'static str {
+        match self {
+            Font::Regular => "F1",
+            Font::Bold => "F2",
+        }
+    }
+}
+
+/// RGB colour with components in the range 0.0 to 1.0.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Color(pub f32, pub f32, pub f32);
+
+impl Color {
+    pub const BLACK: Color = Color(0.0, 0.0, 0.0);
+    pub const GREY: Color = Color(0.45, 0.45, 0.45);
+    pub const LIGHT_GREY: Color = Color(0.85, 0.85, 0.85);
+    pub const RED: Color = Color(0.75, 0.1, 0.1);
+    pub const BLUE: Color = Color(0.1, 0.35, 0.7);
+}
+
+struct JpegImage {
+    width: u32,
+    height: u32,
+    data: Vec<u8>,
+}
+
+#[derive(Default)]
+pub struct PdfDocument {
+    pages: Vec<String>,
+    images: Vec<JpegImage>,
+}
+
+impl PdfDocument {
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Appends a blank page and returns its index.
+    pub fn add_page(&mut self) -> usize {
+        self.pages.push(String::new());
+        self.pages.len() - 1
+    }
+
+    #[must_use]
+    pub fn page_count(&self) -> usize {
+        self.pages.len()
+    }
+
+    /// Draws a single line of text with its baseline at (`x`, `y`).
+    #[allow(clippy::too_many_arguments)]
+    pub fn text(
+        &mut self,
+        page: usize,
+        x: f32,
+        y: f32,
+        font: Font,
+        size: f32,
+        color: Color,
+        text: &str,
+    ) {
+        let Some(content) = self.pages.get_mut(page) else {
+            return;
+        };
+        let _ = writeln!(
+            content,
+            "BT /{} {:.2} Tf {:.3} {:.3} {:.3} rg {:.2} {:.2} Td ({}) Tj ET",
+            font.resource_name(),
+            size,
+            color.0,
+            color.1,
+            color.2,
+            x,
+            y,
+            escape_text(text)
+        );
+    }
+
+    /// Draws a straight line.
+    #[allow(clippy::too_many_arguments)]
+    pub fn line(
+        &mut self,
+        page: usize,
+        x1: f32,
+        y1: f32,
+        x2: f32,
+        y2: f32,
+        width: f32,
+        color: Color,
+    ) {
+        self.polyline(page, &[(x1, y1), (x2, y2)], width, color);
+    }
+
+    /// Draws connected line segments through `points`.
+    pub fn polyline(&mut self, page: usize, points: &[(f32, f32)], width: f32, color: Color) {
+        let Some(content) = self.pages.get_mut(page) else {
+            return;
+        };
+        let Some(((first_x, first_y), rest)) = points.split_first() else {
+            return;
+        };
+        let _ = write!(
+            content,
+            "q {:.2} w {:.3} {:.3} {:.3} RG {:.2} {:.2} m",
+            width, color.0, color.1, color.2, first_x, first_y
+        );
+        for (x, y) in rest {
+            let _ = write!(content, " {x:.2} {y:.2} l");
+        }
+        let _ = writeln!(content, " S Q");
+    }
+
+    /// Draws a filled rectangle with its lower-left corner at (`x`, `y`).
+    pub fn fill_rect(&mut self, page: usize, x: f32, y: f32, w: f32, h: f32, color: Color) {
+        let Some(content) = self.pages.get_mut(page) else {
+            return;
+        };
+        let _ = writeln!(
+            content,
+            "q {:.3} {:.3} {:.3} rg {:.2} {:.2} {:.2} {:.2} re f Q",
+            color.0, color.1, color.2, x, y, w, h
+        );
+    }
+
+    /// Draws a rectangle outline with its lower-left corner at (`x`, `y`).
+    #[allow(clippy::too_many_arguments)]
+    pub fn stroke_rect(
+        &mut self,
+        page: usize,
+        x: f32,
+        y: f32,
+        w: f32,
+        h: f32,
+        width: f32,
+        color: Color,
+    ) {
+        let Some(content) = self.pages.get_mut(page) else {
+            return;
+        };
+        let _ = writeln!(
+            content,
+            "q {:.2} w {:.3} {:.3} {:.3} RG {:.2} {:.2} {:.2} {:.2} re S Q",
+            width, color.0, color.1, color.2, x, y, w, h
+        );
+    }
+
+    /// Registers baseline JPEG data for drawing and returns its image index.
+    pub fn add_jpeg(&mut self, width: u32, height: u32, data: Vec<u8>) -> usize {
+        self.images.push(JpegImage {
+            width,
+            height,
+            data,
+        });
+        self.images.len() - 1
+    }
+
+    /// Draws a registered image scaled to `w` x `h` with its lower-left corner at (`x`, `y`).
+    #[allow(clippy::too_many_arguments)]
+    pub fn draw_image(&mut self, page: usize, image: usize, x: f32, y: f32, w: f32, h: f32) {
+        if image >= self.images.len() {
+            return;
+        }
+        let Some(content) = self.pages.get_mut(page) else {
+            return;
+        };
+        let _ = writeln!(
+            content,
+            "q {w:.2} 0 0 {h:.2} {x:.2} {y:.2} cm /Im{image} Do Q"
+        );
+    }
+
+    /// Serializes the document to PDF bytes.
+    #[must_use]
+    pub fn to_bytes(&self) -> Vec<u8> {
+        const FIRST_IMAGE_OBJ: usize = 5;
+        let first_page_obj = FIRST_IMAGE_OBJ + self.images.len();
+
+        let mut out: Vec<u8> = b"%PDF-1.4\n%\xE2\xE3\xCF\xD3\n".to_vec();
+        let mut offsets: Vec<usize> = Vec::new();
+
+        let mut write_object = |out: &mut Vec<u8>, body: &[u8]| {
+            offsets.push(out.len());
+            out.extend_from_slice(format!("{} 0 obj\n", offsets.len()).as_bytes());
+            out.extend_from_slice(body);
+            out.extend_from_slice(b"\nendobj\n");
+        };
+
+        write_object(&mut out, b"<< /Type /Catalog /Pages 2 0 R >>");
+
+        let kids = (0..self.pages.len())
+            .map(|i| format!("{} 0 R", first_page_obj + i * 2))
+            .collect::<Vec<_>>()
+            .join(" ");
+        write_object(
+            &mut out,
+            format!(
+                "<< /Type /Pages /Kids [{kids}] /Count {} >>",
+                self.pages.len()
+            )
+            .as_bytes(),
+        );
+
+        write_object(
+            &mut out,
+            b"<< /Type /Font /Subtype /Type1 /BaseFont /Helvetica /Encoding /WinAnsiEncoding >>",
+        );
+        write_object(
+            &mut out,
+            b"<< /Type /Font /Subtype /Type1 /BaseFont /Helvetica-Bold /Encoding /WinAnsiEncoding >>",
+        );
+
+        for image in &self.images {
+            let mut body = format!(
+                "<< /Type /XObject /Subtype /Image /Width {} /Height {} /ColorSpace /DeviceRGB /BitsPerComponent 8 /Filter /DCTDecode /Length {} >>\nstream\n",
+                image.width,
+                image.height,
+                image.data.len()
+            )
+            .into_bytes();
+            body.extend_from_slice(&image.data);
+            body.extend_from_slice(b"\nendstream");
+            write_object(&mut out, &body);
+        }
+
+        let xobjects = (0..self.images.len())
+            .map(|i| format!("/Im{i} {} 0 R", FIRST_IMAGE_OBJ + i))
+            .collect::<Vec<_>>()
+            .join(" ");
+
+        for (page_index, content) in self.pages.iter().enumerate() {
+            let content_obj = first_page_obj + page_index * 2 + 1;
+            write_object(
+                &mut out,
+                format!(
+                    "<< /Type /Page /Parent 2 0 R /MediaBox [0 0 {PAGE_WIDTH} {PAGE_HEIGHT}] /Resources << /Font << /F1 3 0 R /F2 4 0 R >> /XObject << {xobjects} >> >> /Contents {content_obj} 0 R >>"
+                )
+                .as_bytes(),
+            );
+
+            let mut body = format!("<< /Length {} >>\nstream\n", content.len()).into_bytes();
+            body.extend_from_slice(content.as_bytes());
+            body.extend_from_slice(b"\nendstream");
+            write_object(&mut out, &body);
+        }
+
+        let xref_offset = out.len();
+        let mut xref = format!("xref\n0 {}\n0000000000 65535 f \n", offsets.len() + 1);
+        for offset in &offsets {
+            let _ = writeln!(xref, "{offset:010} 00000 n ");
+        }
+        let _ = write!(
+            xref,
+            "trailer\n<< /Size {} /Root 1 0 R >>\nstartxref\n{xref_offset}\n%%EOF\n",
+            offsets.len() + 1
+        );
+        out.extend_from_slice(xref.as_bytes());
+
+        out
+    }
+}
+
+/// Maps a character to its `WinAnsiEncoding` byte, if it has one.
+fn win_ansi_byte(c: char) -> Option<u8> {
+    match c {
+        ' '..='~' => u8::try_from(c).ok(),
+        '\u{a0}'..='\u{ff}' => u8::try_from(u32::from(c)).ok(),
+        '€' => Some(0x80),
+        '‘' => Some(0x91),
+        '’' => Some(0x92),
+        '“' => Some(0x93),
+        '”' => Some(0x94),
+        '•' => Some(0x95),
+        '–' => Some(0x96),
+        '—' => Some(0x97),
+        _ => None,
+    }
+}
+
+/// Escapes text for use in a PDF literal string.
+///
+/// The content stream is kept ASCII, so `WinAnsiEncoding` bytes above 0x7E are written as octal escapes.
+fn escape_text(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '(' | ')' | '\\' => {
+                escaped.push('\\');
+                escaped.push(c);
+            }
+            '\t' | '\n' | '\r' => escaped.push(' '),
+            _ => match win_ansi_byte(c) {
+                Some(byte) if byte > 0x7e => {
+                    let _ = write!(escaped, "\\{byte:03o}");
+                }
+                Some(byte) => escaped.push(char::from(byte)),
+                None => escaped.push('?'),
+            },
+        }
+    }
+    escaped
+}
+
+/// Approximates the rendered width of `text` in points.
+///
+/// Uses per-class Helvetica glyph widths, which is accurate enough for wrapping and alignment.
+#[must_use]
+pub fn text_width(text: &str, font: Font, size: f32) -> f32 {
+    let em: f32 = text
+        .chars()
+        .map(|c| match c {
+            'i' | 'j' | 'l' | '.' | ',' | ':' | ';' | '\'' | '|' | '!' => 0.24,
+            ' ' | 'f' | 't' | 'r' | 'I' | '(' | ')' | '-' | '/' => 0.32,
+            'm' | 'w' | 'M' | 'W' | '@' => 0.85,
+            'A'..='Z' => 0.68,
+            _ => 0.56,
+        })
+        .sum();
+    let weight = match font {
+        Font::Regular => 1.0,
+        Font::Bold => 1.06,
+    };
+    em * size * weight
+}
+
+/// Splits `text` into lines no wider than `max_width`, breaking on whitespace where possible.
+#[must_use]
+pub fn wrap_text(text: &str, font: Font, size: f32, max_width: f32) -> Vec<String> {
+    let mut lines = Vec::new();
+    let mut current = String::new();
+
+    for word in text.split_whitespace() {
+        let candidate = if current.is_empty() {
+            word.to_string()
+        } else {
+            format!("{current} {word}")
+        };
+
+        if text_width(&candidate, font, size) <= max_width {
+            current = candidate;
+            continue;
+        }
+
+        if !current.is_empty() {
+            lines.push(std::mem::take(&mut current));
+        }
+
+        // Hard-break words that are wider than a full line
+        let mut chunk = String::new();
+        for c in word.chars() {
+            chunk.push(c);
+            if text_width(&chunk, font, size) > max_width && chunk.chars().count() > 1 {
+                chunk.pop();
+                lines.push(std::mem::take(&mut chunk));
+                chunk.push(c);
+            }
+        }
+        current = chunk;
+    }
+
+    if !current.is_empty() || lines.is_empty() {
+        lines.push(current);
+    }
+
+    lines
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_escape_text_special_characters() {
+        assert_eq!(escape_text("a (b) \\ c"), "a \\(b\\) \\\\ c");
+        assert_eq!(escape_text("4°C"), "4\\260C");
+        assert_eq!(escape_text("日本"), "??");
+    }
+
+    #[test]
+    fn test_wrap_text_respects_width() {
+        let text = "The quick brown fox jumps over the lazy dog";
+        let lines = wrap_text(text, Font::Regular, 10.0, 80.0);
+        assert!(lines.len() > 1);
+        for line in &lines {
+            assert!(text_width(line, Font::Regular, 10.0) <= 80.0);
+        }
+        assert_eq!(lines.join(" "), text);
+    }
+
+    #[test]
+    fn test_wrap_text_breaks_long_words() {
+        let lines = wrap_text(&"x".repeat(200), Font::Regular, 10.0, 50.0);
+        assert!(lines.len() > 1);
+        assert_eq!(lines.concat().len(), 200);
+    }
+
+    #[test]
+    fn test_to_bytes_structure() {
+        let mut doc = PdfDocument::new();
+        let page = doc.add_page();
+        doc.text(page, 50.0, 800.0, Font::Bold, 12.0, Color::BLACK, "Report");
+        doc.line(page, 50.0, 790.0, 545.0, 790.0, 1.0, Color::GREY);
+        doc.add_page();
+
+        let bytes = doc.to_bytes();
+        let text = String::from_utf8_lossy(&bytes);
+        assert!(text.starts_with("%PDF-1.4"));
+        assert!(text.trim_end().ends_with("%%EOF"));
+        assert!(text.contains("/Count 2"));
+        assert!(text.contains("(Report) Tj"));
+
+        // startxref must point at the xref table
+        let startxref = text.rfind("startxref\n").unwrap();
+        let offset: usize = text[startxref + 10..]
+            .lines()
+            .next()
+            .unwrap()
+            .parse()
+            .unwrap();
+        assert!(bytes[offset..].starts_with(b"xref"));
+    }
+}
@@ -0,0 +1,339 @@
+use crate::{
+    AppState,
+    attachments_db::{self, AttachmentDocument},
+    db::UserRecord,
+    logs_db,
+    services::{LogEntryService, company_service::infer_content_type},
+    try_db,
+    utils::{ServiceError, svc_err, svc_err_bad_request, svc_err_forbidden, svc_err_not_found},
+};
+use axum::http::StatusCode;
+
+#[cfg(test)]
+mod attachment_service_tests {
+    use super::*;
+    use crate::db::UserRole;
+
+    fn user(id: &str, role: UserRole, branch_id: Option<&str>) -> UserRecord {
+        UserRecord {
+            id: id.to_string(),
+            email: "user@example.com".to_string(),
+            first_name: "Test".to_string(),
+            last_name: "User".to_string(),
+            password_hash: None,
+            company_id: Some("company-1".to_string()),
+            branch_id: branch_id.map(str::to_string),
+            company_name: None,
+            company_deleted_at: None,
+            role,
+            created_at: chrono::Utc::now(),
+            deleted_at: None,
+            oauth_provider: None,
+            oauth_subject: None,
+            oauth_picture: None,
+            profile_picture_id: None,
+        }
+    }
+
+    fn entry(status: logs_db::LogStatus) -> logs_db::LogEntry {
+        let now = chrono::Utc::now();
+        logs_db::LogEntry {
+            entry_id: "entry-1".to_string(),
+            template_id: "template-1".to_string(),
+            template_name: "Deliveries".to_string(),
+            company_id: "company-1".to_string(),
+            branch_id: Some("branch-1".to_string()),
+            user_id: "staff-1".to_string(),
+            entry_data: serde_json::json!({}),
+            created_at: now,
+            updated_at: now,
+            submitted_at: None,
+            status,
+            period: "01/03/2026".to_string(),
+            template_version: None,
+            flagged: false,
+            corrective_action: None,
+            review: None,
+        }
+    }
+
+    #[test]
+    fn test_infer_attachment_type() {
+        assert_eq!(
+            infer_attachment_type(b"%PDF-1.7\n..."),
+            Some("application/pdf".to_string())
+        );
+        assert_eq!(
+            infer_attachment_type(&[0xFF, 0xD8, 0xFF, 0xE0]),
+            Some("image/jpeg".to_string())
+        );
+        assert_eq!(infer_attachment_type(b"MZ\x90\x00"), None);
+        assert_eq!(infer_attachment_type(b"<svg></svg>"), None);
+    }
+
+    #[test]
+    fn test_sanitize_filename() {
+        assert_eq!(sanitize_filename(Some("photo.jpg")), "photo.jpg");
+        assert_eq!(
+            sanitize_filename(Some("C:\\Users\\me\\damaged box.png")),
+            "damaged box.png"
+        );
+        assert_eq!(sanitize_filename(Some("../../etc/\"passwd\"")), "passwd");
+        assert_eq!(sanitize_filename(Some("   ")), "attachment");
+        assert_eq!(sanitize_filename(None), "attachment");
+        assert_eq!(sanitize_filename(Some(&"a".repeat(300))).len(), 255);
+    }
+
+    #[test]
+    fn test_ensure_can_modify() {
+        let draft = entry(logs_db::LogStatus::Draft);
+        let submitted = entry(logs_db::LogStatus::Submitted);
+
+        let author = user("staff-1", UserRole::Staff, Some("branch-1"));
+        assert!(ensure_can_modify(&author, &draft).is_ok());
+        assert!(ensure_can_modify(&author, &submitted).is_err());
+
+        let colleague = user("staff-2", UserRole::Staff, Some("branch-1"));
+        assert!(ensure_can_modify(&colleague, &draft).is_err());
+
+        let manager = user("manager-1", UserRole::BranchManager, Some("branch-1"));
+        assert!(ensure_can_modify(&manager, &submitted).is_ok());
+        let other_manager = user("manager-2", UserRole::BranchManager, Some("branch-2"));
+        assert!(ensure_can_modify(&other_manager, &submitted).is_err());
+
+        let hq = user("hq-1", UserRole::Staff, None);
+        assert!(ensure_can_modify(&hq, &draft).is_err());
+    }
+}
+
+pub struct AttachmentService;
+
+/// Maximum size of a single attachment, in bytes.
+pub const MAX_ATTACHMENT_BYTES: usize = 20 * 1024 * 1024;
+
+/// Request body limit for attachment uploads. It leaves headroom above
+/// `MAX_ATTACHMENT_BYTES` so a slightly oversized file reaches the exact size check.
+pub const MAX_ATTACHMENT_REQUEST_BYTES: usize = MAX_ATTACHMENT_BYTES + 1024 * 1024;
+
+/// Attachment storage allowed per company when `COMPANY_ATTACHMENT_QUOTA_MB` is not set.
+const DEFAULT_COMPANY_QUOTA_MB: i64 = 500;
+
+/// Maximum length of a stored filename, in characters.
+const MAX_FILENAME_LENGTH: usize = 255;
+
+/// Attachment storage allowed per company, in bytes.
+#[must_use]
+pub fn company_quota_bytes() -> i64 {
+    std::env::var("COMPANY_ATTACHMENT_QUOTA_MB")
+        .ok()
+        .and_then(|v| v.parse::<i64>().ok())
+        .filter(|mb| *mb > 0)
+        .unwrap_or(DEFAULT_COMPANY_QUOTA_MB)
+        .saturating_mul(1024 * 1024)
+}
+
+/// Identifies an attachment from its contents. Only images and PDF documents are accepted;
+/// the client's declared type is never trusted.
+fn infer_attachment_type(data: &[u8]) -> Option<String> {
+    if data.starts_with(b"%PDF-") {
+        return Some("application/pdf".to_string());
+    }
+    Some(infer_content_type(data)).filter(|t| t.starts_with("image/"))
+}
+
+/// Keeps only the final path component of a client-supplied filename and strips characters
+/// that would break a `Content-Disposition` header.
+fn sanitize_filename(filename: Option<&str>) -> String {
+    let name = filename
+        .unwrap_or_default()
+        .rsplit(['/', '\\'])
+        .next()
+        .unwrap_or_default();
+    let name: String = name
+        .chars()
+        .filter(|c| !c.is_control() && *c != '"')
+        .take(MAX_FILENAME_LENGTH)
+        .collect();
+    let name = name.trim();
+    if name.is_empty() || name == "." || name == ".." {
+        "attachment".to_string()
+    } else {
+        name.to_string()
+    }
+}
+
+/// Authors may change attachments while their entry is a draft; managers of the entry's
+/// branch may add evidence at any time.
+fn ensure_can_modify(user: &UserRecord, entry: &logs_db::LogEntry) -> Result<(), ServiceError> {
+    if entry.user_id == user.id && entry.status == logs_db::LogStatus::Draft {
+        return Ok(());
+    }
+
+    let manages_entry = user.can_manage_branch()
+        && user.company_id.as_deref() == Some(entry.company_id.as_str())
+        && (!user.is_branch_manager() || entry.branch_id == user.branch_id);
+    if manages_entry {
+        return Ok(());
+    }
+
+    if entry.user_id == user.id {
+        return Err(svc_err(
+            StatusCode::CONFLICT,
+            "Attachments can only be changed while the entry is a draft",
+        ));
+    }
+    Err(svc_err_forbidden(
+        "You do not have permission to change attachments on this entry",
+    ))
+}
+
+impl AttachmentService {
+    /// Attaches a file to a log entry. Its size is reserved against the company quota before
+    /// it is stored, so concurrent uploads cannot exceed the quota.
+    ///
+    /// # Errors
+    /// Returns an error if the entry is not found, the user cannot change it, the file is
+    /// empty, too large or of an unsupported type, the company quota would be exceeded, or
+    /// the upload fails.
+    pub async fn upload_attachment(
+        state: &AppState,
+        user: &UserRecord,
+        entry_id: &str,
+        filename: Option<&str>,
+        data: &[u8],
+    ) -> Result<AttachmentDocument, ServiceError> {
+        let entry = LogEntryService::get_log_entry(state, user, entry_id).await?;
+        ensure_can_modify(user, &entry)?;
+
+        if data.is_empty() {
+            return Err(svc_err_bad_request("No file provided"));
+        }
+        if data.len() > MAX_ATTACHMENT_BYTES {
+            return Err(svc_err(
+                StatusCode::PAYLOAD_TOO_LARGE,
+                "File too large. Maximum size is 20MB",
+            ));
+        }
+        let content_type = infer_attachment_type(data).ok_or_else(|| {
+            svc_err_bad_request("Attachments must be PNG, JPEG or WebP images or PDF documents")
+        })?;
+
+        let size = i64::try_from(data.len()).unwrap_or(i64::MAX);
+        let reserved = try_db!(
+            attachments_db::reserve_attachment_storage(
+                &state.mongodb,
+                &entry.company_id,
+                size,
+                company_quota_bytes()
+            ),
+            "reserving attachment storage"
+        )?;
+        if !reserved {
+            return Err(svc_err(
+                StatusCode::PAYLOAD_TOO_LARGE,
+                "Your company's attachment storage is full. Delete unused attachments and try again",
+            ));
+        }
+
+        let attachment = AttachmentDocument {
+            attachment_id: uuid::Uuid::new_v4().to_string(),
+            entry_id: entry.entry_id,
+            company_id: entry.company_id,
+            filename: sanitize_filename(filename),
+            content_type,
+            size,
+            chunk_count: i32::try_from(data.len().div_ceil(attachments_db::CHUNK_SIZE))
+                .unwrap_or(i32::MAX),
+            uploaded_by: user.id.clone(),
+            uploaded_at: chrono::Utc::now(),
+        };
+        let stored = try_db!(
+            attachments_db::store_attachment(&state.mongodb, &attachment, data),
+            "storing attachment"
+        );
+        if stored.is_err()
+            && let Err(e) = attachments_db::release_attachment_storage(
+                &state.mongodb,
+                &attachment.company_id,
+                size,
+            )
+            .await
+        {
+            tracing::error!("Failed to release attachment storage: {:?}", e);
+        }
+        stored?;
+        Ok(attachment)
+    }
+
+    /// Lists the attachments of an entry the user can view, with the company's storage
+    /// usage in bytes.
+    ///
+    /// # Errors
+    /// Returns an error if the entry is not found, the user cannot view it, or the query fails.
+    pub async fn list_attachments(
+        state: &AppState,
+        user: &UserRecord,
+        entry_id: &str,
+    ) -> Result<(Vec<AttachmentDocument>, i64), ServiceError> {
+        let entry = LogEntryService::get_log_entry(state, user, entry_id).await?;
+
+        let attachments = try_db!(
+            attachments_db::get_entry_attachments(&state.mongodb, entry_id),
+            "fetching attachments"
+        )?;
+        let used = try_db!(
+            attachments_db::get_company_attachment_usage(&state.mongodb, &entry.company_id),
+            "checking attachment storage"
+        )?;
+        Ok((attachments, used))
+    }
+
+    /// Downloads an attachment of an entry the user can view.
+    ///
+    /// # Errors
+    /// Returns an error if the entry or attachment is not found, the user cannot view the
+    /// entry, or the file cannot be read.
+    pub async fn download_attachment(
+        state: &AppState,
+        user: &UserRecord,
+        entry_id: &str,
+        attachment_id: &str,
+    ) -> Result<(AttachmentDocument, Vec<u8>), ServiceError> {
+        LogEntryService::get_log_entry(state, user, entry_id).await?;
+
+        let attachment = try_db!(
+            attachments_db::get_attachment(&state.mongodb, entry_id, attachment_id),
+            "fetching attachment"
+        )?
+        .ok_or_else(|| svc_err_not_found("Attachment not found"))?;
+        let data = try_db!(
+            attachments_db::read_attachment(&state.mongodb, &attachment),
+            "reading attachment"
+        )?;
+        Ok((attachment, data))
+    }
+
+    /// Removes an attachment from an entry.
+    ///
+    /// # Errors
+    /// Returns an error if the entry or attachment is not found, the user cannot change the
+    /// entry, or the deletion fails.
+    pub async fn delete_attachment(
+        state: &AppState,
+        user: &UserRecord,
+        entry_id: &str,
+        attachment_id: &str,
+    ) -> Result<(), ServiceError> {
+        let entry = LogEntryService::get_log_entry(state, user, entry_id).await?;
+        ensure_can_modify(user, &entry)?;
+
+        let deleted = try_db!(
+            attachments_db::delete_attachment(&state.mongodb, entry_id, attachment_id),
+            "deleting attachment"
+        )?;
+        if !deleted {
+            return Err(svc_err_not_found("Attachment not found"));
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,482 @@
+use crate::{
+    AppState,
+    db::{self, BranchCalendar, BranchClosure, BranchOpeningHours, UserRecord},
+    logs_db, try_db,
+    utils::{ServiceError, svc_err, svc_err_bad_request, svc_err_forbidden, svc_err_not_found},
+};
+use axum::http::StatusCode;
+use chrono::NaiveDate;
+
+#[cfg(test)]
+mod branch_calendar_service_tests {
+    use super::*;
+    use crate::db::UserRole;
+
+    fn user(role: UserRole, branch_id: Option<&str>) -> UserRecord {
+        UserRecord {
+            id: "user-1".to_string(),
+            email: "user@example.com".to_string(),
+            first_name: "Test".to_string(),
+            last_name: "User".to_string(),
+            password_hash: None,
+            company_id: Some("company-1".to_string()),
+            branch_id: branch_id.map(str::to_string),
+            company_name: None,
+            company_deleted_at: None,
+            role,
+            created_at: chrono::Utc::now(),
+            deleted_at: None,
+            oauth_provider: None,
+            oauth_subject: None,
+            oauth_picture: None,
+            profile_picture_id: None,
+        }
+    }
+
+    fn date(y: i32, m: u32, d: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(y, m, d).unwrap()
+    }
+
+    #[test]
+    fn test_ensure_can_modify() {
+        assert!(ensure_can_modify(&user(UserRole::CompanyManager, None), "branch-1").is_ok());
+        assert!(
+            ensure_can_modify(&user(UserRole::BranchManager, Some("branch-1")), "branch-1").is_ok()
+        );
+        assert!(
+            ensure_can_modify(&user(UserRole::BranchManager, Some("branch-2")), "branch-1")
+                .is_err()
+        );
+        assert!(ensure_can_modify(&user(UserRole::Staff, Some("branch-1")), "branch-1").is_err());
+    }
+
+    #[test]
+    fn test_parse_opening_hours() {
+        let hours =
+            parse_opening_hours("branch-1", &[(1, "09:00", "17:30"), (6, "10:00", "16:00")])
+                .unwrap();
+        assert_eq!(hours.len(), 2);
+        assert_eq!(hours[0].day_of_week, 1);
+        assert_eq!(
+            hours[0].closes_at,
+            chrono::NaiveTime::from_hms_opt(17, 30, 0).unwrap()
+        );
+
+        assert!(parse_opening_hours("branch-1", &[]).unwrap().is_empty());
+        assert!(parse_opening_hours("branch-1", &[(7, "09:00", "17:00")]).is_err());
+        assert!(parse_opening_hours("branch-1", &[(1, "17:00", "09:00")]).is_err());
+        assert!(parse_opening_hours("branch-1", &[(1, "9am", "17:00")]).is_err());
+        assert!(
+            parse_opening_hours("branch-1", &[(1, "09:00", "12:00"), (1, "13:00", "17:00")])
+                .is_err()
+        );
+    }
+
+    #[test]
+    fn test_validate_closure() {
+        assert_eq!(
+            validate_closure(date(2026, 12, 25), None, " Christmas ").unwrap(),
+            (date(2026, 12, 25), "Christmas".to_string())
+        );
+        assert!(validate_closure(date(2026, 12, 26), Some(date(2026, 12, 25)), "Boxing").is_err());
+        assert!(validate_closure(date(2026, 12, 25), None, "   ").is_err());
+        assert!(validate_closure(date(2026, 1, 1), Some(date(2027, 6, 1)), "Refit").is_err());
+    }
+
+    #[test]
+    fn test_parse_ical_closures() {
+        let ics = "BEGIN:VCALENDAR\r\n\
+            VERSION:2.0\r\n\
+            BEGIN:VEVENT\r\n\
+            UID:christmas-2026@example.com\r\n\
+            DTSTART;VALUE=DATE:20261225\r\n\
+            DTEND;VALUE=DATE:20261227\r\n\
+            SUMMARY:Christmas\\, Boxing Day\r\n\
+            END:VEVENT\r\n\
+            BEGIN:VEVENT\r\n\
+            UID:refit\r\n\
+            DTSTART;VALUE=DATE:20260301\r\n\
+            SUMMARY:Kitchen refit and deep\r\n  clean\r\n\
+            END:VEVENT\r\n\
+            BEGIN:VEVENT\r\n\
+            UID:meeting\r\n\
+            DTSTART;TZID=Europe/London:20260302T230000\r\n\
+            DTEND;TZID=Europe/London:20260303T000000\r\n\
+            SUMMARY:Team meeting\r\n\
+            END:VEVENT\r\n\
+            BEGIN:VEVENT\r\n\
+            UID:weekly\r\n\
+            DTSTART;VALUE=DATE:20260105\r\n\
+            RRULE:FREQ=WEEKLY\r\n\
+            SUMMARY:Closed Mondays\r\n\
+            END:VEVENT\r\n\
+            BEGIN:VEVENT\r\n\
+            SUMMARY:No start date\r\n\
+            END:VEVENT\r\n\
+            END:VCALENDAR\r\n";
+
+        let (closures, skipped) = parse_ical_closures(ics);
+        assert_eq!(
+            skipped, 3,
+            "timed, recurring and undated events are skipped"
+        );
+        assert_eq!(closures.len(), 2);
+
+        assert_eq!(closures[0].uid, "christmas-2026@example.com");
+        assert_eq!(closures[0].starts_on, date(2026, 12, 25));
+        assert_eq!(closures[0].ends_on, date(2026, 12, 26));
+        assert_eq!(closures[0].reason, "Christmas, Boxing Day");
+
+        assert_eq!(closures[1].starts_on, date(2026, 3, 1));
+        assert_eq!(closures[1].ends_on, date(2026, 3, 1));
+        assert_eq!(closures[1].reason, "Kitchen refit and deep clean");
+    }
+}
+
+pub struct BranchCalendarService;
+
+/// Maximum size of an imported iCalendar file, in bytes.
+pub const MAX_ICAL_BYTES: usize = 1024 * 1024;
+
+/// Maximum length of a closure reason, in characters.
+const MAX_REASON_LENGTH: usize = 200;
+
+/// Longest closure that can be recorded, in days.
+const MAX_CLOSURE_DAYS: i64 = 366;
+
+/// Company managers may change any branch's calendar; branch managers only their own.
+fn ensure_can_modify(user: &UserRecord, branch_id: &str) -> Result<(), ServiceError> {
+    if user.can_manage_company()
+        || (user.is_branch_manager() && user.branch_id.as_deref() == Some(branch_id))
+    {
+        return Ok(());
+    }
+    Err(svc_err_forbidden(
+        "You do not have permission to change this branch's calendar",
+    ))
+}
+
+fn parse_time(time: &str) -> Option<chrono::NaiveTime> {
+    let (hours, minutes) = logs_db::parse_time_string(time)?;
+    chrono::NaiveTime::from_hms_opt(hours, minutes, 0)
+}
+
+/// Validates weekly opening hours given as `(day_of_week, opens_at, closes_at)`, with days
+/// numbered from 0 = Sunday and times as `HH:MM`.
+fn parse_opening_hours(
+    branch_id: &str,
+    days: &[(u8, &str, &str)],
+) -> Result<Vec<BranchOpeningHours>, ServiceError> {
+    let mut hours: Vec<BranchOpeningHours> = Vec::with_capacity(days.len());
+    for &(day_of_week, opens_at, closes_at) in days {
+        if day_of_week > 6 {
+            return Err(svc_err_bad_request(
+                "Day of week must be between 0 (Sunday) and 6 (Saturday)",
+            ));
+        }
+        let day_of_week = i16::from(day_of_week);
+        if hours.iter().any(|h| h.day_of_week == day_of_week) {
+            return Err(svc_err_bad_request(
+                "Each day of the week can only appear once",
+            ));
+        }
+        let (Some(opens_at), Some(closes_at)) = (parse_time(opens_at), parse_time(closes_at))
+        else {
+            return Err(svc_err_bad_request("Opening times must be in HH:MM format"));
+        };
+        if opens_at >= closes_at {
+            return Err(svc_err_bad_request(
+                "Opening time must be before closing time",
+            ));
+        }
+        hours.push(BranchOpeningHours {
+            branch_id: branch_id.to_string(),
+            day_of_week,
+            opens_at,
+            closes_at,
+        });
+    }
+    hours.sort_by_key(|h| h.day_of_week);
+    Ok(hours)
+}
+
+/// Validates a closure's dates and reason, returning the last closed day and trimmed reason.
+fn validate_closure(
+    starts_on: NaiveDate,
+    ends_on: Option<NaiveDate>,
+    reason: &str,
+) -> Result<(NaiveDate, String), ServiceError> {
+    let ends_on = ends_on.unwrap_or(starts_on);
+    if ends_on < starts_on {
+        return Err(svc_err_bad_request("Closure cannot end before it starts"));
+    }
+    if (ends_on - starts_on).num_days() >= MAX_CLOSURE_DAYS {
+        return Err(svc_err_bad_request("Closures cannot be longer than a year"));
+    }
+
+    let reason = reason.trim();
+    if reason.is_empty() {
+        return Err(svc_err_bad_request("Closure reason is required"));
+    }
+    if reason.chars().count() > MAX_REASON_LENGTH {
+        return Err(svc_err_bad_request(
+            "Closure reason cannot be longer than 200 characters",
+        ));
+    }
+    Ok((ends_on, reason.to_string()))
+}
+
+fn unescape_ical_text(value: &str) -> String {
+    let mut text = String::with_capacity(value.len());
+    let mut chars = value.chars();
+    while let Some(c) = chars.next() {
+        if c != '\\' {
+            text.push(c);
+            continue;
+        }
+        match chars.next() {
+            Some('n' | 'N') => text.push(' '),
+            Some(escaped) => text.push(escaped),
+            None => {}
+        }
+    }
+    text
+}
+
+/// Parses a date-only (`VALUE=DATE`) value. Date-times are not dates and return `None`.
+fn parse_ical_date(value: &str) -> Option<NaiveDate> {
+    if value.len() != 8 {
+        return None;
+    }
+    NaiveDate::parse_from_str(value, "%Y%m%d").ok()
+}
+
+/// Reads all-day events from an iCalendar file as closures. Timed and recurring events,
+/// events without a start date and events that fail validation are skipped and counted.
+fn parse_ical_closures(ics: &str) -> (Vec<db::ImportedClosure>, usize) {
+    // Continuation lines start with a space or tab and belong to the previous line.
+    let mut lines: Vec<String> = Vec::new();
+    for line in ics.lines() {
+        match (line.strip_prefix([' ', '\t']), lines.last_mut()) {
+            (Some(rest), Some(previous)) => previous.push_str(rest),
+            _ => lines.push(line.to_string()),
+        }
+    }
+
+    let mut closures = Vec::new();
+    let mut skipped = 0;
+    let mut event: Option<Vec<(String, String)>> = None;
+    for line in lines {
+        let Some((name, value)) = line.split_once(':') else {
+            continue;
+        };
+        let name = name
+            .split(';')
+            .next()
+            .unwrap_or_default()
+            .to_ascii_uppercase();
+        match (name.as_str(), value.trim()) {
+            ("BEGIN", v) if v.eq_ignore_ascii_case("VEVENT") => event = Some(Vec::new()),
+            ("END", v) if v.eq_ignore_ascii_case("VEVENT") => {
+                let Some(properties) = event.take() else {
+                    continue;
+                };
+                match closure_from_event(&properties) {
+                    Some(closure) => closures.push(closure),
+                    None => skipped += 1,
+                }
+            }
+            (_, v) => {
+                if let Some(properties) = event.as_mut() {
+                    properties.push((name, v.to_string()));
+                }
+            }
+        }
+    }
+    (closures, skipped)
+}
+
+fn closure_from_event(properties: &[(String, String)]) -> Option<db::ImportedClosure> {
+    let get = |name: &str| {
+        properties
+            .iter()
+            .find(|(n, _)| n == name)
+            .map(|(_, v)| v.as_str())
+    };
+    if get("RRULE").is_some() {
+        return None;
+    }
+
+    // Timed events such as meetings do not close the branch for the day
+    let starts_on = parse_ical_date(get("DTSTART")?)?;
+    let ends_on = match get("DTEND") {
+        // An all-day event's end is exclusive
+        Some(end) => parse_ical_date(end)?.pred_opt()?,
+        None => starts_on,
+    }
+    .max(starts_on);
+
+    let reason = get("SUMMARY")
+        .map(unescape_ical_text)
+        .filter(|s| !s.trim().is_empty())
+        .unwrap_or_else(|| "Closed".to_string());
+    let (ends_on, reason) = validate_closure(starts_on, Some(ends_on), &reason).ok()?;
+
+    let uid = get("UID").map_or_else(|| format!("{starts_on}/{ends_on}/{reason}"), str::to_string);
+    Some(db::ImportedClosure {
+        uid,
+        starts_on,
+        ends_on,
+        reason,
+    })
+}
+
+impl BranchCalendarService {
+    /// Loads a branch of the user's company.
+    async fn get_branch(
+        state: &AppState,
+        user: &UserRecord,
+        branch_id: &str,
+    ) -> Result<db::Branch, ServiceError> {
+        let branch = try_db!(
+            db::get_branch_by_id(&state.postgres, branch_id),
+            "fetching branch"
+        )?
+        .ok_or_else(|| svc_err_not_found("Branch not found"))?;
+
+        if user.company_id.as_deref() != Some(branch.company_id.as_str()) {
+            return Err(svc_err_not_found("Branch not found"));
+        }
+        Ok(branch)
+    }
+
+    /// Retrieves the opening hours and closures of a branch of the user's company.
+    ///
+    /// # Errors
+    /// Returns an error if the branch is not found or the query fails.
+    pub async fn get_calendar(
+        state: &AppState,
+        user: &UserRecord,
+        branch_id: &str,
+    ) -> Result<BranchCalendar, ServiceError> {
+        Self::get_branch(state, user, branch_id).await?;
+        try_db!(
+            db::get_branch_calendar(&state.postgres, branch_id),
+            "fetching branch calendar"
+        )
+    }
+
+    /// Replaces the weekly opening hours of a branch, given as `(day_of_week, opens_at,
+    /// closes_at)`. An empty list leaves the branch open every day.
+    ///
+    /// # Errors
+    /// Returns an error if the branch is not found, the user cannot change it, the hours are
+    /// invalid, or the update fails.
+    pub async fn set_opening_hours(
+        state: &AppState,
+        user: &UserRecord,
+        branch_id: &str,
+        days: &[(u8, &str, &str)],
+    ) -> Result<BranchCalendar, ServiceError> {
+        Self::get_branch(state, user, branch_id).await?;
+        ensure_can_modify(user, branch_id)?;
+
+        let hours = parse_opening_hours(branch_id, days)?;
+        try_db!(
+            db::replace_branch_opening_hours(&state.postgres, branch_id, &hours),
+            "updating opening hours"
+        )?;
+        try_db!(
+            db::get_branch_calendar(&state.postgres, branch_id),
+            "fetching branch calendar"
+        )
+    }
+
+    /// Closes a branch from `starts_on` to `ends_on` inclusive. A missing end date closes a
+    /// single day.
+    ///
+    /// # Errors
+    /// Returns an error if the branch is not found, the user cannot change it, the closure is
+    /// invalid, or the insert fails.
+    pub async fn add_closure(
+        state: &AppState,
+        user: &UserRecord,
+        branch_id: &str,
+        starts_on: NaiveDate,
+        ends_on: Option<NaiveDate>,
+        reason: &str,
+    ) -> Result<BranchClosure, ServiceError> {
+        Self::get_branch(state, user, branch_id).await?;
+        ensure_can_modify(user, branch_id)?;
+
+        let (ends_on, reason) = validate_closure(starts_on, ends_on, reason)?;
+        try_db!(
+            db::upsert_branch_closure(
+                &state.postgres,
+                branch_id,
+                starts_on,
+                ends_on,
+                &reason,
+                None
+            ),
+            "creating closure"
+        )
+    }
+
+    /// Imports closures from an iCalendar file. Events imported before are updated rather
+    /// than duplicated. Returns the number of events imported and skipped.
+    ///
+    /// # Errors
+    /// Returns an error if the branch is not found, the user cannot change it, the file is
+    /// too large or has no events, or saving fails.
+    pub async fn import_closures(
+        state: &AppState,
+        user: &UserRecord,
+        branch_id: &str,
+        ics: &str,
+    ) -> Result<(usize, usize), ServiceError> {
+        Self::get_branch(state, user, branch_id).await?;
+        ensure_can_modify(user, branch_id)?;
+
+        if ics.len() > MAX_ICAL_BYTES {
+            return Err(svc_err(
+                StatusCode::PAYLOAD_TOO_LARGE,
+                "Calendar file too large. Maximum size is 1MB",
+            ));
+        }
+        if !ics.trim_start().starts_with("BEGIN:VCALENDAR") {
+            return Err(svc_err_bad_request("File is not an iCalendar file"));
+        }
+
+        let (closures, skipped) = parse_ical_closures(ics);
+        try_db!(
+            db::import_branch_closures(&state.postgres, branch_id, &closures),
+            "importing closures"
+        )?;
+        Ok((closures.len(), skipped))
+    }
+
+    /// Removes a closure from a branch.
+    ///
+    /// # Errors
+    /// Returns an error if the branch or closure is not found, the user cannot change the
+    /// branch, or the deletion fails.
+    pub async fn delete_closure(
+        state: &AppState,
+        user: &UserRecord,
+        branch_id: &str,
+        closure_id: &str,
+    ) -> Result<(), ServiceError> {
+        Self::get_branch(state, user, branch_id).await?;
+        ensure_can_modify(user, branch_id)?;
+
+        let deleted = try_db!(
+            db::delete_branch_closure(&state.postgres, branch_id, closure_id),
+            "deleting closure"
+        )?;
+        if !deleted {
+            return Err(svc_err_not_found("Closure not found"));
+        }
+        Ok(())
+    }
+}
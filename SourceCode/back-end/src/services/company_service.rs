@@ -1,7 +1,7 @@
 use crate::{db, images_db};
 use sqlx::PgPool;
 
-fn infer_content_type(data: &[u8]) -> String {
+pub(crate) fn infer_content_type(data: &[u8]) -> String {
     if data.starts_with(&[0x89, 0x50, 0x4E, 0x47]) {
         "image/png".to_string()
     } else if data.starts_with(&[0xFF, 0xD8, 0xFF]) {
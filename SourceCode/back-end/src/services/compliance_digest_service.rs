@@ -0,0 +1,557 @@
+use crate::{
+    AppState,
+    db::{self, DigestFrequency, DigestSubscriber, UserRole},
+    email::{self, BranchCompliance, ComplianceDigest, TemplateCompliance},
+    logs_db, try_db,
+    utils::ServiceError,
+};
+use chrono::{DateTime, Datelike, NaiveDate, Timelike, Utc};
+use std::collections::{BTreeMap, HashMap, HashSet};
+
+#[cfg(test)]
+mod compliance_digest_service_tests {
+    use super::*;
+
+    fn at(y: i32, m: u32, d: u32, h: u32) -> DateTime<Utc> {
+        NaiveDate::from_ymd_opt(y, m, d)
+            .and_then(|date| date.and_hms_opt(h, 0, 0))
+            .expect("valid date")
+            .and_utc()
+    }
+
+    #[test]
+    fn test_digest_window() {
+        // Tuesday 3 March 2026
+        let now = at(2026, 3, 3, 8);
+        let yesterday = NaiveDate::from_ymd_opt(2026, 3, 2).expect("valid date");
+
+        let london = chrono_tz::Europe::London;
+
+        assert_eq!(
+            digest_window(DigestFrequency::Daily, now, london),
+            (yesterday, yesterday)
+        );
+        assert_eq!(
+            digest_window(DigestFrequency::Weekly, now, london),
+            (
+                NaiveDate::from_ymd_opt(2026, 2, 24).expect("valid date"),
+                yesterday
+            )
+        );
+
+        // 20:00 UTC on Tuesday is already Wednesday morning in Auckland
+        let today = NaiveDate::from_ymd_opt(2026, 3, 3).expect("valid date");
+        assert_eq!(
+            digest_window(
+                DigestFrequency::Daily,
+                at(2026, 3, 3, 20),
+                chrono_tz::Pacific::Auckland
+            ),
+            (today, today)
+        );
+    }
+
+    #[test]
+    fn test_is_digest_due() {
+        // Monday 2 March 2026
+        let monday_morning = at(2026, 3, 2, 8);
+        let monday_early = at(2026, 3, 2, 5);
+        let tuesday_morning = at(2026, 3, 3, 8);
+        let london = chrono_tz::Europe::London;
+
+        assert!(is_digest_due(
+            DigestFrequency::Daily,
+            None,
+            monday_morning,
+            london
+        ));
+        assert!(!is_digest_due(
+            DigestFrequency::Daily,
+            None,
+            monday_early,
+            london
+        ));
+        assert!(!is_digest_due(
+            DigestFrequency::Daily,
+            Some(at(2026, 3, 2, 7)),
+            monday_morning,
+            london
+        ));
+        assert!(is_digest_due(
+            DigestFrequency::Daily,
+            Some(at(2026, 3, 1, 7)),
+            monday_morning,
+            london
+        ));
+
+        assert!(is_digest_due(
+            DigestFrequency::Weekly,
+            None,
+            monday_morning,
+            london
+        ));
+        assert!(!is_digest_due(
+            DigestFrequency::Weekly,
+            None,
+            tuesday_morning,
+            london
+        ));
+    }
+
+    #[test]
+    fn test_is_digest_due_in_local_time() {
+        // 06:00 UTC is 07:00 in Paris but still before the send hour in London
+        let early = at(2026, 3, 2, 6);
+        assert!(is_digest_due(
+            DigestFrequency::Daily,
+            None,
+            early,
+            chrono_tz::Europe::Paris
+        ));
+        assert!(!is_digest_due(
+            DigestFrequency::Daily,
+            None,
+            early,
+            chrono_tz::Europe::London
+        ));
+
+        // 20:00 UTC on Sunday is Monday morning in Auckland
+        let auckland = chrono_tz::Pacific::Auckland;
+        let sunday_evening = at(2026, 3, 1, 20);
+        assert!(is_digest_due(
+            DigestFrequency::Weekly,
+            None,
+            sunday_evening,
+            auckland
+        ));
+        // Sent at 19:00 UTC on Sunday, which was already Monday there
+        assert!(!is_digest_due(
+            DigestFrequency::Daily,
+            Some(at(2026, 3, 1, 19)),
+            sunday_evening,
+            auckland
+        ));
+    }
+
+    #[test]
+    fn test_summarize_template() {
+        let due = vec![
+            "01/03/2026".to_string(),
+            "02/03/2026".to_string(),
+            "03/03/2026".to_string(),
+        ];
+        let with_entries: HashSet<String> =
+            HashSet::from(["01/03/2026".to_string(), "02/03/2026".to_string()]);
+        let completed: HashSet<String> = HashSet::from(["01/03/2026".to_string()]);
+
+        let summary = summarize_template("Fridge", &due, Some(&with_entries), &completed, 2);
+        assert_eq!(
+            summary,
+            TemplateCompliance {
+                template_name: "Fridge".to_string(),
+                due: 3,
+                completed: 1,
+                missed: 1,
+                open_drafts: 2,
+            }
+        );
+
+        let summary = summarize_template("Fridge", &due, None, &HashSet::new(), 0);
+        assert_eq!(summary.missed, 3);
+    }
+}
+
+pub struct ComplianceDigestService;
+
+/// How often the digest task checks for subscribers to send to.
+const DIGEST_INTERVAL_SECS: u64 = 3600;
+/// Digests are sent on the first run at or after this local hour.
+const DIGEST_SEND_HOUR: u32 = 7;
+/// Clock-ins older than this without a clock-out are treated as forgotten and not reported.
+const CLOCKED_IN_LOOKBACK_HOURS: i64 = 24;
+const CLOCK_EVENTS_PAGE_SIZE: i64 = 100;
+/// Section heading for templates that are not assigned to a branch.
+const COMPANY_WIDE_LABEL: &str = "Company-wide";
+
+/// Digest content shared by every subscriber with the same frequency and branch scope.
+type DigestContent = (Vec<BranchCompliance>, Vec<String>);
+
+impl ComplianceDigestService {
+    /// Spawns the background task that sends daily and weekly compliance digests.
+    pub fn spawn_digest_task(state: AppState) {
+        tokio::spawn(async move {
+            let mut interval =
+                tokio::time::interval(tokio::time::Duration::from_secs(DIGEST_INTERVAL_SECS));
+            loop {
+                interval.tick().await;
+                Self::run(&state, Utc::now()).await;
+            }
+        });
+    }
+
+    /// Sends a digest to every subscriber whose digest is due. Failures are logged per company.
+    pub async fn run(state: &AppState, now: DateTime<Utc>) {
+        let subscribers = match db::get_digest_subscribers(&state.postgres).await {
+            Ok(subscribers) => subscribers,
+            Err(e) => {
+                tracing::error!("Failed to list compliance digest subscribers: {:?}", e);
+                return;
+            }
+        };
+
+        let mut by_company: BTreeMap<String, Vec<DigestSubscriber>> = BTreeMap::new();
+        for subscriber in subscribers
+            .into_iter()
+            .filter(|s| is_digest_due(s.frequency, s.last_sent_at, now, s.timezone()))
+        {
+            by_company
+                .entry(subscriber.company_id.clone())
+                .or_default()
+                .push(subscriber);
+        }
+
+        for (company_id, subscribers) in by_company {
+            if let Err((_, err)) =
+                Self::send_company_digests(state, &company_id, subscribers, now).await
+            {
+                tracing::error!(
+                    "Compliance digests failed for company {}: {}",
+                    company_id,
+                    err
+                );
+            }
+        }
+    }
+
+    /// Builds and sends the digests for one company's subscribers. Branch managers receive
+    /// figures for their own branch only; everyone else receives the whole company.
+    ///
+    /// # Errors
+    /// Returns an error if a database query fails.
+    pub async fn send_company_digests(
+        state: &AppState,
+        company_id: &str,
+        subscribers: Vec<DigestSubscriber>,
+        now: DateTime<Utc>,
+    ) -> Result<(), ServiceError> {
+        let Some(company) = try_db!(
+            db::get_company_by_id(&state.postgres, company_id),
+            "fetching company"
+        )?
+        else {
+            return Ok(());
+        };
+
+        let templates = try_db!(
+            logs_db::get_templates_by_company(&state.mongodb, company_id),
+            "fetching templates"
+        )?;
+        let branch_names: HashMap<String, String> = try_db!(
+            db::get_branches_by_company_id(&state.postgres, company_id),
+            "fetching branches"
+        )?
+        .into_iter()
+        .map(|b| (b.id, b.name))
+        .collect();
+        let open_drafts = try_db!(
+            logs_db::count_draft_entries_by_template(&state.mongodb, company_id),
+            "counting draft entries"
+        )?;
+        let timezones = try_db!(
+            db::get_company_timezones(&state.postgres, company_id),
+            "fetching timezones"
+        )?;
+        let calendars = try_db!(
+            db::get_company_calendars(&state.postgres, company_id),
+            "fetching branch calendars"
+        )?;
+
+        let frontend_url =
+            std::env::var("FRONTEND_URL").unwrap_or_else(|_| "http://localhost:5173".to_string());
+        let dashboard_link = format!("{frontend_url}/logs-list");
+
+        let mut contents: HashMap<(DigestFrequency, Option<String>), DigestContent> =
+            HashMap::new();
+
+        for subscriber in subscribers {
+            let scope = match subscriber.role {
+                UserRole::BranchManager => subscriber.branch_id.clone(),
+                _ => None,
+            };
+            let window = digest_window(subscriber.frequency, now, subscriber.timezone());
+
+            let key = (subscriber.frequency, scope.clone());
+            if !contents.contains_key(&key) {
+                let scoped: Vec<&logs_db::TemplateDocument> = templates
+                    .iter()
+                    .filter(|t| scope.is_none() || t.branch_id == scope)
+                    .collect();
+                let branches = compliance_by_branch(
+                    state,
+                    company_id,
+                    &scoped,
+                    &branch_names,
+                    &open_drafts,
+                    &timezones,
+                    &calendars,
+                    window,
+                )
+                .await?;
+                let clocked_in = clocked_in_names(state, company_id, scope.clone(), now).await?;
+                contents.insert(key.clone(), (branches, clocked_in));
+            }
+            let (branches, clocked_in) = &contents[&key];
+
+            let digest = ComplianceDigest {
+                company_name: company.name.clone(),
+                window_label: window_label(window),
+                branches: branches.clone(),
+                clocked_in: clocked_in.clone(),
+            };
+
+            if let Err(e) =
+                email::send_compliance_digest(&subscriber.email, &digest, &dashboard_link).await
+            {
+                tracing::warn!(
+                    "Failed to send compliance digest to {}: {:?}",
+                    subscriber.email,
+                    e
+                );
+                continue;
+            }
+
+            try_db!(
+                db::mark_digest_sent(&state.postgres, &subscriber.user_id, now),
+                "recording digest delivery"
+            )?;
+        }
+
+        Ok(())
+    }
+}
+
+/// Whether a subscriber should be sent a digest at `now`: daily digests go out once per day
+/// and weekly digests once on Mondays, both from `DIGEST_SEND_HOUR` in the subscriber's timezone.
+fn is_digest_due(
+    frequency: DigestFrequency,
+    last_sent_at: Option<DateTime<Utc>>,
+    now: DateTime<Utc>,
+    tz: chrono_tz::Tz,
+) -> bool {
+    let local = now.with_timezone(&tz);
+    if local.hour() < DIGEST_SEND_HOUR {
+        return false;
+    }
+    if frequency == DigestFrequency::Weekly && local.weekday() != chrono::Weekday::Mon {
+        return false;
+    }
+    last_sent_at.is_none_or(|sent| sent.with_timezone(&tz).date_naive() < local.date_naive())
+}
+
+/// Inclusive date range a digest covers, in the subscriber's timezone: yesterday for daily
+/// digests, the seven days up to yesterday for weekly ones.
+fn digest_window(
+    frequency: DigestFrequency,
+    now: DateTime<Utc>,
+    tz: chrono_tz::Tz,
+) -> (NaiveDate, NaiveDate) {
+    let end = now.with_timezone(&tz).date_naive() - chrono::Duration::days(1);
+    let start = match frequency {
+        DigestFrequency::Daily => end,
+        DigestFrequency::Weekly => end - chrono::Duration::days(6),
+    };
+    (start, end)
+}
+
+fn window_label((start, end): (NaiveDate, NaiveDate)) -> String {
+    if start == end {
+        start.format("%A %-d %B %Y").to_string()
+    } else {
+        format!(
+            "{} to {}",
+            start.format("%-d %B %Y"),
+            end.format("%-d %B %Y")
+        )
+    }
+}
+
+/// Periods of a template whose due time, in the template's timezone, falls within the window.
+/// Repeatable and unscheduled templates have no due periods.
+fn periods_due_in_window(
+    template: &logs_db::TemplateDocument,
+    (start, end): (NaiveDate, NaiveDate),
+    tz: chrono_tz::Tz,
+) -> Vec<String> {
+    if !template.is_scheduled() {
+        return Vec::new();
+    }
+
+    // Start early enough that monthly and yearly periods due in the window are enumerated
+    let enumerate_from = template
+        .created_at
+        .with_timezone(&tz)
+        .date_naive()
+        .max(start - chrono::Duration::days(366));
+    let enumerate_from = enumerate_from
+        .and_hms_opt(0, 0, 0)
+        .and_then(|dt| dt.and_local_timezone(tz).earliest())
+        .map(|dt| dt.to_rfc3339());
+
+    logs_db::get_missed_periods(&template.schedule, None, enumerate_from.as_deref(), tz)
+        .into_iter()
+        .filter(|period| {
+            let due_date = logs_db::get_due_at_datetime(&template.schedule, period, tz)
+                .and_then(|due| DateTime::parse_from_rfc3339(&due).ok())
+                .map(|due| due.with_timezone(&tz).date_naive())
+                .or_else(|| logs_db::parse_period_to_date(period));
+            due_date.is_some_and(|date| date >= start && date <= end)
+        })
+        .collect()
+}
+
+fn summarize_template(
+    template_name: &str,
+    due: &[String],
+    with_entries: Option<&HashSet<String>>,
+    completed: &HashSet<String>,
+    open_drafts: usize,
+) -> TemplateCompliance {
+    TemplateCompliance {
+        template_name: template_name.to_string(),
+        due: due.len(),
+        completed: due.iter().filter(|p| completed.contains(*p)).count(),
+        missed: due
+            .iter()
+            .filter(|p| with_entries.is_none_or(|periods| !periods.contains(*p)))
+            .count(),
+        open_drafts,
+    }
+}
+
+/// Completion figures for the given templates, grouped by branch and sorted by name.
+/// Templates with nothing due and no open drafts are left out. Periods on days a branch is
+/// closed are not counted as due.
+#[allow(clippy::too_many_arguments)]
+async fn compliance_by_branch(
+    state: &AppState,
+    company_id: &str,
+    templates: &[&logs_db::TemplateDocument],
+    branch_names: &HashMap<String, String>,
+    open_drafts: &HashMap<String, usize>,
+    timezones: &db::CompanyTimezones,
+    calendars: &db::CompanyCalendars,
+    window: (NaiveDate, NaiveDate),
+) -> Result<Vec<BranchCompliance>, ServiceError> {
+    let due_periods: Vec<Vec<String>> = templates
+        .iter()
+        .map(|t| {
+            let mut periods =
+                periods_due_in_window(t, window, timezones.for_branch(t.branch_id.as_deref()));
+            periods.retain(|p| calendars.is_period_open(t.branch_id.as_deref(), &t.schedule, p));
+            periods
+        })
+        .collect();
+
+    let template_ids: Vec<String> = templates.iter().map(|t| t.template_id.clone()).collect();
+    let all_periods: Vec<String> = due_periods
+        .iter()
+        .flatten()
+        .cloned()
+        .collect::<HashSet<_>>()
+        .into_iter()
+        .collect();
+    let periods_with_entries = try_db!(
+        logs_db::get_periods_with_entries_batch(
+            &state.mongodb,
+            company_id,
+            &template_ids,
+            &all_periods
+        ),
+        "fetching periods with entries"
+    )?;
+
+    let mut branches: BTreeMap<String, Vec<TemplateCompliance>> = BTreeMap::new();
+    for (template, due) in templates.iter().zip(&due_periods) {
+        let drafts = open_drafts.get(&template.template_id).copied().unwrap_or(0);
+        if due.is_empty() && drafts == 0 {
+            continue;
+        }
+
+        let completed = try_db!(
+            logs_db::get_completed_periods(&state.mongodb, company_id, &template.template_id, due),
+            "fetching completed periods"
+        )?;
+
+        let branch_name = template
+            .branch_id
+            .as_ref()
+            .and_then(|id| branch_names.get(id).cloned())
+            .unwrap_or_else(|| COMPANY_WIDE_LABEL.to_string());
+        branches
+            .entry(branch_name)
+            .or_default()
+            .push(summarize_template(
+                &template.template_name,
+                due,
+                periods_with_entries.get(&template.template_id),
+                &completed,
+                drafts,
+            ));
+    }
+
+    Ok(branches
+        .into_iter()
+        .map(|(branch_name, mut templates)| {
+            templates.sort_by(|a, b| a.template_name.cmp(&b.template_name));
+            BranchCompliance {
+                branch_name,
+                templates,
+            }
+        })
+        .collect())
+}
+
+/// Names of users who clocked in recently and have not clocked out.
+async fn clocked_in_names(
+    state: &AppState,
+    company_id: &str,
+    branch_id: Option<String>,
+    now: DateTime<Utc>,
+) -> Result<Vec<String>, ServiceError> {
+    let from = now - chrono::Duration::hours(CLOCKED_IN_LOOKBACK_HOURS);
+    let mut names = Vec::new();
+    let mut seen = HashSet::new();
+    let mut cursor = None;
+
+    loop {
+        let (events, next_cursor) = try_db!(
+            db::get_company_clock_events(
+                &state.postgres,
+                company_id,
+                Some(from),
+                None,
+                branch_id.clone(),
+                Some(CLOCK_EVENTS_PAGE_SIZE),
+                cursor,
+            ),
+            "fetching clock events"
+        )?;
+
+        for event in events
+            .into_iter()
+            .filter(db::CompanyClockEventRow::is_clocked_in)
+        {
+            if seen.insert(event.user_id.clone()) {
+                names.push(format!("{} {}", event.first_name, event.last_name));
+            }
+        }
+
+        match next_cursor {
+            Some(next) => cursor = Some(next),
+            None => break,
+        }
+    }
+
+    names.sort();
+    Ok(names)
+}
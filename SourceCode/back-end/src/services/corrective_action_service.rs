@@ -0,0 +1,278 @@
+use crate::{
+    AppState,
+    db::UserRecord,
+    logs_db::{self, CorrectiveActionIssue, IssueStatus},
+    try_db,
+    utils::{ServiceError, svc_err, svc_err_bad_request, svc_err_forbidden, svc_err_not_found},
+};
+use axum::http::StatusCode;
+
+#[cfg(test)]
+mod corrective_action_service_tests {
+    use super::*;
+
+    fn user(role: crate::db::UserRole, branch_id: Option<&str>) -> UserRecord {
+        UserRecord {
+            id: "user-1".to_string(),
+            email: "user@example.com".to_string(),
+            first_name: "Test".to_string(),
+            last_name: "User".to_string(),
+            password_hash: None,
+            company_id: Some("company-1".to_string()),
+            branch_id: branch_id.map(str::to_string),
+            company_name: None,
+            company_deleted_at: None,
+            role,
+            created_at: chrono::Utc::now(),
+            deleted_at: None,
+            oauth_provider: None,
+            oauth_subject: None,
+            oauth_picture: None,
+            profile_picture_id: None,
+        }
+    }
+
+    #[test]
+    fn test_issue_branch_scope() {
+        use crate::db::UserRole;
+
+        let manager = user(UserRole::BranchManager, Some("branch-1"));
+        assert_eq!(
+            issue_branch_scope(&manager, None).unwrap(),
+            Some(vec!["branch-1".to_string()])
+        );
+        assert!(issue_branch_scope(&manager, Some("branch-2")).is_err());
+
+        let owner = user(UserRole::CompanyManager, None);
+        assert_eq!(issue_branch_scope(&owner, None).unwrap(), None);
+        assert_eq!(
+            issue_branch_scope(&owner, Some("branch-2")).unwrap(),
+            Some(vec!["branch-2".to_string()])
+        );
+    }
+
+    #[test]
+    fn test_can_resolve_issue() {
+        use crate::db::UserRole;
+
+        assert!(can_resolve_issue(
+            &user(UserRole::BranchManager, Some("branch-1")),
+            Some("branch-1")
+        ));
+        assert!(!can_resolve_issue(
+            &user(UserRole::BranchManager, Some("branch-1")),
+            Some("branch-2")
+        ));
+        assert!(!can_resolve_issue(
+            &user(UserRole::BranchManager, Some("branch-1")),
+            None
+        ));
+        assert!(can_resolve_issue(
+            &user(UserRole::CompanyManager, None),
+            None
+        ));
+        assert!(!can_resolve_issue(&user(UserRole::Staff, None), None));
+    }
+
+    #[test]
+    fn test_readings_without_closed_issue() {
+        let reading = |field_id: &str| logs_db::OutOfRangeReading {
+            field_id: field_id.to_string(),
+            label: None,
+            value: 9.0,
+            min: Some(0.0),
+            max: Some(5.0),
+            unit: None,
+        };
+        let issue = |status, field_id: &str| logs_db::CorrectiveActionIssue {
+            issue_id: "issue-1".to_string(),
+            company_id: "company-1".to_string(),
+            branch_id: None,
+            entry_id: "entry-1".to_string(),
+            template_id: "template-1".to_string(),
+            template_name: "Fridge".to_string(),
+            period: "01/03/2026".to_string(),
+            readings: vec![reading(field_id)],
+            corrective_action: None,
+            status,
+            raised_by: "user-1".to_string(),
+            raised_at: chrono::Utc::now(),
+            acknowledged_by: None,
+            acknowledged_at: None,
+            closed_by: None,
+            closed_at: None,
+            resolution_note: None,
+        };
+
+        let readings = vec![reading("fridge"), reading("freezer")];
+        let remaining = logs_db::readings_without_closed_issue(
+            &readings,
+            &[issue(logs_db::IssueStatus::Closed, "fridge")],
+        );
+        assert_eq!(remaining, vec![reading("freezer")]);
+
+        // Open issues are updated in place rather than skipped
+        let remaining = logs_db::readings_without_closed_issue(
+            &readings,
+            &[issue(logs_db::IssueStatus::Open, "fridge")],
+        );
+        assert_eq!(remaining, readings);
+    }
+}
+
+pub struct CorrectiveActionService;
+
+/// Maximum length of corrective-action and resolution notes.
+pub const MAX_NOTE_LENGTH: usize = 2000;
+
+impl CorrectiveActionService {
+    /// Lists the company's corrective-action issues with the given statuses. Branch managers
+    /// only see issues for their own branch.
+    ///
+    /// # Errors
+    /// Returns an error if the user has no company, requests another branch's issues, or the query fails.
+    pub async fn list_issues(
+        state: &AppState,
+        user: &UserRecord,
+        branch_id: Option<&str>,
+        statuses: &[IssueStatus],
+    ) -> Result<Vec<CorrectiveActionIssue>, ServiceError> {
+        let company_id = user
+            .company_id
+            .as_deref()
+            .ok_or_else(|| svc_err_forbidden("User is not associated with a company"))?;
+        let branch_ids = issue_branch_scope(user, branch_id)?;
+
+        try_db!(
+            logs_db::list_corrective_action_issues(
+                &state.mongodb,
+                company_id,
+                branch_ids.as_deref(),
+                statuses
+            ),
+            "listing corrective action issues"
+        )
+    }
+
+    /// Acknowledges an open issue on behalf of the responsible manager.
+    ///
+    /// # Errors
+    /// Returns an error if the issue is not found, the user is not responsible for it, or it is not open.
+    pub async fn acknowledge_issue(
+        state: &AppState,
+        user: &UserRecord,
+        issue_id: &str,
+    ) -> Result<CorrectiveActionIssue, ServiceError> {
+        Self::load_resolvable_issue(state, user, issue_id).await?;
+
+        let updated = try_db!(
+            logs_db::acknowledge_corrective_action_issue(&state.mongodb, issue_id, &user.id),
+            "acknowledging corrective action issue"
+        )?;
+        if !updated {
+            return Err(svc_err(
+                StatusCode::CONFLICT,
+                "Only open issues can be acknowledged",
+            ));
+        }
+
+        Self::load_resolvable_issue(state, user, issue_id).await
+    }
+
+    /// Closes an acknowledged issue, optionally recording how it was resolved.
+    ///
+    /// # Errors
+    /// Returns an error if the issue is not found, the user is not responsible for it, or it has not been acknowledged.
+    pub async fn close_issue(
+        state: &AppState,
+        user: &UserRecord,
+        issue_id: &str,
+        resolution_note: Option<&str>,
+    ) -> Result<CorrectiveActionIssue, ServiceError> {
+        let resolution_note = resolution_note
+            .map(str::trim)
+            .filter(|note| !note.is_empty());
+        if resolution_note.is_some_and(|note| note.chars().count() > MAX_NOTE_LENGTH) {
+            return Err(svc_err_bad_request(&format!(
+                "Resolution note must not exceed {MAX_NOTE_LENGTH} characters"
+            )));
+        }
+
+        Self::load_resolvable_issue(state, user, issue_id).await?;
+
+        let updated = try_db!(
+            logs_db::close_corrective_action_issue(
+                &state.mongodb,
+                issue_id,
+                &user.id,
+                resolution_note
+            ),
+            "closing corrective action issue"
+        )?;
+        if !updated {
+            return Err(svc_err(
+                StatusCode::CONFLICT,
+                "Issues must be acknowledged before they can be closed",
+            ));
+        }
+
+        Self::load_resolvable_issue(state, user, issue_id).await
+    }
+
+    async fn load_resolvable_issue(
+        state: &AppState,
+        user: &UserRecord,
+        issue_id: &str,
+    ) -> Result<CorrectiveActionIssue, ServiceError> {
+        let company_id = user
+            .company_id
+            .as_deref()
+            .ok_or_else(|| svc_err_forbidden("User is not associated with a company"))?;
+
+        let issue = try_db!(
+            logs_db::get_corrective_action_issue(&state.mongodb, issue_id, company_id),
+            "fetching corrective action issue"
+        )?
+        .ok_or_else(|| svc_err_not_found("Issue not found"))?;
+
+        if !can_resolve_issue(user, issue.branch_id.as_deref()) {
+            return Err(svc_err_forbidden(
+                "You do not have permission to resolve this issue",
+            ));
+        }
+
+        Ok(issue)
+    }
+}
+
+/// Branches whose issues a user may list: branch managers are limited to their own branch.
+fn issue_branch_scope(
+    user: &UserRecord,
+    requested: Option<&str>,
+) -> Result<Option<Vec<String>>, ServiceError> {
+    if user.is_branch_manager() {
+        let own = user
+            .branch_id
+            .clone()
+            .ok_or_else(|| svc_err_forbidden("Branch manager is not assigned to a branch"))?;
+        if requested.is_some_and(|b| b != own) {
+            return Err(svc_err_forbidden(
+                "You can only view issues for your own branch",
+            ));
+        }
+        return Ok(Some(vec![own]));
+    }
+
+    Ok(requested.map(|b| vec![b.to_string()]))
+}
+
+/// Company managers can resolve any issue in their company; branch managers only those
+/// raised against their branch.
+fn can_resolve_issue(user: &UserRecord, issue_branch_id: Option<&str>) -> bool {
+    if user.can_manage_company() {
+        return true;
+    }
+    user.is_branch_manager()
+        && issue_branch_id.is_some()
+        && issue_branch_id == user.branch_id.as_deref()
+}
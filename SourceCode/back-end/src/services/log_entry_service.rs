@@ -1,14 +1,395 @@
 use crate::{
-    AppState,
+    AppState, attachments_db,
     db::{self, UserRecord},
-    logs_db,
+    images_db, logs_db,
+    services::{company_service::infer_content_type, corrective_action_service::MAX_NOTE_LENGTH},
 };
 use axum::http::StatusCode;
 use serde_json::json;
+use std::collections::BTreeMap;
 use uuid::Uuid;
 
 #[cfg(test)]
 mod log_entry_service_tests {
+    use super::*;
+
+    fn field(field_type: &str, props: logs_db::TemplateFieldProps) -> logs_db::TemplateField {
+        logs_db::TemplateField {
+            field_id: String::new(),
+            field_type: field_type.to_string(),
+            position: logs_db::Position { x: 0.0, y: 0.0 },
+            props,
+        }
+    }
+
+    fn sample_layout() -> logs_db::TemplateLayout {
+        vec![
+            field(
+                "label",
+                logs_db::TemplateFieldProps {
+                    text: Some("Fridge checks".to_string()),
+                    ..Default::default()
+                },
+            ),
+            field(
+                "temperature",
+                logs_db::TemplateFieldProps {
+                    min: Some(0.0),
+                    max: Some(8.0),
+                    required: Some(true),
+                    ..Default::default()
+                },
+            ),
+            field(
+                "text_input",
+                logs_db::TemplateFieldProps {
+                    min_length: Some(2),
+                    max_length: Some(5),
+                    ..Default::default()
+                },
+            ),
+            field(
+                "text_input",
+                logs_db::TemplateFieldProps {
+                    input_type: Some("int".to_string()),
+                    ..Default::default()
+                },
+            ),
+            field(
+                "dropdown",
+                logs_db::TemplateFieldProps {
+                    options: Some(vec!["Yes".to_string(), "No".to_string()]),
+                    required: Some(true),
+                    ..Default::default()
+                },
+            ),
+            field(
+                "checkbox",
+                logs_db::TemplateFieldProps {
+                    required: Some(true),
+                    ..Default::default()
+                },
+            ),
+        ]
+    }
+
+    #[test]
+    fn test_validate_entry_data_valid_submission() {
+        let data = json!({ "1": 4.5, "2": "abc", "3": "12", "4": "Yes", "5": true });
+        assert!(LogEntryService::validate_entry_data(&sample_layout(), &data, true).is_ok());
+    }
+
+    #[test]
+    fn test_validate_entry_data_required_only_enforced_on_submit() {
+        let data = json!({});
+        assert!(LogEntryService::validate_entry_data(&sample_layout(), &data, false).is_ok());
+
+        let errors =
+            LogEntryService::validate_entry_data(&sample_layout(), &data, true).unwrap_err();
+        assert!(errors.contains_key("1"));
+        assert!(errors.contains_key("4"));
+        assert!(errors.contains_key("5"));
+        assert!(!errors.contains_key("2"));
+    }
+
+    #[test]
+    fn test_validate_entry_data_temperature_range() {
+        // Out-of-range readings are valid; they are flagged on submit instead
+        assert!(
+            LogEntryService::validate_entry_data(&sample_layout(), &json!({ "1": 9.0 }), false)
+                .is_ok()
+        );
+
+        let errors =
+            LogEntryService::validate_entry_data(&sample_layout(), &json!({ "1": "4" }), false)
+                .unwrap_err();
+        assert_eq!(errors["1"], "Value must be a number");
+    }
+
+    #[test]
+    fn test_validate_entry_data_text_constraints() {
+        let data = json!({ "2": "a", "3": "1.5" });
+        let errors =
+            LogEntryService::validate_entry_data(&sample_layout(), &data, false).unwrap_err();
+        assert_eq!(errors["2"], "Value must be at least 2 characters long");
+        assert_eq!(errors["3"], "Value must be a whole number");
+    }
+
+    #[test]
+    fn test_validate_entry_data_dropdown_option() {
+        let errors =
+            LogEntryService::validate_entry_data(&sample_layout(), &json!({ "4": "Maybe" }), false)
+                .unwrap_err();
+        assert_eq!(errors["4"], "Value must be one of the available options");
+    }
+
+    #[test]
+    fn test_validate_entry_data_rejects_unknown_fields() {
+        let errors = LogEntryService::validate_entry_data(
+            &sample_layout(),
+            &json!({ "0": "not an input", "42": 1 }),
+            false,
+        )
+        .unwrap_err();
+        assert!(errors.contains_key("0"));
+        assert!(errors.contains_key("42"));
+    }
+
+    #[test]
+    fn test_validate_entry_data_rejects_non_object() {
+        let errors = LogEntryService::validate_entry_data(&sample_layout(), &json!([1, 2]), false)
+            .unwrap_err();
+        assert!(errors.contains_key("entry_data"));
+    }
+
+    #[test]
+    fn test_validate_entry_data_keyed_by_field_id() {
+        let layout = logs_db::assign_field_ids(&sample_layout(), None);
+        let temperature_id = layout[1].field_id.clone();
+
+        let data = logs_db::normalize_entry_data_keys(&layout, &json!({ "1": "20" }));
+        assert!(data.get(&temperature_id).is_some());
+
+        let errors = LogEntryService::validate_entry_data(&layout, &data, false).unwrap_err();
+        assert_eq!(errors[&temperature_id], "Value must be a number");
+
+        let errors =
+            LogEntryService::validate_entry_data(&layout, &json!({ "1": 4.0 }), false).unwrap_err();
+        assert_eq!(errors["1"], "Unknown field");
+    }
+
+    #[test]
+    fn test_out_of_range_readings() {
+        let layout = sample_layout();
+
+        assert!(LogEntryService::out_of_range_readings(&layout, &json!({ "1": 8.0 })).is_empty());
+        assert!(LogEntryService::out_of_range_readings(&layout, &json!({})).is_empty());
+
+        let readings = LogEntryService::out_of_range_readings(&layout, &json!({ "1": -1.5 }));
+        assert_eq!(
+            readings,
+            vec![logs_db::OutOfRangeReading {
+                field_id: "1".to_string(),
+                label: None,
+                value: -1.5,
+                min: Some(0.0),
+                max: Some(8.0),
+                unit: None,
+            }]
+        );
+    }
+
+    fn condition(
+        field_id: &str,
+        operator: logs_db::ConditionOperator,
+        value: Option<serde_json::Value>,
+    ) -> Option<logs_db::FieldCondition> {
+        Some(logs_db::FieldCondition {
+            field_id: field_id.to_string(),
+            operator,
+            value,
+        })
+    }
+
+    fn rules_layout() -> logs_db::TemplateLayout {
+        vec![
+            field(
+                "temperature",
+                logs_db::TemplateFieldProps {
+                    max: Some(8.0),
+                    required: Some(true),
+                    ..Default::default()
+                },
+            ),
+            field(
+                "text_input",
+                logs_db::TemplateFieldProps {
+                    required: Some(true),
+                    visible_if: condition("0", logs_db::ConditionOperator::AboveMax, None),
+                    ..Default::default()
+                },
+            ),
+            field("checkbox", logs_db::TemplateFieldProps::default()),
+            field(
+                "text_input",
+                logs_db::TemplateFieldProps {
+                    required_if: condition("2", logs_db::ConditionOperator::Unchecked, None),
+                    ..Default::default()
+                },
+            ),
+        ]
+    }
+
+    #[test]
+    fn test_validate_entry_data_hidden_field_not_required() {
+        let data = json!({ "0": 5.0, "2": true });
+        assert!(LogEntryService::validate_entry_data(&rules_layout(), &data, true).is_ok());
+
+        let data = json!({ "0": 9.5, "2": true });
+        let errors =
+            LogEntryService::validate_entry_data(&rules_layout(), &data, true).unwrap_err();
+        assert_eq!(errors["1"], "This field is required");
+    }
+
+    #[test]
+    fn test_validate_entry_data_required_if() {
+        let data = json!({ "0": 5.0 });
+        let errors =
+            LogEntryService::validate_entry_data(&rules_layout(), &data, true).unwrap_err();
+        assert_eq!(errors.keys().collect::<Vec<_>>(), vec!["3"]);
+
+        let data = json!({ "0": 5.0, "3": "Checked later" });
+        assert!(LogEntryService::validate_entry_data(&rules_layout(), &data, true).is_ok());
+    }
+
+    #[test]
+    fn test_out_of_range_readings_skip_hidden_fields() {
+        let mut layout = rules_layout();
+        layout[0].props.visible_if = condition("2", logs_db::ConditionOperator::Checked, None);
+        assert!(LogEntryService::out_of_range_readings(&layout, &json!({ "0": 12.0 })).is_empty());
+        assert_eq!(
+            LogEntryService::out_of_range_readings(&layout, &json!({ "0": 12.0, "2": true })).len(),
+            1
+        );
+    }
+
+    fn typed_layout() -> logs_db::TemplateLayout {
+        vec![
+            field(
+                "number",
+                logs_db::TemplateFieldProps {
+                    min: Some(0.0),
+                    max: Some(50.0),
+                    unit: Some("kg".to_string()),
+                    decimals: Some(1),
+                    ..Default::default()
+                },
+            ),
+            field("date", logs_db::TemplateFieldProps::default()),
+            field("time", logs_db::TemplateFieldProps::default()),
+            field("datetime", logs_db::TemplateFieldProps::default()),
+            field(
+                "multi_select",
+                logs_db::TemplateFieldProps {
+                    options: Some(vec!["Floors".to_string(), "Walls".to_string()]),
+                    required: Some(true),
+                    ..Default::default()
+                },
+            ),
+            field("signature", logs_db::TemplateFieldProps::default()),
+        ]
+    }
+
+    #[test]
+    fn test_validate_entry_data_typed_fields() {
+        let data = json!({
+            "0": 12.5,
+            "1": "2026-03-01",
+            "2": "14:30",
+            "3": "2026-03-01T14:30:00+01:00",
+            "4": ["Walls", "Floors"],
+            "5": "0f8fad5b-d9cb-469f-a165-70867728950e",
+        });
+        assert!(LogEntryService::validate_entry_data(&typed_layout(), &data, true).is_ok());
+
+        let data = json!({
+            "0": 12.55,
+            "1": "01/03/2026",
+            "2": "2:30pm",
+            "3": "2026-03-01 14:30",
+            "4": ["Floors", "Floors"],
+            "5": "not a signature",
+        });
+        let errors =
+            LogEntryService::validate_entry_data(&typed_layout(), &data, true).unwrap_err();
+        assert_eq!(errors["0"], "Value must have at most 1 decimal places");
+        assert_eq!(errors["1"], "Value must be a date in YYYY-MM-DD format");
+        assert_eq!(errors["2"], "Value must be a time in HH:MM format");
+        assert_eq!(errors["3"], "Value must be an RFC 3339 date and time");
+        assert_eq!(errors["4"], "Options cannot be selected more than once");
+        assert_eq!(errors["5"], "Value must be a signature image");
+
+        let errors = LogEntryService::validate_entry_data(
+            &typed_layout(),
+            &json!({ "0": 51, "4": ["Ceilings"] }),
+            false,
+        )
+        .unwrap_err();
+        assert_eq!(errors["0"], "Value must not exceed 50");
+        assert_eq!(errors["4"], "Values must be among the available options");
+
+        let errors =
+            LogEntryService::validate_entry_data(&typed_layout(), &json!({ "4": [] }), true)
+                .unwrap_err();
+        assert_eq!(errors["4"], "This field is required");
+    }
+
+    #[test]
+    fn test_normalize_datetimes() {
+        let data = LogEntryService::normalize_datetimes(
+            &typed_layout(),
+            json!({ "3": "2026-03-01T14:30:00+01:00" }),
+        );
+        assert_eq!(data["3"], "2026-03-01T13:30:00Z");
+    }
+
+    #[test]
+    fn test_decode_signature() {
+        use base64::Engine as _;
+
+        let png = [0x89, 0x50, 0x4E, 0x47, 0x0D, 0x0A, 0x1A, 0x0A];
+        let encoded = base64::engine::general_purpose::STANDARD.encode(png);
+        let (data, content_type) =
+            decode_signature(&format!("data:image/png;base64,{encoded}")).unwrap();
+        assert_eq!(data, png);
+        assert_eq!(content_type, "image/png");
+
+        let text = base64::engine::general_purpose::STANDARD.encode("<svg/>");
+        assert!(decode_signature(&format!("data:image/svg+xml;base64,{text}")).is_err());
+        assert!(decode_signature("data:image/png,rawdata").is_err());
+    }
+
+    #[test]
+    fn test_calculated_fields() {
+        let mut layout = vec![
+            field("temperature", logs_db::TemplateFieldProps::default()),
+            field("temperature", logs_db::TemplateFieldProps::default()),
+            field(
+                "calculated",
+                logs_db::TemplateFieldProps {
+                    expression: Some("round(avg({0}, {1}), 1)".to_string()),
+                    ..Default::default()
+                },
+            ),
+            field(
+                "text_input",
+                logs_db::TemplateFieldProps {
+                    required: Some(true),
+                    visible_if: condition(
+                        "2",
+                        logs_db::ConditionOperator::GreaterThan,
+                        Some(json!(5)),
+                    ),
+                    ..Default::default()
+                },
+            ),
+        ];
+
+        let data = logs_db::calculate_fields(&layout, &json!({ "0": 4.0, "1": 7.5, "2": 0 }));
+        assert_eq!(data["2"], json!(5.8));
+        let errors = LogEntryService::validate_entry_data(&layout, &data, true).unwrap_err();
+        assert!(errors.contains_key("3"));
+
+        // Missing inputs leave the calculated value empty
+        let data = logs_db::calculate_fields(&layout, &json!({ "0": 4.0, "2": 99 }));
+        assert!(data.get("2").is_none());
+        assert!(LogEntryService::validate_entry_data(&layout, &data, true).is_ok());
+
+        layout[2].props.expression = Some("{0} / {1}".to_string());
+        let data = logs_db::calculate_fields(&layout, &json!({ "0": 4.0, "1": 0.0 }));
+        assert!(data.get("2").is_none());
+    }
+
     #[tokio::test]
     async fn test_log_entry_service_basic() {
         assert!(true);
@@ -17,15 +398,566 @@ mod log_entry_service_tests {
 
 pub struct LogEntryService;
 
+/// Field types whose values are captured in `entry_data`.
+pub(crate) const INPUT_FIELD_TYPES: [&str; 11] = [
+    "temperature",
+    "text",
+    "text_input",
+    "checkbox",
+    "dropdown",
+    "number",
+    "date",
+    "time",
+    "datetime",
+    "multi_select",
+    "signature",
+];
+
+/// Maximum size of a signature image, in bytes.
+const MAX_SIGNATURE_BYTES: usize = 512 * 1024;
+
+/// Decodes a signature submitted as a base64 `data:` URL into its image bytes and sniffed
+/// content type. The declared media type is ignored in favour of the image's own header.
+fn decode_signature(data_url: &str) -> Result<(Vec<u8>, String), String> {
+    use base64::Engine as _;
+
+    let invalid = || "Signature must be a base64 image data URL".to_string();
+    let (header, encoded) = data_url
+        .strip_prefix("data:")
+        .and_then(|rest| rest.split_once(','))
+        .ok_or_else(invalid)?;
+    if !header.ends_with(";base64") {
+        return Err(invalid());
+    }
+    if encoded.len() > MAX_SIGNATURE_BYTES.div_ceil(3) * 4 {
+        return Err("Signature image must not exceed 512KB".to_string());
+    }
+
+    let data = base64::engine::general_purpose::STANDARD
+        .decode(encoded.trim())
+        .map_err(|_| invalid())?;
+    if data.is_empty() || data.len() > MAX_SIGNATURE_BYTES {
+        return Err("Signature image must not exceed 512KB".to_string());
+    }
+    let content_type = infer_content_type(&data);
+    if !content_type.starts_with("image/") {
+        return Err("Signature must be a PNG, JPEG or WebP image".to_string());
+    }
+    Ok((data, content_type))
+}
+
+/// Whether `number` has no more than `decimals` decimal places, allowing for float error.
+fn has_at_most_decimals(number: f64, decimals: u8) -> bool {
+    let scaled = number * 10f64.powi(i32::from(decimals));
+    (scaled - scaled.round()).abs() <= 1e-6 * scaled.abs().max(1.0)
+}
+
 impl LogEntryService {
+    /// Validates `entry_data` against the input fields of a template layout.
+    /// Values are keyed by each field's `field_id` (or index, for layouts without ids).
+    /// Required fields are only enforced when `enforce_required` is set, so drafts can be saved partially.
+    /// Fields hidden by a `visible_if` rule are skipped; `required_if` rules are applied on submit.
+    /// Returns a map of field key to error message if any value is invalid.
+    fn validate_entry_data(
+        template_layout: &logs_db::TemplateLayout,
+        entry_data: &serde_json::Value,
+        enforce_required: bool,
+    ) -> Result<(), BTreeMap<String, String>> {
+        let mut errors = BTreeMap::new();
+
+        let Some(values) = entry_data.as_object() else {
+            errors.insert(
+                "entry_data".to_string(),
+                "Entry data must be an object keyed by field".to_string(),
+            );
+            return Err(errors);
+        };
+
+        let input_fields: Vec<(String, &logs_db::TemplateField)> = template_layout
+            .iter()
+            .enumerate()
+            .filter(|(_, field)| INPUT_FIELD_TYPES.contains(&field.field_type.as_str()))
+            .map(|(field_index, field)| (field.entry_key(field_index), field))
+            .collect();
+
+        // Calculated values are filled in by the server, so they are known but not validated
+        let calculated_keys: Vec<String> = template_layout
+            .iter()
+            .enumerate()
+            .filter(|(_, field)| field.field_type == "calculated")
+            .map(|(field_index, field)| field.entry_key(field_index))
+            .collect();
+
+        for key in values.keys() {
+            if !input_fields.iter().any(|(field_key, _)| field_key == key)
+                && !calculated_keys.contains(key)
+            {
+                errors.insert(key.clone(), "Unknown field".to_string());
+            }
+        }
+
+        let rules = logs_db::FieldRules::new(template_layout, entry_data);
+        for (key, field) in input_fields {
+            if rules.is_hidden(&key) {
+                continue;
+            }
+            let value = values.get(&key).filter(|v| !v.is_null());
+            let required = enforce_required && rules.is_required(field);
+
+            if let Err(e) = Self::validate_field_value(field, value, required) {
+                errors.insert(key, e);
+            }
+        }
+
+        if errors.is_empty() {
+            Ok(())
+        } else {
+            Err(errors)
+        }
+    }
+
+    /// Validates a single field value against the constraints in its props.
+    fn validate_field_value(
+        field: &logs_db::TemplateField,
+        value: Option<&serde_json::Value>,
+        required: bool,
+    ) -> Result<(), String> {
+        let props = &field.props;
+
+        let missing = || {
+            if required {
+                Err("This field is required".to_string())
+            } else {
+                Ok(())
+            }
+        };
+
+        match field.field_type.as_str() {
+            "number" => {
+                let Some(value) = value else {
+                    return missing();
+                };
+                let number = value
+                    .as_f64()
+                    .filter(|n| n.is_finite())
+                    .ok_or_else(|| "Value must be a number".to_string())?;
+                if let Some(min) = props.min
+                    && number < f64::from(min)
+                {
+                    return Err(format!("Value must be at least {min}"));
+                }
+                if let Some(max) = props.max
+                    && number > f64::from(max)
+                {
+                    return Err(format!("Value must not exceed {max}"));
+                }
+                if let Some(decimals) = props.decimals
+                    && !has_at_most_decimals(number, decimals)
+                {
+                    return Err(format!("Value must have at most {decimals} decimal places"));
+                }
+            }
+            "date" | "time" | "datetime" => {
+                let text = match value {
+                    None => "",
+                    Some(v) => v
+                        .as_str()
+                        .ok_or_else(|| "Value must be a string".to_string())?,
+                };
+                if text.trim().is_empty() {
+                    return missing();
+                }
+                let (valid, message) = match field.field_type.as_str() {
+                    "date" => (
+                        chrono::NaiveDate::parse_from_str(text, "%Y-%m-%d").is_ok(),
+                        "Value must be a date in YYYY-MM-DD format",
+                    ),
+                    "time" => (
+                        chrono::NaiveTime::parse_from_str(text, "%H:%M").is_ok()
+                            || chrono::NaiveTime::parse_from_str(text, "%H:%M:%S").is_ok(),
+                        "Value must be a time in HH:MM format",
+                    ),
+                    _ => (
+                        chrono::DateTime::parse_from_rfc3339(text).is_ok(),
+                        "Value must be an RFC 3339 date and time",
+                    ),
+                };
+                if !valid {
+                    return Err(message.to_string());
+                }
+            }
+            "multi_select" => {
+                let selected = match value {
+                    None => Vec::new(),
+                    Some(v) => v
+                        .as_array()
+                        .and_then(|items| {
+                            items
+                                .iter()
+                                .map(serde_json::Value::as_str)
+                                .collect::<Option<Vec<&str>>>()
+                        })
+                        .ok_or_else(|| "Value must be a list of options".to_string())?,
+                };
+                if selected.is_empty() {
+                    return missing();
+                }
+                if let Some(options) = &props.options
+                    && !selected.iter().all(|s| options.iter().any(|o| o == s))
+                {
+                    return Err("Values must be among the available options".to_string());
+                }
+                let unique: std::collections::HashSet<&&str> = selected.iter().collect();
+                if unique.len() != selected.len() {
+                    return Err("Options cannot be selected more than once".to_string());
+                }
+            }
+            "signature" => {
+                let text = match value {
+                    None => "",
+                    Some(v) => v
+                        .as_str()
+                        .ok_or_else(|| "Value must be a string".to_string())?,
+                };
+                if text.is_empty() {
+                    return missing();
+                }
+                // New signatures arrive as data URLs and are stored as images, leaving their id
+                if text.starts_with("data:") {
+                    decode_signature(text)?;
+                } else if Uuid::parse_str(text).is_err() {
+                    return Err("Value must be a signature image".to_string());
+                }
+            }
+            "temperature" => {
+                let Some(value) = value else {
+                    return missing();
+                };
+                // Out-of-range readings are accepted and flagged on submit
+                value
+                    .as_f64()
+                    .filter(|n| n.is_finite())
+                    .ok_or_else(|| "Value must be a number".to_string())?;
+            }
+            "checkbox" => {
+                let checked = match value {
+                    None => false,
+                    Some(v) => v
+                        .as_bool()
+                        .ok_or_else(|| "Value must be true or false".to_string())?,
+                };
+                if !checked {
+                    return missing();
+                }
+            }
+            "dropdown" => {
+                let selected = match value {
+                    None => "",
+                    Some(v) => v
+                        .as_str()
+                        .ok_or_else(|| "Value must be a string".to_string())?,
+                };
+                if selected.is_empty() {
+                    return missing();
+                }
+                if let Some(options) = &props.options
+                    && !options.iter().any(|o| o == selected)
+                {
+                    return Err("Value must be one of the available options".to_string());
+                }
+            }
+            _ => {
+                let text = match value {
+                    None => "",
+                    Some(v) => v
+                        .as_str()
+                        .ok_or_else(|| "Value must be a string".to_string())?,
+                };
+                if text.trim().is_empty() {
+                    return missing();
+                }
+
+                let length = i64::try_from(text.chars().count()).unwrap_or(i64::MAX);
+                if let Some(min_length) = props.min_length
+                    && length < i64::from(min_length)
+                {
+                    return Err(format!(
+                        "Value must be at least {min_length} characters long"
+                    ));
+                }
+                if let Some(max_length) = props.max_length
+                    && length > i64::from(max_length)
+                {
+                    return Err(format!("Value must not exceed {max_length} characters"));
+                }
+
+                match props.input_type.as_deref() {
+                    Some("int") if text.trim().parse::<i64>().is_err() => {
+                        return Err("Value must be a whole number".to_string());
+                    }
+                    Some("float") if !text.trim().parse::<f64>().is_ok_and(|n| n.is_finite()) => {
+                        return Err("Value must be a number".to_string());
+                    }
+                    _ => {}
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Temperature readings in `entry_data` that fall outside their field's `min`/`max`.
+    /// Readings in hidden fields are ignored.
+    fn out_of_range_readings(
+        template_layout: &logs_db::TemplateLayout,
+        entry_data: &serde_json::Value,
+    ) -> Vec<logs_db::OutOfRangeReading> {
+        let rules = logs_db::FieldRules::new(template_layout, entry_data);
+        template_layout
+            .iter()
+            .enumerate()
+            .filter(|(_, field)| field.field_type == "temperature")
+            .filter_map(|(field_index, field)| {
+                let key = field.entry_key(field_index);
+                if rules.is_hidden(&key) {
+                    return None;
+                }
+                let value = entry_data.get(&key)?.as_f64()?;
+                let props = &field.props;
+                let below = props.min.is_some_and(|min| value < f64::from(min));
+                let above = props.max.is_some_and(|max| value > f64::from(max));
+                (below || above).then(|| logs_db::OutOfRangeReading {
+                    field_id: key,
+                    label: props.text.clone(),
+                    value,
+                    min: props.min,
+                    max: props.max,
+                    unit: props.unit.clone(),
+                })
+            })
+            .collect()
+    }
+
+    /// Stores `datetime` values in UTC so entries sort and compare consistently.
+    /// Values must already have been validated.
+    fn normalize_datetimes(
+        template_layout: &logs_db::TemplateLayout,
+        mut entry_data: serde_json::Value,
+    ) -> serde_json::Value {
+        let Some(values) = entry_data.as_object_mut() else {
+            return entry_data;
+        };
+        for (field_index, field) in template_layout.iter().enumerate() {
+            if field.field_type != "datetime" {
+                continue;
+            }
+            if let Some(value) = values.get_mut(&field.entry_key(field_index))
+                && let Some(datetime) = value
+                    .as_str()
+                    .and_then(|v| chrono::DateTime::parse_from_rfc3339(v).ok())
+            {
+                *value = json!(
+                    datetime
+                        .with_timezone(&chrono::Utc)
+                        .to_rfc3339_opts(chrono::SecondsFormat::Secs, true)
+                );
+            }
+        }
+        entry_data
+    }
+
+    /// Stores newly captured signatures as images and replaces their data URLs with the
+    /// stored image id. Signatures already stored must belong to this entry.
+    async fn store_signatures(
+        state: &AppState,
+        entry_id: &str,
+        template_layout: &logs_db::TemplateLayout,
+        mut entry_data: serde_json::Value,
+    ) -> Result<serde_json::Value, (StatusCode, serde_json::Value)> {
+        let Some(values) = entry_data.as_object_mut() else {
+            return Ok(entry_data);
+        };
+
+        for (field_index, field) in template_layout.iter().enumerate() {
+            if field.field_type != "signature" {
+                continue;
+            }
+            let key = field.entry_key(field_index);
+            let Some(text) = values
+                .get(&key)
+                .and_then(serde_json::Value::as_str)
+                .filter(|text| !text.is_empty())
+                .map(str::to_string)
+            else {
+                continue;
+            };
+
+            if text.starts_with("data:") {
+                let (data, content_type) = decode_signature(&text).map_err(|e| {
+                    (
+                        StatusCode::BAD_REQUEST,
+                        json!({
+                            "error": "Entry data failed validation",
+                            "field_errors": { key.clone(): e },
+                        }),
+                    )
+                })?;
+                let signature_id = images_db::upload_entry_signature(
+                    &state.mongodb,
+                    data,
+                    entry_id,
+                    &content_type,
+                )
+                .await
+                .map_err(|e| {
+                    tracing::error!("Failed to store signature: {:?}", e);
+                    (
+                        StatusCode::INTERNAL_SERVER_ERROR,
+                        json!({ "error": "Failed to store signature" }),
+                    )
+                })?;
+                values.insert(key, json!(signature_id));
+            } else {
+                let stored = images_db::get_entry_signature(&state.mongodb, entry_id, &text)
+                    .await
+                    .map_err(|e| {
+                        tracing::error!("Failed to get signature: {:?}", e);
+                        (
+                            StatusCode::INTERNAL_SERVER_ERROR,
+                            json!({ "error": "Failed to get signature" }),
+                        )
+                    })?;
+                if stored.is_none() {
+                    return Err((
+                        StatusCode::BAD_REQUEST,
+                        json!({
+                            "error": "Entry data failed validation",
+                            "field_errors": { key: "Signature not found" },
+                        }),
+                    ));
+                }
+            }
+        }
+
+        Ok(entry_data)
+    }
+
+    /// Deletes signature images of an entry that its data no longer references.
+    async fn remove_unused_signatures(
+        state: &AppState,
+        entry_id: &str,
+        template_layout: &logs_db::TemplateLayout,
+        entry_data: &serde_json::Value,
+    ) {
+        let keep: Vec<String> = template_layout
+            .iter()
+            .enumerate()
+            .filter(|(_, field)| field.field_type == "signature")
+            .filter_map(|(field_index, field)| {
+                entry_data
+                    .get(field.entry_key(field_index))?
+                    .as_str()
+                    .map(str::to_string)
+            })
+            .collect();
+
+        if let Err(e) = images_db::delete_entry_signatures(&state.mongodb, entry_id, &keep).await {
+            tracing::error!("Failed to delete unused signatures: {:?}", e);
+        }
+    }
+
+    /// Retrieves a signature image captured on an entry the user can view.
+    /// Returns the image's content type and data.
+    ///
+    /// # Errors
+    /// Returns an error if the entry or signature is not found or the user cannot view the entry.
+    pub async fn get_entry_signature(
+        state: &AppState,
+        user: &UserRecord,
+        entry_id: &str,
+        signature_id: &str,
+    ) -> Result<(String, Vec<u8>), (StatusCode, serde_json::Value)> {
+        Self::get_log_entry(state, user, entry_id).await?;
+
+        images_db::get_entry_signature(&state.mongodb, entry_id, signature_id)
+            .await
+            .map_err(|e| {
+                tracing::error!("Failed to get signature: {:?}", e);
+                (
+                    StatusCode::INTERNAL_SERVER_ERROR,
+                    json!({ "error": "Failed to get signature" }),
+                )
+            })?
+            .ok_or((
+                StatusCode::NOT_FOUND,
+                json!({ "error": "Signature not found" }),
+            ))
+    }
+
+    /// Loads the template version an entry was created against and validates `entry_data` against it.
+    /// Index-keyed values from older clients are rewritten to field ids and calculated fields
+    /// are recomputed first.
+    /// Returns the normalized entry data and the layout it was validated against.
+    async fn check_entry_data(
+        state: &AppState,
+        entry: &logs_db::LogEntry,
+        entry_data: &serde_json::Value,
+        enforce_required: bool,
+    ) -> Result<(serde_json::Value, logs_db::TemplateLayout), (StatusCode, serde_json::Value)> {
+        let template =
+            logs_db::get_template_by_id(&state.mongodb, &entry.template_id, &entry.company_id)
+                .await
+                .map_err(|e| {
+                    tracing::error!("Failed to get template: {:?}", e);
+                    (
+                        StatusCode::INTERNAL_SERVER_ERROR,
+                        json!({ "error": "Failed to get template" }),
+                    )
+                })?
+                .ok_or((
+                    StatusCode::NOT_FOUND,
+                    json!({ "error": "Template not found" }),
+                ))?;
+
+        let template_layout =
+            logs_db::get_entry_template_layout(&state.mongodb, &template, entry.template_version)
+                .await
+                .map_err(|e| {
+                    tracing::error!("Failed to get template version: {:?}", e);
+                    (
+                        StatusCode::INTERNAL_SERVER_ERROR,
+                        json!({ "error": "Failed to get template version" }),
+                    )
+                })?;
+
+        let entry_data = logs_db::normalize_entry_data_keys(&template_layout, entry_data);
+        let entry_data = logs_db::calculate_fields(&template_layout, &entry_data);
+
+        Self::validate_entry_data(&template_layout, &entry_data, enforce_required)
+            .map(|()| {
+                let entry_data = Self::normalize_datetimes(&template_layout, entry_data);
+                (entry_data, template_layout)
+            })
+            .map_err(|field_errors| {
+                (
+                    StatusCode::BAD_REQUEST,
+                    json!({
+                        "error": "Entry data failed validation",
+                        "field_errors": field_errors,
+                    }),
+                )
+            })
+    }
+
     /// Creates a new log entry draft based on a template.
     ///
     /// # Errors
-    /// Returns an error if the user has no company, the template is not found, or an entry already exists for the period.
+    /// Returns an error if the user has no company, the template is not found, or an entry already exists for the period
+    /// of a scheduled template.
     pub async fn create_log_entry(
         state: &AppState,
         user: &UserRecord,
-        template_name: &str,
+        template_key: logs_db::TemplateKey<'_>,
         period: Option<&str>,
     ) -> Result<String, (StatusCode, serde_json::Value)> {
         let company_id = user.company_id.as_ref().ok_or((
@@ -33,7 +965,7 @@ impl LogEntryService {
             json!({ "error": "User is not associated with a company" }),
         ))?;
 
-        let template = logs_db::get_template_by_name(&state.mongodb, template_name, company_id)
+        let template = logs_db::get_template(&state.mongodb, template_key, company_id)
             .await
             .map_err(|e| {
                 tracing::error!("Failed to get template: {:?}", e);
@@ -47,6 +979,13 @@ impl LogEntryService {
                 json!({ "error": "Template not found" }),
             ))?;
 
+        if template.is_archived() {
+            return Err((
+                StatusCode::CONFLICT,
+                json!({ "error": "Template is archived" }),
+            ));
+        }
+
         match user.role {
             db::UserRole::BranchManager => {
                 if Some(template.branch_id.as_ref()) != Some(user.branch_id.as_ref()) {
@@ -72,13 +1011,30 @@ impl LogEntryService {
         }
 
         let now = chrono::Utc::now();
+        let tz =
+            db::get_branch_timezone(&state.postgres, company_id, template.branch_id.as_deref())
+                .await
+                .map_err(|e| {
+                    tracing::error!("Failed to get timezone: {:?}", e);
+                    (
+                        StatusCode::INTERNAL_SERVER_ERROR,
+                        json!({ "error": "Failed to get timezone" }),
+                    )
+                })?;
         let period_to_use = match period {
+            Some(_) if template.entry_mode == logs_db::EntryMode::Unscheduled => {
+                return Err((
+                    StatusCode::BAD_REQUEST,
+                    json!({ "error": "Entries of unscheduled templates are labelled with the day they are started and cannot be given a period" }),
+                ));
+            }
             Some(p) => {
                 match logs_db::validate_period_business_rules(
                     &template.schedule,
                     p,
                     Some(template.created_at),
                     now,
+                    tz,
                 ) {
                     Ok(normalized) => normalized,
                     Err(err) => {
@@ -94,29 +1050,35 @@ impl LogEntryService {
                                 }),
                                 logs_db::PeriodValidationError::DueDateInFuture => "Period due date cannot be in the future".to_string(),
                                 logs_db::PeriodValidationError::WeekdayNotAllowed => "Period weekday is not allowed for this schedule".to_string(),
+                                logs_db::PeriodValidationError::NotScheduled => "Period is not scheduled for this template".to_string(),
                                 logs_db::PeriodValidationError::BeforeTemplateCreation => "Period cannot be before template creation date".to_string(),
                             }}),
                         ));
                     }
                 }
             }
-            None => logs_db::format_period_for_frequency(&template.schedule.frequency),
+            None if template.entry_mode == logs_db::EntryMode::Unscheduled => {
+                now.with_timezone(&tz).format("%d/%m/%Y").to_string()
+            }
+            None => logs_db::format_period_for_schedule(&template.schedule, tz),
         };
 
-        let has_entry = logs_db::has_entry_for_period(
-            &state.mongodb,
-            company_id,
-            template_name,
-            &period_to_use,
-        )
-        .await
-        .map_err(|e| {
-            tracing::error!("Failed to check for existing entries: {:?}", e);
-            (
-                StatusCode::INTERNAL_SERVER_ERROR,
-                json!({ "error": "Failed to check for existing entries" }),
+        // Repeatable and unscheduled templates take any number of entries per period
+        let has_entry = template.is_scheduled()
+            && logs_db::has_entry_for_period(
+                &state.mongodb,
+                company_id,
+                &template.template_id,
+                &period_to_use,
             )
-        })?;
+            .await
+            .map_err(|e| {
+                tracing::error!("Failed to check for existing entries: {:?}", e);
+                (
+                    StatusCode::INTERNAL_SERVER_ERROR,
+                    json!({ "error": "Failed to check for existing entries" }),
+                )
+            })?;
 
         if has_entry {
             return Err((
@@ -130,7 +1092,8 @@ impl LogEntryService {
 
         let entry = logs_db::LogEntry {
             entry_id: entry_id.clone(),
-            template_name: template_name.to_string(),
+            template_id: template.template_id.clone(),
+            template_name: template.template_name.clone(),
             company_id: company_id.clone(),
             branch_id: template.branch_id.clone(),
             user_id: user.id.clone(),
@@ -140,8 +1103,30 @@ impl LogEntryService {
             submitted_at: None,
             status: logs_db::LogStatus::Draft,
             period,
+            template_version: Some(template.version),
+            flagged: false,
+            corrective_action: None,
+            review: None,
         };
 
+        // Marked before inserting so a concurrent purge cannot delete the template under the entry
+        let available =
+            logs_db::mark_template_has_entries(&state.mongodb, &template.template_id, company_id)
+                .await
+                .map_err(|e| {
+                    tracing::error!("Failed to mark template as used: {:?}", e);
+                    (
+                        StatusCode::INTERNAL_SERVER_ERROR,
+                        json!({ "error": "Failed to create log entry" }),
+                    )
+                })?;
+        if !available {
+            return Err((
+                StatusCode::CONFLICT,
+                json!({ "error": "Template is archived or no longer exists" }),
+            ));
+        }
+
         logs_db::create_log_entry(&state.mongodb, &entry)
             .await
             .map_err(|e| {
@@ -152,6 +1137,16 @@ impl LogEntryService {
                 )
             })?;
 
+        Self::record_transition(
+            state,
+            &entry,
+            logs_db::EntryAction::Created,
+            None,
+            &user.id,
+            None,
+        )
+        .await;
+
         Ok(entry_id)
     }
 
@@ -230,7 +1225,19 @@ impl LogEntryService {
             ));
         }
 
-        logs_db::update_log_entry(&state.mongodb, entry_id, entry_data)
+        if entry.status != logs_db::LogStatus::Draft {
+            return Err((
+                StatusCode::CONFLICT,
+                json!({ "error": "Only draft entries can be edited" }),
+            ));
+        }
+
+        let (entry_data, template_layout) =
+            Self::check_entry_data(state, &entry, entry_data, false).await?;
+        let entry_data =
+            Self::store_signatures(state, entry_id, &template_layout, entry_data).await?;
+
+        logs_db::update_log_entry(&state.mongodb, entry_id, &entry_data)
             .await
             .map_err(|e| {
                 tracing::error!("Failed to update log entry: {:?}", e);
@@ -240,6 +1247,8 @@ impl LogEntryService {
                 )
             })?;
 
+        Self::remove_unused_signatures(state, entry_id, &template_layout, &entry_data).await;
+
         let updated_entry = logs_db::get_log_entry(&state.mongodb, entry_id)
             .await
             .map_err(|e| {
@@ -255,14 +1264,20 @@ impl LogEntryService {
     }
 
     /// Submits a log entry, marking it as final.
+    /// Entries with out-of-range temperature readings are flagged, must include a
+    /// corrective-action note, and raise a corrective-action issue for a manager to close.
+    /// Fields that already had an issue closed are not raised again. Returns the id of the
+    /// raised issue, if any.
     ///
     /// # Errors
-    /// Returns an error if the entry is not found, user doesn't own it, or submission fails.
+    /// Returns an error if the entry is not found, user doesn't own it, a required
+    /// corrective-action note is missing, or submission fails.
     pub async fn submit_log_entry(
         state: &AppState,
         user_id: &str,
         entry_id: &str,
-    ) -> Result<(), (StatusCode, serde_json::Value)> {
+        corrective_action: Option<&str>,
+    ) -> Result<Option<String>, (StatusCode, serde_json::Value)> {
         let entry = logs_db::get_log_entry(&state.mongodb, entry_id)
             .await
             .map_err(|e| {
@@ -281,17 +1296,104 @@ impl LogEntryService {
             ));
         }
 
-        logs_db::submit_log_entry(&state.mongodb, entry_id)
-            .await
-            .map_err(|e| {
-                tracing::error!("Failed to submit log entry: {:?}", e);
-                (
-                    StatusCode::INTERNAL_SERVER_ERROR,
-                    json!({ "error": "Failed to submit log entry" }),
-                )
-            })?;
+        if entry.status != logs_db::LogStatus::Draft {
+            return Err((
+                StatusCode::CONFLICT,
+                json!({ "error": "Only draft entries can be submitted" }),
+            ));
+        }
 
-        Ok(())
+        let (entry_data, template_layout) =
+            Self::check_entry_data(state, &entry, &entry.entry_data, true).await?;
+
+        let readings = Self::out_of_range_readings(&template_layout, &entry_data);
+        let corrective_action = corrective_action
+            .map(str::trim)
+            .filter(|note| !note.is_empty());
+        if corrective_action.is_some_and(|note| note.chars().count() > MAX_NOTE_LENGTH) {
+            return Err((
+                StatusCode::BAD_REQUEST,
+                json!({ "error": format!("Corrective action must not exceed {MAX_NOTE_LENGTH} characters") }),
+            ));
+        }
+
+        if !readings.is_empty() && corrective_action.is_none() {
+            return Err((
+                StatusCode::BAD_REQUEST,
+                json!({
+                    "error": "A corrective action note is required for out-of-range readings",
+                    "out_of_range": readings,
+                }),
+            ));
+        }
+
+        let submitted = logs_db::submit_log_entry(
+            &state.mongodb,
+            entry_id,
+            &entry_data,
+            !readings.is_empty(),
+            corrective_action,
+        )
+        .await
+        .map_err(|e| {
+            tracing::error!("Failed to submit log entry: {:?}", e);
+            (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                json!({ "error": "Failed to submit log entry" }),
+            )
+        })?;
+        if !submitted {
+            return Err((
+                StatusCode::CONFLICT,
+                json!({ "error": "Only draft entries can be submitted" }),
+            ));
+        }
+
+        Self::record_transition(
+            state,
+            &entry,
+            logs_db::EntryAction::Submitted,
+            Some(logs_db::LogStatus::Submitted),
+            user_id,
+            corrective_action,
+        )
+        .await;
+
+        // Raised only once the entry is submitted, so a lost submission race leaves no issue behind
+        let issue_id = if readings.is_empty() {
+            None
+        } else {
+            let issue = logs_db::CorrectiveActionIssue {
+                issue_id: Uuid::new_v4().to_string(),
+                company_id: entry.company_id.clone(),
+                branch_id: entry.branch_id.clone(),
+                entry_id: entry.entry_id.clone(),
+                template_id: entry.template_id.clone(),
+                template_name: entry.template_name.clone(),
+                period: entry.period.clone(),
+                readings,
+                corrective_action: corrective_action.map(str::to_string),
+                status: logs_db::IssueStatus::Open,
+                raised_by: user_id.to_string(),
+                raised_at: chrono::Utc::now(),
+                acknowledged_by: None,
+                acknowledged_at: None,
+                closed_by: None,
+                closed_at: None,
+                resolution_note: None,
+            };
+            logs_db::raise_corrective_action_issue(&state.mongodb, &issue)
+                .await
+                .map_err(|e| {
+                    tracing::error!("Failed to raise corrective action issue: {:?}", e);
+                    (
+                        StatusCode::INTERNAL_SERVER_ERROR,
+                        json!({ "error": "Log entry was submitted but its corrective action issue could not be raised" }),
+                    )
+                })?
+        };
+
+        Ok(issue_id)
     }
 
     /// Returns a submitted log entry to draft status (admin only).
@@ -348,10 +1450,185 @@ impl LogEntryService {
                 )
             })?;
 
+        Self::record_transition(
+            state,
+            &entry,
+            logs_db::EntryAction::Unsubmitted,
+            Some(logs_db::LogStatus::Draft),
+            &user.id,
+            None,
+        )
+        .await;
+
         Ok(())
     }
 
-    /// Deletes a log entry.
+    /// Approves or rejects a submitted entry. Approved entries are final; rejected entries
+    /// return to draft for their author, who sees the reviewer's comment.
+    ///
+    /// # Errors
+    /// Returns an error if the entry is not found, the reviewer cannot review it, a rejection
+    /// has no comment, or the entry is not awaiting review.
+    pub async fn review_log_entry(
+        state: &AppState,
+        user: &UserRecord,
+        entry_id: &str,
+        decision: logs_db::ReviewDecision,
+        comment: Option<&str>,
+    ) -> Result<logs_db::LogEntry, (StatusCode, serde_json::Value)> {
+        let comment = comment.map(str::trim).filter(|c| !c.is_empty());
+        if decision == logs_db::ReviewDecision::Rejected && comment.is_none() {
+            return Err((
+                StatusCode::BAD_REQUEST,
+                json!({ "error": "A comment is required when rejecting an entry" }),
+            ));
+        }
+        if comment.is_some_and(|c| c.chars().count() > MAX_NOTE_LENGTH) {
+            return Err((
+                StatusCode::BAD_REQUEST,
+                json!({ "error": format!("Comment must not exceed {MAX_NOTE_LENGTH} characters") }),
+            ));
+        }
+
+        let entry = logs_db::get_log_entry(&state.mongodb, entry_id)
+            .await
+            .map_err(|e| {
+                tracing::error!("Failed to get log entry: {:?}", e);
+                (
+                    StatusCode::INTERNAL_SERVER_ERROR,
+                    json!({ "error": "Failed to get log entry" }),
+                )
+            })?
+            .ok_or((StatusCode::NOT_FOUND, json!({ "error": "Entry not found" })))?;
+
+        if !Self::can_review(user, &entry) {
+            return Err((
+                StatusCode::FORBIDDEN,
+                json!({ "error": "You do not have permission to review this entry" }),
+            ));
+        }
+        if entry.user_id == user.id {
+            return Err((
+                StatusCode::FORBIDDEN,
+                json!({ "error": "You cannot review your own entry" }),
+            ));
+        }
+
+        let review = logs_db::EntryReview {
+            decision,
+            reviewer_id: user.id.clone(),
+            reviewed_at: chrono::Utc::now(),
+            comment: comment.map(str::to_string),
+        };
+        let reviewed = logs_db::review_log_entry(&state.mongodb, entry_id, &review)
+            .await
+            .map_err(|e| {
+                tracing::error!("Failed to review log entry: {:?}", e);
+                (
+                    StatusCode::INTERNAL_SERVER_ERROR,
+                    json!({ "error": "Failed to review log entry" }),
+                )
+            })?;
+        if !reviewed {
+            return Err((
+                StatusCode::CONFLICT,
+                json!({ "error": "Only submitted entries can be reviewed" }),
+            ));
+        }
+
+        let (action, to_status) = match decision {
+            logs_db::ReviewDecision::Approved => {
+                (logs_db::EntryAction::Approved, logs_db::LogStatus::Approved)
+            }
+            logs_db::ReviewDecision::Rejected => {
+                (logs_db::EntryAction::Rejected, logs_db::LogStatus::Draft)
+            }
+        };
+        Self::record_transition(state, &entry, action, Some(to_status), &user.id, comment).await;
+
+        logs_db::get_log_entry(&state.mongodb, entry_id)
+            .await
+            .map_err(|e| {
+                tracing::error!("Failed to fetch reviewed log entry: {:?}", e);
+                (
+                    StatusCode::INTERNAL_SERVER_ERROR,
+                    json!({ "error": "Failed to fetch reviewed entry" }),
+                )
+            })?
+            .ok_or((StatusCode::NOT_FOUND, json!({ "error": "Entry not found" })))
+    }
+
+    /// Retrieves the status history of an entry the user can view.
+    ///
+    /// # Errors
+    /// Returns an error if the entry is not found, the user cannot view it, or the query fails.
+    pub async fn get_entry_history(
+        state: &AppState,
+        user: &UserRecord,
+        entry_id: &str,
+    ) -> Result<Vec<logs_db::EntryTransition>, (StatusCode, serde_json::Value)> {
+        Self::get_log_entry(state, user, entry_id).await?;
+
+        logs_db::get_entry_transitions(&state.mongodb, entry_id)
+            .await
+            .map_err(|e| {
+                tracing::error!("Failed to get entry history: {:?}", e);
+                (
+                    StatusCode::INTERNAL_SERVER_ERROR,
+                    json!({ "error": "Failed to get entry history" }),
+                )
+            })
+    }
+
+    /// Company managers can review entries in their company; branch managers only entries
+    /// of their own branch.
+    fn can_review(user: &UserRecord, entry: &logs_db::LogEntry) -> bool {
+        if user.company_id.as_deref() != Some(entry.company_id.as_str()) {
+            return false;
+        }
+        if user.can_manage_company() {
+            return true;
+        }
+        user.is_branch_manager() && entry.branch_id.is_some() && entry.branch_id == user.branch_id
+    }
+
+    /// Appends a status change to the entry's audit trail. Failures are logged rather than
+    /// returned, as the transition itself has already been applied.
+    async fn record_transition(
+        state: &AppState,
+        entry: &logs_db::LogEntry,
+        action: logs_db::EntryAction,
+        to_status: Option<logs_db::LogStatus>,
+        actor_id: &str,
+        comment: Option<&str>,
+    ) {
+        let (from_status, to_status) = match to_status {
+            Some(to_status) => (Some(entry.status), to_status),
+            None => (None, entry.status),
+        };
+        let transition = logs_db::EntryTransition {
+            entry_id: entry.entry_id.clone(),
+            company_id: entry.company_id.clone(),
+            action,
+            from_status,
+            to_status,
+            actor_id: actor_id.to_string(),
+            comment: comment.map(str::to_string),
+            at: chrono::Utc::now(),
+        };
+
+        if let Err(e) = logs_db::record_entry_transition(&state.mongodb, &transition).await {
+            tracing::error!(
+                "Failed to record {:?} transition for entry {}: {:?}",
+                action,
+                entry.entry_id,
+                e
+            );
+        }
+    }
+
+    /// Deletes a log entry together with its signatures, attachments, corrective-action
+    /// issues and status history.
     ///
     /// # Errors
     /// Returns an error if the entry is not found, user is not authorized, or deletion fails.
@@ -402,10 +1679,25 @@ impl LogEntryService {
                 )
             })?;
 
+        if let Err(e) = images_db::delete_entry_signatures(&state.mongodb, entry_id, &[]).await {
+            tracing::error!("Failed to delete entry signatures: {:?}", e);
+        }
+        if let Err(e) = attachments_db::delete_entry_attachments(&state.mongodb, entry_id).await {
+            tracing::error!("Failed to delete entry attachments: {:?}", e);
+        }
+        if let Err(e) = logs_db::delete_entry_issues(&state.mongodb, entry_id).await {
+            tracing::error!("Failed to delete entry corrective action issues: {:?}", e);
+        }
+        if let Err(e) = logs_db::delete_entry_transitions(&state.mongodb, entry_id).await {
+            tracing::error!("Failed to delete entry status history: {:?}", e);
+        }
+
         Ok(())
     }
 
-    /// Lists all log templates for a company.
+    /// Lists the log templates for a company, split into scheduled templates, which can fall
+    /// due, and repeatable or unscheduled templates, which are never due but can be started
+    /// at any time.
     ///
     /// # Errors
     /// Returns an error if the database query fails.
@@ -413,16 +1705,24 @@ impl LogEntryService {
         state: &AppState,
         company_id: &str,
         branch_id: Option<&str>,
-    ) -> Result<Vec<logs_db::TemplateDocument>, (StatusCode, serde_json::Value)> {
-        logs_db::get_templates_by_company_and_branch(&state.mongodb, company_id, branch_id)
-            .await
-            .map_err(|e| {
-                tracing::error!("Failed to get templates: {:?}", e);
-                (
-                    StatusCode::INTERNAL_SERVER_ERROR,
-                    json!({ "error": "Failed to get templates" }),
-                )
-            })
+    ) -> Result<
+        (
+            Vec<logs_db::TemplateDocument>,
+            Vec<logs_db::TemplateDocument>,
+        ),
+        (StatusCode, serde_json::Value),
+    > {
+        let templates =
+            logs_db::get_templates_by_company_and_branch(&state.mongodb, company_id, branch_id)
+                .await
+                .map_err(|e| {
+                    tracing::error!("Failed to get templates: {:?}", e);
+                    (
+                        StatusCode::INTERNAL_SERVER_ERROR,
+                        json!({ "error": "Failed to get templates" }),
+                    )
+                })?;
+        Ok(templates.into_iter().partition(|t| t.is_scheduled()))
     }
 
     /// Retrieves all log entries for a user in a company.
@@ -0,0 +1,399 @@
+use crate::{
+    AppState,
+    db::{self, UserRole},
+    email::{self, MissedLogSummary},
+    logs_db, try_db,
+    utils::ServiceError,
+};
+use chrono::{DateTime, Utc};
+use std::collections::{BTreeMap, HashMap};
+
+#[cfg(test)]
+mod missed_log_alert_service_tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    fn daily_schedule(due_at_time: &str) -> logs_db::Schedule {
+        logs_db::Schedule {
+            frequency: logs_db::Frequency::Daily,
+            days_of_week: None,
+            day_of_week: None,
+            day_of_month: None,
+            month_of_year: None,
+            available_from_time: Some("00:00".to_string()),
+            due_at_time: Some(due_at_time.to_string()),
+            interval: None,
+            starts_on: None,
+            time_slots: None,
+        }
+    }
+
+    fn summary(template_name: &str) -> MissedLogSummary {
+        MissedLogSummary {
+            template_name: template_name.to_string(),
+            branch_name: None,
+            period: "01/03/2026".to_string(),
+            escalated: false,
+        }
+    }
+
+    #[test]
+    fn test_newly_overdue_periods_respects_lookback() {
+        let now = Utc.with_ymd_and_hms(2026, 3, 15, 12, 0, 0).unwrap();
+        let schedule = daily_schedule("00:00");
+
+        // Template created long ago: only yesterday and today are within the lookback
+        let periods = newly_overdue_periods(
+            &schedule,
+            now - chrono::Duration::days(30),
+            now,
+            chrono_tz::UTC,
+        );
+        assert_eq!(
+            periods,
+            vec!["14/03/2026".to_string(), "15/03/2026".to_string()]
+        );
+
+        // Template created today: nothing before its creation is reported
+        let periods = newly_overdue_periods(&schedule, now, now, chrono_tz::UTC);
+        assert_eq!(periods, vec!["15/03/2026".to_string()]);
+    }
+
+    #[test]
+    fn test_alert_recipients_fall_back_to_company_managers() {
+        let branch_managers = HashMap::from([(
+            "branch-1".to_string(),
+            vec!["branch@example.com".to_string()],
+        )]);
+        let company_managers = vec!["owner@example.com".to_string()];
+
+        assert_eq!(
+            alert_recipients(Some("branch-1"), &branch_managers, &company_managers),
+            vec!["branch@example.com".to_string()]
+        );
+        assert_eq!(
+            alert_recipients(Some("branch-2"), &branch_managers, &company_managers),
+            company_managers
+        );
+        assert_eq!(
+            alert_recipients(None, &branch_managers, &company_managers),
+            company_managers
+        );
+    }
+
+    #[test]
+    fn test_group_by_recipient() {
+        let notices = vec![
+            ("a@example.com".to_string(), summary("Fridge")),
+            ("b@example.com".to_string(), summary("Fridge")),
+            ("a@example.com".to_string(), summary("Freezer")),
+        ];
+        let grouped = group_by_recipient(notices);
+
+        assert_eq!(grouped.len(), 2);
+        let names: Vec<&str> = grouped["a@example.com"]
+            .iter()
+            .map(|m| m.template_name.as_str())
+            .collect();
+        assert_eq!(names, vec!["Fridge", "Freezer"]);
+    }
+}
+
+pub struct MissedLogAlertService;
+
+/// How often the alert pass runs.
+const ALERT_INTERVAL_SECS: u64 = 900;
+/// Only periods that became due within this many days are alerted on, which bounds the
+/// alerts sent on first run or after downtime.
+const ALERT_LOOKBACK_DAYS: i64 = 1;
+
+/// An email to send: recipient address and the missed period it concerns.
+type Notice = (String, MissedLogSummary);
+
+impl MissedLogAlertService {
+    /// Spawns the background task that alerts on newly overdue logs and escalates
+    /// alerts that stay outstanding.
+    pub fn spawn_alert_task(state: AppState) {
+        tokio::spawn(async move {
+            let mut interval =
+                tokio::time::interval(tokio::time::Duration::from_secs(ALERT_INTERVAL_SECS));
+            loop {
+                interval.tick().await;
+                Self::run(&state, Utc::now()).await;
+            }
+        });
+    }
+
+    /// Runs one alerting pass over every active company. Failures are logged per company.
+    pub async fn run(state: &AppState, now: DateTime<Utc>) {
+        let company_ids = match db::get_active_company_ids(&state.postgres).await {
+            Ok(ids) => ids,
+            Err(e) => {
+                tracing::error!("Failed to list companies for missed log alerts: {:?}", e);
+                return;
+            }
+        };
+
+        for company_id in company_ids {
+            if let Err((_, err)) = Self::run_for_company(state, &company_id, now).await {
+                tracing::error!(
+                    "Missed log alerts failed for company {}: {}",
+                    company_id,
+                    err
+                );
+            }
+        }
+    }
+
+    /// Alerts branch managers about newly overdue periods, resolves alerts whose period has
+    /// since been completed, and escalates the rest to company managers once they have been
+    /// outstanding for the configured number of hours.
+    ///
+    /// # Errors
+    /// Returns an error if a database query fails.
+    pub async fn run_for_company(
+        state: &AppState,
+        company_id: &str,
+        now: DateTime<Utc>,
+    ) -> Result<(), ServiceError> {
+        let settings = try_db!(
+            db::get_missed_log_alert_settings(&state.postgres, company_id),
+            "fetching missed log alert settings"
+        )?;
+        if !settings.enabled {
+            return Ok(());
+        }
+
+        let Some(company) = try_db!(
+            db::get_company_by_id(&state.postgres, company_id),
+            "fetching company"
+        )?
+        else {
+            return Ok(());
+        };
+
+        let templates: HashMap<String, logs_db::TemplateDocument> = try_db!(
+            logs_db::get_templates_by_company(&state.mongodb, company_id),
+            "fetching templates"
+        )?
+        .into_iter()
+        .map(|t| (t.template_id.clone(), t))
+        .collect();
+
+        let timezones = try_db!(
+            db::get_company_timezones(&state.postgres, company_id),
+            "fetching timezones"
+        )?;
+        let calendars = try_db!(
+            db::get_company_calendars(&state.postgres, company_id),
+            "fetching branch calendars"
+        )?;
+
+        let branch_names: HashMap<String, String> = try_db!(
+            db::get_branches_by_company_id(&state.postgres, company_id),
+            "fetching branches"
+        )?
+        .into_iter()
+        .map(|b| (b.id, b.name))
+        .collect();
+
+        let mut branch_managers: HashMap<String, Vec<String>> = HashMap::new();
+        let mut company_managers = Vec::new();
+        for user in try_db!(
+            db::get_users_by_company_id(&state.postgres, company_id),
+            "fetching users"
+        )? {
+            match (user.role, user.branch_id) {
+                (UserRole::BranchManager, Some(branch_id)) => {
+                    branch_managers
+                        .entry(branch_id)
+                        .or_default()
+                        .push(user.email);
+                }
+                (UserRole::CompanyManager, _) => company_managers.push(user.email),
+                _ => {}
+            }
+        }
+
+        let summarize =
+            |template: &logs_db::TemplateDocument, period: &str, escalated| MissedLogSummary {
+                template_name: template.template_name.clone(),
+                branch_name: template
+                    .branch_id
+                    .as_ref()
+                    .and_then(|id| branch_names.get(id).cloned()),
+                period: period.to_string(),
+                escalated,
+            };
+
+        let mut notices: Vec<Notice> = Vec::new();
+
+        // Newly overdue periods go to the responsible branch managers
+        for template in templates.values().filter(|t| t.is_scheduled()) {
+            let mut overdue = newly_overdue_periods(
+                &template.schedule,
+                template.created_at,
+                now,
+                timezones.for_branch(template.branch_id.as_deref()),
+            );
+            overdue.retain(|period| {
+                calendars.is_period_open(template.branch_id.as_deref(), &template.schedule, period)
+            });
+            let completed = try_db!(
+                logs_db::get_completed_periods(
+                    &state.mongodb,
+                    company_id,
+                    &template.template_id,
+                    &overdue
+                ),
+                "fetching completed periods"
+            )?;
+
+            for period in overdue.iter().filter(|p| !completed.contains(*p)) {
+                let is_new = try_db!(
+                    db::record_missed_log_alert(
+                        &state.postgres,
+                        company_id,
+                        template.branch_id.as_deref(),
+                        &template.template_id,
+                        &template.template_name,
+                        period,
+                    ),
+                    "recording missed log alert"
+                )?;
+                if !is_new {
+                    continue;
+                }
+
+                for recipient in alert_recipients(
+                    template.branch_id.as_deref(),
+                    &branch_managers,
+                    &company_managers,
+                ) {
+                    notices.push((recipient, summarize(template, period, false)));
+                }
+            }
+        }
+
+        // Outstanding alerts are resolved or escalated
+        let open_alerts = try_db!(
+            db::get_open_missed_log_alerts(&state.postgres, company_id),
+            "fetching open missed log alerts"
+        )?;
+        let mut alerts_by_template: BTreeMap<String, Vec<db::MissedLogAlert>> = BTreeMap::new();
+        for alert in open_alerts {
+            alerts_by_template
+                .entry(alert.template_id.clone().unwrap_or_default())
+                .or_default()
+                .push(alert);
+        }
+
+        let escalate_after = chrono::Duration::hours(i64::from(settings.escalation_hours));
+        for (template_id, alerts) in alerts_by_template {
+            let periods: Vec<String> = alerts.iter().map(|a| a.period.clone()).collect();
+            let completed = try_db!(
+                logs_db::get_completed_periods(&state.mongodb, company_id, &template_id, &periods),
+                "fetching completed periods"
+            )?;
+            let template = templates.get(&template_id);
+
+            for alert in alerts {
+                let Some(template) = template.filter(|_| !completed.contains(&alert.period)) else {
+                    // Completed since the alert, or the template no longer exists
+                    try_db!(
+                        db::mark_missed_log_alert_resolved(&state.postgres, &alert.id),
+                        "resolving missed log alert"
+                    )?;
+                    continue;
+                };
+
+                if settings.escalation_hours <= 0 || now - alert.alerted_at < escalate_after {
+                    continue;
+                }
+
+                for recipient in &company_managers {
+                    notices.push((recipient.clone(), summarize(template, &alert.period, true)));
+                }
+                try_db!(
+                    db::mark_missed_log_alert_escalated(&state.postgres, &alert.id),
+                    "escalating missed log alert"
+                )?;
+            }
+        }
+
+        send_notices(&company.name, notices, settings.digest).await;
+        Ok(())
+    }
+}
+
+/// Periods of a schedule that are overdue at `now`, became due within the lookback window
+/// and fall on or after the template's creation date, all in the template's timezone.
+fn newly_overdue_periods(
+    schedule: &logs_db::Schedule,
+    template_created_at: DateTime<Utc>,
+    now: DateTime<Utc>,
+    tz: chrono_tz::Tz,
+) -> Vec<String> {
+    let lookback_start = now - chrono::Duration::days(ALERT_LOOKBACK_DAYS);
+    let start = template_created_at.max(lookback_start);
+    let start = start
+        .with_timezone(&tz)
+        .date_naive()
+        .and_hms_opt(0, 0, 0)
+        .and_then(|dt| dt.and_local_timezone(tz).earliest())
+        .map(|dt| dt.to_rfc3339());
+
+    logs_db::get_missed_periods(schedule, None, start.as_deref(), tz)
+        .into_iter()
+        .filter(|period| {
+            logs_db::get_availability_status_for_period(schedule, period, now, tz)
+                == logs_db::AvailabilityStatus::Overdue
+        })
+        .collect()
+}
+
+/// Branch managers of the template's branch, or the company managers when the template is
+/// company-wide or its branch has no manager.
+fn alert_recipients(
+    branch_id: Option<&str>,
+    branch_managers: &HashMap<String, Vec<String>>,
+    company_managers: &[String],
+) -> Vec<String> {
+    branch_id
+        .and_then(|id| branch_managers.get(id))
+        .filter(|managers| !managers.is_empty())
+        .cloned()
+        .unwrap_or_else(|| company_managers.to_vec())
+}
+
+fn group_by_recipient(notices: Vec<Notice>) -> BTreeMap<String, Vec<MissedLogSummary>> {
+    let mut grouped: BTreeMap<String, Vec<MissedLogSummary>> = BTreeMap::new();
+    for (recipient, missed) in notices {
+        grouped.entry(recipient).or_default().push(missed);
+    }
+    grouped
+}
+
+async fn send_notices(company_name: &str, notices: Vec<Notice>, digest: bool) {
+    let frontend_url =
+        std::env::var("FRONTEND_URL").unwrap_or_else(|_| "http://localhost:5173".to_string());
+    let logs_link = format!("{frontend_url}/logs-list");
+
+    if digest {
+        for (recipient, missed) in group_by_recipient(notices) {
+            if let Err(e) =
+                email::send_missed_log_digest(&recipient, company_name, &missed, &logs_link).await
+            {
+                tracing::warn!("Failed to send missed log digest to {}: {:?}", recipient, e);
+            }
+        }
+    } else {
+        for (recipient, missed) in notices {
+            if let Err(e) =
+                email::send_missed_log_alert(&recipient, company_name, &missed, &logs_link).await
+            {
+                tracing::warn!("Failed to send missed log alert to {}: {:?}", recipient, e);
+            }
+        }
+    }
+}
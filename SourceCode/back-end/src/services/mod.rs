@@ -1,17 +1,33 @@
+pub mod attachment_service;
 pub mod auth_service;
+pub mod branch_calendar_service;
 pub mod clock_service;
 pub mod company_service;
+pub mod compliance_digest_service;
+pub mod corrective_action_service;
 pub mod invitation_service;
 pub mod log_entry_service;
+pub mod missed_log_alert_service;
 pub mod oauth_service;
+pub mod report_service;
+pub mod template_bundle_service;
+pub mod template_library_service;
 pub mod template_service;
 pub mod user_service;
 
+pub use attachment_service::AttachmentService;
 pub use auth_service::AuthService;
+pub use branch_calendar_service::BranchCalendarService;
 pub use clock_service::ClockService;
 pub use company_service::CompanyService;
+pub use compliance_digest_service::ComplianceDigestService;
+pub use corrective_action_service::CorrectiveActionService;
 pub use invitation_service::InvitationService;
 pub use log_entry_service::LogEntryService;
+pub use missed_log_alert_service::MissedLogAlertService;
 pub use oauth_service::GoogleOAuthClient;
-pub use template_service::TemplateService;
+pub use report_service::ReportService;
+pub use template_bundle_service::TemplateBundleService;
+pub use template_library_service::{LibraryTemplateDetails, TemplateLibraryService};
+pub use template_service::{NewTemplate, TemplateService};
 pub use user_service::UserService;
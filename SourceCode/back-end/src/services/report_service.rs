@@ -0,0 +1,1515 @@
+use crate::{
+    AppState,
+    db::{self, UserRecord},
+    dto::{ExportLogEntriesQuery, ReportRunParams},
+    images_db, logs_db,
+    pdf::{self, Color, Font, PdfDocument},
+    try_db,
+    utils::{
+        ServiceError, csv_escape, svc_err_bad_request, svc_err_forbidden, svc_err_internal,
+        svc_err_not_found,
+    },
+    xlsx::{self, Cell},
+};
+use chrono::{DateTime, NaiveDate, Utc};
+use std::collections::{BTreeMap, HashMap, HashSet};
+
+#[cfg(test)]
+mod report_service_tests {
+    use super::*;
+
+    fn temperature_field(field_id: &str) -> logs_db::TemplateField {
+        logs_db::TemplateField {
+            field_id: field_id.to_string(),
+            field_type: "temperature".to_string(),
+            position: logs_db::Position { x: 0.0, y: 0.0 },
+            props: logs_db::TemplateFieldProps {
+                text: Some("Fridge".to_string()),
+                unit: Some("°C".to_string()),
+                min: Some(0.0),
+                max: Some(8.0),
+                ..Default::default()
+            },
+        }
+    }
+
+    fn sample_entry(period: &str, value: f64, day: u32) -> ReportEntry {
+        let created_at = NaiveDate::from_ymd_opt(2026, 3, day)
+            .unwrap()
+            .and_hms_opt(9, 0, 0)
+            .unwrap()
+            .and_utc();
+        ReportEntry {
+            template_name: "Fridge Temps".to_string(),
+            period: period.to_string(),
+            status: logs_db::LogStatus::Submitted,
+            recorded_by: "Jane Smith".to_string(),
+            created_at,
+            submitted_at: Some(created_at),
+            template_layout: vec![temperature_field("fridge")],
+            entry_data: serde_json::json!({ "fridge": value }),
+        }
+    }
+
+    #[test]
+    fn test_parse_report_date() {
+        assert_eq!(
+            parse_report_date("2026-03-01"),
+            NaiveDate::from_ymd_opt(2026, 3, 1)
+        );
+        assert_eq!(
+            parse_report_date("2026-03-01T00:00:00.000Z"),
+            NaiveDate::from_ymd_opt(2026, 3, 1)
+        );
+        assert_eq!(parse_report_date("01/03/2026"), None);
+    }
+
+    #[test]
+    fn test_excluded_field_types() {
+        assert!(excluded_field_types(&[]).is_empty());
+        assert!(excluded_field_types(&["all".to_string()]).is_empty());
+
+        let excluded = excluded_field_types(&["type3".to_string()]);
+        assert!(excluded.contains("text"));
+        assert!(excluded.contains("text_input"));
+        assert!(excluded.contains("checkbox"));
+        assert!(excluded.contains("dropdown"));
+        assert!(!excluded.contains("temperature"));
+    }
+
+    #[test]
+    fn test_format_field_value() {
+        let field = temperature_field("fridge");
+        assert_eq!(
+            format_field_value(&field, Some(&serde_json::json!(4.5))),
+            ("4.5°C".to_string(), false)
+        );
+        assert_eq!(
+            format_field_value(&field, Some(&serde_json::json!(9))),
+            ("9°C".to_string(), true)
+        );
+        assert_eq!(format_field_value(&field, None), ("-".to_string(), false));
+    }
+
+    #[test]
+    fn test_temperature_series_requires_two_points() {
+        let entries = vec![sample_entry("01/03/2026", 3.0, 1)];
+        assert!(temperature_series(&entries).is_empty());
+
+        let entries = vec![
+            sample_entry("02/03/2026", 5.0, 2),
+            sample_entry("01/03/2026", 3.0, 1),
+        ];
+        let series = temperature_series(&entries);
+        assert_eq!(series.len(), 1);
+        assert_eq!(series[0].points.len(), 2);
+        assert!(series[0].points[0].0 < series[0].points[1].0);
+    }
+
+    #[test]
+    fn test_export_columns_merge_versions() {
+        let mut current = vec![temperature_field("fridge")];
+        current.push(logs_db::TemplateField {
+            field_id: "notes".to_string(),
+            field_type: "text_input".to_string(),
+            position: logs_db::Position { x: 0.0, y: 0.0 },
+            props: logs_db::TemplateFieldProps {
+                text: Some("Notes".to_string()),
+                ..Default::default()
+            },
+        });
+        let mut removed = temperature_field("freezer");
+        removed.props.text = Some("Freezer".to_string());
+        let previous = vec![temperature_field("fridge"), removed];
+
+        let columns = export_columns([&current, &previous]);
+        let headers: Vec<&str> = columns.iter().map(|c| c.header.as_str()).collect();
+        assert_eq!(headers, vec!["Fridge (°C)", "Notes", "Freezer (°C)"]);
+        assert_eq!(columns[2].key, "freezer");
+    }
+
+    #[test]
+    fn test_export_cell() {
+        assert_eq!(
+            export_cell("temperature", Some(&serde_json::json!(-18.5))),
+            Cell::Number(-18.5)
+        );
+        assert_eq!(
+            export_cell("checkbox", Some(&serde_json::json!(true))),
+            Cell::Text("Yes".to_string())
+        );
+        assert_eq!(
+            export_cell("calculated", Some(&serde_json::json!(2.5))),
+            Cell::Number(2.5)
+        );
+        assert_eq!(
+            export_cell(
+                "multi_select",
+                Some(&serde_json::json!(["Floors", "Walls"]))
+            ),
+            Cell::Text("Floors, Walls".to_string())
+        );
+        assert_eq!(
+            export_cell("signature", Some(&serde_json::json!("4b9f0c1e"))),
+            Cell::Text("Signed".to_string())
+        );
+        assert_eq!(export_cell("text", None), Cell::Empty);
+    }
+
+    #[test]
+    fn test_rows_to_csv() {
+        let rows = vec![
+            vec![
+                Cell::Text("Notes".to_string()),
+                Cell::Text("Temp".to_string()),
+            ],
+            vec![Cell::Text("=SUM(A1)".to_string()), Cell::Number(-18.5)],
+            vec![Cell::Empty, Cell::Number(4.0)],
+        ];
+        assert_eq!(
+            rows_to_csv(&rows),
+            "\"Notes\",\"Temp\"\n\"'=SUM(A1)\",-18.5\n\"\",4\n"
+        );
+    }
+
+    #[test]
+    fn test_filename_slug() {
+        assert_eq!(filename_slug("Fridge Temps (AM)"), "fridge-temps-am");
+        assert_eq!(filename_slug("***"), "template");
+    }
+
+    #[test]
+    fn test_render_report_pdf() {
+        let entries = vec![
+            sample_entry("02/03/2026", 9.5, 2),
+            sample_entry("01/03/2026", 3.0, 1),
+        ];
+        let report = ReportData {
+            company_name: "Acme Foods".to_string(),
+            logo: None,
+            date_from: NaiveDate::from_ymd_opt(2026, 3, 1).unwrap(),
+            date_to: NaiveDate::from_ymd_opt(2026, 3, 7).unwrap(),
+            arrange_by_log_type: true,
+            generated_at: Utc::now(),
+            charts: temperature_series(&entries),
+            sections: vec![BranchSection {
+                name: "Plymouth".to_string(),
+                entries,
+                missed_periods: BTreeMap::from([(
+                    "Fridge Temps".to_string(),
+                    vec!["03/03/2026".to_string()],
+                )]),
+            }],
+        };
+
+        let bytes = render_report_pdf(&report);
+        let text = String::from_utf8_lossy(&bytes);
+        assert!(text.starts_with("%PDF-1.4"));
+        assert!(text.contains("(Acme Foods) Tj"));
+        assert!(text.contains("(Plymouth) Tj"));
+        assert!(text.contains("03/03/2026"));
+    }
+}
+
+pub struct ReportService;
+
+const MARGIN: f32 = 50.0;
+const CONTENT_WIDTH: f32 = pdf::PAGE_WIDTH - 2.0 * MARGIN;
+const CHART_HEIGHT: f32 = 150.0;
+
+/// Report log type ids, as used by the reports page, and the field types each one covers.
+const LOG_TYPE_FIELD_TYPES: [(&str, &[&str]); 4] = [
+    ("type1", &["text", "text_input"]),
+    ("type2", &["checkbox"]),
+    ("type3", &["temperature"]),
+    ("type4", &["dropdown"]),
+];
+
+/// A log entry resolved against the layout it was captured with.
+struct ReportEntry {
+    template_name: String,
+    period: String,
+    status: logs_db::LogStatus,
+    recorded_by: String,
+    created_at: DateTime<Utc>,
+    submitted_at: Option<DateTime<Utc>>,
+    template_layout: logs_db::TemplateLayout,
+    entry_data: serde_json::Value,
+}
+
+struct BranchSection {
+    name: String,
+    entries: Vec<ReportEntry>,
+    /// Missed periods keyed by template name.
+    missed_periods: BTreeMap<String, Vec<String>>,
+}
+
+struct TemperatureSeries {
+    template_name: String,
+    field_label: String,
+    unit: String,
+    min: Option<f32>,
+    max: Option<f32>,
+    points: Vec<(DateTime<Utc>, f64)>,
+}
+
+struct ReportLogo {
+    width: u32,
+    height: u32,
+    jpeg: Vec<u8>,
+}
+
+struct ReportData {
+    company_name: String,
+    logo: Option<ReportLogo>,
+    date_from: NaiveDate,
+    date_to: NaiveDate,
+    arrange_by_log_type: bool,
+    generated_at: DateTime<Utc>,
+    sections: Vec<BranchSection>,
+    charts: Vec<TemperatureSeries>,
+}
+
+/// A generated tabular export, ready to be sent as a download.
+pub struct TabularExport {
+    pub filename: String,
+    pub content_type: &'static str,
+    pub data: Vec<u8>,
+}
+
+enum ExportFormat {
+    Csv,
+    Xlsx,
+}
+
+const XLSX_CONTENT_TYPE: &str = "application/vnd.openxmlformats-officedocument.spreadsheetml.sheet";
+
+const EXPORT_BASE_HEADERS: [&str; 8] = [
+    "Entry ID",
+    "Branch",
+    "Period",
+    "Status",
+    "Recorded by",
+    "Created at",
+    "Submitted at",
+    "Template version",
+];
+
+/// A template field column in a tabular export.
+#[derive(Debug)]
+struct ExportColumn {
+    key: String,
+    field_type: String,
+    header: String,
+}
+
+impl ReportService {
+    /// Runs a report for the given parameters and renders it as a PDF.
+    ///
+    /// Entries are scoped the same way as the company log entries listing: company managers
+    /// and HQ staff see the selected branches (or the whole company), branch managers only
+    /// their own branch.
+    ///
+    /// # Errors
+    /// Returns an error if the parameters are invalid, the user has no company, or a database query fails.
+    pub async fn generate_report_pdf(
+        state: &AppState,
+        user: &UserRecord,
+        params: &ReportRunParams,
+    ) -> Result<Vec<u8>, ServiceError> {
+        let report = Self::build_report_data(state, user, params).await?;
+        Ok(render_report_pdf(&report))
+    }
+
+    /// Exports a template's log entries as a table with one row per entry and one column
+    /// per template field, as CSV or XLSX.
+    ///
+    /// Entries are scoped the same way as the company log entries listing. Fields that only
+    /// exist in older template versions get their own columns after the current layout.
+    ///
+    /// # Errors
+    /// Returns an error if the query is invalid, the template is not found, or a database query fails.
+    pub async fn export_template_entries(
+        state: &AppState,
+        user: &UserRecord,
+        query: &ExportLogEntriesQuery,
+    ) -> Result<TabularExport, ServiceError> {
+        let company_id = user
+            .company_id
+            .as_deref()
+            .ok_or(svc_err_forbidden("User is not associated with a company"))?;
+
+        let format = match query.format.as_deref().unwrap_or("csv") {
+            "csv" => ExportFormat::Csv,
+            "xlsx" => ExportFormat::Xlsx,
+            _ => return Err(svc_err_bad_request("format must be 'csv' or 'xlsx'")),
+        };
+
+        let parse_bound = |value: Option<&str>| match value.filter(|v| !v.is_empty()) {
+            Some(raw) => parse_report_date(raw).map(Some).ok_or(svc_err_bad_request(
+                "date_from and date_to must be dates in YYYY-MM-DD format",
+            )),
+            None => Ok(None),
+        };
+        let date_from = parse_bound(query.date_from.as_deref())?;
+        let date_to = parse_bound(query.date_to.as_deref())?;
+        if let (Some(from), Some(to)) = (date_from, date_to)
+            && from > to
+        {
+            return Err(svc_err_bad_request("date_from must not be after date_to"));
+        }
+
+        let template_key = crate::services::TemplateService::template_key(
+            query.template_id.as_deref(),
+            query.template_name.as_deref(),
+        )?;
+        let template = try_db!(
+            logs_db::get_template(&state.mongodb, template_key, company_id),
+            "fetching template"
+        )?
+        .ok_or(svc_err_not_found("Template not found"))?;
+
+        let requested_branch_ids: Vec<String> = query
+            .branch_ids
+            .as_deref()
+            .unwrap_or_default()
+            .split(',')
+            .map(|id| id.trim().to_string())
+            .collect();
+        let branch_scope = report_branch_scope(user, &requested_branch_ids)?;
+
+        let timezones = try_db!(
+            db::get_company_timezones(&state.postgres, company_id),
+            "fetching timezones"
+        )?;
+        let entries = entries_created_between(
+            state,
+            company_id,
+            &EntryScope {
+                branch_ids: branch_scope,
+                template_ids: Some(vec![template.template_id.clone()]),
+            },
+            &timezones,
+            date_from,
+            date_to,
+        )
+        .await?;
+
+        let branch_names: HashMap<String, String> = try_db!(
+            db::get_branches_by_company_id(&state.postgres, company_id),
+            "fetching branches"
+        )?
+        .into_iter()
+        .map(|b| (b.id, b.name))
+        .collect();
+
+        let user_names: HashMap<String, String> = try_db!(
+            db::get_all_users_by_company_id(&state.postgres, company_id),
+            "fetching users"
+        )?
+        .into_iter()
+        .map(|u| (u.id, format!("{} {}", u.first_name, u.last_name)))
+        .collect();
+
+        let mut archived_layouts = logs_db::TemplateLayoutCache::default();
+        let mut entry_layouts = Vec::with_capacity(entries.len());
+        for entry in &entries {
+            entry_layouts.push(try_db!(
+                archived_layouts.entry_layout(&state.mongodb, &template, entry.template_version),
+                "fetching template version"
+            )?);
+        }
+
+        let columns =
+            export_columns(std::iter::once(&template.template_layout).chain(entry_layouts.iter()));
+
+        let mut rows = Vec::with_capacity(entries.len() + 1);
+        rows.push(
+            EXPORT_BASE_HEADERS
+                .iter()
+                .map(|h| (*h).to_string())
+                .chain(columns.iter().map(|c| c.header.clone()))
+                .map(Cell::Text)
+                .collect::<Vec<_>>(),
+        );
+
+        let now = Utc::now();
+        for entry in &entries {
+            let (status, _) = template.derive_entry_status(
+                entry.status,
+                &entry.period,
+                now,
+                timezones.for_branch(entry.branch_id.as_deref()),
+            );
+            let text = |value: String| Cell::Text(value);
+
+            let mut row = vec![
+                text(entry.entry_id.clone()),
+                entry.branch_id.as_ref().map_or(Cell::Empty, |id| {
+                    text(branch_names.get(id).cloned().unwrap_or_else(|| id.clone()))
+                }),
+                text(entry.period.clone()),
+                text(status_label(status).to_string()),
+                text(
+                    user_names
+                        .get(&entry.user_id)
+                        .cloned()
+                        .unwrap_or_else(|| "Unknown user".to_string()),
+                ),
+                text(entry.created_at.to_rfc3339()),
+                entry
+                    .submitted_at
+                    .map_or(Cell::Empty, |at| text(at.to_rfc3339())),
+                entry
+                    .template_version
+                    .map_or(Cell::Empty, |v| Cell::Number(f64::from(v))),
+            ];
+            row.extend(
+                columns
+                    .iter()
+                    .map(|c| export_cell(&c.field_type, entry.entry_data.get(&c.key))),
+            );
+            rows.push(row);
+        }
+
+        let file_stem = format!(
+            "{}-entries-{}",
+            filename_slug(&template.template_name),
+            now.format("%Y%m%d")
+        );
+
+        match format {
+            ExportFormat::Csv => Ok(TabularExport {
+                filename: format!("{file_stem}.csv"),
+                content_type: "text/csv; charset=utf-8",
+                data: rows_to_csv(&rows).into_bytes(),
+            }),
+            ExportFormat::Xlsx => {
+                let data = xlsx::write_workbook(&template.template_name, &rows).map_err(|e| {
+                    tracing::error!("Failed to build XLSX export: {:?}", e);
+                    svc_err_internal("Failed to build export")
+                })?;
+                Ok(TabularExport {
+                    filename: format!("{file_stem}.xlsx"),
+                    content_type: XLSX_CONTENT_TYPE,
+                    data,
+                })
+            }
+        }
+    }
+
+    #[allow(clippy::too_many_lines)]
+    async fn build_report_data(
+        state: &AppState,
+        user: &UserRecord,
+        params: &ReportRunParams,
+    ) -> Result<ReportData, ServiceError> {
+        let company_id = user
+            .company_id
+            .as_deref()
+            .ok_or(svc_err_forbidden("User is not associated with a company"))?;
+
+        let (Some(date_from), Some(date_to)) = (
+            parse_report_date(&params.date_from_iso),
+            parse_report_date(&params.date_to_iso),
+        ) else {
+            return Err(svc_err_bad_request(
+                "date_from_iso and date_to_iso must be dates in YYYY-MM-DD format",
+            ));
+        };
+        if date_from > date_to {
+            return Err(svc_err_bad_request(
+                "date_from_iso must not be after date_to_iso",
+            ));
+        }
+
+        let branch_scope = report_branch_scope(user, &params.selected_branch_ids)?;
+        let timezones = try_db!(
+            db::get_company_timezones(&state.postgres, company_id),
+            "fetching timezones"
+        )?;
+        let entries = entries_created_between(
+            state,
+            company_id,
+            &EntryScope {
+                branch_ids: branch_scope.clone(),
+                template_ids: None,
+            },
+            &timezones,
+            Some(date_from),
+            Some(date_to),
+        )
+        .await?;
+
+        let templates: Vec<logs_db::TemplateDocument> = try_db!(
+            logs_db::get_templates_by_company_including_archived(&state.mongodb, company_id),
+            "fetching templates"
+        )?
+        .into_iter()
+        .filter(|t| match &branch_scope {
+            None => true,
+            Some(branch_ids) => t
+                .branch_id
+                .as_ref()
+                .is_some_and(|id| branch_ids.contains(id)),
+        })
+        .collect();
+
+        let company = try_db!(
+            db::get_company_by_id(&state.postgres, company_id),
+            "fetching company"
+        )?
+        .ok_or(svc_err_not_found("Company not found"))?;
+
+        let calendars = try_db!(
+            db::get_company_calendars(&state.postgres, company_id),
+            "fetching branch calendars"
+        )?;
+
+        let branch_names: HashMap<String, String> = try_db!(
+            db::get_branches_by_company_id(&state.postgres, company_id),
+            "fetching branches"
+        )?
+        .into_iter()
+        .map(|b| (b.id, b.name))
+        .collect();
+
+        let user_names: HashMap<String, String> = try_db!(
+            db::get_all_users_by_company_id(&state.postgres, company_id),
+            "fetching users"
+        )?
+        .into_iter()
+        .map(|u| (u.id, format!("{} {}", u.first_name, u.last_name)))
+        .collect();
+
+        let logo = match company.logo_id.as_deref() {
+            Some(logo_id) => match images_db::get_company_logo(&state.mongodb, logo_id).await {
+                Ok(Some((_, data))) => prepare_logo(&data),
+                Ok(None) => None,
+                Err(e) => {
+                    tracing::warn!("Failed to load company logo for report: {:?}", e);
+                    None
+                }
+            },
+            None => None,
+        };
+
+        let excluded = excluded_field_types(&params.selected_log_type_ids);
+        let templates_by_id: HashMap<&str, &logs_db::TemplateDocument> = templates
+            .iter()
+            .map(|t| (t.template_id.as_str(), t))
+            .collect();
+
+        // Periods that already have a completed entry, per template. Entries for a period in
+        // range may be submitted after the range ends, so only the start is bounded.
+        let mut completed_periods: HashMap<String, HashSet<String>> = HashMap::new();
+        let (range_start, _) = timezones.utc_range(date_from, date_to);
+        for (template_id, period) in try_db!(
+            logs_db::get_completed_entry_periods(
+                &state.mongodb,
+                company_id,
+                branch_scope.as_deref(),
+                range_start,
+            ),
+            "fetching completed periods"
+        )? {
+            completed_periods
+                .entry(template_id)
+                .or_default()
+                .insert(period);
+        }
+
+        let mut archived_layouts = logs_db::TemplateLayoutCache::default();
+        let mut sections: BTreeMap<Option<String>, Vec<ReportEntry>> = BTreeMap::new();
+
+        for entry in &entries {
+            let template = templates_by_id.get(entry.template_id.as_str());
+            let template_layout = match template {
+                Some(template) => try_db!(
+                    archived_layouts.entry_layout(&state.mongodb, template, entry.template_version),
+                    "fetching template version"
+                )?,
+                None => Vec::new(),
+            };
+
+            let has_remaining_fields = template_layout.is_empty()
+                || template_layout
+                    .iter()
+                    .any(|f| !excluded.contains(f.field_type.as_str()));
+            if !has_remaining_fields {
+                continue;
+            }
+
+            let status = template.map_or(entry.status, |t| {
+                t.derive_entry_status(
+                    entry.status,
+                    &entry.period,
+                    Utc::now(),
+                    timezones.for_branch(entry.branch_id.as_deref()),
+                )
+                .0
+            });
+
+            sections
+                .entry(entry.branch_id.clone())
+                .or_default()
+                .push(ReportEntry {
+                    template_name: entry.template_name.clone(),
+                    period: entry.period.clone(),
+                    status,
+                    recorded_by: user_names
+                        .get(&entry.user_id)
+                        .cloned()
+                        .unwrap_or_else(|| "Unknown user".to_string()),
+                    created_at: entry.created_at,
+                    submitted_at: entry.submitted_at,
+                    template_layout: template_layout
+                        .into_iter()
+                        .filter(|f| !excluded.contains(f.field_type.as_str()))
+                        .collect(),
+                    entry_data: entry.entry_data.clone(),
+                });
+        }
+
+        // Missed periods within the report range, grouped by the template's branch
+        let mut missed: BTreeMap<Option<String>, BTreeMap<String, Vec<String>>> = BTreeMap::new();
+        for template in templates
+            .iter()
+            .filter(|t| !t.is_archived() && t.is_scheduled())
+        {
+            let tz = timezones.for_branch(template.branch_id.as_deref());
+            let start = template
+                .created_at
+                .with_timezone(&tz)
+                .date_naive()
+                .max(date_from);
+            let start = start
+                .and_hms_opt(0, 0, 0)
+                .and_then(|dt| dt.and_local_timezone(tz).earliest())
+                .map(|dt| dt.to_rfc3339());
+            let completed = completed_periods.get(template.template_id.as_str());
+
+            let periods: Vec<String> =
+                logs_db::get_missed_periods(&template.schedule, None, start.as_deref(), tz)
+                    .into_iter()
+                    .filter(|p| {
+                        logs_db::parse_period_to_date(p)
+                            .is_some_and(|d| d >= date_from && d <= date_to)
+                    })
+                    .filter(|p| completed.is_none_or(|c| !c.contains(p.as_str())))
+                    .filter(|p| {
+                        calendars.is_period_open(
+                            template.branch_id.as_deref(),
+                            &template.schedule,
+                            p,
+                        )
+                    })
+                    .collect();
+
+            if !periods.is_empty() {
+                missed
+                    .entry(template.branch_id.clone())
+                    .or_default()
+                    .insert(template.template_name.clone(), periods);
+            }
+        }
+
+        let arrange_by_log_type = params.arrange_by == "logType";
+        let section_keys: Vec<Option<String>> = sections
+            .keys()
+            .chain(missed.keys())
+            .cloned()
+            .collect::<std::collections::BTreeSet<_>>()
+            .into_iter()
+            .collect();
+
+        let mut report_sections: Vec<BranchSection> = section_keys
+            .into_iter()
+            .map(|branch_id| {
+                let name = match &branch_id {
+                    None => "Company-wide".to_string(),
+                    Some(id) => branch_names
+                        .get(id)
+                        .cloned()
+                        .unwrap_or_else(|| "Unknown branch".to_string()),
+                };
+                let mut entries = sections.remove(&branch_id).unwrap_or_default();
+                if arrange_by_log_type {
+                    entries.sort_by(|a, b| {
+                        a.template_name
+                            .cmp(&b.template_name)
+                            .then(b.created_at.cmp(&a.created_at))
+                    });
+                } else {
+                    entries.sort_by_key(|e| std::cmp::Reverse(e.created_at));
+                }
+                BranchSection {
+                    name,
+                    entries,
+                    missed_periods: missed.remove(&branch_id).unwrap_or_default(),
+                }
+            })
+            .collect();
+        report_sections.sort_by(|a, b| a.name.cmp(&b.name));
+
+        let charts = if params.include_temperature_graphs && !excluded.contains("temperature") {
+            temperature_series(report_sections.iter().flat_map(|s| &s.entries))
+        } else {
+            Vec::new()
+        };
+
+        Ok(ReportData {
+            company_name: company.name,
+            logo,
+            date_from,
+            date_to,
+            arrange_by_log_type,
+            generated_at: Utc::now(),
+            sections: report_sections,
+            charts,
+        })
+    }
+}
+
+/// Resolves the branches a user may report on, mirroring the company log entries listing.
+/// Returns `None` when the whole company is in scope.
+fn report_branch_scope(
+    user: &UserRecord,
+    requested_branch_ids: &[String],
+) -> Result<Option<Vec<String>>, ServiceError> {
+    if user.can_manage_company() || user.is_readonly_hq() {
+        let selected: Vec<String> = requested_branch_ids
+            .iter()
+            .filter(|id| !id.is_empty())
+            .cloned()
+            .collect();
+        Ok((!selected.is_empty()).then_some(selected))
+    } else {
+        let branch_id = user
+            .branch_id
+            .clone()
+            .ok_or(svc_err_forbidden("Branch manager has no branch assigned"))?;
+        Ok(Some(vec![branch_id]))
+    }
+}
+
+/// Branches and templates whose entries a report or export covers. `None` means all of them.
+struct EntryScope {
+    branch_ids: Option<Vec<String>>,
+    template_ids: Option<Vec<String>>,
+}
+
+/// Fetches the in-scope entries created between two local dates, inclusive, oldest first.
+/// The query spans the dates in every company timezone; each entry is then checked in its
+/// branch's timezone. A missing bound leaves that side of the range open.
+async fn entries_created_between(
+    state: &AppState,
+    company_id: &str,
+    scope: &EntryScope,
+    timezones: &db::CompanyTimezones,
+    date_from: Option<NaiveDate>,
+    date_to: Option<NaiveDate>,
+) -> Result<Vec<logs_db::LogEntry>, ServiceError> {
+    let filters = logs_db::LogEntryFilters {
+        branch_ids: scope.branch_ids.clone(),
+        template_ids: scope.template_ids.clone(),
+        created_from: date_from.map(|from| timezones.utc_range(from, from).0),
+        created_to: date_to.map(|to| timezones.utc_range(to, to).1),
+        ..Default::default()
+    };
+    let page = try_db!(
+        logs_db::get_company_log_entries_page(
+            &state.mongodb,
+            company_id,
+            &filters,
+            logs_db::LogEntrySort::CreatedAt,
+            false,
+            None,
+            None,
+        ),
+        "fetching log entries"
+    )?;
+
+    Ok(page
+        .entries
+        .into_iter()
+        .filter(|e| {
+            let created = timezones.local_date(e.branch_id.as_deref(), e.created_at);
+            date_from.is_none_or(|from| created >= from) && date_to.is_none_or(|to| created <= to)
+        })
+        .collect())
+}
+
+/// Parses a report date given as `YYYY-MM-DD`, optionally followed by a time component.
+fn parse_report_date(value: &str) -> Option<NaiveDate> {
+    let date_part = value.get(..10).unwrap_or(value);
+    NaiveDate::parse_from_str(date_part, "%Y-%m-%d").ok()
+}
+
+/// Returns the field types hidden by the selected log types. No selection means all types are shown.
+fn excluded_field_types(selected_log_type_ids: &[String]) -> HashSet<&'static str> {
+    if selected_log_type_ids.is_empty() || selected_log_type_ids.iter().any(|id| id == "all") {
+        return HashSet::new();
+    }
+
+    LOG_TYPE_FIELD_TYPES
+        .iter()
+        .filter(|(id, _)| !selected_log_type_ids.iter().any(|s| s == id))
+        .flat_map(|(_, field_types)| field_types.iter().copied())
+        .collect()
+}
+
+/// Builds the field columns for an export from the given layouts, in order of first
+/// appearance. Columns are keyed by the entry data key, so a field keeps one column across
+/// template versions.
+fn export_columns<'a>(
+    layouts: impl IntoIterator<Item = &'a logs_db::TemplateLayout>,
+) -> Vec<ExportColumn> {
+    let mut columns: Vec<ExportColumn> = Vec::new();
+    let mut seen = HashSet::new();
+
+    for layout in layouts {
+        for (field_index, field) in layout.iter().enumerate() {
+            if !matches!(
+                field.field_type.as_str(),
+                "temperature"
+                    | "text"
+                    | "text_input"
+                    | "checkbox"
+                    | "dropdown"
+                    | "calculated"
+                    | "number"
+                    | "date"
+                    | "time"
+                    | "datetime"
+                    | "multi_select"
+                    | "signature"
+            ) {
+                continue;
+            }
+            let key = field.entry_key(field_index);
+            if !seen.insert(key.clone()) {
+                continue;
+            }
+
+            let label = field
+                .props
+                .text
+                .clone()
+                .filter(|t| !t.trim().is_empty())
+                .unwrap_or_else(|| format!("Field {}", field_index + 1));
+            let header = match (field.field_type.as_str(), field.props.unit.as_deref()) {
+                ("temperature", unit) => format!("{label} ({})", unit.unwrap_or("°C")),
+                ("number", Some(unit)) if !unit.trim().is_empty() => format!("{label} ({unit})"),
+                _ => label,
+            };
+
+            columns.push(ExportColumn {
+                key,
+                field_type: field.field_type.clone(),
+                header,
+            });
+        }
+    }
+
+    columns
+}
+
+fn export_cell(field_type: &str, value: Option<&serde_json::Value>) -> Cell {
+    match (field_type, value) {
+        (_, None | Some(serde_json::Value::Null)) => Cell::Empty,
+        ("temperature" | "calculated" | "number", Some(serde_json::Value::Number(n))) => {
+            n.as_f64().map_or(Cell::Empty, Cell::Number)
+        }
+        ("signature", Some(_)) => Cell::Text("Signed".to_string()),
+        (_, Some(serde_json::Value::Array(items))) => Cell::Text(join_selected(items)),
+        (_, Some(serde_json::Value::Bool(checked))) => {
+            Cell::Text(if *checked { "Yes" } else { "No" }.to_string())
+        }
+        (_, Some(serde_json::Value::String(s))) => Cell::Text(s.clone()),
+        (_, Some(other)) => Cell::Text(other.to_string()),
+    }
+}
+
+fn rows_to_csv(rows: &[Vec<Cell>]) -> String {
+    let mut csv = String::new();
+    for row in rows {
+        let line: Vec<String> = row
+            .iter()
+            .map(|cell| match cell {
+                // Numbers are written bare so negative readings are not treated as formulas
+                Cell::Number(n) => n.to_string(),
+                other => csv_escape(&other.to_text()),
+            })
+            .collect();
+        csv.push_str(&line.join(","));
+        csv.push('\n');
+    }
+    csv
+}
+
+fn filename_slug(name: &str) -> String {
+    let slug: String = name
+        .chars()
+        .map(|c| {
+            if c.is_ascii_alphanumeric() {
+                c.to_ascii_lowercase()
+            } else {
+                '-'
+            }
+        })
+        .collect::<String>()
+        .split('-')
+        .filter(|part| !part.is_empty())
+        .collect::<Vec<_>>()
+        .join("-");
+    if slug.is_empty() {
+        "template".to_string()
+    } else {
+        slug
+    }
+}
+
+/// Decodes a stored logo and re-encodes it as a small JPEG for embedding.
+fn prepare_logo(data: &[u8]) -> Option<ReportLogo> {
+    let image = match image::load_from_memory(data) {
+        Ok(image) => image.thumbnail(400, 160).to_rgb8(),
+        Err(e) => {
+            tracing::warn!("Failed to decode company logo for report: {:?}", e);
+            return None;
+        }
+    };
+
+    let mut jpeg = Vec::new();
+    let encoder = image::codecs::jpeg::JpegEncoder::new_with_quality(&mut jpeg, 85);
+    if let Err(e) = image.write_with_encoder(encoder) {
+        tracing::warn!("Failed to encode company logo for report: {:?}", e);
+        return None;
+    }
+
+    Some(ReportLogo {
+        width: image.width(),
+        height: image.height(),
+        jpeg,
+    })
+}
+
+/// Joins the options selected in a multi-select field for display.
+fn join_selected(items: &[serde_json::Value]) -> String {
+    items
+        .iter()
+        .filter_map(serde_json::Value::as_str)
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+/// Formats an entry value for display. The flag is set for temperatures outside the field's limits.
+fn format_field_value(
+    field: &logs_db::TemplateField,
+    value: Option<&serde_json::Value>,
+) -> (String, bool) {
+    let Some(value) = value.filter(|v| !v.is_null()) else {
+        return ("-".to_string(), false);
+    };
+
+    match (field.field_type.as_str(), value) {
+        ("temperature", serde_json::Value::Number(n)) => {
+            let unit = field.props.unit.as_deref().unwrap_or("°C");
+            let reading = n.as_f64().unwrap_or_default();
+            let out_of_range = field.props.min.is_some_and(|min| reading < f64::from(min))
+                || field.props.max.is_some_and(|max| reading > f64::from(max));
+            (format!("{n}{unit}"), out_of_range)
+        }
+        ("number", serde_json::Value::Number(n)) => match field.props.unit.as_deref() {
+            Some(unit) if !unit.trim().is_empty() => (format!("{n} {unit}"), false),
+            _ => (n.to_string(), false),
+        },
+        ("signature", _) => ("Signed".to_string(), false),
+        (_, serde_json::Value::Array(items)) => (join_selected(items), false),
+        (_, serde_json::Value::Bool(checked)) => {
+            (if *checked { "Yes" } else { "No" }.to_string(), false)
+        }
+        (_, serde_json::Value::String(s)) if s.trim().is_empty() => ("-".to_string(), false),
+        (_, serde_json::Value::String(s)) => (s.clone(), false),
+        (_, other) => (other.to_string(), false),
+    }
+}
+
+/// Collects temperature readings per template field, in chronological order.
+/// Only series with at least two readings are returned.
+fn temperature_series<'a>(
+    entries: impl IntoIterator<Item = &'a ReportEntry>,
+) -> Vec<TemperatureSeries> {
+    let mut series: BTreeMap<(String, String), TemperatureSeries> = BTreeMap::new();
+
+    for entry in entries {
+        for (field_index, field) in entry.template_layout.iter().enumerate() {
+            if field.field_type != "temperature" {
+                continue;
+            }
+            let key = field.entry_key(field_index);
+            let Some(reading) = entry
+                .entry_data
+                .get(&key)
+                .and_then(serde_json::Value::as_f64)
+            else {
+                continue;
+            };
+
+            series
+                .entry((entry.template_name.clone(), key))
+                .or_insert_with(|| TemperatureSeries {
+                    template_name: entry.template_name.clone(),
+                    field_label: field
+                        .props
+                        .text
+                        .clone()
+                        .unwrap_or_else(|| format!("Temperature {}", field_index + 1)),
+                    unit: field.props.unit.clone().unwrap_or_else(|| "°C".to_string()),
+                    min: field.props.min,
+                    max: field.props.max,
+                    points: Vec::new(),
+                })
+                .points
+                .push((entry.created_at, reading));
+        }
+    }
+
+    series
+        .into_values()
+        .filter(|s| s.points.len() >= 2)
+        .map(|mut s| {
+            s.points.sort_by_key(|(at, _)| *at);
+            s
+        })
+        .collect()
+}
+
+/// Lays content out top-to-bottom, starting a new page when the current one is full.
+struct PageFlow {
+    doc: PdfDocument,
+    page: usize,
+    y: f32,
+}
+
+impl PageFlow {
+    fn new() -> Self {
+        let mut doc = PdfDocument::new();
+        let page = doc.add_page();
+        Self {
+            doc,
+            page,
+            y: pdf::PAGE_HEIGHT - MARGIN,
+        }
+    }
+
+    fn ensure_space(&mut self, height: f32) {
+        if self.y - height < MARGIN + 20.0 {
+            self.page = self.doc.add_page();
+            self.y = pdf::PAGE_HEIGHT - MARGIN;
+        }
+    }
+
+    fn line(&mut self, indent: f32, font: Font, size: f32, color: Color, text: &str) {
+        let height = size * 1.4;
+        self.ensure_space(height);
+        self.doc.text(
+            self.page,
+            MARGIN + indent,
+            self.y - size,
+            font,
+            size,
+            color,
+            text,
+        );
+        self.y -= height;
+    }
+
+    fn wrapped(&mut self, indent: f32, font: Font, size: f32, color: Color, text: &str) {
+        for line in pdf::wrap_text(text, font, size, CONTENT_WIDTH - indent) {
+            self.line(indent, font, size, color, &line);
+        }
+    }
+
+    fn rule(&mut self) {
+        self.ensure_space(8.0);
+        self.y -= 4.0;
+        self.doc.line(
+            self.page,
+            MARGIN,
+            self.y,
+            pdf::PAGE_WIDTH - MARGIN,
+            self.y,
+            0.5,
+            Color::LIGHT_GREY,
+        );
+        self.y -= 4.0;
+    }
+
+    fn gap(&mut self, height: f32) {
+        self.y -= height;
+    }
+}
+
+fn format_timestamp(at: DateTime<Utc>) -> String {
+    at.format("%d/%m/%Y %H:%M").to_string()
+}
+
+fn status_label(status: logs_db::LogStatus) -> &'static str {
+    match status {
+        logs_db::LogStatus::Draft => "Draft",
+        logs_db::LogStatus::Submitted => "Submitted",
+        logs_db::LogStatus::Reviewed => "Reviewed",
+        logs_db::LogStatus::Approved => "Approved",
+        logs_db::LogStatus::Overdue => "Overdue",
+    }
+}
+
+fn render_report_pdf(report: &ReportData) -> Vec<u8> {
+    let mut flow = PageFlow::new();
+
+    render_header(&mut flow, report);
+
+    for section in &report.sections {
+        render_section(&mut flow, section, report.arrange_by_log_type);
+    }
+
+    if !report.charts.is_empty() {
+        flow.gap(8.0);
+        flow.line(0.0, Font::Bold, 14.0, Color::BLACK, "Temperature Charts");
+        flow.rule();
+        for chart in &report.charts {
+            render_chart(&mut flow, chart);
+        }
+    }
+
+    // Footer on every page now the page count is known
+    let page_count = flow.doc.page_count();
+    let generated = format!("Generated {} UTC", format_timestamp(report.generated_at));
+    for page in 0..page_count {
+        flow.doc.text(
+            page,
+            MARGIN,
+            30.0,
+            Font::Regular,
+            8.0,
+            Color::GREY,
+            &generated,
+        );
+        let label = format!("Page {} of {}", page + 1, page_count);
+        let width = pdf::text_width(&label, Font::Regular, 8.0);
+        flow.doc.text(
+            page,
+            pdf::PAGE_WIDTH - MARGIN - width,
+            30.0,
+            Font::Regular,
+            8.0,
+            Color::GREY,
+            &label,
+        );
+    }
+
+    flow.doc.to_bytes()
+}
+
+fn render_header(flow: &mut PageFlow, report: &ReportData) {
+    if let Some(logo) = &report.logo {
+        let height = 48.0_f32;
+        #[allow(clippy::cast_precision_loss)]
+        let width = (height * logo.width as f32 / logo.height.max(1) as f32).min(180.0);
+        let image = flow
+            .doc
+            .add_jpeg(logo.width, logo.height, logo.jpeg.clone());
+        flow.doc.draw_image(
+            flow.page,
+            image,
+            pdf::PAGE_WIDTH - MARGIN - width,
+            flow.y - height,
+            width,
+            height,
+        );
+    }
+
+    flow.line(0.0, Font::Bold, 20.0, Color::BLACK, "Compliance Report");
+    flow.line(0.0, Font::Bold, 13.0, Color::BLACK, &report.company_name);
+    flow.line(
+        0.0,
+        Font::Regular,
+        10.0,
+        Color::GREY,
+        &format!(
+            "Period: {} - {}",
+            report.date_from.format("%d/%m/%Y"),
+            report.date_to.format("%d/%m/%Y")
+        ),
+    );
+    flow.line(
+        0.0,
+        Font::Regular,
+        10.0,
+        Color::GREY,
+        &format!(
+            "Arranged by: {}",
+            if report.arrange_by_log_type {
+                "Log Type"
+            } else {
+                "Date"
+            }
+        ),
+    );
+
+    let entry_count: usize = report.sections.iter().map(|s| s.entries.len()).sum();
+    let draft_count: usize = report
+        .sections
+        .iter()
+        .flat_map(|s| &s.entries)
+        .filter(|e| e.status == logs_db::LogStatus::Draft)
+        .count();
+    let missed_count: usize = report
+        .sections
+        .iter()
+        .flat_map(|s| s.missed_periods.values())
+        .map(Vec::len)
+        .sum();
+    flow.line(
+        0.0,
+        Font::Regular,
+        10.0,
+        Color::GREY,
+        &format!("Entries: {entry_count} ({draft_count} draft) | Missed periods: {missed_count}"),
+    );
+    flow.rule();
+}
+
+fn render_section(flow: &mut PageFlow, section: &BranchSection, arrange_by_log_type: bool) {
+    flow.gap(6.0);
+    flow.ensure_space(60.0);
+    flow.line(0.0, Font::Bold, 14.0, Color::BLACK, &section.name);
+    flow.rule();
+
+    if section.entries.is_empty() {
+        flow.line(
+            0.0,
+            Font::Regular,
+            10.0,
+            Color::GREY,
+            "No entries recorded in this period.",
+        );
+    }
+
+    let mut current_template: Option<&str> = None;
+    for entry in &section.entries {
+        if arrange_by_log_type && current_template != Some(entry.template_name.as_str()) {
+            current_template = Some(entry.template_name.as_str());
+            flow.gap(4.0);
+            flow.ensure_space(50.0);
+            flow.line(0.0, Font::Bold, 12.0, Color::BLUE, &entry.template_name);
+        }
+        render_entry(flow, entry);
+    }
+
+    flow.gap(4.0);
+    flow.ensure_space(30.0);
+    flow.line(0.0, Font::Bold, 11.0, Color::BLACK, "Missed periods");
+    if section.missed_periods.is_empty() {
+        flow.line(12.0, Font::Regular, 9.0, Color::GREY, "None");
+    }
+    for (template_name, periods) in &section.missed_periods {
+        flow.wrapped(
+            12.0,
+            Font::Regular,
+            9.0,
+            Color::RED,
+            &format!("{template_name}: {}", periods.join(", ")),
+        );
+    }
+}
+
+fn render_entry(flow: &mut PageFlow, entry: &ReportEntry) {
+    flow.gap(4.0);
+    flow.ensure_space(40.0);
+    flow.line(
+        0.0,
+        Font::Bold,
+        10.0,
+        Color::BLACK,
+        &format!(
+            "{} | Period {} | {}",
+            entry.template_name,
+            entry.period,
+            status_label(entry.status)
+        ),
+    );
+
+    let mut meta = format!(
+        "Recorded by {} | Created {}",
+        entry.recorded_by,
+        format_timestamp(entry.created_at)
+    );
+    if let Some(submitted_at) = entry.submitted_at {
+        meta.push_str(&format!(" | Submitted {}", format_timestamp(submitted_at)));
+    }
+    flow.line(0.0, Font::Regular, 8.0, Color::GREY, &meta);
+
+    for (field_index, field) in entry.template_layout.iter().enumerate() {
+        if !matches!(
+            field.field_type.as_str(),
+            "temperature"
+                | "text"
+                | "text_input"
+                | "checkbox"
+                | "dropdown"
+                | "calculated"
+                | "number"
+                | "date"
+                | "time"
+                | "datetime"
+                | "multi_select"
+                | "signature"
+        ) {
+            continue;
+        }
+        let label = field
+            .props
+            .text
+            .clone()
+            .unwrap_or_else(|| format!("Field {}", field_index + 1));
+        let (value, out_of_range) =
+            format_field_value(field, entry.entry_data.get(field.entry_key(field_index)));
+        let color = if out_of_range {
+            Color::RED
+        } else {
+            Color::BLACK
+        };
+        flow.wrapped(
+            12.0,
+            Font::Regular,
+            9.0,
+            color,
+            &format!("{label}: {value}"),
+        );
+    }
+}
+
+#[allow(clippy::cast_possible_truncation, clippy::cast_precision_loss)]
+fn render_chart(flow: &mut PageFlow, chart: &TemperatureSeries) {
+    flow.ensure_space(CHART_HEIGHT + 50.0);
+    flow.line(
+        0.0,
+        Font::Bold,
+        10.0,
+        Color::BLACK,
+        &format!(
+            "{} - {} ({})",
+            chart.template_name, chart.field_label, chart.unit
+        ),
+    );
+
+    let readings: Vec<f64> = chart.points.iter().map(|(_, v)| *v).collect();
+    let mut low = readings.iter().copied().fold(f64::INFINITY, f64::min);
+    let mut high = readings.iter().copied().fold(f64::NEG_INFINITY, f64::max);
+    for limit in [chart.min, chart.max].into_iter().flatten() {
+        low = low.min(f64::from(limit));
+        high = high.max(f64::from(limit));
+    }
+    if (high - low).abs() < f64::EPSILON {
+        low -= 1.0;
+        high += 1.0;
+    }
+
+    let axis_width = 36.0;
+    let left = MARGIN + axis_width;
+    let right = pdf::PAGE_WIDTH - MARGIN;
+    let top = flow.y - 6.0;
+    let bottom = top - CHART_HEIGHT;
+    let page = flow.page;
+
+    let to_y = |value: f64| bottom + ((value - low) / (high - low)) as f32 * CHART_HEIGHT;
+    let first_at = chart.points[0].0.timestamp() as f64;
+    let last_at = chart.points[chart.points.len() - 1].0.timestamp() as f64;
+    let span = (last_at - first_at).max(1.0);
+    let to_x = |at: DateTime<Utc>| {
+        left + ((at.timestamp() as f64 - first_at) / span) as f32 * (right - left)
+    };
+
+    flow.doc.stroke_rect(
+        page,
+        left,
+        bottom,
+        right - left,
+        CHART_HEIGHT,
+        0.5,
+        Color::GREY,
+    );
+
+    for step in 0..=4 {
+        let value = low + (high - low) * f64::from(step) / 4.0;
+        let y = to_y(value);
+        if step > 0 && step < 4 {
+            flow.doc
+                .line(page, left, y, right, y, 0.25, Color::LIGHT_GREY);
+        }
+        flow.doc.text(
+            page,
+            MARGIN,
+            y - 3.0,
+            Font::Regular,
+            7.0,
+            Color::GREY,
+            &format!("{value:.1}"),
+        );
+    }
+
+    for limit in [chart.min, chart.max].into_iter().flatten() {
+        let y = to_y(f64::from(limit));
+        flow.doc.line(page, left, y, right, y, 0.5, Color::RED);
+    }
+
+    let points: Vec<(f32, f32)> = chart
+        .points
+        .iter()
+        .map(|(at, value)| (to_x(*at), to_y(*value)))
+        .collect();
+    flow.doc.polyline(page, &points, 1.2, Color::BLUE);
+    for (x, y) in &points {
+        flow.doc
+            .fill_rect(page, x - 1.5, y - 1.5, 3.0, 3.0, Color::BLUE);
+    }
+
+    let first_label = chart.points[0].0.format("%d/%m/%Y").to_string();
+    let last_label = chart.points[chart.points.len() - 1]
+        .0
+        .format("%d/%m/%Y")
+        .to_string();
+    flow.doc.text(
+        page,
+        left,
+        bottom - 10.0,
+        Font::Regular,
+        7.0,
+        Color::GREY,
+        &first_label,
+    );
+    let last_width = pdf::text_width(&last_label, Font::Regular, 7.0);
+    flow.doc.text(
+        page,
+        right - last_width,
+        bottom - 10.0,
+        Font::Regular,
+        7.0,
+        Color::GREY,
+        &last_label,
+    );
+
+    let summary = format!(
+        "Min {:.1}{} | Max {:.1}{} | Readings {}",
+        readings.iter().copied().fold(f64::INFINITY, f64::min),
+        chart.unit,
+        readings.iter().copied().fold(f64::NEG_INFINITY, f64::max),
+        chart.unit,
+        readings.len()
+    );
+    flow.y = bottom - 14.0;
+    flow.line(axis_width, Font::Regular, 8.0, Color::GREY, &summary);
+    flow.gap(8.0);
+}
@@ -0,0 +1,385 @@
+use crate::{
+    AppState,
+    db::UserRecord,
+    dto::{
+        BundleNameConflict, BundledTemplate, BundledTemplateVersion, ImportTemplateBundleResponse,
+        ImportedTemplateInfo, TemplateBundle, TemplateBundlePayload,
+    },
+    jwt_manager::JwtManager,
+    logs_db,
+    services::TemplateService,
+    try_db,
+    utils::{
+        ServiceError, svc_err_bad_request, svc_err_forbidden, svc_err_internal, svc_err_not_found,
+    },
+};
+use jsonwebtoken::{Algorithm, DecodingKey, EncodingKey};
+use std::collections::HashSet;
+
+#[cfg(test)]
+mod template_bundle_service_tests {
+    use super::*;
+
+    #[test]
+    fn test_signature_round_trip() {
+        let payload = serde_json::json!({
+            "exported_at": "2026-06-12T09:00:00+00:00",
+            "templates": [{ "template_name": "Fridge Checks", "version": 2 }],
+        });
+        let signature = sign_payload(&payload, "secret").unwrap();
+        assert!(verify_payload(&payload, &signature, "secret"));
+        assert!(!verify_payload(&payload, &signature, "other-secret"));
+
+        let tampered = serde_json::json!({
+            "exported_at": "2026-06-12T09:00:00+00:00",
+            "templates": [{ "template_name": "Fridge Checks", "version": 3 }],
+        });
+        assert!(!verify_payload(&tampered, &signature, "secret"));
+        assert!(!verify_payload(&payload, "not-a-signature", "secret"));
+    }
+
+    #[test]
+    fn test_signature_ignores_key_order() {
+        let payload: serde_json::Value =
+            serde_json::from_str(r#"{"b": 1, "a": {"d": [1, 2], "c": null}}"#).unwrap();
+        let reordered: serde_json::Value =
+            serde_json::from_str(r#"{"a": {"c": null, "d": [1, 2]}, "b": 1}"#).unwrap();
+        let signature = sign_payload(&payload, "secret").unwrap();
+        assert!(verify_payload(&reordered, &signature, "secret"));
+    }
+}
+
+pub struct TemplateBundleService;
+
+pub const BUNDLE_FORMAT: &str = "logsmart-template-bundle";
+pub const BUNDLE_FORMAT_VERSION: u32 = 1;
+
+/// Bundles are signed with a key derived from the server secret, so they can only be
+/// imported into the LogSmart deployment that produced them.
+fn bundle_key(secret: &str) -> Vec<u8> {
+    format!("template-bundle:{secret}").into_bytes()
+}
+
+/// Serializes a JSON value with object keys sorted so the signature does not depend on
+/// how a client orders keys.
+fn canonical_json(value: &serde_json::Value) -> serde_json::Value {
+    match value {
+        serde_json::Value::Object(map) => {
+            let mut entries: Vec<_> = map.iter().collect();
+            entries.sort_by(|a, b| a.0.cmp(b.0));
+            serde_json::Value::Object(
+                entries
+                    .into_iter()
+                    .map(|(key, value)| (key.clone(), canonical_json(value)))
+                    .collect(),
+            )
+        }
+        serde_json::Value::Array(items) => {
+            serde_json::Value::Array(items.iter().map(canonical_json).collect())
+        }
+        other => other.clone(),
+    }
+}
+
+fn sign_payload(payload: &serde_json::Value, secret: &str) -> Result<String, ServiceError> {
+    let message = serde_json::to_vec(&canonical_json(payload))
+        .map_err(|_| svc_err_internal("Failed to encode bundle"))?;
+    jsonwebtoken::crypto::sign(
+        &message,
+        &EncodingKey::from_secret(&bundle_key(secret)),
+        Algorithm::HS256,
+    )
+    .map_err(|e| {
+        tracing::error!("Failed to sign template bundle: {:?}", e);
+        svc_err_internal("Failed to sign bundle")
+    })
+}
+
+fn verify_payload(payload: &serde_json::Value, signature: &str, secret: &str) -> bool {
+    let Ok(message) = serde_json::to_vec(&canonical_json(payload)) else {
+        return false;
+    };
+    jsonwebtoken::crypto::verify(
+        signature,
+        &message,
+        &DecodingKey::from_secret(&bundle_key(secret)),
+        Algorithm::HS256,
+    )
+    .unwrap_or(false)
+}
+
+/// Prefixes a layout validation error with the template it came from.
+fn for_template(template_name: &str, (status, err): ServiceError) -> ServiceError {
+    let message = err["error"].as_str().unwrap_or("Invalid template layout");
+    (
+        status,
+        serde_json::json!({ "error": format!("{template_name}: {message}") }),
+    )
+}
+
+impl TemplateBundleService {
+    /// Exports templates, with their schedules and optionally their version history, as a
+    /// signed bundle.
+    ///
+    /// # Errors
+    /// Returns an error if a template is not found or not accessible, or the database query fails.
+    pub async fn export_bundle(
+        state: &AppState,
+        user: &UserRecord,
+        template_ids: &[String],
+        include_versions: bool,
+    ) -> Result<TemplateBundle, ServiceError> {
+        let company_id = user
+            .company_id
+            .as_deref()
+            .ok_or_else(|| svc_err_forbidden("User is not associated with a company"))?;
+
+        let mut requested = Vec::new();
+        for id in template_ids {
+            if !id.is_empty() && !requested.contains(id) {
+                requested.push(id.clone());
+            }
+        }
+        if requested.is_empty() {
+            return Err(svc_err_bad_request("At least one template_id is required"));
+        }
+
+        let mut templates = try_db!(
+            logs_db::get_templates_by_ids(&state.mongodb, company_id, &requested),
+            "fetching templates"
+        )?;
+        if templates.len() != requested.len() {
+            return Err(svc_err_not_found("Template not found"));
+        }
+        if user.is_branch_manager()
+            && templates
+                .iter()
+                .any(|t| !t.is_company_wide() && t.branch_id != user.branch_id)
+        {
+            return Err(svc_err_forbidden("Unauthorized to export this template"));
+        }
+        templates.sort_by_key(|t| requested.iter().position(|id| *id == t.template_id));
+
+        let mut bundled = Vec::with_capacity(templates.len());
+        for template in templates {
+            let versions = if include_versions {
+                let mut versions = try_db!(
+                    logs_db::get_template_versions(
+                        &state.mongodb,
+                        company_id,
+                        &template.template_id
+                    ),
+                    "fetching template versions"
+                )?;
+                versions.reverse();
+                versions
+                    .into_iter()
+                    .map(|v| BundledTemplateVersion {
+                        version: v.version,
+                        version_name: v.version_name,
+                        template_layout: v.template_layout,
+                        schedule: v.schedule,
+                        created_at: v.created_at.to_rfc3339(),
+                    })
+                    .collect()
+            } else {
+                Vec::new()
+            };
+
+            bundled.push(BundledTemplate {
+                template_name: template.template_name,
+                template_layout: template.template_layout,
+                schedule: template.schedule,
+                entry_mode: template.entry_mode,
+                version: template.version,
+                version_name: template.version_name,
+                versions,
+            });
+        }
+
+        let payload = serde_json::to_value(TemplateBundlePayload {
+            exported_at: chrono::Utc::now().to_rfc3339(),
+            templates: bundled,
+        })
+        .map_err(|_| svc_err_internal("Failed to encode bundle"))?;
+        let signature = sign_payload(&payload, &JwtManager::get_secret())?;
+
+        Ok(TemplateBundle {
+            format: BUNDLE_FORMAT.to_string(),
+            format_version: BUNDLE_FORMAT_VERSION,
+            payload,
+            signature,
+        })
+    }
+
+    /// Imports a signed bundle as new templates in the user's company, optionally for one
+    /// branch. Every layout is validated before anything is written. Templates whose name is
+    /// already taken are renamed or skipped according to `on_name_conflict`.
+    ///
+    /// # Errors
+    /// Returns an error if the bundle is malformed, unsigned or tampered with, a layout is
+    /// invalid, the branch is not accessible, or the database insertion fails.
+    pub async fn import_bundle(
+        state: &AppState,
+        user: &UserRecord,
+        bundle: TemplateBundle,
+        branch_id: Option<String>,
+        on_name_conflict: BundleNameConflict,
+    ) -> Result<ImportTemplateBundleResponse, ServiceError> {
+        let company_id = user
+            .company_id
+            .as_deref()
+            .ok_or_else(|| svc_err_forbidden("User is not associated with a company"))?;
+
+        if bundle.format != BUNDLE_FORMAT {
+            return Err(svc_err_bad_request("Not a LogSmart template bundle"));
+        }
+        if bundle.format_version == 0 || bundle.format_version > BUNDLE_FORMAT_VERSION {
+            return Err(svc_err_bad_request(&format!(
+                "Unsupported bundle version {}",
+                bundle.format_version
+            )));
+        }
+        if !verify_payload(
+            &bundle.payload,
+            &bundle.signature,
+            &JwtManager::get_secret(),
+        ) {
+            return Err(svc_err_bad_request("Bundle signature is invalid"));
+        }
+        let payload: TemplateBundlePayload = serde_json::from_value(bundle.payload)
+            .map_err(|_| svc_err_bad_request("Bundle payload is malformed"))?;
+        if payload.templates.is_empty() {
+            return Err(svc_err_bad_request("Bundle contains no templates"));
+        }
+
+        for template in &payload.templates {
+            if template.template_name.trim().is_empty() {
+                return Err(svc_err_bad_request("Template name cannot be empty"));
+            }
+            TemplateService::validate_template_layout(&template.template_layout)
+                .map_err(|e| for_template(&template.template_name, e))?;
+            TemplateService::validate_schedule(&template.schedule)
+                .map_err(|e| for_template(&template.template_name, e))?;
+            for version in &template.versions {
+                TemplateService::validate_template_layout(&version.template_layout)
+                    .map_err(|e| for_template(&template.template_name, e))?;
+            }
+        }
+
+        let branch_id = if user.is_branch_manager() {
+            if branch_id.is_some() && branch_id != user.branch_id {
+                return Err(svc_err_forbidden(
+                    "Branch managers can only import templates into their own branch",
+                ));
+            }
+            Some(user.branch_id.clone().ok_or_else(|| {
+                svc_err_forbidden("Branch managers can only import templates into their own branch")
+            })?)
+        } else {
+            branch_id
+        };
+        if let Some(branch_id) = branch_id.as_deref() {
+            TemplateService::ensure_company_branch(state, company_id, branch_id).await?;
+        }
+
+        let mut taken: HashSet<String> = try_db!(
+            logs_db::get_templates_by_company_including_archived(&state.mongodb, company_id),
+            "fetching templates"
+        )?
+        .into_iter()
+        .map(|t| t.template_name)
+        .collect();
+
+        let created_by = mongodb::bson::Uuid::parse_str(&user.id).map_err(|e| {
+            tracing::error!("Failed to parse user ID as UUID: {:?}", e);
+            svc_err_internal("Server error")
+        })?;
+
+        let mut imported = Vec::new();
+        let mut skipped = Vec::new();
+        for template in payload.templates {
+            let source_name = template.template_name.trim().to_string();
+            let template_name = if taken.contains(&source_name) {
+                match on_name_conflict {
+                    BundleNameConflict::Skip => {
+                        skipped.push(source_name);
+                        continue;
+                    }
+                    BundleNameConflict::Rename => {
+                        TemplateService::resolve_template_name(&source_name, &taken)
+                    }
+                }
+            } else {
+                source_name.clone()
+            };
+            taken.insert(template_name.clone());
+
+            let template_id = uuid::Uuid::new_v4().to_string();
+            let now = chrono::Utc::now();
+            // Without its history the template starts again at version 1
+            let version = if template.versions.is_empty() {
+                1
+            } else {
+                template.version
+            };
+
+            try_db!(
+                logs_db::add_template(
+                    &state.mongodb,
+                    &logs_db::TemplateDocument {
+                        template_id: template_id.clone(),
+                        template_name: template_name.clone(),
+                        template_layout: logs_db::assign_field_ids(&template.template_layout, None),
+                        company_id: company_id.to_string(),
+                        branch_id: branch_id.clone(),
+                        created_at: now,
+                        updated_at: now,
+                        created_by,
+                        schedule: template.schedule,
+                        version,
+                        version_name: template.version_name,
+                        archived_at: None,
+                        linked_from: None,
+                        entry_mode: template.entry_mode,
+                    }
+                ),
+                "adding template"
+            )?;
+
+            for bundled_version in template.versions {
+                let created_at = chrono::DateTime::parse_from_rfc3339(&bundled_version.created_at)
+                    .map_or(now, |t| t.with_timezone(&chrono::Utc));
+                try_db!(
+                    logs_db::add_template_version(
+                        &state.mongodb,
+                        &logs_db::TemplateVersionDocument {
+                            template_id: template_id.clone(),
+                            template_name: template_name.clone(),
+                            company_id: company_id.to_string(),
+                            branch_id: branch_id.clone(),
+                            version: bundled_version.version,
+                            version_name: bundled_version.version_name,
+                            template_layout: logs_db::assign_field_ids(
+                                &bundled_version.template_layout,
+                                None
+                            ),
+                            schedule: bundled_version.schedule,
+                            created_at,
+                            created_by,
+                        }
+                    ),
+                    "adding template version"
+                )?;
+            }
+
+            imported.push(ImportedTemplateInfo {
+                source_name,
+                template_id,
+                template_name,
+            });
+        }
+
+        Ok(ImportTemplateBundleResponse { imported, skipped })
+    }
+}
@@ -0,0 +1,236 @@
+use crate::{
+    AppState,
+    db::UserRecord,
+    logs_db::{self, LibraryTemplateDocument},
+    services::{NewTemplate, TemplateService},
+    try_db,
+    utils::{ServiceError, svc_err_bad_request, svc_err_forbidden, svc_err_not_found},
+};
+
+#[cfg(test)]
+mod template_library_service_tests {
+    use super::*;
+
+    #[test]
+    fn test_starter_templates_are_valid() {
+        let starters = crate::template_library::starter_templates();
+        let mut ids = std::collections::HashSet::new();
+        for template in &starters {
+            assert!(ids.insert(template.library_template_id.as_str()));
+            assert!(validate_library_details(&template.template_name, &template.category).is_ok());
+            assert!(TemplateService::validate_template_layout(&template.template_layout).is_ok());
+            assert!(
+                template
+                    .template_layout
+                    .iter()
+                    .all(|field| !field.field_id.is_empty())
+            );
+        }
+    }
+
+    #[test]
+    fn test_validate_library_details() {
+        assert!(validate_library_details("Fridge Checks", "Temperature Control").is_ok());
+        assert!(validate_library_details("  ", "Temperature Control").is_err());
+        assert!(validate_library_details("Fridge Checks", "").is_err());
+        assert!(
+            validate_library_details(&"x".repeat(MAX_LIBRARY_TEXT_LENGTH + 1), "Cleaning").is_err()
+        );
+    }
+}
+
+pub struct TemplateLibraryService;
+
+/// Maximum length of a library template's name and category.
+const MAX_LIBRARY_TEXT_LENGTH: usize = 100;
+
+fn validate_library_details(template_name: &str, category: &str) -> Result<(), ServiceError> {
+    if template_name.trim().is_empty() {
+        return Err(svc_err_bad_request("Template name cannot be empty"));
+    }
+    if category.trim().is_empty() {
+        return Err(svc_err_bad_request("Category cannot be empty"));
+    }
+    if template_name.len() > MAX_LIBRARY_TEXT_LENGTH || category.len() > MAX_LIBRARY_TEXT_LENGTH {
+        return Err(svc_err_bad_request(
+            "Template name and category must be at most 100 characters",
+        ));
+    }
+    Ok(())
+}
+
+/// Editable details of a library template.
+pub struct LibraryTemplateDetails {
+    pub template_name: String,
+    pub category: String,
+    pub description: String,
+    pub template_layout: logs_db::TemplateLayout,
+    pub schedule: logs_db::Schedule,
+}
+
+impl TemplateLibraryService {
+    /// Lists the template library and every category in it. When `category` is given only
+    /// templates in that category are returned.
+    ///
+    /// # Errors
+    /// Returns an error if the database query fails.
+    pub async fn list_templates(
+        state: &AppState,
+        category: Option<&str>,
+    ) -> Result<(Vec<LibraryTemplateDocument>, Vec<String>), ServiceError> {
+        let templates = try_db!(
+            logs_db::get_library_templates(&state.mongodb, None),
+            "fetching template library"
+        )?;
+
+        // The library is sorted by category, so duplicates are adjacent
+        let mut categories: Vec<String> = templates.iter().map(|t| t.category.clone()).collect();
+        categories.dedup();
+
+        let templates = templates
+            .into_iter()
+            .filter(|t| category.is_none_or(|c| t.category == c))
+            .collect();
+        Ok((templates, categories))
+    }
+
+    /// Adds a template to the library.
+    ///
+    /// # Errors
+    /// Returns an error if the details are invalid or the database insertion fails.
+    pub async fn create_template(
+        state: &AppState,
+        details: LibraryTemplateDetails,
+    ) -> Result<LibraryTemplateDocument, ServiceError> {
+        validate_library_details(&details.template_name, &details.category)?;
+        TemplateService::validate_template_layout(&details.template_layout)?;
+        TemplateService::validate_schedule(&details.schedule)?;
+
+        let now = chrono::Utc::now();
+        let template = LibraryTemplateDocument {
+            library_template_id: uuid::Uuid::new_v4().to_string(),
+            template_name: details.template_name.trim().to_string(),
+            category: details.category.trim().to_string(),
+            description: details.description,
+            template_layout: logs_db::assign_field_ids(&details.template_layout, None),
+            schedule: details.schedule,
+            created_at: now,
+            updated_at: now,
+        };
+
+        try_db!(
+            logs_db::add_library_template(&state.mongodb, &template),
+            "adding library template"
+        )?;
+        Ok(template)
+    }
+
+    /// Replaces a library template's details. Companies that already cloned it keep their copy.
+    ///
+    /// # Errors
+    /// Returns an error if the template is not found, the details are invalid, or the update fails.
+    pub async fn update_template(
+        state: &AppState,
+        library_template_id: &str,
+        details: LibraryTemplateDetails,
+    ) -> Result<LibraryTemplateDocument, ServiceError> {
+        validate_library_details(&details.template_name, &details.category)?;
+        TemplateService::validate_template_layout(&details.template_layout)?;
+        TemplateService::validate_schedule(&details.schedule)?;
+
+        let existing = try_db!(
+            logs_db::get_library_template(&state.mongodb, library_template_id),
+            "fetching library template"
+        )?
+        .ok_or_else(|| svc_err_not_found("Library template not found"))?;
+
+        let template = LibraryTemplateDocument {
+            library_template_id: existing.library_template_id,
+            template_name: details.template_name.trim().to_string(),
+            category: details.category.trim().to_string(),
+            description: details.description,
+            template_layout: logs_db::assign_field_ids(
+                &details.template_layout,
+                Some(&existing.template_layout),
+            ),
+            schedule: details.schedule,
+            created_at: existing.created_at,
+            updated_at: chrono::Utc::now(),
+        };
+
+        let updated = try_db!(
+            logs_db::replace_library_template(&state.mongodb, &template),
+            "updating library template"
+        )?;
+        if !updated {
+            return Err(svc_err_not_found("Library template not found"));
+        }
+        Ok(template)
+    }
+
+    /// Removes a template from the library.
+    ///
+    /// # Errors
+    /// Returns an error if the template is not found or the deletion fails.
+    pub async fn delete_template(
+        state: &AppState,
+        library_template_id: &str,
+    ) -> Result<(), ServiceError> {
+        let deleted = try_db!(
+            logs_db::delete_library_template(&state.mongodb, library_template_id),
+            "deleting library template"
+        )?;
+        if !deleted {
+            return Err(svc_err_not_found("Library template not found"));
+        }
+        Ok(())
+    }
+
+    /// Copies a library template into the user's company as a normal template, either
+    /// company-wide or for one branch. Returns the new template's id.
+    ///
+    /// # Errors
+    /// Returns an error if the library template or branch is not found, the name is taken,
+    /// or the database insertion fails.
+    pub async fn clone_template(
+        state: &AppState,
+        user: &UserRecord,
+        library_template_id: &str,
+        template_name: Option<String>,
+        branch_id: Option<String>,
+    ) -> Result<String, ServiceError> {
+        let company_id = user
+            .company_id
+            .as_deref()
+            .ok_or_else(|| svc_err_forbidden("User is not associated with a company"))?;
+
+        let template = try_db!(
+            logs_db::get_library_template(&state.mongodb, library_template_id),
+            "fetching library template"
+        )?
+        .ok_or_else(|| svc_err_not_found("Library template not found"))?;
+
+        if let Some(branch_id) = branch_id.as_deref() {
+            TemplateService::ensure_company_branch(state, company_id, branch_id).await?;
+        }
+
+        let template_name = template_name
+            .map(|name| name.trim().to_string())
+            .filter(|name| !name.is_empty())
+            .unwrap_or(template.template_name);
+
+        TemplateService::create_template(
+            state,
+            company_id,
+            NewTemplate {
+                template_name,
+                template_layout: template.template_layout,
+                schedule: template.schedule,
+                entry_mode: logs_db::EntryMode::Scheduled,
+                branch_id,
+            },
+            &user.id,
+        )
+        .await
+    }
+}
@@ -1,7 +1,14 @@
 use crate::{
     AppState,
-    db::{UserRecord, UserRole},
-    logs_db, utils,
+    db::{self, UserRecord, UserRole},
+    dto::{
+        DistributeTemplateResponse, DistributedTemplateInfo, LinkedCopyPreview,
+        PropagateTemplateResponse, PropagationFailure, PropagationPreviewResponse,
+        SchedulePreviewPeriod, SchedulePreviewResponse, TemplateVersionDiffResponse,
+    },
+    logs_db,
+    services::log_entry_service::INPUT_FIELD_TYPES,
+    utils,
 };
 use axum::http::StatusCode;
 use serde_json::json;
@@ -13,6 +20,7 @@ mod template_service_tests {
     // Helper to create a valid template field
     fn valid_field() -> logs_db::TemplateField {
         logs_db::TemplateField {
+            field_id: String::new(),
             field_type: "text".to_string(),
             position: logs_db::Position { x: 10.0, y: 20.0 },
             props: logs_db::TemplateFieldProps {
@@ -27,6 +35,153 @@ mod template_service_tests {
         }
     }
 
+    #[test]
+    fn test_resolve_template_name() {
+        let taken: std::collections::HashSet<String> = ["Fridge Checks", "Fridge Checks (2)"]
+            .into_iter()
+            .map(String::from)
+            .collect();
+        assert_eq!(
+            TemplateService::resolve_template_name("Cleaning", &taken),
+            "Cleaning"
+        );
+        assert_eq!(
+            TemplateService::resolve_template_name("Fridge Checks", &taken),
+            "Fridge Checks (3)"
+        );
+    }
+
+    #[test]
+    fn test_validate_template_layout_field_rules() {
+        let mut checkbox = valid_field();
+        checkbox.field_type = "checkbox".to_string();
+        checkbox.props.input_type = None;
+        let mut note = valid_field();
+        note.props.required_if = Some(logs_db::FieldCondition {
+            field_id: "0".to_string(),
+            operator: logs_db::ConditionOperator::Unchecked,
+            value: None,
+        });
+        let layout = vec![checkbox, note];
+        assert!(TemplateService::validate_template_layout(&layout).is_ok());
+
+        // Referenced by index until saved, then by the assigned id
+        let saved = logs_db::assign_field_ids(&layout, None);
+        let rule = saved[1].props.required_if.as_ref().unwrap();
+        assert_eq!(rule.field_id, saved[0].field_id);
+        assert!(TemplateService::validate_template_layout(&saved).is_ok());
+
+        let mut invalid = layout.clone();
+        invalid[1].props.required_if.as_mut().unwrap().operator =
+            logs_db::ConditionOperator::AboveMax;
+        assert!(TemplateService::validate_template_layout(&invalid).is_err());
+
+        let mut invalid = layout.clone();
+        invalid[1].props.required_if.as_mut().unwrap().field_id = "missing".to_string();
+        assert!(TemplateService::validate_template_layout(&invalid).is_err());
+
+        let mut invalid = layout.clone();
+        invalid[1].props.required_if.as_mut().unwrap().value = Some(json!(true));
+        assert!(TemplateService::validate_template_layout(&invalid).is_err());
+    }
+
+    #[test]
+    fn test_validate_template_layout_calculated_fields() {
+        let calculated = |expression: &str| {
+            let mut field = valid_field();
+            field.field_type = "calculated".to_string();
+            field.props.expression = Some(expression.to_string());
+            field
+        };
+        let mut reading = valid_field();
+        reading.field_type = "temperature".to_string();
+
+        let layout = vec![reading.clone(), reading.clone(), calculated("{0} - {1}")];
+        assert!(TemplateService::validate_template_layout(&layout).is_ok());
+
+        let saved = logs_db::assign_field_ids(&layout, None);
+        assert_eq!(
+            saved[2].props.expression.as_deref(),
+            Some(format!("{{{}}} - {{{}}}", saved[0].field_id, saved[1].field_id).as_str())
+        );
+        assert!(TemplateService::validate_template_layout(&saved).is_ok());
+
+        for expression in ["{0} -", "{7} + 1", "{2} + 1", "unknown({0})"] {
+            let layout = vec![reading.clone(), reading.clone(), calculated(expression)];
+            assert!(TemplateService::validate_template_layout(&layout).is_err());
+        }
+
+        let layout = vec![
+            reading.clone(),
+            calculated("{2} + {0}"),
+            calculated("{1} * 2"),
+        ];
+        assert!(TemplateService::validate_template_layout(&layout).is_err());
+
+        let mut missing = calculated("{0}");
+        missing.props.expression = None;
+        assert!(
+            TemplateService::validate_template_layout(&vec![reading.clone(), missing]).is_err()
+        );
+
+        let mut text = valid_field();
+        text.props.expression = Some("1 + 1".to_string());
+        assert!(TemplateService::validate_template_layout(&vec![text]).is_err());
+    }
+
+    #[test]
+    fn test_validate_template_layout_typed_fields() {
+        let mut number = valid_field();
+        number.field_type = "number".to_string();
+        number.props.min = Some(0.0);
+        number.props.max = Some(10.0);
+        number.props.decimals = Some(2);
+        let mut checklist = valid_field();
+        checklist.field_type = "multi_select".to_string();
+        checklist.props.options = Some(vec!["Floors".to_string(), "Walls".to_string()]);
+        assert!(
+            TemplateService::validate_template_layout(&vec![number.clone(), checklist.clone()])
+                .is_ok()
+        );
+
+        let mut invalid = number.clone();
+        invalid.props.decimals = Some(7);
+        assert!(TemplateService::validate_template_layout(&vec![invalid]).is_err());
+
+        let mut invalid = number;
+        invalid.props.min = Some(20.0);
+        assert!(TemplateService::validate_template_layout(&vec![invalid]).is_err());
+
+        let mut invalid = checklist.clone();
+        invalid.props.options = None;
+        assert!(TemplateService::validate_template_layout(&vec![invalid]).is_err());
+
+        let mut invalid = checklist;
+        invalid.props.options = Some(vec!["Floors".to_string(), "Floors".to_string()]);
+        assert!(TemplateService::validate_template_layout(&vec![invalid]).is_err());
+    }
+
+    #[test]
+    fn test_validate_template_layout_rejects_visibility_cycles() {
+        let visible_if = |field_id: &str| {
+            Some(logs_db::FieldCondition {
+                field_id: field_id.to_string(),
+                operator: logs_db::ConditionOperator::NotEmpty,
+                value: None,
+            })
+        };
+        let mut layout = vec![valid_field(), valid_field(), valid_field()];
+        layout[1].props.visible_if = visible_if("0");
+        layout[2].props.visible_if = visible_if("1");
+        assert!(TemplateService::validate_template_layout(&layout).is_ok());
+
+        layout[0].props.visible_if = visible_if("2");
+        assert!(TemplateService::validate_template_layout(&layout).is_err());
+
+        layout[0].props.visible_if = visible_if("0");
+        assert!(TemplateService::validate_template_layout(&layout).is_err());
+    }
+
     #[test]
     fn test_validate_template_layout_valid_field() {
         let layout = vec![valid_field()];
@@ -181,6 +336,339 @@ mod template_service_tests {
         assert!(err["error"].to_string().contains("Field 1"));
     }
 
+    #[test]
+    fn test_validate_template_layout_duplicate_field_id() {
+        let mut field1 = valid_field();
+        field1.field_id = "temp-check".to_string();
+        let mut field2 = valid_field();
+        field2.field_id = "temp-check".to_string();
+        let layout = vec![field1, field2];
+
+        let result = TemplateService::validate_template_layout(&layout);
+        assert!(result.is_err());
+        let (status, err) = result.unwrap_err();
+        assert_eq!(status, StatusCode::BAD_REQUEST);
+        assert!(err["error"].to_string().contains("Duplicate field_id"));
+    }
+
+    #[test]
+    fn test_validate_template_layout_numeric_field_id() {
+        let mut field = valid_field();
+        field.field_id = "3".to_string();
+        let layout = vec![field];
+
+        let result = TemplateService::validate_template_layout(&layout);
+        assert!(result.is_err());
+        let (_, err) = result.unwrap_err();
+        assert!(err["error"].to_string().contains("Invalid field_id"));
+    }
+
+    #[test]
+    fn test_assign_field_ids_preserves_existing_and_reuses_previous() {
+        let mut kept = valid_field();
+        kept.field_id = "kept".to_string();
+        let previous = vec![valid_field(), kept.clone()];
+        let previous = logs_db::assign_field_ids(&previous, None);
+        assert!(previous.iter().all(|f| !f.field_id.is_empty()));
+        assert_eq!(previous[1].field_id, "kept");
+
+        // Legacy client resends the layout without any ids plus a new trailing field
+        let edited = vec![valid_field(), valid_field(), valid_field()];
+        let assigned = logs_db::assign_field_ids(&edited, Some(&previous));
+        assert_eq!(assigned[0].field_id, previous[0].field_id);
+        assert_eq!(assigned[1].field_id, "kept");
+        assert!(!assigned[2].field_id.is_empty());
+        assert_ne!(assigned[2].field_id, previous[0].field_id);
+
+        // Client that sends ids deleted the first field and added a new one in its place
+        let edited = vec![valid_field(), kept];
+        let assigned = logs_db::assign_field_ids(&edited, Some(&previous));
+        assert_ne!(assigned[0].field_id, previous[0].field_id);
+        assert_eq!(assigned[1].field_id, "kept");
+    }
+
+    #[test]
+    fn test_match_legacy_field_ids_matches_on_type_and_label() {
+        let labelled = |field_type: &str, label: &str| {
+            let mut field = valid_field();
+            field.field_type = field_type.to_string();
+            field.props.text = Some(label.to_string());
+            field
+        };
+        let current = logs_db::assign_field_ids(
+            &vec![
+                labelled("temperature", "Fridge"),
+                labelled("text_input", "Notes"),
+                labelled("checkbox", "Clean"),
+                labelled("checkbox", "Clean"),
+            ],
+            None,
+        );
+
+        // Fields were reordered and "Freezer" was removed after this version was archived
+        let legacy = vec![
+            labelled("text_input", "Notes"),
+            labelled("temperature", "Freezer"),
+            labelled("temperature", "Fridge"),
+            labelled("checkbox", "Clean"),
+        ];
+        let matched = logs_db::match_legacy_field_ids(&legacy, &current);
+        assert_eq!(matched[0].field_id, current[1].field_id);
+        assert_eq!(matched[2].field_id, current[0].field_id);
+        let current_ids: Vec<&str> = current.iter().map(|f| f.field_id.as_str()).collect();
+        assert!(!matched[1].field_id.is_empty());
+        assert!(!current_ids.contains(&matched[1].field_id.as_str()));
+        // Ambiguous: the current layout has two identical checkboxes
+        assert!(!current_ids.contains(&matched[3].field_id.as_str()));
+    }
+
+    #[test]
+    fn test_legacy_entry_layout_uses_layout_in_effect_when_saved() {
+        let at = |hour: u32| {
+            chrono::NaiveDate::from_ymd_opt(2026, 1, 1)
+                .unwrap()
+                .and_hms_opt(hour, 0, 0)
+                .unwrap()
+                .and_utc()
+        };
+        let v1 = logs_db::assign_field_ids(&vec![valid_field()], None);
+        let v2 = logs_db::assign_field_ids(&vec![valid_field(), valid_field()], None);
+        let current = logs_db::assign_field_ids(&vec![valid_field(); 3], None);
+        let archived = vec![(at(10), v1), (at(12), v2)];
+
+        assert!(std::ptr::eq(
+            logs_db::legacy_entry_layout(&archived, &current, at(9)),
+            &archived[0].1
+        ));
+        assert!(std::ptr::eq(
+            logs_db::legacy_entry_layout(&archived, &current, at(11)),
+            &archived[1].1
+        ));
+        assert!(std::ptr::eq(
+            logs_db::legacy_entry_layout(&archived, &current, at(13)),
+            &current
+        ));
+    }
+
+    #[test]
+    fn test_diff_layouts_matches_fields_by_id() {
+        let old = logs_db::assign_field_ids(&vec![valid_field(), valid_field()], None);
+
+        let mut new = vec![old[1].clone(), valid_field()];
+        new[0].props.text = Some("Renamed".to_string());
+        let new = logs_db::assign_field_ids(&new, None);
+
+        let diff = logs_db::diff_layouts(&old, &new);
+        assert_eq!(diff.changed.len(), 1);
+        assert_eq!(diff.changed[0].field.field_id, old[1].field_id);
+        assert_eq!(diff.changed[0].field.label.as_deref(), Some("Renamed"));
+        assert_eq!(diff.changed[0].changes.len(), 1);
+        assert_eq!(diff.changed[0].changes[0].name, "text");
+        assert_eq!(diff.added.len(), 1);
+        assert_eq!(diff.added[0].field_id, new[1].field_id);
+        assert_eq!(diff.removed.len(), 1);
+        assert_eq!(diff.removed[0].field_id, old[0].field_id);
+
+        assert!(logs_db::diff_layouts(&old, &old).is_empty());
+    }
+
+    #[test]
+    fn test_diff_layouts_reports_moves_and_prop_changes() {
+        let old = logs_db::assign_field_ids(&vec![valid_field()], None);
+
+        let mut new = old.clone();
+        new[0].field_type = "text_input".to_string();
+        new[0].props.max_length = Some(50);
+        new[0].props.color = None;
+        new[0].position = logs_db::Position { x: 10.0, y: 80.0 };
+
+        let diff = logs_db::diff_layouts(&old, &new);
+        let names: Vec<&str> = diff.changed[0]
+            .changes
+            .iter()
+            .map(|c| c.name.as_str())
+            .collect();
+        assert_eq!(names, ["field_type", "color", "max_length"]);
+        assert_eq!(diff.changed[0].changes[1].new, None);
+        assert_eq!(diff.changed[0].changes[2].old, Some(json!(100)));
+        assert_eq!(diff.moved.len(), 1);
+        assert!((diff.moved[0].from.y - 20.0).abs() < f64::EPSILON);
+        assert!((diff.moved[0].to.y - 80.0).abs() < f64::EPSILON);
+
+        let mut moved_only = old.clone();
+        moved_only[0].position.x = 40.0;
+        let diff = logs_db::diff_layouts(&old, &moved_only);
+        assert!(diff.changed.is_empty());
+        assert_eq!(diff.moved.len(), 1);
+    }
+
+    #[test]
+    fn test_diff_schedules() {
+        let old = logs_db::Schedule {
+            frequency: logs_db::Frequency::Weekly,
+            days_of_week: None,
+            day_of_week: Some(1),
+            day_of_month: None,
+            month_of_year: None,
+            available_from_time: None,
+            due_at_time: Some("10:00".to_string()),
+            interval: None,
+            starts_on: None,
+            time_slots: None,
+        };
+        let new = logs_db::Schedule {
+            frequency: logs_db::Frequency::Daily,
+            day_of_week: None,
+            due_at_time: Some("10:00".to_string()),
+            available_from_time: Some("08:00".to_string()),
+            ..old.clone()
+        };
+
+        let changes = logs_db::diff_schedules(&old, &new);
+        let names: Vec<&str> = changes.iter().map(|c| c.name.as_str()).collect();
+        assert_eq!(names, ["available_from_time", "day_of_week", "frequency"]);
+        assert_eq!(changes[1].old, Some(json!(1)));
+        assert_eq!(changes[1].new, None);
+        assert!(logs_db::diff_schedules(&old, &old).is_empty());
+    }
+
+    #[test]
+    fn test_validate_schedule() {
+        let slot = |from: &str, due: &str| logs_db::TimeSlot {
+            available_from_time: from.to_string(),
+            due_at_time: due.to_string(),
+        };
+        let daily = logs_db::Schedule {
+            frequency: logs_db::Frequency::Daily,
+            days_of_week: None,
+            day_of_week: None,
+            day_of_month: None,
+            month_of_year: None,
+            available_from_time: None,
+            due_at_time: None,
+            interval: None,
+            starts_on: None,
+            time_slots: Some(vec![slot("08:00", "10:00"), slot("10:00", "12:00")]),
+        };
+        assert!(TemplateService::validate_schedule(&daily).is_ok());
+
+        let overlapping = logs_db::Schedule {
+            time_slots: Some(vec![slot("08:00", "10:30"), slot("10:00", "12:00")]),
+            ..daily.clone()
+        };
+        assert!(TemplateService::validate_schedule(&overlapping).is_err());
+        let backwards = logs_db::Schedule {
+            time_slots: Some(vec![slot("12:00", "11:00")]),
+            ..daily.clone()
+        };
+        assert!(TemplateService::validate_schedule(&backwards).is_err());
+        let empty = logs_db::Schedule {
+            time_slots: Some(vec![]),
+            ..daily.clone()
+        };
+        assert!(TemplateService::validate_schedule(&empty).is_err());
+
+        let every_three_days = logs_db::Schedule {
+            interval: Some(3),
+            starts_on: chrono::NaiveDate::from_ymd_opt(2026, 3, 2),
+            time_slots: None,
+            ..daily.clone()
+        };
+        assert!(TemplateService::validate_schedule(&every_three_days).is_ok());
+        let no_start = logs_db::Schedule {
+            starts_on: None,
+            ..every_three_days.clone()
+        };
+        assert!(TemplateService::validate_schedule(&no_start).is_err());
+        let monthly = logs_db::Schedule {
+            frequency: logs_db::Frequency::Monthly,
+            ..every_three_days.clone()
+        };
+        assert!(TemplateService::validate_schedule(&monthly).is_err());
+        let weekly_slots = logs_db::Schedule {
+            frequency: logs_db::Frequency::Weekly,
+            ..daily
+        };
+        assert!(TemplateService::validate_schedule(&weekly_slots).is_err());
+    }
+
+    #[test]
+    fn test_schedule_problems() {
+        let schedule = |frequency, day_of_month, month_of_year| logs_db::Schedule {
+            frequency,
+            days_of_week: None,
+            day_of_week: None,
+            day_of_month,
+            month_of_year,
+            available_from_time: None,
+            due_at_time: None,
+            interval: None,
+            starts_on: None,
+            time_slots: None,
+        };
+
+        assert!(
+            TemplateService::schedule_problems(&schedule(
+                logs_db::Frequency::Quarterly,
+                Some(15),
+                None
+            ))
+            .is_empty()
+        );
+        assert_eq!(
+            TemplateService::schedule_problems(&schedule(
+                logs_db::Frequency::Quarterly,
+                Some(31),
+                None
+            )),
+            vec![
+                "Day 31 does not exist in the first month of Q2, which is due on that month's last day."
+                    .to_string()
+            ]
+        );
+        assert_eq!(
+            TemplateService::schedule_problems(&schedule(logs_db::Frequency::Monthly, None, None)),
+            vec!["A day of the month is required.".to_string()]
+        );
+        assert_eq!(
+            TemplateService::schedule_problems(&schedule(
+                logs_db::Frequency::Yearly,
+                Some(30),
+                Some(2)
+            ))
+            .len(),
+            1
+        );
+        assert_eq!(
+            TemplateService::schedule_problems(&schedule(logs_db::Frequency::Weekly, None, None)),
+            vec!["A day of the week is required.".to_string()]
+        );
+
+        let backwards_times = logs_db::Schedule {
+            available_from_time: Some("17:00".to_string()),
+            due_at_time: Some("09:00".to_string()),
+            ..schedule(logs_db::Frequency::Daily, None, None)
+        };
+        assert_eq!(
+            TemplateService::schedule_problems(&backwards_times),
+            vec!["The due time must be after the available-from time.".to_string()]
+        );
+    }
+
+    #[test]
+    fn test_template_key_prefers_id() {
+        assert_eq!(
+            TemplateService::template_key(Some("abc"), Some("Fridge")).unwrap(),
+            logs_db::TemplateKey::Id("abc")
+        );
+        assert_eq!(
+            TemplateService::template_key(Some(""), Some("Fridge")).unwrap(),
+            logs_db::TemplateKey::Name("Fridge")
+        );
+        let (status, _) = TemplateService::template_key(None, None).unwrap_err();
+        assert_eq!(status, StatusCode::BAD_REQUEST);
+    }
+
     #[tokio::test]
     async fn test_template_service_basic() {
         assert!(true);
@@ -189,14 +677,115 @@ mod template_service_tests {
 
 pub struct TemplateService;
 
+/// Details of a template to create.
+pub struct NewTemplate {
+    pub template_name: String,
+    pub template_layout: logs_db::TemplateLayout,
+    pub schedule: logs_db::Schedule,
+    pub entry_mode: logs_db::EntryMode,
+    pub branch_id: Option<String>,
+}
+
+/// Largest number of days or weeks between occurrences of an interval schedule.
+const MAX_SCHEDULE_INTERVAL: u16 = 365;
+
+/// Most time slots a sub-daily schedule can have, one every half hour.
+const MAX_TIME_SLOTS: usize = 48;
+
+/// Periods returned by a schedule preview when no count is given.
+const DEFAULT_PREVIEW_PERIODS: usize = 10;
+
+/// Most periods a schedule preview can return.
+const MAX_PREVIEW_PERIODS: usize = 50;
+
 impl TemplateService {
+    /// Picks how a request identifies a template, preferring the immutable id over the name.
+    ///
+    /// # Errors
+    /// Returns a bad request error if neither is given.
+    pub fn template_key<'a>(
+        template_id: Option<&'a str>,
+        template_name: Option<&'a str>,
+    ) -> Result<logs_db::TemplateKey<'a>, (StatusCode, serde_json::Value)> {
+        match (
+            template_id.filter(|id| !id.is_empty()),
+            template_name.filter(|name| !name.is_empty()),
+        ) {
+            (Some(id), _) => Ok(logs_db::TemplateKey::Id(id)),
+            (None, Some(name)) => Ok(logs_db::TemplateKey::Name(name)),
+            (None, None) => Err((
+                StatusCode::BAD_REQUEST,
+                json!({ "error": "template_id or template_name is required" }),
+            )),
+        }
+    }
+
+    /// Returns `name`, or the first of "name (2)", "name (3)", ... that is not taken.
+    pub(crate) fn resolve_template_name(
+        name: &str,
+        taken: &std::collections::HashSet<String>,
+    ) -> String {
+        if !taken.contains(name) {
+            return name.to_string();
+        }
+        (2..)
+            .map(|n| format!("{name} ({n})"))
+            .find(|candidate| !taken.contains(candidate))
+            .unwrap_or_else(|| name.to_string())
+    }
+
+    /// Checks that a branch exists and belongs to the company before templates are created for it.
+    ///
+    /// # Errors
+    /// Returns not found if the branch is missing or belongs to another company.
+    pub async fn ensure_company_branch(
+        state: &AppState,
+        company_id: &str,
+        branch_id: &str,
+    ) -> Result<(), (StatusCode, serde_json::Value)> {
+        let branch = db::get_branch_by_id(&state.postgres, branch_id)
+            .await
+            .map_err(|e| {
+                tracing::error!("Failed to fetch branch: {:?}", e);
+                (
+                    StatusCode::INTERNAL_SERVER_ERROR,
+                    json!({ "error": "Database error" }),
+                )
+            })?;
+        if branch.is_none_or(|b| b.company_id != company_id) {
+            return Err((
+                StatusCode::NOT_FOUND,
+                json!({ "error": "Branch not found" }),
+            ));
+        }
+        Ok(())
+    }
+
     /// Validates that all template fields contain safe values and valid constraints.
     /// Checks for CSS injection, input types, and length constraints.
     /// Returns an error if any field contains invalid data.
-    fn validate_template_layout(
+    pub(crate) fn validate_template_layout(
         template_layout: &logs_db::TemplateLayout,
     ) -> Result<(), (StatusCode, serde_json::Value)> {
+        let mut seen_field_ids = std::collections::HashSet::new();
+
         for (field_index, field) in template_layout.iter().enumerate() {
+            // Validate field_id if present; ids key entry data so must be unique and non-numeric
+            if !field.field_id.is_empty() {
+                if field.field_id.len() > 64 || field.field_id.parse::<usize>().is_ok() {
+                    return Err((
+                        StatusCode::BAD_REQUEST,
+                        json!({ "error": format!("Field {}: Invalid field_id '{}'.", field_index, field.field_id) }),
+                    ));
+                }
+                if !seen_field_ids.insert(field.field_id.as_str()) {
+                    return Err((
+                        StatusCode::BAD_REQUEST,
+                        json!({ "error": format!("Field {}: Duplicate field_id '{}'.", field_index, field.field_id) }),
+                    ));
+                }
+            }
+
             // Validate color field if present
             if let Some(color) = &field.props.color
                 && !utils::is_valid_css_color(color)
@@ -237,6 +826,13 @@ impl TemplateService {
                 ));
             }
 
+            if let Err(e) = Self::validate_field_type_props(field) {
+                return Err((
+                    StatusCode::BAD_REQUEST,
+                    json!({ "error": format!("Field {}: {}", field_index, e) }),
+                ));
+            }
+
             // Validate length constraints (min_length and max_length)
             if let Err(e) =
                 utils::validate_length_constraints(field.props.min_length, field.props.max_length)
@@ -247,93 +843,584 @@ impl TemplateService {
                 ));
             }
         }
+
+        Self::validate_calculated_fields(template_layout)?;
+        Self::validate_field_rules(template_layout)
+    }
+
+    /// Validates props that only apply to particular field types.
+    fn validate_field_type_props(field: &logs_db::TemplateField) -> Result<(), String> {
+        let props = &field.props;
+        match field.field_type.as_str() {
+            "number" => {
+                if let (Some(min), Some(max)) = (props.min, props.max)
+                    && min > max
+                {
+                    return Err("min must not be greater than max.".to_string());
+                }
+                if props.decimals.is_some_and(|d| d > 6) {
+                    return Err("decimals must be between 0 and 6.".to_string());
+                }
+                if props.unit.as_ref().is_some_and(|u| u.chars().count() > 20) {
+                    return Err("unit must not exceed 20 characters.".to_string());
+                }
+            }
+            "multi_select" => {
+                let options = props.options.as_deref().unwrap_or_default();
+                if options.is_empty() {
+                    return Err("Multi-select fields require at least one option.".to_string());
+                }
+                let unique: std::collections::HashSet<&str> =
+                    options.iter().map(|o| o.trim()).collect();
+                if unique.contains("") || unique.len() != options.len() {
+                    return Err("Options must be unique and not blank.".to_string());
+                }
+            }
+            _ => {}
+        }
         Ok(())
     }
-    /// Creates a new log template.
-    ///
-    /// # Errors
-    /// Returns an error if a template with the same name already exists or if database operations fail.
-    pub async fn create_template(
-        state: &AppState,
-        company_id: &str,
-        template_name: String,
-        template_layout: logs_db::TemplateLayout,
-        schedule: logs_db::Schedule,
-        user_id: &str,
-        branch_id: Option<String>,
-    ) -> Result<(), (StatusCode, serde_json::Value)> {
-        // Validate template layout for malicious content
-        Self::validate_template_layout(&template_layout)?;
 
-        let existing_template =
-            logs_db::get_template_by_name(&state.mongodb, &template_name, company_id)
-                .await
-                .map_err(|e| {
-                    tracing::error!("Failed to check for existing template: {:?}", e);
-                    (
-                        StatusCode::INTERNAL_SERVER_ERROR,
-                        json!({ "error": "Database error" }),
-                    )
-                })?;
+    /// Resolves a reference to another field by its id or, for fields without an id yet, its index.
+    fn field_reference(
+        template_layout: &logs_db::TemplateLayout,
+        reference: &str,
+    ) -> Option<usize> {
+        template_layout
+            .iter()
+            .enumerate()
+            .position(|(field_index, field)| field.entry_key(field_index) == reference)
+            .or_else(|| {
+                reference
+                    .parse::<usize>()
+                    .ok()
+                    .filter(|index| template_layout.get(*index).is_some())
+            })
+    }
 
-        if existing_template.is_some() {
-            return Err((
-                StatusCode::CONFLICT,
-                json!({ "error": "A template with this name already exists for your company" }),
-            ));
-        }
+    /// Whether entries store a value for the field, either entered or calculated.
+    fn has_value(field: &logs_db::TemplateField) -> bool {
+        field.field_type == "calculated" || INPUT_FIELD_TYPES.contains(&field.field_type.as_str())
+    }
 
-        let template_document = logs_db::TemplateDocument {
-            template_name,
-            template_layout,
-            company_id: company_id.to_string(),
-            branch_id,
-            created_at: chrono::Utc::now(),
-            updated_at: chrono::Utc::now(),
-            created_by: mongodb::bson::Uuid::parse_str(user_id).map_err(|e| {
-                tracing::error!("Failed to parse user ID as UUID: {:?}", e);
-                (
-                    StatusCode::INTERNAL_SERVER_ERROR,
-                    json!({ "error": "Server error" }),
-                )
-            })?,
-            schedule,
-            version: 1,
-            version_name: None,
+    /// Validates the expressions of `calculated` fields. Expressions must parse, reference
+    /// other input or calculated fields, and calculated fields must not depend on each other
+    /// in a cycle.
+    fn validate_calculated_fields(
+        template_layout: &logs_db::TemplateLayout,
+    ) -> Result<(), (StatusCode, serde_json::Value)> {
+        let invalid = |field_index: usize, message: String| {
+            (
+                StatusCode::BAD_REQUEST,
+                json!({ "error": format!("Field {}: {}", field_index, message) }),
+            )
         };
 
-        logs_db::add_template(&state.mongodb, &template_document)
-            .await
-            .map_err(|e: anyhow::Error| {
-                tracing::error!("Failed to add template: {:?}", e);
-                (
-                    StatusCode::INTERNAL_SERVER_ERROR,
-                    json!({ "error": "Failed to add template" }),
-                )
-            })?;
+        let mut dependencies = vec![Vec::new(); template_layout.len()];
+        for (field_index, field) in template_layout.iter().enumerate() {
+            let expression = field.props.expression.as_deref();
+            if field.field_type != "calculated" {
+                if expression.is_some() {
+                    return Err(invalid(
+                        field_index,
+                        "expression can only be set on calculated fields.".to_string(),
+                    ));
+                }
+                continue;
+            }
 
-        Ok(())
-    }
+            let Some(expression) = expression else {
+                return Err(invalid(
+                    field_index,
+                    "Calculated fields require an expression.".to_string(),
+                ));
+            };
+            let expr = crate::expression::parse(expression)
+                .map_err(|e| invalid(field_index, format!("Invalid expression: {e}.")))?;
 
-    /// Retrieves a specific log template by its name.
-    ///
-    /// # Errors
-    /// Returns an error if the template is not found or if database lookup fails.
+            for reference in expr.references() {
+                let Some(source) = Self::field_reference(template_layout, reference) else {
+                    return Err(invalid(
+                        field_index,
+                        format!("Expression references unknown field '{reference}'."),
+                    ));
+                };
+                if source == field_index {
+                    return Err(invalid(
+                        field_index,
+                        "Expression cannot reference the field itself.".to_string(),
+                    ));
+                }
+                if !Self::has_value(&template_layout[source])
+                    || matches!(
+                        template_layout[source].field_type.as_str(),
+                        "multi_select" | "signature"
+                    )
+                {
+                    return Err(invalid(
+                        field_index,
+                        format!("Expression cannot use the value of field '{reference}'."),
+                    ));
+                }
+                if template_layout[source].field_type == "calculated" {
+                    dependencies[field_index].push(source);
+                }
+            }
+        }
+
+        // Depth-first search for a calculated field that depends on itself
+        fn visit(field_index: usize, dependencies: &[Vec<usize>], state: &mut [u8]) -> bool {
+            match state[field_index] {
+                1 => return false,
+                2 => return true,
+                _ => {}
+            }
+            state[field_index] = 1;
+            let acyclic = dependencies[field_index]
+                .iter()
+                .all(|&source| visit(source, dependencies, state));
+            state[field_index] = 2;
+            acyclic
+        }
+        let mut state = vec![0u8; template_layout.len()];
+        for field_index in 0..template_layout.len() {
+            if !visit(field_index, &dependencies, &mut state) {
+                return Err(invalid(
+                    field_index,
+                    "Calculated fields cannot depend on each other in a cycle.".to_string(),
+                ));
+            }
+        }
+        Ok(())
+    }
+
+    /// Validates the `visible_if` and `required_if` rules of a layout. Rules must reference
+    /// another input field by id (or index), use an operator that suits that field, and
+    /// visibility rules must not depend on each other in a cycle.
+    fn validate_field_rules(
+        template_layout: &logs_db::TemplateLayout,
+    ) -> Result<(), (StatusCode, serde_json::Value)> {
+        let source_index = |condition: &logs_db::FieldCondition| {
+            Self::field_reference(template_layout, &condition.field_id)
+        };
+        let invalid = |field_index: usize, message: String| {
+            (
+                StatusCode::BAD_REQUEST,
+                json!({ "error": format!("Field {}: {}", field_index, message) }),
+            )
+        };
+
+        let mut visibility_sources = vec![None; template_layout.len()];
+        for (field_index, field) in template_layout.iter().enumerate() {
+            let is_input = INPUT_FIELD_TYPES.contains(&field.field_type.as_str());
+            if field.props.required_if.is_some() && !is_input {
+                return Err(invalid(
+                    field_index,
+                    "required_if can only be set on input fields.".to_string(),
+                ));
+            }
+
+            let rules = [
+                ("visible_if", &field.props.visible_if),
+                ("required_if", &field.props.required_if),
+            ];
+            for (rule, condition) in rules {
+                let Some(condition) = condition else {
+                    continue;
+                };
+                let Some(source) = source_index(condition) else {
+                    return Err(invalid(
+                        field_index,
+                        format!("{rule} references unknown field '{}'.", condition.field_id),
+                    ));
+                };
+                let source_field = &template_layout[source];
+                if source == field_index {
+                    return Err(invalid(
+                        field_index,
+                        format!("{rule} cannot reference the field itself."),
+                    ));
+                }
+                if !Self::has_value(source_field) {
+                    return Err(invalid(
+                        field_index,
+                        format!("{rule} must reference an input or calculated field."),
+                    ));
+                }
+                if !condition.operator.applies_to(source_field) {
+                    return Err(invalid(
+                        field_index,
+                        format!(
+                            "{rule} operator cannot be used with the {} field it references.",
+                            source_field.field_type
+                        ),
+                    ));
+                }
+                match (&condition.value, condition.operator.takes_value()) {
+                    (None, true) => {
+                        return Err(invalid(
+                            field_index,
+                            format!("{rule} requires a value to compare against."),
+                        ));
+                    }
+                    (Some(_), false) => {
+                        return Err(invalid(
+                            field_index,
+                            format!("{rule} operator does not take a value."),
+                        ));
+                    }
+                    (Some(value), true)
+                        if !(value.is_string() || value.is_number() || value.is_boolean()) =>
+                    {
+                        return Err(invalid(
+                            field_index,
+                            format!("{rule} value must be a string, number or boolean."),
+                        ));
+                    }
+                    _ => {}
+                }
+                if rule == "visible_if" {
+                    visibility_sources[field_index] = Some(source);
+                }
+            }
+        }
+
+        // Each field has at most one visibility source, so following the chain from any field
+        // either ends or revisits a field within `len` steps
+        for start in 0..template_layout.len() {
+            let mut current = start;
+            for _ in 0..template_layout.len() {
+                let Some(source) = visibility_sources[current] else {
+                    break;
+                };
+                if source == start {
+                    return Err(invalid(
+                        start,
+                        "visible_if rules cannot depend on each other in a cycle.".to_string(),
+                    ));
+                }
+                current = source;
+            }
+        }
+        Ok(())
+    }
+    /// Validates the interval, start date and time slots of a schedule.
+    ///
+    /// # Errors
+    /// Returns a bad request error describing the first invalid setting.
+    pub fn validate_schedule(
+        schedule: &logs_db::Schedule,
+    ) -> Result<(), (StatusCode, serde_json::Value)> {
+        let invalid = |message: &str| Err((StatusCode::BAD_REQUEST, json!({ "error": message })));
+        let daily_or_weekly = matches!(
+            schedule.frequency,
+            logs_db::Frequency::Daily | logs_db::Frequency::Weekly
+        );
+
+        if let Some(interval) = schedule.interval {
+            if !daily_or_weekly {
+                return invalid("An interval can only be set on daily or weekly schedules.");
+            }
+            if !(1..=MAX_SCHEDULE_INTERVAL).contains(&interval) {
+                return invalid(&format!(
+                    "Interval must be between 1 and {MAX_SCHEDULE_INTERVAL}."
+                ));
+            }
+            if interval > 1 && schedule.starts_on.is_none() {
+                return invalid(
+                    "A start date is required for schedules that repeat every few days or weeks.",
+                );
+            }
+        }
+        if schedule.starts_on.is_some() && !daily_or_weekly {
+            return invalid("A start date can only be set on daily or weekly schedules.");
+        }
+
+        let Some(time_slots) = &schedule.time_slots else {
+            return Ok(());
+        };
+        if !matches!(schedule.frequency, logs_db::Frequency::Daily) {
+            return invalid("Time slots can only be set on daily schedules.");
+        }
+        if time_slots.is_empty() || time_slots.len() > MAX_TIME_SLOTS {
+            return invalid(&format!(
+                "A schedule must have between 1 and {MAX_TIME_SLOTS} time slots."
+            ));
+        }
+        for slot in time_slots {
+            let (Some(from), Some(due)) = (
+                logs_db::parse_time_string(&slot.available_from_time),
+                logs_db::parse_time_string(&slot.due_at_time),
+            ) else {
+                return invalid("Time slot times must be in HH:MM format.");
+            };
+            if due <= from {
+                return invalid(&format!(
+                    "Time slot starting at {} must be due after it opens.",
+                    slot.available_from_time
+                ));
+            }
+        }
+
+        let slots = schedule.daily_slots();
+        if slots.windows(2).any(|pair| pair[1].0 < pair[0].1) {
+            return invalid("Time slots cannot overlap.");
+        }
+        Ok(())
+    }
+
+    /// Settings of a schedule that are rejected on save, or that are accepted but name a day
+    /// some periods do not have.
+    #[must_use]
+    pub fn schedule_problems(schedule: &logs_db::Schedule) -> Vec<String> {
+        use logs_db::Frequency;
+
+        let mut problems = Vec::new();
+        if let Err((_, err)) = Self::validate_schedule(schedule) {
+            problems.push(err["error"].as_str().unwrap_or_default().to_string());
+        }
+
+        let times = (
+            schedule.available_from_time.as_deref(),
+            schedule.due_at_time.as_deref(),
+        );
+        let parsed = (
+            times.0.map(logs_db::parse_time_string),
+            times.1.map(logs_db::parse_time_string),
+        );
+        if matches!(parsed, (Some(None), _) | (_, Some(None))) {
+            problems.push("Times must be in HH:MM format.".to_string());
+        } else if let (Some(Some(from)), Some(Some(due))) = parsed
+            && due <= from
+        {
+            problems.push("The due time must be after the available-from time.".to_string());
+        }
+
+        let day_of_month = |problems: &mut Vec<String>| match schedule.day_of_month {
+            None => {
+                problems.push("A day of the month is required.".to_string());
+                None
+            }
+            Some(day) if !(1..=31).contains(&day) => {
+                problems.push("Day of the month must be between 1 and 31.".to_string());
+                None
+            }
+            Some(day) => Some(u32::from(day)),
+        };
+
+        match schedule.frequency {
+            Frequency::Daily => match &schedule.days_of_week {
+                Some(days) if days.iter().any(|d| *d > 6) => problems.push(
+                    "Days of the week must be between 0 (Sunday) and 6 (Saturday).".to_string(),
+                ),
+                Some(days) if days.is_empty() => problems.push(
+                    "No days of the week are selected, so the form is never due.".to_string(),
+                ),
+                _ => {}
+            },
+            Frequency::Weekly => match schedule.day_of_week {
+                None => problems.push("A day of the week is required.".to_string()),
+                Some(day) if day > 6 => problems.push(
+                    "Day of the week must be between 0 (Sunday) and 6 (Saturday).".to_string(),
+                ),
+                Some(_) => {}
+            },
+            Frequency::Monthly => {
+                if let Some(day) = day_of_month(&mut problems)
+                    && day > 28
+                {
+                    problems.push(format!(
+                        "Months with fewer than {day} days are due on their last day."
+                    ));
+                }
+            }
+            Frequency::Quarterly => {
+                if let Some(day) = day_of_month(&mut problems) {
+                    // Quarters are due in their first month: January, April, July and October
+                    let shortened: Vec<String> = [(1, 31), (2, 30), (3, 31), (4, 31)]
+                        .iter()
+                        .filter(|(_, days)| day > *days)
+                        .map(|(quarter, _)| format!("Q{quarter}"))
+                        .collect();
+                    if !shortened.is_empty() {
+                        problems.push(format!(
+                            "Day {day} does not exist in the first month of {}, which is due on that month's last day.",
+                            shortened.join(", ")
+                        ));
+                    }
+                }
+            }
+            Frequency::Yearly => {
+                let month = match schedule.month_of_year {
+                    None => {
+                        problems.push("A month of the year is required.".to_string());
+                        None
+                    }
+                    Some(month) if !(1..=12).contains(&month) => {
+                        problems.push("Month of the year must be between 1 and 12.".to_string());
+                        None
+                    }
+                    Some(month) => Some(u32::from(month)),
+                };
+                if let (Some(month), Some(day)) = (month, day_of_month(&mut problems)) {
+                    if month == 2 && day == 29 {
+                        problems.push(
+                            "29 February only exists in leap years. Other years are due on 28 February."
+                                .to_string(),
+                        );
+                    } else if chrono::NaiveDate::from_ymd_opt(2025, month, day).is_none() {
+                        problems.push(
+                            "The day does not exist in the chosen month, which is due on its last day."
+                                .to_string(),
+                        );
+                    }
+                }
+            }
+        }
+        problems
+    }
+
+    /// Lists the next `count` periods of a schedule with their opening and due times in the
+    /// branch or company timezone, along with any problems with the schedule.
+    ///
+    /// # Errors
+    /// Returns an error if the count is out of range or the timezone cannot be loaded.
+    pub async fn preview_schedule(
+        state: &AppState,
+        company_id: &str,
+        branch_id: Option<&str>,
+        schedule: &logs_db::Schedule,
+        created_at: Option<chrono::DateTime<chrono::Utc>>,
+        count: Option<usize>,
+    ) -> Result<SchedulePreviewResponse, (StatusCode, serde_json::Value)> {
+        let count = count.unwrap_or(DEFAULT_PREVIEW_PERIODS);
+        if !(1..=MAX_PREVIEW_PERIODS).contains(&count) {
+            return Err((
+                StatusCode::BAD_REQUEST,
+                json!({ "error": format!("Count must be between 1 and {MAX_PREVIEW_PERIODS}") }),
+            ));
+        }
+
+        let tz = db::get_branch_timezone(&state.postgres, company_id, branch_id)
+            .await
+            .map_err(|e| {
+                tracing::error!("Failed to get timezone: {:?}", e);
+                (
+                    StatusCode::INTERNAL_SERVER_ERROR,
+                    json!({ "error": "Database error" }),
+                )
+            })?;
+
+        let now = chrono::Utc::now();
+        let from = created_at.map_or(now, |created_at| created_at.max(now));
+        let periods =
+            logs_db::upcoming_periods(schedule, from.with_timezone(&tz).date_naive(), count)
+                .into_iter()
+                .filter_map(|period| {
+                    Some(SchedulePreviewPeriod {
+                        due_date: logs_db::compute_due_date_for_period(schedule, &period)?,
+                        available_from: logs_db::get_available_from_datetime(schedule, &period, tz),
+                        due_at: logs_db::get_due_at_datetime(schedule, &period, tz),
+                        period,
+                    })
+                })
+                .collect();
+
+        Ok(SchedulePreviewResponse {
+            timezone: tz.name().to_string(),
+            periods,
+            problems: Self::schedule_problems(schedule),
+        })
+    }
+
+    /// Creates a new log template.
+    ///
+    /// # Errors
+    /// Returns an error if a template with the same name already exists or if database operations fail.
+    pub async fn create_template(
+        state: &AppState,
+        company_id: &str,
+        template: NewTemplate,
+        user_id: &str,
+    ) -> Result<String, (StatusCode, serde_json::Value)> {
+        let NewTemplate {
+            template_name,
+            template_layout,
+            schedule,
+            entry_mode,
+            branch_id,
+        } = template;
+        // Validate template layout for malicious content
+        Self::validate_template_layout(&template_layout)?;
+        Self::validate_schedule(&schedule)?;
+
+        let existing_template =
+            logs_db::get_template_by_name(&state.mongodb, &template_name, company_id)
+                .await
+                .map_err(|e| {
+                    tracing::error!("Failed to check for existing template: {:?}", e);
+                    (
+                        StatusCode::INTERNAL_SERVER_ERROR,
+                        json!({ "error": "Database error" }),
+                    )
+                })?;
+
+        if existing_template.is_some() {
+            return Err((
+                StatusCode::CONFLICT,
+                json!({ "error": "A template with this name already exists for your company" }),
+            ));
+        }
+
+        let template_layout = logs_db::assign_field_ids(&template_layout, None);
+
+        let template_id = uuid::Uuid::new_v4().to_string();
+        let template_document = logs_db::TemplateDocument {
+            template_id: template_id.clone(),
+            template_name,
+            template_layout,
+            company_id: company_id.to_string(),
+            branch_id,
+            created_at: chrono::Utc::now(),
+            updated_at: chrono::Utc::now(),
+            created_by: mongodb::bson::Uuid::parse_str(user_id).map_err(|e| {
+                tracing::error!("Failed to parse user ID as UUID: {:?}", e);
+                (
+                    StatusCode::INTERNAL_SERVER_ERROR,
+                    json!({ "error": "Server error" }),
+                )
+            })?,
+            schedule,
+            version: 1,
+            version_name: None,
+            archived_at: None,
+            linked_from: None,
+            entry_mode,
+        };
+
+        logs_db::add_template(&state.mongodb, &template_document)
+            .await
+            .map_err(|e: anyhow::Error| {
+                tracing::error!("Failed to add template: {:?}", e);
+                (
+                    StatusCode::INTERNAL_SERVER_ERROR,
+                    json!({ "error": "Failed to add template" }),
+                )
+            })?;
+
+        Ok(template_id)
+    }
+
+    /// Retrieves a specific log template by its id or name.
+    ///
+    /// # Errors
+    /// Returns an error if the template is not found or if database lookup fails.
     pub async fn get_template(
         state: &AppState,
         company_id: &str,
-        template_name: &str,
-    ) -> Result<
-        (
-            String,
-            logs_db::TemplateLayout,
-            u16,
-            Option<String>,
-            Option<String>,
-        ),
-        (StatusCode, serde_json::Value),
-    > {
-        let template = logs_db::get_template_by_name(&state.mongodb, template_name, company_id)
+        template_key: logs_db::TemplateKey<'_>,
+    ) -> Result<logs_db::TemplateDocument, (StatusCode, serde_json::Value)> {
+        let template = logs_db::get_template(&state.mongodb, template_key, company_id)
             .await
             .map_err(|e: anyhow::Error| {
                 tracing::error!("Failed to get template: {:?}", e);
@@ -343,22 +1430,14 @@ impl TemplateService {
                 )
             })?;
 
-        match template {
-            Some(t) => Ok((
-                t.template_name,
-                t.template_layout,
-                t.version,
-                t.version_name,
-                t.branch_id,
-            )),
-            None => Err((
-                StatusCode::NOT_FOUND,
-                json!({ "error": "Template not found" }),
-            )),
-        }
+        template.ok_or((
+            StatusCode::NOT_FOUND,
+            json!({ "error": "Template not found" }),
+        ))
     }
 
-    /// Retrieves all log templates associated with a company.
+    /// Retrieves the active log templates associated with a company, or the archived ones
+    /// when `archived` is set.
     ///
     /// # Errors
     /// Returns an error if the database query fails.
@@ -366,36 +1445,46 @@ impl TemplateService {
         state: &AppState,
         company_id: &str,
         branch_id: Option<&str>,
+        archived: bool,
     ) -> Result<
         Vec<(
+            String,
             String,
             chrono::DateTime<chrono::Utc>,
             chrono::DateTime<chrono::Utc>,
             String,
             logs_db::Schedule,
+            logs_db::EntryMode,
+            Option<chrono::DateTime<chrono::Utc>>,
         )>,
         (StatusCode, serde_json::Value),
     > {
-        let templates =
+        let templates = if archived {
+            logs_db::get_archived_templates(&state.mongodb, company_id, branch_id).await
+        } else {
             logs_db::get_templates_by_company_and_branch(&state.mongodb, company_id, branch_id)
                 .await
-                .map_err(|e: anyhow::Error| {
-                    tracing::error!("Failed to get templates: {:?}", e);
-                    (
-                        StatusCode::INTERNAL_SERVER_ERROR,
-                        json!({ "error": "Failed to get templates" }),
-                    )
-                })?;
+        };
+        let templates = templates.map_err(|e: anyhow::Error| {
+            tracing::error!("Failed to get templates: {:?}", e);
+            (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                json!({ "error": "Failed to get templates" }),
+            )
+        })?;
 
         let result = templates
             .into_iter()
             .map(|t| {
                 (
+                    t.template_id,
                     t.template_name,
                     t.created_at,
                     t.updated_at,
                     t.created_by.to_string(),
                     t.schedule,
+                    t.entry_mode,
+                    t.archived_at,
                 )
             })
             .collect();
@@ -409,7 +1498,7 @@ impl TemplateService {
     /// Returns an error if the database update fails.
     pub async fn update_template(
         state: &AppState,
-        template_name: &str,
+        template_key: logs_db::TemplateKey<'_>,
         template_layout: Option<&logs_db::TemplateLayout>,
         schedule: Option<&logs_db::Schedule>,
         user: &UserRecord,
@@ -420,6 +1509,9 @@ impl TemplateService {
         if let Some(layout) = template_layout {
             Self::validate_template_layout(layout)?;
         }
+        if let Some(schedule) = schedule {
+            Self::validate_schedule(schedule)?;
+        }
 
         let company_id = user.company_id.as_ref().ok_or((
             StatusCode::INTERNAL_SERVER_ERROR,
@@ -427,20 +1519,19 @@ impl TemplateService {
         ))?;
 
         // 1. Fetch current template state
-        let current_template =
-            logs_db::get_template_by_name(&state.mongodb, template_name, company_id)
-                .await
-                .map_err(|e| {
-                    tracing::error!("Failed to fetch template for versioning: {:?}", e);
-                    (
-                        StatusCode::INTERNAL_SERVER_ERROR,
-                        json!({ "error": "Database error" }),
-                    )
-                })?
-                .ok_or((
-                    StatusCode::NOT_FOUND,
-                    json!({ "error": "Template not found" }),
-                ))?;
+        let current_template = logs_db::get_template(&state.mongodb, template_key, company_id)
+            .await
+            .map_err(|e| {
+                tracing::error!("Failed to fetch template for versioning: {:?}", e);
+                (
+                    StatusCode::INTERNAL_SERVER_ERROR,
+                    json!({ "error": "Database error" }),
+                )
+            })?
+            .ok_or((
+                StatusCode::NOT_FOUND,
+                json!({ "error": "Template not found" }),
+            ))?;
 
         // Authorization check
         if user.is_branch_manager() {
@@ -466,6 +1557,7 @@ impl TemplateService {
 
         // 2. Archive current state as a version
         let version_doc = logs_db::TemplateVersionDocument {
+            template_id: current_template.template_id.clone(),
             template_name: current_template.template_name.clone(),
             company_id: current_template.company_id.clone(),
             branch_id: current_template.branch_id.clone(),
@@ -489,12 +1581,17 @@ impl TemplateService {
             })?;
 
         // 3. Update template (this increments version in DB)
+        // Fields keep their ids; new fields get one, reusing the previous id where the client omitted it
+        let template_layout = template_layout.map(|layout| {
+            logs_db::assign_field_ids(layout, Some(&current_template.template_layout))
+        });
+
         logs_db::update_template(
             &state.mongodb,
-            template_name,
+            &current_template.template_id,
             company_id,
             schedule,
-            template_layout,
+            template_layout.as_ref(),
             version_name,
             resolved_branch_id,
         )
@@ -515,15 +1612,16 @@ impl TemplateService {
     /// Returns an error if database query fails.
     pub async fn get_versions(
         state: &AppState,
-        template_name: &str,
+        template_key: logs_db::TemplateKey<'_>,
         user: &UserRecord,
     ) -> Result<Vec<logs_db::TemplateVersionDocument>, (StatusCode, serde_json::Value)> {
         let company_id = user.company_id.as_ref().ok_or((
             StatusCode::INTERNAL_SERVER_ERROR,
             json!({ "error": "User record missing company ID" }),
         ))?;
+        let template = Self::get_template(state, company_id, template_key).await?;
         let template_versions =
-            logs_db::get_template_versions(&state.mongodb, company_id, template_name)
+            logs_db::get_template_versions(&state.mongodb, company_id, &template.template_id)
                 .await
                 .map_err(|e| {
                     tracing::error!("Failed to fetch template versions: {:?}", e);
@@ -547,6 +1645,93 @@ impl TemplateService {
         template_versions
     }
 
+    /// Compares two versions of a template, or a version with the current template when
+    /// `to` is omitted. Either version may be the template's current version number.
+    ///
+    /// # Errors
+    /// Returns an error if the template or a version is not found, the user cannot view the
+    /// template's versions, or the database query fails.
+    pub async fn diff_versions(
+        state: &AppState,
+        template_key: logs_db::TemplateKey<'_>,
+        from: u16,
+        to: Option<u16>,
+        user: &UserRecord,
+    ) -> Result<TemplateVersionDiffResponse, (StatusCode, serde_json::Value)> {
+        let company_id = user.company_id.as_ref().ok_or((
+            StatusCode::INTERNAL_SERVER_ERROR,
+            json!({ "error": "User record missing company ID" }),
+        ))?;
+        let template = Self::get_template(state, company_id, template_key).await?;
+        if user.role == UserRole::BranchManager
+            && template.branch_id.as_deref() != user.branch_id.as_deref()
+        {
+            return Err((
+                StatusCode::FORBIDDEN,
+                json!({ "error": "Unauthorized to view versions of this template" }),
+            ));
+        }
+
+        let to = to.unwrap_or(template.version);
+        let (old_name, old_layout, old_schedule) =
+            Self::version_snapshot(state, &template, from).await?;
+        let (new_name, new_layout, new_schedule) =
+            Self::version_snapshot(state, &template, to).await?;
+
+        Ok(TemplateVersionDiffResponse {
+            template_id: template.template_id.clone(),
+            from_version: from,
+            to_version: to,
+            from_version_name: old_name,
+            to_version_name: new_name,
+            layout_changes: logs_db::diff_layouts(&old_layout, &new_layout),
+            schedule_changes: logs_db::diff_schedules(&old_schedule, &new_schedule),
+        })
+    }
+
+    /// Loads the name, layout and schedule of a template version, using the template itself
+    /// for its current version.
+    async fn version_snapshot(
+        state: &AppState,
+        template: &logs_db::TemplateDocument,
+        version: u16,
+    ) -> Result<
+        (Option<String>, logs_db::TemplateLayout, logs_db::Schedule),
+        (StatusCode, serde_json::Value),
+    > {
+        if version == template.version {
+            return Ok((
+                template.version_name.clone(),
+                template.template_layout.clone(),
+                template.schedule.clone(),
+            ));
+        }
+
+        let snapshot = logs_db::get_template_version(
+            &state.mongodb,
+            &template.company_id,
+            &template.template_id,
+            version,
+        )
+        .await
+        .map_err(|e| {
+            tracing::error!("Failed to fetch template version: {:?}", e);
+            (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                json!({ "error": "Database error" }),
+            )
+        })?
+        .ok_or((
+            StatusCode::NOT_FOUND,
+            json!({ "error": format!("Version {version} not found") }),
+        ))?;
+        Ok((
+            snapshot.version_name,
+            snapshot.template_layout,
+            snapshot.schedule,
+        ))
+    }
+
     /// Restores a specific version of a template.
     ///
     /// # Errors
@@ -554,31 +1739,37 @@ impl TemplateService {
     pub async fn restore_version(
         state: &AppState,
         company_id: &str,
-        template_name: &str,
+        template_key: logs_db::TemplateKey<'_>,
         version: u16,
         user: &UserRecord,
     ) -> Result<(), (StatusCode, serde_json::Value)> {
+        let template = Self::get_template(state, company_id, template_key).await?;
+
         // 1. Fetch target version
-        let target_version =
-            logs_db::get_template_version(&state.mongodb, company_id, template_name, version)
-                .await
-                .map_err(|e| {
-                    tracing::error!("Failed to fetch target version: {:?}", e);
-                    (
-                        StatusCode::INTERNAL_SERVER_ERROR,
-                        json!({ "error": "Database error" }),
-                    )
-                })?
-                .ok_or((
-                    StatusCode::NOT_FOUND,
-                    json!({ "error": "Version not found" }),
-                ))?;
+        let target_version = logs_db::get_template_version(
+            &state.mongodb,
+            company_id,
+            &template.template_id,
+            version,
+        )
+        .await
+        .map_err(|e| {
+            tracing::error!("Failed to fetch target version: {:?}", e);
+            (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                json!({ "error": "Database error" }),
+            )
+        })?
+        .ok_or((
+            StatusCode::NOT_FOUND,
+            json!({ "error": "Version not found" }),
+        ))?;
 
         // 2. Call update_template with the target data
         // This handles archiving the CURRENT state before overwriting it with the OLD state
         Self::update_template(
             state,
-            template_name,
+            logs_db::TemplateKey::Id(&template.template_id),
             Some(&target_version.template_layout),
             Some(&target_version.schedule),
             user,
@@ -595,12 +1786,19 @@ impl TemplateService {
     pub async fn rename_template(
         state: &AppState,
         company_id: &str,
-        old_name: &str,
+        template_key: logs_db::TemplateKey<'_>,
         new_name: &str,
         user_branch_id: Option<&str>,
         user_role: &UserRole,
     ) -> Result<(), (StatusCode, serde_json::Value)> {
-        let template = logs_db::get_template_by_name(&state.mongodb, old_name, company_id)
+        if new_name.trim().is_empty() {
+            return Err((
+                StatusCode::BAD_REQUEST,
+                json!({ "error": "Template name cannot be empty" }),
+            ));
+        }
+
+        let template = logs_db::get_template(&state.mongodb, template_key, company_id)
             .await
             .map_err(|e| {
                 tracing::error!("Failed to fetch template for rename: {:?}", e);
@@ -623,7 +1821,7 @@ impl TemplateService {
             ));
         }
 
-        logs_db::rename_template(&state.mongodb, old_name, new_name, company_id)
+        logs_db::rename_template(&state.mongodb, &template.template_id, new_name, company_id)
             .await
             .map_err(|e: anyhow::Error| {
                 tracing::error!("Failed to rename template: {:?}", e);
@@ -635,21 +1833,20 @@ impl TemplateService {
         Ok(())
     }
 
-    /// Deletes a log template.
-    ///
-    /// # Errors
-    /// Returns an error if the database deletion fails.
-    pub async fn delete_template(
+    /// Fetches a template that the user is about to archive, restore or purge, checking
+    /// that branch managers only touch their own branch's templates.
+    async fn get_managed_template(
         state: &AppState,
         company_id: &str,
-        template_name: &str,
+        template_key: logs_db::TemplateKey<'_>,
         user_branch_id: Option<&str>,
         user_role: &UserRole,
-    ) -> Result<(), (StatusCode, serde_json::Value)> {
-        let template = logs_db::get_template_by_name(&state.mongodb, template_name, company_id)
+        action: &str,
+    ) -> Result<logs_db::TemplateDocument, (StatusCode, serde_json::Value)> {
+        let template = logs_db::get_template(&state.mongodb, template_key, company_id)
             .await
             .map_err(|e| {
-                tracing::error!("Failed to fetch template for delete: {:?}", e);
+                tracing::error!("Failed to fetch template to {action}: {:?}", e);
                 (
                     StatusCode::INTERNAL_SERVER_ERROR,
                     json!({ "error": "Database error" }),
@@ -665,11 +1862,115 @@ impl TemplateService {
         {
             return Err((
                 StatusCode::FORBIDDEN,
-                json!({ "error": "Unauthorized to delete this template" }),
+                json!({ "error": format!("Unauthorized to {action} this template") }),
             ));
         }
+        Ok(template)
+    }
 
-        logs_db::delete_template(&state.mongodb, template_name, company_id)
+    /// Archives a log template. Archived templates are no longer due, but their entries
+    /// and versions stay available.
+    ///
+    /// # Errors
+    /// Returns an error if the template is already archived or the database update fails.
+    pub async fn archive_template(
+        state: &AppState,
+        company_id: &str,
+        template_key: logs_db::TemplateKey<'_>,
+        user_branch_id: Option<&str>,
+        user_role: &UserRole,
+    ) -> Result<(), (StatusCode, serde_json::Value)> {
+        let template = Self::get_managed_template(
+            state,
+            company_id,
+            template_key,
+            user_branch_id,
+            user_role,
+            "archive",
+        )
+        .await?;
+
+        let archived = logs_db::archive_template(&state.mongodb, &template.template_id, company_id)
+            .await
+            .map_err(|e: anyhow::Error| {
+                tracing::error!("Failed to archive template: {:?}", e);
+                (
+                    StatusCode::INTERNAL_SERVER_ERROR,
+                    json!({ "error": "Failed to archive template" }),
+                )
+            })?;
+        if !archived {
+            return Err((
+                StatusCode::CONFLICT,
+                json!({ "error": "Template is already archived" }),
+            ));
+        }
+        Ok(())
+    }
+
+    /// Restores an archived log template.
+    ///
+    /// # Errors
+    /// Returns an error if the template is not archived or the database update fails.
+    pub async fn unarchive_template(
+        state: &AppState,
+        company_id: &str,
+        template_key: logs_db::TemplateKey<'_>,
+        user_branch_id: Option<&str>,
+        user_role: &UserRole,
+    ) -> Result<(), (StatusCode, serde_json::Value)> {
+        let template = Self::get_managed_template(
+            state,
+            company_id,
+            template_key,
+            user_branch_id,
+            user_role,
+            "unarchive",
+        )
+        .await?;
+
+        let restored =
+            logs_db::unarchive_template(&state.mongodb, &template.template_id, company_id)
+                .await
+                .map_err(|e: anyhow::Error| {
+                    tracing::error!("Failed to unarchive template: {:?}", e);
+                    (
+                        StatusCode::INTERNAL_SERVER_ERROR,
+                        json!({ "error": "Failed to unarchive template" }),
+                    )
+                })?;
+        if !restored {
+            return Err((
+                StatusCode::CONFLICT,
+                json!({ "error": "Template is not archived" }),
+            ));
+        }
+        Ok(())
+    }
+
+    /// Permanently deletes a log template and its versions. Only templates that never
+    /// had entries can be purged; anything else has to be archived.
+    ///
+    /// # Errors
+    /// Returns an error if the template has entries or the database deletion fails.
+    pub async fn purge_template(
+        state: &AppState,
+        company_id: &str,
+        template_key: logs_db::TemplateKey<'_>,
+        user_branch_id: Option<&str>,
+        user_role: &UserRole,
+    ) -> Result<(), (StatusCode, serde_json::Value)> {
+        let template = Self::get_managed_template(
+            state,
+            company_id,
+            template_key,
+            user_branch_id,
+            user_role,
+            "delete",
+        )
+        .await?;
+
+        let purged = logs_db::purge_template(&state.mongodb, &template.template_id, company_id)
             .await
             .map_err(|e: anyhow::Error| {
                 tracing::error!("Failed to delete template: {:?}", e);
@@ -678,6 +1979,320 @@ impl TemplateService {
                     json!({ "error": "Failed to delete template" }),
                 )
             })?;
+        if !purged {
+            return Err((
+                StatusCode::CONFLICT,
+                json!({ "error": "Templates with log entries cannot be deleted; archive it instead" }),
+            ));
+        }
+        Ok(())
+    }
+
+    /// Copies a template to each of the given branches. Linked copies remember the template
+    /// they came from so later changes can be propagated to them.
+    ///
+    /// # Errors
+    /// Returns an error if the template or a branch is not found, the template is archived,
+    /// or the database insertion fails.
+    pub async fn distribute_template(
+        state: &AppState,
+        user: &UserRecord,
+        template_key: logs_db::TemplateKey<'_>,
+        branch_ids: &[String],
+        linked: bool,
+    ) -> Result<DistributeTemplateResponse, (StatusCode, serde_json::Value)> {
+        let company_id = user.company_id.as_deref().ok_or((
+            StatusCode::FORBIDDEN,
+            json!({ "error": "User is not associated with a company" }),
+        ))?;
+
+        let mut targets: Vec<&str> = Vec::new();
+        for branch_id in branch_ids {
+            if !branch_id.is_empty() && !targets.contains(&branch_id.as_str()) {
+                targets.push(branch_id);
+            }
+        }
+        if targets.is_empty() {
+            return Err((
+                StatusCode::BAD_REQUEST,
+                json!({ "error": "At least one branch is required" }),
+            ));
+        }
+
+        let template = Self::get_template(state, company_id, template_key).await?;
+        if template.is_archived() {
+            return Err((
+                StatusCode::CONFLICT,
+                json!({ "error": "Template is archived" }),
+            ));
+        }
+
+        let branch_names: std::collections::HashMap<String, String> =
+            db::get_branches_by_company_id(&state.postgres, company_id)
+                .await
+                .map_err(|e| {
+                    tracing::error!("Failed to fetch branches: {:?}", e);
+                    (
+                        StatusCode::INTERNAL_SERVER_ERROR,
+                        json!({ "error": "Database error" }),
+                    )
+                })?
+                .into_iter()
+                .map(|b| (b.id, b.name))
+                .collect();
+        if targets.iter().any(|id| !branch_names.contains_key(*id)) {
+            return Err((
+                StatusCode::NOT_FOUND,
+                json!({ "error": "Branch not found" }),
+            ));
+        }
+
+        let templates =
+            logs_db::get_templates_by_company_including_archived(&state.mongodb, company_id)
+                .await
+                .map_err(|e: anyhow::Error| {
+                    tracing::error!("Failed to get templates: {:?}", e);
+                    (
+                        StatusCode::INTERNAL_SERVER_ERROR,
+                        json!({ "error": "Failed to get templates" }),
+                    )
+                })?;
+        let linked_branches: std::collections::HashSet<String> = templates
+            .iter()
+            .filter(|t| {
+                !t.is_archived() && t.linked_from.as_deref() == Some(template.template_id.as_str())
+            })
+            .filter_map(|t| t.branch_id.clone())
+            .collect();
+        let mut taken: std::collections::HashSet<String> =
+            templates.into_iter().map(|t| t.template_name).collect();
+
+        let created_by = mongodb::bson::Uuid::parse_str(&user.id).map_err(|e| {
+            tracing::error!("Failed to parse user ID as UUID: {:?}", e);
+            (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                json!({ "error": "Server error" }),
+            )
+        })?;
+
+        let mut copies = Vec::new();
+        let mut skipped_branch_ids = Vec::new();
+        for branch_id in targets {
+            if template.branch_id.as_deref() == Some(branch_id)
+                || (linked && linked_branches.contains(branch_id))
+            {
+                skipped_branch_ids.push(branch_id.to_string());
+                continue;
+            }
+
+            let template_name = Self::resolve_template_name(
+                &format!("{} ({})", template.template_name, branch_names[branch_id]),
+                &taken,
+            );
+            taken.insert(template_name.clone());
+
+            let now = chrono::Utc::now();
+            let copy = logs_db::TemplateDocument {
+                template_id: uuid::Uuid::new_v4().to_string(),
+                template_name,
+                template_layout: template.template_layout.clone(),
+                company_id: company_id.to_string(),
+                branch_id: Some(branch_id.to_string()),
+                created_at: now,
+                updated_at: now,
+                created_by,
+                schedule: template.schedule.clone(),
+                version: 1,
+                version_name: template.version_name.clone(),
+                archived_at: None,
+                linked_from: linked.then(|| template.template_id.clone()),
+                entry_mode: template.entry_mode,
+            };
+            logs_db::add_template(&state.mongodb, &copy)
+                .await
+                .map_err(|e: anyhow::Error| {
+                    tracing::error!("Failed to add template copy: {:?}", e);
+                    (
+                        StatusCode::INTERNAL_SERVER_ERROR,
+                        json!({ "error": "Failed to add template" }),
+                    )
+                })?;
+
+            copies.push(DistributedTemplateInfo {
+                branch_id: branch_id.to_string(),
+                template_id: copy.template_id,
+                template_name: copy.template_name,
+            });
+        }
+
+        Ok(DistributeTemplateResponse {
+            copies,
+            skipped_branch_ids,
+        })
+    }
+
+    async fn get_linked_copies(
+        state: &AppState,
+        company_id: &str,
+        template_id: &str,
+    ) -> Result<Vec<logs_db::TemplateDocument>, (StatusCode, serde_json::Value)> {
+        logs_db::get_linked_templates(&state.mongodb, company_id, template_id)
+            .await
+            .map_err(|e: anyhow::Error| {
+                tracing::error!("Failed to get linked templates: {:?}", e);
+                (
+                    StatusCode::INTERNAL_SERVER_ERROR,
+                    json!({ "error": "Failed to get linked templates" }),
+                )
+            })
+    }
+
+    /// Describes what propagating a template would change in each of its linked copies.
+    fn preview_copy(
+        template: &logs_db::TemplateDocument,
+        copy: &logs_db::TemplateDocument,
+    ) -> LinkedCopyPreview {
+        let layout_changes =
+            logs_db::diff_layouts(&copy.template_layout, &template.template_layout);
+        let schedule_changed = serde_json::to_value(&copy.schedule).ok()
+            != serde_json::to_value(&template.schedule).ok();
+        LinkedCopyPreview {
+            template_id: copy.template_id.clone(),
+            template_name: copy.template_name.clone(),
+            branch_id: copy.branch_id.clone(),
+            up_to_date: layout_changes.is_empty() && !schedule_changed,
+            layout_changes,
+            schedule_changed,
+        }
+    }
+
+    /// Previews propagating a template's current layout and schedule to its linked copies.
+    ///
+    /// # Errors
+    /// Returns an error if the template is not found or the database query fails.
+    pub async fn preview_propagation(
+        state: &AppState,
+        company_id: &str,
+        template_key: logs_db::TemplateKey<'_>,
+    ) -> Result<PropagationPreviewResponse, (StatusCode, serde_json::Value)> {
+        let template = Self::get_template(state, company_id, template_key).await?;
+        let copies = Self::get_linked_copies(state, company_id, &template.template_id).await?;
+
+        Ok(PropagationPreviewResponse {
+            copies: copies
+                .iter()
+                .map(|copy| Self::preview_copy(&template, copy))
+                .collect(),
+            template_id: template.template_id,
+            version: template.version,
+        })
+    }
+
+    /// Pushes a template's current layout and schedule to its linked copies. Each copy that
+    /// changes gets a new version, so its previous state stays in its history. A copy that
+    /// fails to update is reported in `failed` and does not stop the others.
+    ///
+    /// # Errors
+    /// Returns an error if the template or a requested copy is not found.
+    pub async fn propagate_template(
+        state: &AppState,
+        user: &UserRecord,
+        template_key: logs_db::TemplateKey<'_>,
+        copy_ids: Option<&[String]>,
+    ) -> Result<PropagateTemplateResponse, (StatusCode, serde_json::Value)> {
+        let company_id = user.company_id.as_deref().ok_or((
+            StatusCode::FORBIDDEN,
+            json!({ "error": "User is not associated with a company" }),
+        ))?;
+
+        let template = Self::get_template(state, company_id, template_key).await?;
+        let mut copies = Self::get_linked_copies(state, company_id, &template.template_id).await?;
+        if let Some(copy_ids) = copy_ids {
+            if copy_ids
+                .iter()
+                .any(|id| !copies.iter().any(|c| c.template_id == *id))
+            {
+                return Err((
+                    StatusCode::NOT_FOUND,
+                    json!({ "error": "Linked copy not found" }),
+                ));
+            }
+            copies.retain(|c| copy_ids.contains(&c.template_id));
+        }
+
+        let mut updated = Vec::new();
+        let mut up_to_date = Vec::new();
+        let mut failed = Vec::new();
+        for copy in copies {
+            if Self::preview_copy(&template, &copy).up_to_date {
+                up_to_date.push(copy.template_id);
+                continue;
+            }
+            let result = Self::update_template(
+                state,
+                logs_db::TemplateKey::Id(&copy.template_id),
+                Some(&template.template_layout),
+                Some(&template.schedule),
+                user,
+                Some(format!(
+                    "Synced from {} version {}",
+                    template.template_name, template.version
+                )),
+                None,
+            )
+            .await;
+            match result {
+                Ok(()) => updated.push(copy.template_id),
+                Err((_, err)) => failed.push(PropagationFailure {
+                    template_id: copy.template_id,
+                    error: err["error"].as_str().unwrap_or("Update failed").to_string(),
+                }),
+            }
+        }
+
+        Ok(PropagateTemplateResponse {
+            updated,
+            up_to_date,
+            failed,
+        })
+    }
+
+    /// Detaches a distributed copy so it no longer receives changes from its master.
+    ///
+    /// # Errors
+    /// Returns an error if the template is not linked or the database update fails.
+    pub async fn unlink_template(
+        state: &AppState,
+        company_id: &str,
+        template_key: logs_db::TemplateKey<'_>,
+        user_branch_id: Option<&str>,
+        user_role: &UserRole,
+    ) -> Result<(), (StatusCode, serde_json::Value)> {
+        let template = Self::get_managed_template(
+            state,
+            company_id,
+            template_key,
+            user_branch_id,
+            user_role,
+            "unlink",
+        )
+        .await?;
+
+        let unlinked = logs_db::unlink_template(&state.mongodb, &template.template_id, company_id)
+            .await
+            .map_err(|e: anyhow::Error| {
+                tracing::error!("Failed to unlink template: {:?}", e);
+                (
+                    StatusCode::INTERNAL_SERVER_ERROR,
+                    json!({ "error": "Failed to unlink template" }),
+                )
+            })?;
+        if !unlinked {
+            return Err((
+                StatusCode::CONFLICT,
+                json!({ "error": "Template is not linked to another template" }),
+            ));
+        }
         Ok(())
     }
 }
@@ -0,0 +1,232 @@
+//! Starter templates loaded into the global template library on first start.
+//!
+//! These cover the routine food-safety records most premises are expected to keep.
+//! LogSmart admins can edit or extend the library afterwards through the API.
+
+use crate::logs_db::{
+    Frequency, LibraryTemplateDocument, Position, Schedule, TemplateField, TemplateFieldProps,
+    TemplateLayout, assign_field_ids,
+};
+
+const LEFT: f64 = 24.0;
+const TOP: f64 = 20.0;
+const ROW_HEIGHT: f64 = 60.0;
+
+/// Lays fields out top to bottom in a single column.
+struct LayoutBuilder {
+    fields: TemplateLayout,
+}
+
+impl LayoutBuilder {
+    fn new(title: &str) -> Self {
+        let mut builder = Self { fields: Vec::new() };
+        builder.push(
+            "label",
+            TemplateFieldProps {
+                text: Some(title.to_string()),
+                size: Some("24".to_string()),
+                weight: Some("bold".to_string()),
+                editable: Some(false),
+                ..TemplateFieldProps::default()
+            },
+        );
+        builder
+    }
+
+    fn push(&mut self, field_type: &str, props: TemplateFieldProps) {
+        let y = TOP + ROW_HEIGHT * self.fields.len() as f64;
+        self.fields.push(TemplateField {
+            field_id: String::new(),
+            field_type: field_type.to_string(),
+            position: Position { x: LEFT, y },
+            props,
+        });
+    }
+
+    fn temperature(mut self, text: &str, min: f32, max: f32) -> Self {
+        self.push(
+            "temperature",
+            TemplateFieldProps {
+                text: Some(text.to_string()),
+                min: Some(min),
+                max: Some(max),
+                unit: Some("°C".to_string()),
+                required: Some(true),
+                ..TemplateFieldProps::default()
+            },
+        );
+        self
+    }
+
+    fn checkbox(mut self, text: &str) -> Self {
+        self.push(
+            "checkbox",
+            TemplateFieldProps {
+                text: Some(text.to_string()),
+                selected: Some("false".to_string()),
+                required: Some(true),
+                ..TemplateFieldProps::default()
+            },
+        );
+        self
+    }
+
+    fn text_input(mut self, text: &str, placeholder: &str, required: bool) -> Self {
+        self.push(
+            "text_input",
+            TemplateFieldProps {
+                text: Some(text.to_string()),
+                placeholder: Some(placeholder.to_string()),
+                required: Some(required),
+                ..TemplateFieldProps::default()
+            },
+        );
+        self
+    }
+
+    fn dropdown(mut self, text: &str, options: &[&str]) -> Self {
+        self.push(
+            "dropdown",
+            TemplateFieldProps {
+                text: Some(text.to_string()),
+                options: Some(options.iter().map(ToString::to_string).collect()),
+                required: Some(true),
+                ..TemplateFieldProps::default()
+            },
+        );
+        self
+    }
+
+    fn build(self) -> TemplateLayout {
+        assign_field_ids(&self.fields, None)
+    }
+}
+
+fn daily(available_from_time: Option<&str>, due_at_time: Option<&str>) -> Schedule {
+    Schedule {
+        frequency: Frequency::Daily,
+        days_of_week: None,
+        day_of_week: None,
+        day_of_month: None,
+        month_of_year: None,
+        available_from_time: available_from_time.map(str::to_string),
+        due_at_time: due_at_time.map(str::to_string),
+        interval: None,
+        starts_on: None,
+        time_slots: None,
+    }
+}
+
+fn library_template(
+    library_template_id: &str,
+    template_name: &str,
+    category: &str,
+    description: &str,
+    template_layout: TemplateLayout,
+    schedule: Schedule,
+) -> LibraryTemplateDocument {
+    let now = chrono::Utc::now();
+    LibraryTemplateDocument {
+        library_template_id: library_template_id.to_string(),
+        template_name: template_name.to_string(),
+        category: category.to_string(),
+        description: description.to_string(),
+        template_layout,
+        schedule,
+        created_at: now,
+        updated_at: now,
+    }
+}
+
+/// Returns the templates the library is seeded with.
+#[must_use]
+pub fn starter_templates() -> Vec<LibraryTemplateDocument> {
+    vec![
+        library_template(
+            "fridge-freezer-temperatures",
+            "Fridge and Freezer Temperatures",
+            "Temperature Control",
+            "Twice-daily check that chilled storage stays at 8°C or below and frozen storage at -18°C or below.",
+            LayoutBuilder::new("Fridge and Freezer Temperatures")
+                .temperature("Fridge 1 (AM)", -2.0, 8.0)
+                .temperature("Fridge 1 (PM)", -2.0, 8.0)
+                .temperature("Freezer 1 (AM)", -30.0, -18.0)
+                .temperature("Freezer 1 (PM)", -30.0, -18.0)
+                .text_input(
+                    "Action taken if out of range",
+                    "e.g. Stock moved, engineer called",
+                    false,
+                )
+                .build(),
+            daily(None, Some("20:00")),
+        ),
+        library_template(
+            "daily-cleaning-schedule",
+            "Daily Cleaning Schedule",
+            "Cleaning",
+            "End-of-day cleaning and disinfection of food contact surfaces, equipment and floors.",
+            LayoutBuilder::new("Daily Cleaning Schedule")
+                .checkbox("Food preparation surfaces cleaned and disinfected")
+                .checkbox("Chopping boards and utensils washed")
+                .checkbox("Hand wash basins stocked with soap and towels")
+                .checkbox("Bins emptied and cleaned")
+                .checkbox("Floors swept and mopped")
+                .text_input(
+                    "Cleaning chemicals used",
+                    "e.g. Sanitiser brand and dilution",
+                    false,
+                )
+                .build(),
+            daily(Some("14:00"), Some("23:00")),
+        ),
+        library_template(
+            "opening-checks",
+            "Opening Checks",
+            "Opening and Closing",
+            "Checks to complete before food is prepared or served each day.",
+            LayoutBuilder::new("Opening Checks")
+                .checkbox("Fridges and freezers working at correct temperature")
+                .checkbox("Preparation areas and equipment clean")
+                .checkbox("Hot and cold running water available")
+                .checkbox("Staff fit for work and wearing clean clothing")
+                .checkbox("Probe thermometer tested")
+                .text_input("Problems found", "Describe any issues and actions", false)
+                .build(),
+            daily(Some("05:00"), Some("11:00")),
+        ),
+        library_template(
+            "closing-checks",
+            "Closing Checks",
+            "Opening and Closing",
+            "Checks to complete at the end of service before the premises are closed.",
+            LayoutBuilder::new("Closing Checks")
+                .checkbox("Food covered, labelled and stored correctly")
+                .checkbox("Out-of-date food discarded")
+                .checkbox("Cleaning schedule completed")
+                .checkbox("Waste removed")
+                .temperature("Fridge temperature at close", -2.0, 8.0)
+                .text_input("Problems found", "Describe any issues and actions", false)
+                .build(),
+            daily(Some("16:00"), Some("23:59")),
+        ),
+        library_template(
+            "delivery-intake",
+            "Delivery Intake",
+            "Deliveries",
+            "Checks on incoming deliveries of chilled and frozen goods before they are accepted.",
+            LayoutBuilder::new("Delivery Intake")
+                .text_input("Supplier", "Supplier name", true)
+                .temperature("Chilled goods temperature", -2.0, 8.0)
+                .temperature("Frozen goods temperature", -30.0, -15.0)
+                .checkbox("Packaging intact and undamaged")
+                .checkbox("Use-by dates acceptable")
+                .dropdown(
+                    "Delivery outcome",
+                    &["Accepted", "Partially rejected", "Rejected"],
+                )
+                .text_input("Items rejected and reason", "List rejected items", false)
+                .build(),
+            daily(None, None),
+        ),
+    ]
+}
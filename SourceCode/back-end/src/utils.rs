@@ -717,6 +717,27 @@ pub fn validate_length_constraints(
     Ok(())
 }
 
+/// Quotes a value for a CSV cell, neutralising spreadsheet formula injection.
+#[must_use]
+pub fn csv_escape(value: &str) -> String {
+    let sanitized = csv_sanitize_cell(value);
+    let escaped = sanitized.replace('"', "\"\"");
+    format!("\"{escaped}\"")
+}
+
+fn csv_sanitize_cell(value: &str) -> String {
+    let starts_with_formula_symbol = matches!(
+        value.chars().find(|c| !c.is_whitespace()),
+        Some('=' | '+' | '-' | '@')
+    );
+
+    if starts_with_formula_symbol {
+        format!("'{value}")
+    } else {
+        value.to_string()
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
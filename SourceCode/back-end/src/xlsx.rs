@@ -0,0 +1,206 @@
+//! Minimal XLSX (Office Open XML spreadsheet) writer for tabular exports.
+//!
+//! Produces a single-sheet workbook using inline strings, with the first row
+//! styled as a bold header.
+
+use std::io::Write;
+
+/// A single spreadsheet cell value.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Cell {
+    Text(String),
+    Number(f64),
+    Empty,
+}
+
+impl Cell {
+    /// Renders the cell as plain text, as used for CSV output.
+    #[must_use]
+    pub fn to_text(&self) -> String {
+        match self {
+            Self::Text(text) => text.clone(),
+            Self::Number(n) => n.to_string(),
+            Self::Empty => String::new(),
+        }
+    }
+}
+
+const CONTENT_TYPES: &str = r#"<?xml version="1.0" encoding="UTF-8" standalone="yes"?>
+<Types xmlns="http://schemas.openxmlformats.org/package/2006/content-types"><Default Extension="rels" ContentType="application/vnd.openxmlformats-package.relationships+xml"/><Default Extension="xml" ContentType="application/xml"/><Override PartName="/xl/workbook.xml" ContentType="application/vnd.openxmlformats-officedocument.spreadsheetml.sheet.main+xml"/><Override PartName="/xl/worksheets/sheet1.xml" ContentType="application/vnd.openxmlformats-officedocument.spreadsheetml.worksheet+xml"/><Override PartName="/xl/styles.xml" ContentType="application/vnd.openxmlformats-officedocument.spreadsheetml.styles+xml"/></Types>"#;
+
+const ROOT_RELS: &str = r#"<?xml version="1.0" encoding="UTF-8" standalone="yes"?>
+<Relationships xmlns="http://schemas.openxmlformats.org/package/2006/relationships"><Relationship Id="rId1" Type="http://schemas.openxmlformats.org/officeDocument/2006/relationships/officeDocument" Target="xl/workbook.xml"/></Relationships>"#;
+
+const WORKBOOK_RELS: &str = r#"<?xml version="1.0" encoding="UTF-8" standalone="yes"?>
+<Relationships xmlns="http://schemas.openxmlformats.org/package/2006/relationships"><Relationship Id="rId1" Type="http://schemas.openxmlformats.org/officeDocument/2006/relationships/worksheet" Target="worksheets/sheet1.xml"/><Relationship Id="rId2" Type="http://schemas.openxmlformats.org/officeDocument/2006/relationships/styles" Target="styles.xml"/></Relationships>"#;
+
+const STYLES: &str = r#"<?xml version="1.0" encoding="UTF-8" standalone="yes"?>
+<styleSheet xmlns="http://schemas.openxmlformats.org/spreadsheetml/2006/main"><fonts count="2"><font><sz val="11"/><name val="Calibri"/></font><font><b/><sz val="11"/><name val="Calibri"/></font></fonts><fills count="2"><fill><patternFill patternType="none"/></fill><fill><patternFill patternType="gray125"/></fill></fills><borders count="1"><border><left/><right/><top/><bottom/><diagonal/></border></borders><cellStyleXfs count="1"><xf numFmtId="0" fontId="0" fillId="0" borderId="0"/></cellStyleXfs><cellXfs count="2"><xf numFmtId="0" fontId="0" fillId="0" borderId="0" xfId="0"/><xf numFmtId="0" fontId="1" fillId="0" borderId="0" xfId="0" applyFont="1"/></cellXfs></styleSheet>"#;
+
+/// Builds an XLSX workbook with a single sheet. The first row is treated as the header.
+///
+/// # Errors
+/// Returns an error if the archive cannot be written.
+pub fn write_workbook(sheet_name: &str, rows: &[Vec<Cell>]) -> zip::result::ZipResult<Vec<u8>> {
+    let mut buf = Vec::new();
+    let mut zip = zip::ZipWriter::new(std::io::Cursor::new(&mut buf));
+    let options = zip::write::SimpleFileOptions::default()
+        .compression_method(zip::CompressionMethod::Deflated);
+
+    let workbook = format!(
+        r#"<?xml version="1.0" encoding="UTF-8" standalone="yes"?>
+<workbook xmlns="http://schemas.openxmlformats.org/spreadsheetml/2006/main" xmlns:r="http://schemas.openxmlformats.org/officeDocument/2006/relationships"><sheets><sheet name="{}" sheetId="1" r:id="rId1"/></sheets></workbook>"#,
+        xml_escape(&sheet_title(sheet_name))
+    );
+
+    for (path, contents) in [
+        ("[Content_Types].xml", CONTENT_TYPES.to_string()),
+        ("_rels/.rels", ROOT_RELS.to_string()),
+        ("xl/workbook.xml", workbook),
+        ("xl/_rels/workbook.xml.rels", WORKBOOK_RELS.to_string()),
+        ("xl/styles.xml", STYLES.to_string()),
+        ("xl/worksheets/sheet1.xml", sheet_xml(rows)),
+    ] {
+        zip.start_file(path, options)?;
+        zip.write_all(contents.as_bytes())?;
+    }
+
+    zip.finish()?;
+    Ok(buf)
+}
+
+fn sheet_xml(rows: &[Vec<Cell>]) -> String {
+    let mut xml = String::from(
+        r#"<?xml version="1.0" encoding="UTF-8" standalone="yes"?>
+<worksheet xmlns="http://schemas.openxmlformats.org/spreadsheetml/2006/main"><sheetViews><sheetView workbookViewId="0"><pane ySplit="1" topLeftCell="A2" activePane="bottomLeft" state="frozen"/></sheetView></sheetViews><sheetData>"#,
+    );
+
+    for (row_index, row) in rows.iter().enumerate() {
+        let row_number = row_index + 1;
+        let style = if row_index == 0 { r#" s="1""# } else { "" };
+        xml.push_str(&format!(r#"<row r="{row_number}">"#));
+        for (column_index, cell) in row.iter().enumerate() {
+            let reference = format!("{}{row_number}", column_name(column_index));
+            match cell {
+                Cell::Text(text) => xml.push_str(&format!(
+                    r#"<c r="{reference}"{style} t="inlineStr"><is><t xml:space="preserve">{}</t></is></c>"#,
+                    xml_escape(text)
+                )),
+                Cell::Number(n) if n.is_finite() => {
+                    xml.push_str(&format!(r#"<c r="{reference}"{style}><v>{n}</v></c>"#));
+                }
+                Cell::Number(_) | Cell::Empty => {}
+            }
+        }
+        xml.push_str("</row>");
+    }
+
+    xml.push_str("</sheetData></worksheet>");
+    xml
+}
+
+/// Converts a zero-based column index to its spreadsheet name (0 -> A, 26 -> AA).
+fn column_name(mut index: usize) -> String {
+    let mut name = Vec::new();
+    loop {
+        #[allow(clippy::cast_possible_truncation)]
+        name.push(b'A' + (index % 26) as u8);
+        if index < 26 {
+            break;
+        }
+        index = index / 26 - 1;
+    }
+    name.reverse();
+    String::from_utf8(name).unwrap_or_default()
+}
+
+/// Sheet names are limited to 31 characters and may not contain `[]:*?/\`.
+fn sheet_title(name: &str) -> String {
+    let title: String = name
+        .chars()
+        .filter(|c| !matches!(c, '[' | ']' | ':' | '*' | '?' | '/' | '\\'))
+        .take(31)
+        .collect();
+    if title.trim().is_empty() {
+        "Sheet1".to_string()
+    } else {
+        title
+    }
+}
+
+fn xml_escape(value: &str) -> String {
+    let mut escaped = String::with_capacity(value.len());
+    for c in value.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&apos;"),
+            // Control characters other than tab/newline are not valid in XML 1.0
+            c if c.is_control() && !matches!(c, '\t' | '\n' | '\r') => {}
+            c => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Read;
+
+    #[test]
+    fn test_column_name() {
+        assert_eq!(column_name(0), "A");
+        assert_eq!(column_name(25), "Z");
+        assert_eq!(column_name(26), "AA");
+        assert_eq!(column_name(27), "AB");
+        assert_eq!(column_name(701), "ZZ");
+        assert_eq!(column_name(702), "AAA");
+    }
+
+    #[test]
+    fn test_sheet_title() {
+        assert_eq!(sheet_title("Fridge [AM/PM]"), "Fridge AMPM");
+        assert_eq!(sheet_title("???"), "Sheet1");
+        assert_eq!(sheet_title(&"x".repeat(40)).len(), 31);
+    }
+
+    #[test]
+    fn test_xml_escape() {
+        assert_eq!(xml_escape("a < b & \"c\""), "a &lt; b &amp; &quot;c&quot;");
+        assert_eq!(xml_escape("bell\u{7}"), "bell");
+    }
+
+    #[test]
+    fn test_write_workbook() {
+        let rows = vec![
+            vec![
+                Cell::Text("Name".to_string()),
+                Cell::Text("Temp".to_string()),
+            ],
+            vec![Cell::Text("Fridge <1>".to_string()), Cell::Number(4.5)],
+            vec![Cell::Empty, Cell::Number(3.0)],
+        ];
+        let bytes = write_workbook("Fridge Temps", &rows).expect("workbook should build");
+
+        let mut archive =
+            zip::ZipArchive::new(std::io::Cursor::new(bytes)).expect("valid zip archive");
+        assert!(archive.by_name("[Content_Types].xml").is_ok());
+        assert!(archive.by_name("xl/workbook.xml").is_ok());
+
+        let mut sheet = String::new();
+        archive
+            .by_name("xl/worksheets/sheet1.xml")
+            .expect("sheet should exist")
+            .read_to_string(&mut sheet)
+            .expect("sheet should be utf-8");
+        assert!(sheet.contains(
+            r#"<c r="A1" s="1" t="inlineStr"><is><t xml:space="preserve">Name</t></is></c>"#
+        ));
+        assert!(sheet.contains("Fridge &lt;1&gt;"));
+        assert!(sheet.contains(r#"<c r="B2"><v>4.5</v></c>"#));
+        assert!(!sheet.contains(r#"r="A3""#));
+    }
+}
@@ -34,6 +34,7 @@ fn create_test_invitation() -> Invitation {
 /// Helper to create a test template field
 fn create_test_field() -> TemplateField {
     TemplateField {
+        field_id: String::new(),
         field_type: "text".to_string(),
         position: Position { x: 100.0, y: 100.0 },
         props: TemplateFieldProps {
@@ -55,6 +56,10 @@ fn create_test_field() -> TemplateField {
             max_length: None,
             min_length: None,
             input_type: None,
+            decimals: None,
+            visible_if: None,
+            required_if: None,
+            expression: None,
         },
     }
 }
@@ -63,6 +68,7 @@ fn create_test_field() -> TemplateField {
 fn create_test_log(company_id: &str, user_id: &str) -> LogEntry {
     LogEntry {
         entry_id: Uuid::new_v4().to_string(),
+        template_id: "template-1".to_string(),
         template_name: "Test Template".to_string(),
         company_id: company_id.to_string(),
         branch_id: None,
@@ -73,6 +79,10 @@ fn create_test_log(company_id: &str, user_id: &str) -> LogEntry {
         updated_at: Utc::now(),
         submitted_at: None,
         period: "default".to_string(),
+        template_version: Some(1),
+        flagged: false,
+        corrective_action: None,
+        review: None,
     }
 }
 
@@ -549,3 +559,166 @@ fn test_data_isolation_by_company() {
         "Logs should belong to different users"
     );
 }
+
+#[test]
+fn test_log_entries_cursor_round_trip() {
+    use back_end::logs_db::{LogEntrySort, format_log_entries_cursor, parse_log_entries_cursor};
+
+    let created_at = Utc::now();
+    let cursor = format_log_entries_cursor(LogEntrySort::CreatedAt, created_at, "entry-1");
+
+    let (parsed_at, parsed_id) =
+        parse_log_entries_cursor(&cursor, LogEntrySort::CreatedAt).expect("cursor should parse");
+    assert_eq!(parsed_at, created_at, "cursor must keep full precision");
+    assert_eq!(parsed_id, "entry-1");
+
+    assert!(
+        parse_log_entries_cursor(&cursor, LogEntrySort::UpdatedAt).is_err(),
+        "cursor issued for another sort field must be rejected"
+    );
+    assert!(parse_log_entries_cursor("not-a-cursor", LogEntrySort::CreatedAt).is_err());
+}
+
+#[test]
+fn test_log_entries_page_filter_combines_status_and_cursor() {
+    use back_end::logs_db::{
+        DraftPeriod, EntryStatusFilter, LogEntryFilters, LogEntrySort, log_entries_page_filter,
+    };
+
+    let past_due = DraftPeriod {
+        template_id: "template-1".to_string(),
+        branch_id: Some("branch-1".to_string()),
+        period: "01/03/2026".to_string(),
+    };
+    let filters = LogEntryFilters {
+        template_ids: Some(vec!["template-1".to_string()]),
+        status: Some(EntryStatusFilter::Overdue(vec![past_due])),
+        ..Default::default()
+    };
+    let cursor = (Utc::now(), "entry-1".to_string());
+
+    let filter = log_entries_page_filter(
+        "company-1",
+        &filters,
+        LogEntrySort::CreatedAt,
+        true,
+        Some(&cursor),
+    )
+    .expect("filter should build");
+
+    assert_eq!(filter.get_str("company_id").unwrap(), "company-1");
+    assert!(
+        filter.get("$or").is_none(),
+        "status and cursor must not share a top-level $or"
+    );
+    let clauses = filter.get_array("$and").expect("status and cursor clauses");
+    assert_eq!(clauses.len(), 2);
+
+    let status = clauses[0].as_document().unwrap();
+    let branches = status.get_array("$or").unwrap();
+    assert_eq!(
+        branches[0]
+            .as_document()
+            .unwrap()
+            .get_str("status")
+            .unwrap(),
+        "overdue"
+    );
+    let past_due_drafts = branches[1].as_document().unwrap();
+    assert_eq!(past_due_drafts.get_str("status").unwrap(), "draft");
+    assert_eq!(past_due_drafts.get_array("$or").unwrap().len(), 1);
+
+    let cursor_clause = clauses[1].as_document().unwrap();
+    assert_eq!(cursor_clause.get_array("$or").unwrap().len(), 2);
+}
+
+#[test]
+fn test_log_entries_page_filter_open_drafts_exclude_past_due_periods() {
+    use back_end::logs_db::{
+        DraftPeriod, EntryStatusFilter, LogEntryFilters, LogEntrySort, log_entries_page_filter,
+    };
+
+    let no_past_due = LogEntryFilters {
+        status: Some(EntryStatusFilter::OpenDrafts(Vec::new())),
+        ..Default::default()
+    };
+    let filter = log_entries_page_filter(
+        "company-1",
+        &no_past_due,
+        LogEntrySort::CreatedAt,
+        true,
+        None,
+    )
+    .unwrap();
+    let clauses = filter.get_array("$and").unwrap();
+    assert_eq!(clauses.len(), 1);
+    let status = clauses[0].as_document().unwrap();
+    assert_eq!(status.get_str("status").unwrap(), "draft");
+    assert!(status.get("$nor").is_none());
+
+    let with_past_due = LogEntryFilters {
+        status: Some(EntryStatusFilter::OpenDrafts(vec![DraftPeriod {
+            template_id: "template-1".to_string(),
+            branch_id: None,
+            period: "01/03/2026".to_string(),
+        }])),
+        ..Default::default()
+    };
+    let filter = log_entries_page_filter(
+        "company-1",
+        &with_past_due,
+        LogEntrySort::CreatedAt,
+        true,
+        None,
+    )
+    .unwrap();
+    let status = filter.get_array("$and").unwrap()[0]
+        .as_document()
+        .unwrap()
+        .clone();
+    let excluded = status.get_array("$nor").unwrap();
+    assert_eq!(excluded.len(), 1);
+    assert!(
+        excluded[0]
+            .as_document()
+            .unwrap()
+            .get("branch_id")
+            .is_some(),
+        "company-wide drafts must match on a null branch"
+    );
+}
+
+#[test]
+fn test_split_log_entries_page_fills_page_and_continues_from_cursor() {
+    use back_end::logs_db::{LogEntrySort, parse_log_entries_cursor, split_log_entries_page};
+
+    let start = Utc::now();
+    let entries: Vec<LogEntry> = (0..5)
+        .map(|i| {
+            let mut entry = create_test_log("company-1", "user-1");
+            entry.entry_id = format!("entry-{i}");
+            entry.created_at = start - Duration::minutes(i);
+            entry
+        })
+        .collect();
+
+    // The query fetches one row beyond the limit to tell whether more remain.
+    let page = split_log_entries_page(entries[..3].to_vec(), LogEntrySort::CreatedAt, Some(2));
+    assert_eq!(page.entries.len(), 2, "page must be filled to the limit");
+    let cursor = page.next_cursor.expect("more entries remain");
+    let (cursor_at, cursor_id) =
+        parse_log_entries_cursor(&cursor, LogEntrySort::CreatedAt).unwrap();
+    assert_eq!(cursor_id, "entry-1");
+    assert_eq!(cursor_at, entries[1].created_at);
+
+    let last = split_log_entries_page(entries[2..4].to_vec(), LogEntrySort::CreatedAt, Some(2));
+    assert_eq!(last.entries.len(), 2);
+    assert!(
+        last.next_cursor.is_none(),
+        "no cursor once the last page is reached"
+    );
+
+    let all = split_log_entries_page(entries.clone(), LogEntrySort::CreatedAt, None);
+    assert_eq!(all.entries.len(), 5);
+    assert!(all.next_cursor.is_none());
+}
@@ -289,6 +289,7 @@ async fn test_get_company_clock_events_branch_filter() {
         company.id.clone(),
         "Branch A".to_string(),
         "A St".to_string(),
+        None,
     )
     .await
     .expect("Failed to create branch A");
@@ -298,6 +299,7 @@ async fn test_get_company_clock_events_branch_filter() {
         company.id.clone(),
         "Branch B".to_string(),
         "B St".to_string(),
+        None,
     )
     .await
     .expect("Failed to create branch B");
@@ -1,6 +1,7 @@
 //! Test data factories for creating test entities
 
 use crate::db::{Company, UserRecord, UserRole};
+use crate::logs_db::{EntryMode, Frequency, LogEntry, LogStatus, Schedule, TemplateDocument};
 use chrono::Utc;
 use uuid::Uuid;
 
@@ -31,6 +32,26 @@ impl UserFactory {
         }
     }
 
+    /// Creates a user with a unique id and email in `company_id`
+    #[must_use]
+    pub fn create_in_company(
+        company_id: &str,
+        role: UserRole,
+        branch_id: Option<&str>,
+    ) -> UserRecord {
+        let id = Uuid::new_v4().to_string();
+        UserRecord {
+            email: format!("{id}@example.com"),
+            id,
+            password_hash: None,
+            company_id: Some(company_id.to_string()),
+            branch_id: branch_id.map(str::to_string),
+            company_name: None,
+            role,
+            ..Self::create_basic()
+        }
+    }
+
     /// Creates a company manager user
     #[must_use]
     pub fn create_company_manager() -> UserRecord {
@@ -102,3 +123,73 @@ impl CompanyFactory {
         }
     }
 }
+
+/// Factory for creating test log templates
+pub struct TemplateFactory;
+
+impl TemplateFactory {
+    /// Creates a company-wide daily template with no fields
+    #[must_use]
+    pub fn create_daily(company_id: &str) -> TemplateDocument {
+        TemplateDocument {
+            template_id: Uuid::new_v4().to_string(),
+            template_name: "Test Template".to_string(),
+            template_layout: Vec::new(),
+            company_id: company_id.to_string(),
+            branch_id: None,
+            created_at: Utc::now(),
+            updated_at: Utc::now(),
+            schedule: Schedule {
+                frequency: Frequency::Daily,
+                days_of_week: None,
+                day_of_week: None,
+                day_of_month: None,
+                month_of_year: None,
+                available_from_time: None,
+                due_at_time: None,
+                interval: None,
+                starts_on: None,
+                time_slots: None,
+            },
+            created_by: mongodb::bson::Uuid::new(),
+            version: 1,
+            version_name: None,
+            archived_at: None,
+            linked_from: None,
+            entry_mode: EntryMode::Scheduled,
+        }
+    }
+}
+
+/// Factory for creating test log entries
+pub struct LogEntryFactory;
+
+impl LogEntryFactory {
+    /// Creates today's draft entry of `template` by `user_id`
+    #[must_use]
+    pub fn create_draft(
+        template: &TemplateDocument,
+        user_id: &str,
+        entry_data: serde_json::Value,
+    ) -> LogEntry {
+        let now = Utc::now();
+        LogEntry {
+            entry_id: Uuid::new_v4().to_string(),
+            template_id: template.template_id.clone(),
+            template_name: template.template_name.clone(),
+            company_id: template.company_id.clone(),
+            branch_id: template.branch_id.clone(),
+            user_id: user_id.to_string(),
+            entry_data,
+            created_at: now,
+            updated_at: now,
+            submitted_at: None,
+            status: LogStatus::Draft,
+            period: now.format("%d/%m/%Y").to_string(),
+            template_version: Some(template.version),
+            flagged: false,
+            corrective_action: None,
+            review: None,
+        }
+    }
+}
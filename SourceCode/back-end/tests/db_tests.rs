@@ -830,6 +830,7 @@ fn test_branch_creation() {
         name: "Main Office".to_string(),
         address: "123 Main St".to_string(),
         created_at: Utc::now(),
+        timezone: None,
     };
     assert_eq!(branch.id, "branch1");
     assert_eq!(branch.company_id, "company1");
@@ -846,6 +847,7 @@ fn test_branch_equality() {
         name: "Main Office".to_string(),
         address: "123 Main St".to_string(),
         created_at,
+        timezone: None,
     };
     let branch2 = Branch {
         id: "branch1".to_string(),
@@ -853,6 +855,7 @@ fn test_branch_equality() {
         name: "Main Office".to_string(),
         address: "123 Main St".to_string(),
         created_at,
+        timezone: None,
     };
     assert_eq!(branch1, branch2);
 }
@@ -865,6 +868,7 @@ fn test_branch_serialization() {
         name: "Main Office".to_string(),
         address: "123 Main St".to_string(),
         created_at: Utc::now(),
+        timezone: None,
     };
     let json = serde_json::to_string(&branch).unwrap();
     assert!(json.contains("branch1"));
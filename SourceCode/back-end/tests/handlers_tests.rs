@@ -358,6 +358,7 @@ fn test_create_branch_request_validation() {
     let req = CreateBranchRequest {
         name: "Main Office".to_string(),
         address: "123 Main St".to_string(),
+        timezone: None,
     };
     assert_eq!(req.name, "Main Office");
     assert_eq!(req.address, "123 Main St");
@@ -369,6 +370,7 @@ fn test_update_branch_request_validation() {
         branch_id: "branch123".to_string(),
         name: "Updated Office".to_string(),
         address: "456 New St".to_string(),
+        timezone: Some("Europe/Dublin".to_string()),
     };
     assert_eq!(req.branch_id, "branch123");
     assert_eq!(req.name, "Updated Office");
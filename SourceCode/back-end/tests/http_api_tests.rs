@@ -2065,3 +2065,135 @@ async fn test_validate_deletion_token_expires_after_6_hours() {
     assert_eq!(validate_status, StatusCode::BAD_REQUEST);
     assert!(validate_body["error"].as_str().unwrap().contains("expired"));
 }
+
+#[tokio::test]
+async fn test_company_log_entries_page_filters_status_before_paging() {
+    use back_end::logs_db::{
+        self, DraftPeriod, EntryStatusFilter, LogEntry, LogEntryFilters, LogEntrySort, LogStatus,
+    };
+
+    let mongodb = logs_db::init_mongodb()
+        .await
+        .expect("Failed to initialize MongoDB");
+    let company_id = Uuid::new_v4().to_string();
+    let start = chrono::Utc::now();
+
+    // Drafts and submitted entries alternate, so a status filter applied after paging
+    // would return short pages.
+    let statuses = [
+        LogStatus::Draft,
+        LogStatus::Submitted,
+        LogStatus::Draft,
+        LogStatus::Submitted,
+        LogStatus::Draft,
+        LogStatus::Submitted,
+    ];
+    for (i, status) in statuses.into_iter().enumerate() {
+        let created_at = start - chrono::Duration::minutes(i64::try_from(i).unwrap());
+        let entry = LogEntry {
+            entry_id: format!("{company_id}-{i}"),
+            template_id: "template-1".to_string(),
+            template_name: "Fridge Temperatures".to_string(),
+            company_id: company_id.clone(),
+            branch_id: None,
+            user_id: "user-1".to_string(),
+            entry_data: json!({}),
+            created_at,
+            updated_at: created_at,
+            submitted_at: None,
+            status,
+            period: format!("0{}/03/2026", i + 1),
+            template_version: Some(1),
+            flagged: false,
+            corrective_action: None,
+            review: None,
+        };
+        logs_db::create_log_entry(&mongodb, &entry)
+            .await
+            .expect("Failed to create log entry");
+    }
+
+    let submitted = LogEntryFilters {
+        status: Some(EntryStatusFilter::Stored(LogStatus::Submitted)),
+        ..Default::default()
+    };
+    let first = logs_db::get_company_log_entries_page(
+        &mongodb,
+        &company_id,
+        &submitted,
+        LogEntrySort::CreatedAt,
+        true,
+        Some(2),
+        None,
+    )
+    .await
+    .expect("Failed to get first page");
+    assert_eq!(first.entries.len(), 2);
+    assert!(
+        first
+            .entries
+            .iter()
+            .all(|e| e.status == LogStatus::Submitted)
+    );
+    let cursor = first.next_cursor.expect("one submitted entry remains");
+
+    let second = logs_db::get_company_log_entries_page(
+        &mongodb,
+        &company_id,
+        &submitted,
+        LogEntrySort::CreatedAt,
+        true,
+        Some(2),
+        Some(logs_db::parse_log_entries_cursor(&cursor, LogEntrySort::CreatedAt).unwrap()),
+    )
+    .await
+    .expect("Failed to get second page");
+    assert_eq!(second.entries.len(), 1);
+    assert_eq!(second.entries[0].entry_id, format!("{company_id}-5"));
+    assert!(second.next_cursor.is_none());
+
+    // The second draft is past due, so it counts as overdue rather than draft.
+    let past_due = vec![DraftPeriod {
+        template_id: "template-1".to_string(),
+        branch_id: None,
+        period: "03/03/2026".to_string(),
+    }];
+    let drafts = logs_db::get_draft_periods(&mongodb, &company_id, &submitted)
+        .await
+        .expect("Failed to get draft periods");
+    assert_eq!(drafts.len(), 3);
+
+    let page_ids = |status: EntryStatusFilter| {
+        let mongodb = mongodb.clone();
+        let company_id = company_id.clone();
+        async move {
+            let filters = LogEntryFilters {
+                status: Some(status),
+                ..Default::default()
+            };
+            logs_db::get_company_log_entries_page(
+                &mongodb,
+                &company_id,
+                &filters,
+                LogEntrySort::CreatedAt,
+                true,
+                Some(50),
+                None,
+            )
+            .await
+            .expect("Failed to get page")
+            .entries
+            .into_iter()
+            .map(|e| e.entry_id)
+            .collect::<Vec<_>>()
+        }
+    };
+    assert_eq!(
+        page_ids(EntryStatusFilter::Overdue(past_due.clone())).await,
+        vec![format!("{company_id}-2")]
+    );
+    assert_eq!(
+        page_ids(EntryStatusFilter::OpenDrafts(past_due)).await,
+        vec![format!("{company_id}-0"), format!("{company_id}-4")]
+    );
+}
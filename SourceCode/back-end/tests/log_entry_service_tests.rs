@@ -0,0 +1,214 @@
+//! Log entry workflow tests against MongoDB.
+//!
+//! Postgres is connected lazily: the workflows covered here only touch MongoDB.
+
+use back_end::AppState;
+use back_end::common::{LogEntryFactory, TemplateFactory, UserFactory, setup_test_state};
+use back_end::db::{UserRecord, UserRole};
+use back_end::logs_db::{
+    self, LogStatus, Position, TemplateDocument, TemplateField, TemplateFieldProps,
+};
+use back_end::services::LogEntryService;
+use uuid::Uuid;
+
+/// Creates a daily fridge template with a 0-5°C temperature field and a draft entry of it
+/// reading `temperature`. Returns the entry id.
+async fn create_fridge_entry(
+    state: &AppState,
+    company_id: &str,
+    author: &UserRecord,
+    temperature: f64,
+) -> String {
+    let template = TemplateDocument {
+        template_name: "Fridge Temperatures".to_string(),
+        template_layout: vec![TemplateField {
+            field_id: "fridge".to_string(),
+            field_type: "temperature".to_string(),
+            position: Position { x: 0.0, y: 0.0 },
+            props: TemplateFieldProps {
+                text: Some("Fridge".to_string()),
+                min: Some(0.0),
+                max: Some(5.0),
+                unit: Some("C".to_string()),
+                ..TemplateFieldProps::default()
+            },
+        }],
+        ..TemplateFactory::create_daily(company_id)
+    };
+    logs_db::add_template(&state.mongodb, &template)
+        .await
+        .expect("Failed to create template");
+
+    let entry = LogEntryFactory::create_draft(
+        &template,
+        &author.id,
+        serde_json::json!({ "fridge": temperature }),
+    );
+    logs_db::create_log_entry(&state.mongodb, &entry)
+        .await
+        .expect("Failed to create log entry");
+    entry.entry_id
+}
+
+async fn entry_issues(state: &AppState, company_id: &str) -> Vec<logs_db::CorrectiveActionIssue> {
+    logs_db::list_corrective_action_issues(
+        &state.mongodb,
+        company_id,
+        None,
+        &[
+            logs_db::IssueStatus::Open,
+            logs_db::IssueStatus::Acknowledged,
+            logs_db::IssueStatus::Closed,
+        ],
+    )
+    .await
+    .expect("Failed to list issues")
+}
+
+#[tokio::test]
+async fn test_resubmitted_entry_does_not_raise_closed_issue_again() {
+    let state = setup_test_state().await;
+    let company_id = Uuid::new_v4().to_string();
+    let author = UserFactory::create_in_company(&company_id, UserRole::Staff, None);
+    let manager = UserFactory::create_in_company(&company_id, UserRole::CompanyManager, None);
+    let entry_id = create_fridge_entry(&state, &company_id, &author, 9.0).await;
+
+    let issue_id =
+        LogEntryService::submit_log_entry(&state, &author.id, &entry_id, Some("Moved stock"))
+            .await
+            .expect("submit should succeed")
+            .expect("out-of-range reading should raise an issue");
+    assert!(
+        logs_db::acknowledge_corrective_action_issue(&state.mongodb, &issue_id, &manager.id)
+            .await
+            .unwrap()
+    );
+    assert!(
+        logs_db::close_corrective_action_issue(&state.mongodb, &issue_id, &manager.id, None)
+            .await
+            .unwrap()
+    );
+
+    LogEntryService::review_log_entry(
+        &state,
+        &manager,
+        &entry_id,
+        logs_db::ReviewDecision::Rejected,
+        Some("Add the probe reading"),
+    )
+    .await
+    .expect("reject should succeed");
+
+    let resubmitted =
+        LogEntryService::submit_log_entry(&state, &author.id, &entry_id, Some("Moved stock"))
+            .await
+            .expect("resubmit should succeed");
+    assert!(
+        resubmitted.is_none(),
+        "the field already has a closed issue"
+    );
+    assert_eq!(entry_issues(&state, &company_id).await.len(), 1);
+
+    LogEntryService::delete_log_entry(&state, &manager, &entry_id)
+        .await
+        .expect("delete should succeed");
+    assert!(
+        entry_issues(&state, &company_id).await.is_empty(),
+        "issues must be deleted with their entry"
+    );
+}
+
+#[tokio::test]
+async fn test_review_records_transition_history() {
+    let state = setup_test_state().await;
+    let company_id = Uuid::new_v4().to_string();
+    let author = UserFactory::create_in_company(&company_id, UserRole::Staff, None);
+    let manager = UserFactory::create_in_company(&company_id, UserRole::CompanyManager, None);
+    let entry_id = create_fridge_entry(&state, &company_id, &author, 3.0).await;
+
+    LogEntryService::submit_log_entry(&state, &author.id, &entry_id, None)
+        .await
+        .expect("submit should succeed");
+    let reviewed = LogEntryService::review_log_entry(
+        &state,
+        &manager,
+        &entry_id,
+        logs_db::ReviewDecision::Approved,
+        Some("Looks good"),
+    )
+    .await
+    .expect("approve should succeed");
+    assert_eq!(reviewed.status, LogStatus::Approved);
+    assert_eq!(
+        reviewed.review.map(|review| review.reviewer_id),
+        Some(manager.id.clone())
+    );
+
+    let history = LogEntryService::get_entry_history(&state, &manager, &entry_id)
+        .await
+        .expect("history should load");
+    let steps: Vec<_> = history
+        .iter()
+        .map(|t| (t.action, t.from_status, t.to_status, t.actor_id.as_str()))
+        .collect();
+    assert_eq!(
+        steps,
+        vec![
+            (
+                logs_db::EntryAction::Submitted,
+                Some(LogStatus::Draft),
+                LogStatus::Submitted,
+                author.id.as_str(),
+            ),
+            (
+                logs_db::EntryAction::Approved,
+                Some(LogStatus::Submitted),
+                LogStatus::Approved,
+                manager.id.as_str(),
+            ),
+        ]
+    );
+    assert_eq!(history[1].comment.as_deref(), Some("Looks good"));
+
+    LogEntryService::delete_log_entry(&state, &manager, &entry_id)
+        .await
+        .expect("delete should succeed");
+    assert!(
+        logs_db::get_entry_transitions(&state.mongodb, &entry_id)
+            .await
+            .unwrap()
+            .is_empty(),
+        "status history must be deleted with its entry"
+    );
+}
+
+#[tokio::test]
+async fn test_concurrent_attachment_reservations_respect_quota() {
+    let state = setup_test_state().await;
+    let company_id = Uuid::new_v4().to_string();
+
+    let reservations = (0..10).map(|_| {
+        back_end::attachments_db::reserve_attachment_storage(&state.mongodb, &company_id, 300, 1000)
+    });
+    let granted = futures_util::future::join_all(reservations)
+        .await
+        .into_iter()
+        .filter(|reserved| *reserved.as_ref().expect("reservation should not fail"))
+        .count();
+    assert_eq!(granted, 3, "only three 300 byte files fit in 1000 bytes");
+
+    back_end::attachments_db::release_attachment_storage(&state.mongodb, &company_id, 300)
+        .await
+        .unwrap();
+    assert!(
+        back_end::attachments_db::reserve_attachment_storage(
+            &state.mongodb,
+            &company_id,
+            300,
+            1000
+        )
+        .await
+        .unwrap(),
+        "released storage can be reserved again"
+    );
+}
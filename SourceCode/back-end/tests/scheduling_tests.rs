@@ -1,5 +1,6 @@
-use back_end::logs_db::{Frequency, Schedule};
+use back_end::logs_db::{Frequency, Schedule, TimeSlot};
 use chrono::Datelike;
+use chrono_tz::UTC;
 
 fn daily_schedule(days_of_week: Option<Vec<u8>>) -> Schedule {
     Schedule {
@@ -10,6 +11,9 @@ fn daily_schedule(days_of_week: Option<Vec<u8>>) -> Schedule {
         month_of_year: None,
         available_from_time: None,
         due_at_time: None,
+        interval: None,
+        starts_on: None,
+        time_slots: None,
     }
 }
 
@@ -22,6 +26,9 @@ fn weekly_schedule(day_of_week: u8) -> Schedule {
         month_of_year: None,
         available_from_time: None,
         due_at_time: None,
+        interval: None,
+        starts_on: None,
+        time_slots: None,
     }
 }
 
@@ -34,6 +41,9 @@ fn monthly_schedule(day_of_month: u8) -> Schedule {
         month_of_year: None,
         available_from_time: None,
         due_at_time: None,
+        interval: None,
+        starts_on: None,
+        time_slots: None,
     }
 }
 
@@ -46,6 +56,9 @@ fn yearly_schedule(month_of_year: u8, day_of_month: u8) -> Schedule {
         month_of_year: Some(month_of_year),
         available_from_time: None,
         due_at_time: None,
+        interval: None,
+        starts_on: None,
+        time_slots: None,
     }
 }
 
@@ -68,7 +81,7 @@ fn test_is_form_due_today_daily_weekdays_only() {
         chrono::Weekday::Sat => 6,
     };
 
-    let result = back_end::logs_db::is_form_due_today(&schedule);
+    let result = back_end::logs_db::is_form_due_today(&schedule, UTC);
     // Should be due on Mon-Fri (1-5), not on Sun(0) or Sat(6)
     let expected = (1..=5).contains(&today_num);
     assert_eq!(
@@ -81,7 +94,7 @@ fn test_is_form_due_today_daily_weekdays_only() {
 fn test_is_form_due_today_daily_every_day() {
     // No days_of_week restriction means due every day
     let schedule = daily_schedule(None);
-    assert!(back_end::logs_db::is_form_due_today(&schedule));
+    assert!(back_end::logs_db::is_form_due_today(&schedule, UTC));
 }
 
 #[test]
@@ -90,7 +103,7 @@ fn test_is_form_due_today_daily_sunday_only() {
     let schedule = daily_schedule(Some(vec![0]));
 
     let today = chrono::Utc::now().weekday();
-    let result = back_end::logs_db::is_form_due_today(&schedule);
+    let result = back_end::logs_db::is_form_due_today(&schedule, UTC);
 
     match today {
         chrono::Weekday::Sun => assert!(result, "Should be due on Sunday"),
@@ -104,7 +117,7 @@ fn test_is_form_due_today_daily_saturday_only() {
     let schedule = daily_schedule(Some(vec![6]));
 
     let today = chrono::Utc::now().weekday();
-    let result = back_end::logs_db::is_form_due_today(&schedule);
+    let result = back_end::logs_db::is_form_due_today(&schedule, UTC);
 
     match today {
         chrono::Weekday::Sat => assert!(result, "Should be due on Saturday"),
@@ -118,7 +131,7 @@ fn test_is_form_due_today_weekly_monday() {
     let schedule = weekly_schedule(1);
 
     let today = chrono::Utc::now().weekday();
-    let result = back_end::logs_db::is_form_due_today(&schedule);
+    let result = back_end::logs_db::is_form_due_today(&schedule, UTC);
 
     match today {
         chrono::Weekday::Mon => assert!(result, "Should be due on Monday"),
@@ -132,7 +145,7 @@ fn test_is_form_due_today_weekly_sunday() {
     let schedule = weekly_schedule(0);
 
     let today = chrono::Utc::now().weekday();
-    let result = back_end::logs_db::is_form_due_today(&schedule);
+    let result = back_end::logs_db::is_form_due_today(&schedule, UTC);
 
     match today {
         chrono::Weekday::Sun => assert!(result, "Should be due on Sunday"),
@@ -146,7 +159,7 @@ fn test_is_form_due_today_weekly_friday() {
     let schedule = weekly_schedule(5);
 
     let today = chrono::Utc::now().weekday();
-    let result = back_end::logs_db::is_form_due_today(&schedule);
+    let result = back_end::logs_db::is_form_due_today(&schedule, UTC);
 
     match today {
         chrono::Weekday::Fri => assert!(result, "Should be due on Friday"),
@@ -168,7 +181,7 @@ fn test_get_missed_periods_daily_weekdays() {
         .and_utc()
         .to_rfc3339();
 
-    let missed = back_end::logs_db::get_missed_periods(&schedule, None, Some(&created_at));
+    let missed = back_end::logs_db::get_missed_periods(&schedule, None, Some(&created_at), UTC);
 
     // Every day between two_days_ago and today (inclusive) that is Mon-Fri
     // should appear in missed periods
@@ -217,7 +230,7 @@ fn test_get_missed_periods_daily_sunday_only() {
         .and_utc()
         .to_rfc3339();
 
-    let missed = back_end::logs_db::get_missed_periods(&schedule, None, Some(&created_at));
+    let missed = back_end::logs_db::get_missed_periods(&schedule, None, Some(&created_at), UTC);
 
     let mut current = two_days_ago;
     while current <= today {
@@ -247,7 +260,7 @@ fn test_get_missed_periods_daily_sunday_only() {
 fn test_format_period_for_frequency_weekly_starts_sunday() {
     // format_period_for_frequency for Weekly should produce a week period
     // where the week starts on Sunday
-    let period = back_end::logs_db::format_period_for_frequency(&Frequency::Weekly);
+    let period = back_end::logs_db::format_period_for_frequency(&Frequency::Weekly, UTC);
 
     // The period format is "start_day-end_day/month/year"
     let parts: Vec<&str> = period.split('/').collect();
@@ -317,7 +330,7 @@ fn test_get_missed_periods_weekly_no_period_before_created_at() {
         .and_utc()
         .to_rfc3339();
 
-    let missed = back_end::logs_db::get_missed_periods(&schedule, None, Some(&created_at));
+    let missed = back_end::logs_db::get_missed_periods(&schedule, None, Some(&created_at), UTC);
 
     assert!(
         !missed.is_empty(),
@@ -347,7 +360,7 @@ fn test_get_missed_periods_weekly_created_on_target_day() {
         .and_utc()
         .to_rfc3339();
 
-    let missed = back_end::logs_db::get_missed_periods(&schedule, None, Some(&created_at));
+    let missed = back_end::logs_db::get_missed_periods(&schedule, None, Some(&created_at), UTC);
 
     assert!(
         !missed.is_empty(),
@@ -373,7 +386,7 @@ fn test_get_missed_periods_weekly_with_last_submission() {
     let last_period = "12-18/1/2025";
     let last_date = chrono::NaiveDate::from_ymd_opt(2025, 1, 18).unwrap();
 
-    let missed = back_end::logs_db::get_missed_periods(&schedule, Some(last_period), None);
+    let missed = back_end::logs_db::get_missed_periods(&schedule, Some(last_period), None, UTC);
 
     assert!(
         !missed.is_empty(),
@@ -390,6 +403,34 @@ fn test_get_missed_periods_weekly_with_last_submission() {
     }
 }
 
+#[test]
+fn test_get_missed_periods_match_due_dates_on_short_months() {
+    use back_end::logs_db::compute_due_date_for_period;
+
+    // Day 31 falls on 30 April in Q2, and 30 February on the last day of February
+    let quarterly = Schedule {
+        frequency: Frequency::Quarterly,
+        ..monthly_schedule(31)
+    };
+    let created_at = "2025-01-01T00:00:00Z".to_string();
+    let missed = back_end::logs_db::get_missed_periods(&quarterly, None, Some(&created_at), UTC);
+    assert!(missed.contains(&"2/2025".to_string()), "Q2 must fall due");
+    for period in &missed {
+        assert!(
+            compute_due_date_for_period(&quarterly, period).is_some(),
+            "Missed period {period} has no due date"
+        );
+    }
+
+    let yearly = yearly_schedule(2, 30);
+    let missed = back_end::logs_db::get_missed_periods(&yearly, Some("2024"), None, UTC);
+    assert!(missed.contains(&"2025".to_string()), "2025 must fall due");
+    assert_eq!(
+        compute_due_date_for_period(&yearly, "2025"),
+        chrono::NaiveDate::from_ymd_opt(2025, 2, 28)
+    );
+}
+
 #[test]
 fn test_validate_and_normalize_period_daily_rejects_non_daily_shapes() {
     let schedule = daily_schedule(None);
@@ -424,7 +465,7 @@ fn test_get_missed_periods_monthly_no_period_before_created_at() {
         .and_utc()
         .to_rfc3339();
 
-    let missed = back_end::logs_db::get_missed_periods(&schedule, None, Some(&created_at));
+    let missed = back_end::logs_db::get_missed_periods(&schedule, None, Some(&created_at), UTC);
 
     assert!(
         !missed.is_empty(),
@@ -454,7 +495,7 @@ fn test_get_missed_periods_yearly_no_period_before_created_at() {
         .and_utc()
         .to_rfc3339();
 
-    let missed = back_end::logs_db::get_missed_periods(&schedule, None, Some(&created_at));
+    let missed = back_end::logs_db::get_missed_periods(&schedule, None, Some(&created_at), UTC);
 
     assert!(
         !missed.is_empty(),
@@ -470,3 +511,470 @@ fn test_get_missed_periods_yearly_no_period_before_created_at() {
         );
     }
 }
+
+#[test]
+fn test_derive_log_status_only_drafts_become_overdue() {
+    use back_end::logs_db::{AvailabilityStatus, LogStatus, derive_log_status};
+
+    let schedule = daily_schedule(None);
+    let now = chrono::Utc::now();
+    let past = now - chrono::Duration::days(3);
+    let period = format!("{:02}/{:02}/{:04}", past.day(), past.month(), past.year());
+
+    assert_eq!(
+        derive_log_status(LogStatus::Draft, &schedule, &period, now, UTC),
+        (LogStatus::Overdue, AvailabilityStatus::Overdue)
+    );
+    for status in [LogStatus::Submitted, LogStatus::Approved] {
+        assert_eq!(
+            derive_log_status(status, &schedule, &period, now, UTC).0,
+            status
+        );
+    }
+}
+
+fn slot(available_from_time: &str, due_at_time: &str) -> TimeSlot {
+    TimeSlot {
+        available_from_time: available_from_time.to_string(),
+        due_at_time: due_at_time.to_string(),
+    }
+}
+
+fn hot_hold_schedule() -> Schedule {
+    Schedule {
+        time_slots: Some(vec![slot("12:00", "13:00"), slot("10:00", "11:00")]),
+        ..daily_schedule(None)
+    }
+}
+
+#[test]
+fn test_time_slot_periods_are_normalized() {
+    let schedule = hot_hold_schedule();
+
+    assert_eq!(
+        back_end::logs_db::validate_and_normalize_period(&schedule, "2/3/2026 10:00"),
+        Some("02/03/2026 10:00".to_string())
+    );
+    assert_eq!(
+        back_end::logs_db::validate_and_normalize_period(&schedule, "02/03/2026 11:00"),
+        None,
+        "11:00 is not the start of a slot"
+    );
+    assert_eq!(
+        back_end::logs_db::validate_and_normalize_period(&schedule, "02/03/2026"),
+        None,
+        "Sub-daily periods must name a slot"
+    );
+    assert_eq!(
+        back_end::logs_db::validate_and_normalize_period(&daily_schedule(None), "02/03/2026 10:00"),
+        None,
+        "Plain daily periods cannot name a slot"
+    );
+}
+
+#[test]
+fn test_time_slot_availability_and_due_times() {
+    use back_end::logs_db::{AvailabilityStatus, get_availability_status_for_period};
+
+    let schedule = hot_hold_schedule();
+    let period = "02/03/2026 12:00";
+    let at = |hour, minute| {
+        chrono::NaiveDate::from_ymd_opt(2026, 3, 2)
+            .unwrap()
+            .and_hms_opt(hour, minute, 0)
+            .unwrap()
+            .and_utc()
+    };
+
+    assert_eq!(
+        get_availability_status_for_period(&schedule, period, at(11, 30), UTC),
+        AvailabilityStatus::NotAvailable
+    );
+    assert_eq!(
+        get_availability_status_for_period(&schedule, period, at(12, 30), UTC),
+        AvailabilityStatus::Available
+    );
+    assert_eq!(
+        get_availability_status_for_period(&schedule, period, at(13, 0), UTC),
+        AvailabilityStatus::Overdue
+    );
+    assert_eq!(
+        back_end::logs_db::get_available_from_datetime(&schedule, period, UTC).as_deref(),
+        Some("2026-03-02T12:00:00+00:00")
+    );
+    assert_eq!(
+        back_end::logs_db::get_due_at_datetime(&schedule, period, UTC).as_deref(),
+        Some("2026-03-02T13:00:00+00:00")
+    );
+}
+
+#[test]
+fn test_get_missed_periods_time_slots() {
+    let schedule = hot_hold_schedule();
+    let yesterday = chrono::Utc::now().date_naive() - chrono::Duration::days(1);
+    let date = format!(
+        "{:02}/{:02}/{:04}",
+        yesterday.day(),
+        yesterday.month(),
+        yesterday.year()
+    );
+    let created_at = yesterday
+        .and_hms_opt(0, 0, 0)
+        .unwrap()
+        .and_utc()
+        .to_rfc3339();
+
+    let missed = back_end::logs_db::get_missed_periods(&schedule, None, Some(&created_at), UTC);
+    assert_eq!(missed[0], format!("{date} 10:00"));
+    assert_eq!(missed[1], format!("{date} 12:00"));
+
+    let last = format!("{date} 10:00");
+    let missed = back_end::logs_db::get_missed_periods(&schedule, Some(&last), None, UTC);
+    assert_eq!(
+        missed[0],
+        format!("{date} 12:00"),
+        "The later slot on the last submitted day is still missed"
+    );
+}
+
+#[test]
+fn test_every_n_days_schedule() {
+    let today = chrono::Utc::now().date_naive();
+    let starts_on = today - chrono::Duration::days(6);
+    let schedule = Schedule {
+        interval: Some(3),
+        starts_on: Some(starts_on),
+        ..daily_schedule(None)
+    };
+
+    let created_at = starts_on
+        .and_hms_opt(0, 0, 0)
+        .unwrap()
+        .and_utc()
+        .to_rfc3339();
+    let missed = back_end::logs_db::get_missed_periods(&schedule, None, Some(&created_at), UTC);
+    let expected: Vec<String> = [6, 3, 0]
+        .into_iter()
+        .map(|days_back| {
+            let d = today - chrono::Duration::days(days_back);
+            format!("{:02}/{:02}/{:04}", d.day(), d.month(), d.year())
+        })
+        .collect();
+    assert_eq!(missed, expected);
+
+    assert!(back_end::logs_db::is_form_due_today(&schedule, UTC));
+    assert!(!schedule.matches_interval(today - chrono::Duration::days(1)));
+    assert!(!schedule.matches_interval(starts_on - chrono::Duration::days(3)));
+
+    let skipped = today - chrono::Duration::days(2);
+    let period = format!(
+        "{:02}/{:02}/{:04}",
+        skipped.day(),
+        skipped.month(),
+        skipped.year()
+    );
+    assert_eq!(
+        back_end::logs_db::validate_period_business_rules(
+            &schedule,
+            &period,
+            None,
+            chrono::Utc::now(),
+            UTC
+        ),
+        Err(back_end::logs_db::PeriodValidationError::NotScheduled)
+    );
+}
+
+#[test]
+fn test_every_n_weeks_schedule() {
+    let starts_on = chrono::NaiveDate::from_ymd_opt(2026, 1, 5).unwrap();
+    let schedule = Schedule {
+        interval: Some(2),
+        starts_on: Some(starts_on),
+        ..weekly_schedule(1)
+    };
+
+    assert!(schedule.matches_interval(starts_on));
+    assert!(!schedule.matches_interval(starts_on + chrono::Duration::weeks(1)));
+    assert!(schedule.matches_interval(starts_on + chrono::Duration::weeks(2)));
+    // Any day of a scheduled Sunday-to-Saturday week matches
+    assert!(schedule.matches_interval(chrono::NaiveDate::from_ymd_opt(2026, 1, 4).unwrap()));
+
+    let created_at = starts_on
+        .and_hms_opt(0, 0, 0)
+        .unwrap()
+        .and_utc()
+        .to_rfc3339();
+    let missed = back_end::logs_db::get_missed_periods(&schedule, None, Some(&created_at), UTC);
+    assert_eq!(missed[0], "4-10/1/2026");
+    assert_eq!(missed[1], "18-24/1/2026");
+}
+
+#[test]
+fn test_format_period_for_schedule_with_time_slots() {
+    let period = back_end::logs_db::format_period_for_schedule(&hot_hold_schedule(), UTC);
+    let normalized =
+        back_end::logs_db::validate_and_normalize_period(&hot_hold_schedule(), &period);
+    assert_eq!(normalized, Some(period));
+}
+
+#[test]
+fn test_due_times_follow_local_time_across_dst() {
+    let schedule = daily_schedule(None);
+    let london = chrono_tz::Europe::London;
+
+    // Default due time is 17:00 local: 17:00 UTC in GMT, 16:00 UTC once BST starts
+    assert_eq!(
+        back_end::logs_db::get_due_at_datetime(&schedule, "27/03/2026", london).as_deref(),
+        Some("2026-03-27T17:00:00+00:00")
+    );
+    assert_eq!(
+        back_end::logs_db::get_due_at_datetime(&schedule, "30/03/2026", london).as_deref(),
+        Some("2026-03-30T16:00:00+00:00")
+    );
+    assert_eq!(
+        back_end::logs_db::get_available_from_datetime(&schedule, "30/03/2026", london).as_deref(),
+        Some("2026-03-30T07:00:00+00:00")
+    );
+}
+
+#[test]
+fn test_slots_on_dst_transition_days() {
+    let schedule = Schedule {
+        time_slots: Some(vec![slot("01:30", "03:00")]),
+        ..daily_schedule(None)
+    };
+    let london = chrono_tz::Europe::London;
+
+    // 01:30 does not exist when the clocks go forward and resolves to the hour after
+    assert_eq!(
+        back_end::logs_db::get_available_from_datetime(&schedule, "29/03/2026 01:30", london)
+            .as_deref(),
+        Some("2026-03-29T01:30:00+00:00")
+    );
+    // 01:30 happens twice when the clocks go back; the first occurrence is used
+    assert_eq!(
+        back_end::logs_db::get_available_from_datetime(&schedule, "25/10/2026 01:30", london)
+            .as_deref(),
+        Some("2026-10-25T00:30:00+00:00")
+    );
+}
+
+#[test]
+fn test_availability_uses_local_date_and_time() {
+    use back_end::logs_db::{AvailabilityStatus, get_availability_status_for_period};
+
+    let schedule = daily_schedule(None);
+    let new_york = chrono_tz::America::New_York;
+    let at = |day, hour| {
+        chrono::NaiveDate::from_ymd_opt(2026, 3, day)
+            .unwrap()
+            .and_hms_opt(hour, 0, 0)
+            .unwrap()
+            .and_utc()
+    };
+
+    // 02:00 UTC on 2 March is still the evening of 1 March in New York
+    assert_eq!(
+        get_availability_status_for_period(&schedule, "02/03/2026", at(2, 2), new_york),
+        AvailabilityStatus::NotAvailable
+    );
+    // 14:00 UTC is 09:00 in New York, inside the 08:00 to 17:00 window
+    assert_eq!(
+        get_availability_status_for_period(&schedule, "02/03/2026", at(2, 14), new_york),
+        AvailabilityStatus::Available
+    );
+    // 22:00 UTC is 17:00 in New York, when the entry becomes overdue
+    assert_eq!(
+        get_availability_status_for_period(&schedule, "02/03/2026", at(2, 22), new_york),
+        AvailabilityStatus::Overdue
+    );
+    // 20:00 UTC is past the due time in UTC but only 15:00 in New York
+    assert_eq!(
+        get_availability_status_for_period(&schedule, "02/03/2026", at(2, 20), UTC),
+        AvailabilityStatus::Overdue
+    );
+    assert_eq!(
+        get_availability_status_for_period(&schedule, "02/03/2026", at(2, 20), new_york),
+        AvailabilityStatus::Available
+    );
+}
+
+fn calendar(
+    opening_hours: &[(i16, &str, &str)],
+    closures: &[(&str, &str)],
+) -> back_end::db::BranchCalendar {
+    let time = |t: &str| chrono::NaiveTime::parse_from_str(t, "%H:%M").unwrap();
+    let date = |d: &str| chrono::NaiveDate::parse_from_str(d, "%Y-%m-%d").unwrap();
+    back_end::db::BranchCalendar {
+        opening_hours: opening_hours
+            .iter()
+            .map(
+                |&(day_of_week, opens_at, closes_at)| back_end::db::BranchOpeningHours {
+                    branch_id: "branch-1".to_string(),
+                    day_of_week,
+                    opens_at: time(opens_at),
+                    closes_at: time(closes_at),
+                },
+            )
+            .collect(),
+        closures: closures
+            .iter()
+            .map(|&(starts_on, ends_on)| back_end::db::BranchClosure {
+                id: format!("closure-{starts_on}"),
+                branch_id: "branch-1".to_string(),
+                starts_on: date(starts_on),
+                ends_on: date(ends_on),
+                reason: "Closed".to_string(),
+                source_uid: None,
+                created_at: chrono::Utc::now(),
+            })
+            .collect(),
+    }
+}
+
+#[test]
+fn test_branch_calendar_suppresses_closed_days() {
+    // Open Monday to Saturday, closed for Christmas and Boxing Day
+    let weekdays: Vec<(i16, &str, &str)> = (1..=6).map(|d| (d, "09:00", "17:00")).collect();
+    let calendar = calendar(&weekdays, &[("2026-12-25", "2026-12-26")]);
+    let daily = daily_schedule(None);
+
+    assert!(calendar.is_period_open(&daily, "24/12/2026"));
+    assert!(!calendar.is_period_open(&daily, "25/12/2026"));
+    assert!(!calendar.is_period_open(&daily, "26/12/2026"));
+    assert!(
+        !calendar.is_period_open(&daily, "27/12/2026"),
+        "27 December 2026 is a Sunday"
+    );
+    assert!(calendar.is_period_open(&daily, "28/12/2026"));
+
+    // Weekly periods stay due while any day of the week is open, even if the due day is not
+    let friday = weekly_schedule(5);
+    assert!(calendar.is_period_open(&friday, "20-26/12/2026"));
+    assert!(calendar.is_period_open(&friday, "13-19/12/2026"));
+
+    // Without opening hours every day is open unless closed
+    let holidays_only = back_end::db::BranchCalendar {
+        opening_hours: Vec::new(),
+        ..calendar.clone()
+    };
+    assert!(holidays_only.is_period_open(&daily, "27/12/2026"));
+    assert!(!holidays_only.is_period_open(&daily, "25/12/2026"));
+}
+
+#[test]
+fn test_branch_calendar_suppresses_periods_closed_throughout() {
+    // Closed for the week of Sunday 2 to Saturday 8 August 2026, then to the end of the month
+    let closures = calendar(
+        &[],
+        &[("2026-08-02", "2026-08-08"), ("2026-08-10", "2026-08-31")],
+    );
+
+    let friday = weekly_schedule(5);
+    assert!(!closures.is_period_open(&friday, "2-8/8/2026"));
+    assert!(
+        closures.is_period_open(&friday, "9-15/8/2026"),
+        "Open on Sunday 9 August"
+    );
+
+    assert!(closures.is_period_open(&monthly_schedule(31), "08/2026"));
+    let august = calendar(&[], &[("2026-08-01", "2026-08-31")]);
+    assert!(!august.is_period_open(&monthly_schedule(31), "08/2026"));
+    assert!(august.is_period_open(&monthly_schedule(31), "09/2026"));
+
+    // The third quarter runs July to September
+    let quarterly = Schedule {
+        frequency: Frequency::Quarterly,
+        ..monthly_schedule(1)
+    };
+    let summer = calendar(&[], &[("2026-07-01", "2026-09-30")]);
+    assert!(!summer.is_period_open(&quarterly, "3/2026"));
+    assert!(summer.is_period_open(&quarterly, "4/2026"));
+    assert_eq!(
+        back_end::logs_db::period_date_range(&quarterly, "3/2026"),
+        Some((
+            chrono::NaiveDate::from_ymd_opt(2026, 7, 1).unwrap(),
+            chrono::NaiveDate::from_ymd_opt(2026, 9, 30).unwrap()
+        ))
+    );
+    assert!(summer.is_period_open(&yearly_schedule(8, 1), "2026"));
+}
+
+#[test]
+fn test_branch_calendar_suppresses_slots_outside_opening_hours() {
+    // 2 March 2026 is a Monday
+    let calendar = calendar(&[(1, "11:00", "17:00")], &[]);
+    let schedule = hot_hold_schedule();
+
+    assert!(!calendar.is_period_open(&schedule, "02/03/2026 10:00"));
+    assert!(calendar.is_period_open(&schedule, "02/03/2026 12:00"));
+    assert!(!calendar.is_period_open(&schedule, "03/03/2026 12:00"));
+
+    let calendars = back_end::db::CompanyCalendars {
+        branches: [("branch-1".to_string(), calendar)].into(),
+    };
+    assert!(!calendars.is_period_open(Some("branch-1"), &schedule, "02/03/2026 10:00"));
+    assert!(
+        calendars.is_period_open(None, &schedule, "02/03/2026 10:00"),
+        "Company-wide templates are never suppressed"
+    );
+    assert!(calendars.is_period_open(Some("branch-2"), &schedule, "02/03/2026 10:00"));
+}
+
+#[test]
+fn test_upcoming_periods() {
+    use back_end::logs_db::upcoming_periods;
+
+    // 6 March 2026 is a Friday
+    let friday = chrono::NaiveDate::from_ymd_opt(2026, 3, 6).unwrap();
+
+    assert_eq!(
+        upcoming_periods(&daily_schedule(Some(vec![1, 2, 3, 4, 5])), friday, 3),
+        vec!["06/03/2026", "09/03/2026", "10/03/2026"]
+    );
+    assert_eq!(
+        upcoming_periods(&weekly_schedule(1), friday, 2),
+        vec!["8-14/3/2026", "15-21/3/2026"]
+    );
+    assert_eq!(
+        upcoming_periods(&hot_hold_schedule(), friday, 3),
+        vec!["06/03/2026 10:00", "06/03/2026 12:00", "07/03/2026 10:00"]
+    );
+    assert_eq!(
+        upcoming_periods(&monthly_schedule(31), friday, 2),
+        vec!["03/2026", "04/2026"]
+    );
+
+    // Quarterly periods are due on the day in their first month, or its last day
+    let quarterly = Schedule {
+        frequency: Frequency::Quarterly,
+        ..monthly_schedule(31)
+    };
+    assert_eq!(
+        upcoming_periods(&quarterly, friday, 3),
+        vec!["2/2026", "3/2026", "4/2026"]
+    );
+    assert_eq!(
+        back_end::logs_db::compute_due_date_for_period(&quarterly, "2/2026"),
+        chrono::NaiveDate::from_ymd_opt(2026, 4, 30)
+    );
+    assert_eq!(
+        back_end::logs_db::compute_due_date_for_period(&quarterly, "3/2026"),
+        chrono::NaiveDate::from_ymd_opt(2026, 7, 31)
+    );
+    assert_eq!(
+        back_end::logs_db::compute_due_date_for_period(&quarterly, "0/2026"),
+        None
+    );
+
+    // Yearly periods are due on the last day of months without the chosen day
+    assert_eq!(
+        upcoming_periods(&yearly_schedule(2, 30), friday, 2),
+        vec!["2027", "2028"]
+    );
+    assert_eq!(
+        back_end::logs_db::compute_due_date_for_period(&yearly_schedule(2, 30), "2028"),
+        chrono::NaiveDate::from_ymd_opt(2028, 2, 29)
+    );
+}
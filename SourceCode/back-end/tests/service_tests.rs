@@ -1,7 +1,8 @@
+use back_end::common::{TemplateFactory, UserFactory, setup_test_state};
 use back_end::db::{Company, Invitation, UserRecord, UserRole};
 use back_end::logs_db::{
-    Frequency, LogEntry, LogStatus, Position, Schedule, TemplateDocument, TemplateField,
-    TemplateFieldProps,
+    AvailabilityStatus, EntryMode, Frequency, LogEntry, LogStatus, Position, Schedule,
+    TemplateDocument, TemplateField, TemplateFieldProps,
 };
 use chrono::Utc;
 use mongodb::bson::Uuid;
@@ -65,8 +66,10 @@ fn test_invitation_creation() {
 #[test]
 fn test_template_creation() {
     let template = TemplateDocument {
+        template_id: "template123".to_string(),
         template_name: "Test Template".to_string(),
         template_layout: vec![TemplateField {
+            field_id: String::new(),
             field_type: "text".to_string(),
             position: Position { x: 0.0, y: 0.0 },
             props: TemplateFieldProps::default(),
@@ -83,21 +86,66 @@ fn test_template_creation() {
             month_of_year: None,
             available_from_time: None,
             due_at_time: None,
+            interval: None,
+            starts_on: None,
+            time_slots: None,
         },
         created_by: Uuid::new(),
         version: 1,
         version_name: None,
+        archived_at: None,
+        linked_from: None,
+        entry_mode: EntryMode::Scheduled,
     };
 
     assert_eq!(template.template_name, "Test Template");
     assert_eq!(template.template_layout.len(), 1);
     assert_eq!(template.branch_id, Some("branch123".to_string()));
+    assert!(!template.is_archived());
+}
+
+#[test]
+fn test_entry_mode_status() {
+    let template = |entry_mode| TemplateDocument {
+        template_name: "Deliveries".to_string(),
+        entry_mode,
+        ..TemplateFactory::create_daily("company123")
+    };
+    let now = Utc::now();
+    let tz = chrono_tz::UTC;
+
+    let scheduled = template(EntryMode::Scheduled);
+    assert!(scheduled.is_scheduled());
+    assert_eq!(
+        scheduled.derive_entry_status(LogStatus::Draft, "01/01/2024", now, tz),
+        (LogStatus::Overdue, AvailabilityStatus::Overdue)
+    );
+
+    for mode in [EntryMode::Repeatable, EntryMode::Unscheduled] {
+        let template = template(mode);
+        assert!(!template.is_scheduled());
+        assert_eq!(
+            template.derive_entry_status(LogStatus::Draft, "01/01/2024", now, tz),
+            (LogStatus::Draft, AvailabilityStatus::Available)
+        );
+        assert_eq!(
+            template.availability_for_period("01/01/2024", now, tz),
+            AvailabilityStatus::Available
+        );
+    }
+
+    assert_eq!(
+        serde_json::from_str::<EntryMode>("\"repeatable\"").unwrap(),
+        EntryMode::Repeatable
+    );
+    assert_eq!(EntryMode::default(), EntryMode::Scheduled);
 }
 
 #[test]
 fn test_log_entry_creation() {
     let entry = LogEntry {
         entry_id: Uuid::new().to_string(),
+        template_id: "template123".to_string(),
         template_name: "Test Template".to_string(),
         company_id: "company123".to_string(),
         branch_id: Some("branch123".to_string()),
@@ -108,6 +156,10 @@ fn test_log_entry_creation() {
         submitted_at: None,
         status: LogStatus::Draft,
         period: "2024-01".to_string(),
+        template_version: Some(1),
+        flagged: false,
+        corrective_action: None,
+        review: None,
     };
 
     assert_eq!(entry.template_name, "Test Template");
@@ -115,3 +167,282 @@ fn test_log_entry_creation() {
     assert_eq!(entry.branch_id, Some("branch123".to_string()));
     assert!(entry.submitted_at.is_none());
 }
+
+async fn add_stored_template(state: &back_end::AppState, company_id: &str) -> TemplateDocument {
+    let template = TemplateDocument {
+        template_name: "Opening Checks".to_string(),
+        ..TemplateFactory::create_daily(company_id)
+    };
+    back_end::logs_db::add_template(&state.mongodb, &template)
+        .await
+        .expect("Failed to create template");
+    template
+}
+
+#[tokio::test]
+async fn test_archive_and_unarchive_template() {
+    use back_end::logs_db::{self, TemplateKey};
+    use back_end::services::TemplateService;
+
+    let state = setup_test_state().await;
+    let company_id = Uuid::new().to_string();
+    let template = add_stored_template(&state, &company_id).await;
+    let key = || TemplateKey::Id(&template.template_id);
+    let role = UserRole::CompanyManager;
+
+    TemplateService::archive_template(&state, &company_id, key(), None, &role)
+        .await
+        .expect("archive should succeed");
+    let (status, _) = TemplateService::archive_template(&state, &company_id, key(), None, &role)
+        .await
+        .expect_err("archiving twice should conflict");
+    assert_eq!(status, axum::http::StatusCode::CONFLICT);
+
+    let archived = logs_db::get_archived_templates(&state.mongodb, &company_id, None)
+        .await
+        .unwrap();
+    assert_eq!(archived.len(), 1);
+    assert!(archived[0].is_archived());
+    assert!(
+        !logs_db::mark_template_has_entries(&state.mongodb, &template.template_id, &company_id)
+            .await
+            .unwrap(),
+        "archived templates take no new entries"
+    );
+
+    TemplateService::unarchive_template(&state, &company_id, key(), None, &role)
+        .await
+        .expect("unarchive should succeed");
+    let (status, _) = TemplateService::unarchive_template(&state, &company_id, key(), None, &role)
+        .await
+        .expect_err("restoring an active template should conflict");
+    assert_eq!(status, axum::http::StatusCode::CONFLICT);
+    assert!(
+        logs_db::get_archived_templates(&state.mongodb, &company_id, None)
+            .await
+            .unwrap()
+            .is_empty()
+    );
+}
+
+#[tokio::test]
+async fn test_purge_template_with_entries_conflicts() {
+    use back_end::logs_db::{self, TemplateKey};
+    use back_end::services::TemplateService;
+
+    let state = setup_test_state().await;
+    let company_id = Uuid::new().to_string();
+    let role = UserRole::CompanyManager;
+
+    let used = add_stored_template(&state, &company_id).await;
+    assert!(
+        logs_db::mark_template_has_entries(&state.mongodb, &used.template_id, &company_id)
+            .await
+            .unwrap()
+    );
+    let (status, _) = TemplateService::purge_template(
+        &state,
+        &company_id,
+        TemplateKey::Id(&used.template_id),
+        None,
+        &role,
+    )
+    .await
+    .expect_err("templates with entries cannot be purged");
+    assert_eq!(status, axum::http::StatusCode::CONFLICT);
+    assert!(
+        logs_db::get_template_by_id(&state.mongodb, &used.template_id, &company_id)
+            .await
+            .unwrap()
+            .is_some()
+    );
+
+    let unused = add_stored_template(&state, &company_id).await;
+    TemplateService::purge_template(
+        &state,
+        &company_id,
+        TemplateKey::Id(&unused.template_id),
+        None,
+        &role,
+    )
+    .await
+    .expect("unused templates can be purged");
+    assert!(
+        !logs_db::mark_template_has_entries(&state.mongodb, &unused.template_id, &company_id)
+            .await
+            .unwrap(),
+        "no entry can be created once the template is purged"
+    );
+}
+
+/// Adds a copy of `master` linked to it for `branch_id`, with a layout the master lacks.
+async fn add_linked_copy(
+    state: &back_end::AppState,
+    master: &TemplateDocument,
+    branch_id: &str,
+) -> String {
+    let copy = TemplateDocument {
+        template_name: format!("{} ({branch_id})", master.template_name),
+        template_layout: vec![TemplateField {
+            field_id: "notes".to_string(),
+            field_type: "text".to_string(),
+            position: Position { x: 0.0, y: 0.0 },
+            props: TemplateFieldProps::default(),
+        }],
+        branch_id: Some(branch_id.to_string()),
+        linked_from: Some(master.template_id.clone()),
+        ..TemplateFactory::create_daily(&master.company_id)
+    };
+    back_end::logs_db::add_template(&state.mongodb, &copy)
+        .await
+        .expect("Failed to create template copy");
+    copy.template_id
+}
+
+#[tokio::test]
+async fn test_distribute_then_propagate_template() {
+    use back_end::db;
+    use back_end::logs_db::TemplateKey;
+    use back_end::services::TemplateService;
+
+    let state = setup_test_state().await;
+    let company = db::create_company(
+        &state.postgres,
+        format!("Company {}", Uuid::new()),
+        "1 High St".to_string(),
+    )
+    .await
+    .expect("Failed to create company");
+    let north = db::create_branch(
+        &state.postgres,
+        company.id.clone(),
+        "North".to_string(),
+        "2 High St".to_string(),
+        None,
+    )
+    .await
+    .expect("Failed to create branch");
+    let south = db::create_branch(
+        &state.postgres,
+        company.id.clone(),
+        "South".to_string(),
+        "3 High St".to_string(),
+        None,
+    )
+    .await
+    .expect("Failed to create branch");
+    let owner = UserFactory::create_in_company(&company.id, UserRole::CompanyManager, None);
+    let master = add_stored_template(&state, &company.id).await;
+    let key = || TemplateKey::Id(&master.template_id);
+    let branch_ids = [north.id.clone(), south.id.clone(), north.id.clone()];
+
+    let distributed =
+        TemplateService::distribute_template(&state, &owner, key(), &branch_ids, true)
+            .await
+            .expect("distribute should succeed");
+    let mut names: Vec<_> = distributed
+        .copies
+        .iter()
+        .map(|c| c.template_name.as_str())
+        .collect();
+    names.sort_unstable();
+    assert_eq!(names, ["Opening Checks (North)", "Opening Checks (South)"]);
+    assert!(distributed.skipped_branch_ids.is_empty());
+
+    let again = TemplateService::distribute_template(&state, &owner, key(), &branch_ids, true)
+        .await
+        .expect("distribute should succeed");
+    assert!(
+        again.copies.is_empty(),
+        "branches with a linked copy are skipped"
+    );
+    assert_eq!(again.skipped_branch_ids.len(), 2);
+
+    let (status, _) = TemplateService::distribute_template(
+        &state,
+        &owner,
+        key(),
+        &[Uuid::new().to_string()],
+        true,
+    )
+    .await
+    .expect_err("unknown branches are rejected");
+    assert_eq!(status, axum::http::StatusCode::NOT_FOUND);
+
+    let layout = vec![TemplateField {
+        field_id: "notes".to_string(),
+        field_type: "text".to_string(),
+        position: Position { x: 0.0, y: 0.0 },
+        props: TemplateFieldProps::default(),
+    }];
+    TemplateService::update_template(&state, key(), Some(&layout), None, &owner, None, None)
+        .await
+        .expect("update should succeed");
+
+    let propagated = TemplateService::propagate_template(&state, &owner, key(), None)
+        .await
+        .expect("propagate should succeed");
+    assert_eq!(propagated.updated.len(), 2);
+    assert!(propagated.failed.is_empty());
+    for copy in &distributed.copies {
+        let stored =
+            back_end::logs_db::get_template_by_id(&state.mongodb, &copy.template_id, &company.id)
+                .await
+                .unwrap()
+                .expect("copy should exist");
+        assert_eq!(stored.template_layout.len(), 1);
+        assert_eq!(stored.version, 2);
+    }
+
+    let repeated = TemplateService::propagate_template(&state, &owner, key(), None)
+        .await
+        .expect("propagate should succeed");
+    assert!(repeated.updated.is_empty());
+    assert_eq!(repeated.up_to_date.len(), 2);
+}
+
+#[tokio::test]
+async fn test_propagate_template_reports_failed_copies() {
+    use back_end::logs_db::TemplateKey;
+    use back_end::services::TemplateService;
+
+    let state = setup_test_state().await;
+    let company_id = Uuid::new().to_string();
+    let master = add_stored_template(&state, &company_id).await;
+    let own_copy = add_linked_copy(&state, &master, "branch-a").await;
+    let other_copy = add_linked_copy(&state, &master, "branch-b").await;
+
+    // A branch manager may only update their own branch's copy
+    let branch_manager =
+        UserFactory::create_in_company(&company_id, UserRole::BranchManager, Some("branch-a"));
+    let result = TemplateService::propagate_template(
+        &state,
+        &branch_manager,
+        TemplateKey::Id(&master.template_id),
+        None,
+    )
+    .await
+    .expect("propagate should report per-copy results");
+    assert_eq!(result.updated, vec![own_copy.clone()]);
+    assert_eq!(result.failed.len(), 1);
+    assert_eq!(result.failed[0].template_id, other_copy);
+    assert_eq!(
+        result.failed[0].error,
+        "Branch managers can only update templates for their own branch"
+    );
+
+    let own = back_end::logs_db::get_template_by_id(&state.mongodb, &own_copy, &company_id)
+        .await
+        .unwrap()
+        .expect("copy should exist");
+    assert!(own.template_layout.is_empty());
+    let other = back_end::logs_db::get_template_by_id(&state.mongodb, &other_copy, &company_id)
+        .await
+        .unwrap()
+        .expect("copy should exist");
+    assert_eq!(
+        other.template_layout.len(),
+        1,
+        "failed copies are left unchanged"
+    );
+}
@@ -712,7 +712,9 @@ export interface paths {
 		};
 		/**
 		 * Lists all log forms that are due today for the current user's company.
-		 * @description # Errors
+		 * @description Repeatable and unscheduled templates are never due and are listed in `startable` instead.
+		 *
+		 *     # Errors
 		 *     Returns an error if the user is not authorized or if the query fails.
 		 */
 		get: operations['list_due_forms_today'];
@@ -809,7 +811,7 @@ export interface paths {
 		put?: never;
 		/** Adds a new log template for the current company. */
 		post: operations['add_template'];
-		/** Deletes a specific log template. */
+		/** Archives a specific log template. Its entries and versions are kept. */
 		delete: operations['delete_template'];
 		options?: never;
 		head?: never;
@@ -823,7 +825,7 @@ export interface paths {
 			path?: never;
 			cookie?: never;
 		};
-		/** Retrieves all log templates for the current company/branch. */
+		/** Retrieves all active (or archived) log templates for the current company/branch. */
 		get: operations['get_all_templates'];
 		put?: never;
 		post?: never;
@@ -833,6 +835,23 @@ export interface paths {
 		patch?: never;
 		trace?: never;
 	};
+	'/logs/templates/purge': {
+		parameters: {
+			query?: never;
+			header?: never;
+			path?: never;
+			cookie?: never;
+		};
+		get?: never;
+		put?: never;
+		post?: never;
+		/** Permanently deletes a log template that has no entries. */
+		delete: operations['purge_template'];
+		options?: never;
+		head?: never;
+		patch?: never;
+		trace?: never;
+	};
 	'/logs/templates/rename': {
 		parameters: {
 			query?: never;
@@ -850,6 +869,23 @@ export interface paths {
 		patch?: never;
 		trace?: never;
 	};
+	'/logs/templates/unarchive': {
+		parameters: {
+			query?: never;
+			header?: never;
+			path?: never;
+			cookie?: never;
+		};
+		get?: never;
+		put?: never;
+		/** Restores an archived log template. */
+		post: operations['unarchive_template'];
+		delete?: never;
+		options?: never;
+		head?: never;
+		patch?: never;
+		trace?: never;
+	};
 	'/logs/templates/update': {
 		parameters: {
 			query?: never;
@@ -1002,6 +1038,8 @@ export interface components {
 		AddTemplateRequest: {
 			/** @example branch-uuid-here */
 			branch_id?: string | null;
+			/** @description `scheduled` (default), `repeatable` or `unscheduled`. Cannot be changed later. */
+			entry_mode?: components['schemas']['EntryMode'];
 			schedule: components['schemas']['Schedule'];
 			template_layout: components['schemas']['Vec'];
 			/** @example Kitchen Daily Log */
@@ -1009,6 +1047,7 @@ export interface components {
 		};
 		AddTemplateResponse: {
 			message: string;
+			template_id: string;
 		};
 		AddTokenResponse: {
 			message: string;
@@ -1027,10 +1066,45 @@ export interface components {
 			/** @example staff */
 			role: string;
 		};
+		AttachmentResponse: {
+			content_type: string;
+			entry_id: string;
+			filename: string;
+			id: string;
+			/** Format: int64 */
+			size: number;
+			uploaded_at: string;
+			uploaded_by: string;
+		};
 		AuthResponse: {
 			token: string;
 			user: components['schemas']['UserResponse'];
 		};
+		BranchCalendarResponse: {
+			closures: components['schemas']['BranchClosureDto'][];
+			/** @description Weekly opening hours. Days without hours are closed; an empty list means the branch is open every day. */
+			opening_hours: components['schemas']['OpeningHoursDto'][];
+		};
+		BranchClosureDto: {
+			/** Format: date-time */
+			created_at: string;
+			/**
+			 * Format: date
+			 * @description Last closed day, inclusive.
+			 * @example 2026-12-26
+			 */
+			ends_on: string;
+			id: string;
+			/** @description Whether the closure came from an imported iCalendar file. */
+			imported: boolean;
+			/** @example Christmas */
+			reason: string;
+			/**
+			 * Format: date
+			 * @example 2026-12-25
+			 */
+			starts_on: string;
+		};
 		BranchDto: {
 			address: string;
 			company_id: string;
@@ -1041,6 +1115,8 @@ export interface components {
 			has_pending_deletion?: boolean;
 			id: string;
 			name: string;
+			/** @description IANA timezone of the branch. `None` means the company timezone applies. */
+			timezone?: string | null;
 		};
 		CancelInvitationRequest: {
 			/** @example invitation-uuid-here */
@@ -1062,6 +1138,9 @@ export interface components {
 			is_clocked_in: boolean;
 			recent_events: components['schemas']['ClockEventResponse'][];
 		};
+		CloseCorrectiveActionIssueRequest: {
+			resolution_note?: string | null;
+		};
 		CompanyClockEventResponse: {
 			/** Format: date-time */
 			clock_in: string;
@@ -1092,7 +1171,27 @@ export interface components {
 			logo_id?: string | null;
 			logo_url?: string | null;
 			name: string;
+			/**
+			 * @description IANA timezone used for log periods and due times.
+			 * @example Europe/London
+			 */
+			timezone: string;
 		};
+		/** @enum {string} */
+		ConditionOperator:
+			| 'equals'
+			| 'not_equals'
+			| 'greater_than'
+			| 'greater_than_or_equal'
+			| 'less_than'
+			| 'less_than_or_equal'
+			| 'checked'
+			| 'unchecked'
+			| 'empty'
+			| 'not_empty'
+			| 'above_max'
+			| 'below_min'
+			| 'out_of_range';
 		ConfirmBranchDeletionRequest: {
 			/** @example deletion-token-here */
 			token: string;
@@ -1100,17 +1199,60 @@ export interface components {
 		ConfirmBranchDeletionResponse: {
 			message: string;
 		};
+		CorrectiveActionIssueResponse: {
+			acknowledged_at?: string | null;
+			acknowledged_by?: string | null;
+			branch_id?: string | null;
+			closed_at?: string | null;
+			closed_by?: string | null;
+			corrective_action?: string | null;
+			entry_id: string;
+			id: string;
+			period: string;
+			raised_at: string;
+			raised_by: string;
+			readings: components['schemas']['OutOfRangeReading'][];
+			resolution_note?: string | null;
+			status: components['schemas']['IssueStatus'];
+			template_id: string;
+			template_name: string;
+		};
+		CreateBranchClosureRequest: {
+			/**
+			 * Format: date
+			 * @description Last closed day, inclusive. Omit for a single day.
+			 * @example 2026-12-26
+			 */
+			ends_on?: string | null;
+			/** @example Christmas */
+			reason: string;
+			/**
+			 * Format: date
+			 * @example 2026-12-25
+			 */
+			starts_on: string;
+		};
 		CreateBranchRequest: {
 			/** @example 123 Regent St, London */
 			address: string;
 			/** @example London Office */
 			name: string;
+			/**
+			 * @description IANA timezone of the branch. Omit to use the company timezone.
+			 * @example Europe/Dublin
+			 */
+			timezone?: string | null;
 		};
 		CreateLogEntryRequest: {
 			/** @example 17/03/2026 */
 			period?: string | null;
-			/** @example Kitchen Daily Log */
-			template_name: string;
+			/** @example template-uuid-here */
+			template_id?: string | null;
+			/**
+			 * @description Accepted when `template_id` is not given.
+			 * @example Kitchen Daily Log
+			 */
+			template_name?: string | null;
 		};
 		CreateLogEntryResponse: {
 			id: string;
@@ -1143,7 +1285,9 @@ export interface components {
 			message: string;
 		};
 		DeleteTemplateRequest: {
-			template_name: string;
+			template_id?: string | null;
+			/** @description Accepted when `template_id` is not given. */
+			template_name?: string | null;
 		};
 		DeleteTemplateResponse: {
 			message: string;
@@ -1155,15 +1299,28 @@ export interface components {
 			last_submitted?: string | null;
 			period: string;
 			status?: string | null;
+			template_id: string;
 			template_layout: components['schemas']['Vec'];
 			template_name: string;
 		};
 		DueFormsResponse: {
 			forms: components['schemas']['DueFormInfo'][];
+			/** @description Repeatable and unscheduled templates, which are never due. */
+			startable: components['schemas']['StartableFormInfo'][];
 		};
+		/**
+		 * @description How a template's entries relate to its schedule. Set when the template is created.
+		 * @enum {string}
+		 */
+		EntryMode: 'scheduled' | 'repeatable' | 'unscheduled';
 		ErrorResponse: {
 			error: string;
 		};
+		FieldCondition: {
+			field_id: string;
+			operator: components['schemas']['ConditionOperator'];
+			value?: unknown;
+		};
 		/** @enum {string} */
 		Frequency: 'Daily' | 'Weekly' | 'Monthly' | 'Quarterly' | 'Yearly';
 		GetAllTemplatesResponse: {
@@ -1185,11 +1342,20 @@ export interface components {
 			invitations: components['schemas']['InvitationResponse'][];
 		};
 		GetTemplateRequest: {
-			/** @example Kitchen Daily Log */
-			template_name: string;
+			/** @example template-uuid-here */
+			template_id?: string | null;
+			/**
+			 * @description Accepted when `template_id` is not given.
+			 * @example Kitchen Daily Log
+			 */
+			template_name?: string | null;
 		};
 		GetTemplateResponse: {
+			archived_at?: string | null;
 			branch_id?: string | null;
+			entry_mode: components['schemas']['EntryMode'];
+			linked_from?: string | null;
+			template_id: string;
 			template_layout: components['schemas']['Vec'];
 			template_name: string;
 			/** Format: int32 */
@@ -1203,6 +1369,11 @@ export interface components {
 			metrics: components['schemas']['DatabaseHealthMetrics'];
 			status: string;
 		};
+		ImportBranchClosuresResponse: {
+			imported: number;
+			/** @description Timed, recurring or invalid events that were not imported. */
+			skipped: number;
+		};
 		IndexUsageResponse: {
 			indexes: components['schemas']['IndexUsageStats'][];
 			unused_indexes: string[];
@@ -1230,6 +1401,8 @@ export interface components {
 			email: string;
 			role?: null | components['schemas']['UserRole'];
 		};
+		/** @enum {string} */
+		IssueStatus: 'open' | 'acknowledged' | 'closed';
 		JwtVerifyResponse: {
 			email: string;
 		};
@@ -1239,9 +1412,25 @@ export interface components {
 		LayoutGenerationResponse: {
 			layout: unknown;
 		};
+		ListAttachmentsResponse: {
+			attachments: components['schemas']['AttachmentResponse'][];
+			/**
+			 * Format: int64
+			 * @description Attachment storage allowed for the company, in bytes.
+			 */
+			quota_bytes: number;
+			/**
+			 * Format: int64
+			 * @description Attachment storage used by the company, in bytes.
+			 */
+			used_bytes: number;
+		};
 		ListBranchesResponse: {
 			branches: components['schemas']['BranchDto'][];
 		};
+		ListCorrectiveActionIssuesResponse: {
+			issues: components['schemas']['CorrectiveActionIssueResponse'][];
+		};
 		ListLogEntriesResponse: {
 			entries: components['schemas']['LogEntryResponse'][];
 		};
@@ -1250,12 +1439,16 @@ export interface components {
 		};
 		LogEntryResponse: {
 			availability_status: string;
+			corrective_action?: string | null;
 			created_at: string;
 			entry_data: unknown;
+			/** @description Whether the entry was submitted with out-of-range readings. */
+			flagged: boolean;
 			id: string;
 			period: string;
 			status: string;
 			submitted_at?: string | null;
+			template_id: string;
 			template_layout: components['schemas']['Vec'];
 			template_name: string;
 			updated_at: string;
@@ -1278,6 +1471,30 @@ export interface components {
 		OAuthLinkConfirmRequest: {
 			link_token: string;
 		};
+		OpeningHoursDto: {
+			/** @example 17:30 */
+			closes_at: string;
+			/**
+			 * Format: int32
+			 * @description 0 = Sunday through 6 = Saturday.
+			 * @example 1
+			 */
+			day_of_week: number;
+			/** @example 09:00 */
+			opens_at: string;
+		};
+		/** @description A temperature reading outside the range configured on its template field. */
+		OutOfRangeReading: {
+			field_id: string;
+			label?: string | null;
+			/** Format: float */
+			max?: number | null;
+			/** Format: float */
+			min?: number | null;
+			unit?: string | null;
+			/** Format: double */
+			value: number;
+		};
 		PasswordResetResponse: {
 			message: string;
 		};
@@ -1322,7 +1539,9 @@ export interface components {
 		};
 		RenameTemplateRequest: {
 			new_template_name: string;
-			old_template_name: string;
+			/** @description Accepted when `template_id` is not given. */
+			old_template_name?: string | null;
+			template_id?: string | null;
 		};
 		RenameTemplateResponse: {
 			message: string;
@@ -1376,8 +1595,65 @@ export interface components {
 			days_of_week?: number[] | null;
 			due_at_time?: string | null;
 			frequency: components['schemas']['Frequency'];
+			/**
+			 * Format: int32
+			 * @description Repeat every N days for daily schedules or every N weeks for weekly ones, counted
+			 *     from `starts_on`. Defaults to 1.
+			 */
+			interval?: number | null;
 			/** Format: int32 */
 			month_of_year?: number | null;
+			/**
+			 * Format: date
+			 * @description First day of a daily or weekly schedule. Required when `interval` is greater than 1.
+			 */
+			starts_on?: string | null;
+			/**
+			 * @description Several occurrences per day for daily schedules, each its own `DD/MM/YYYY HH:MM`
+			 *     period. Replaces `available_from_time` and `due_at_time`.
+			 */
+			time_slots?: components['schemas']['TimeSlot'][] | null;
+		};
+		SchedulePreviewPeriod: {
+			/** @example 2026-04-15T08:00:00+01:00 */
+			available_from?: string | null;
+			/**
+			 * Format: date
+			 * @example 2026-04-15
+			 */
+			due_date: string;
+			/** @example 2026-04-15T17:59:00+01:00 */
+			due_at?: string | null;
+			/**
+			 * @description Period label as stored on entries and substituted for `{period}` in template names.
+			 * @example 2/2026
+			 */
+			period: string;
+		};
+		SchedulePreviewRequest: {
+			/** @description Branch whose timezone the times are shown in. The company timezone is used when omitted. */
+			branch_id?: string | null;
+			/**
+			 * @description Number of periods to return, from 1 to 50. Defaults to 10.
+			 * @example 10
+			 */
+			count?: number | null;
+			/**
+			 * Format: date-time
+			 * @description Creation date of the template being edited. Periods due before it are left out.
+			 */
+			created_at?: string | null;
+			schedule: components['schemas']['Schedule'];
+		};
+		SchedulePreviewResponse: {
+			periods: components['schemas']['SchedulePreviewPeriod'][];
+			/** @description Settings that are invalid or name a day some periods do not have. Empty when the schedule behaves as written. */
+			problems: string[];
+			/**
+			 * @description IANA timezone the times are given in.
+			 * @example Europe/London
+			 */
+			timezone: string;
 		};
 		SecurityLogDto: {
 			actor_role?: string | null;
@@ -1454,8 +1730,19 @@ export interface components {
 			/** Format: double */
 			total_time_ms: number;
 		};
-		SubmitLogEntryRequest: Record<string, never>;
+		/** @description A repeatable or unscheduled template. Entries can be started for it at any time. */
+		StartableFormInfo: {
+			entry_mode: components['schemas']['EntryMode'];
+			template_id: string;
+			template_name: string;
+		};
+		SubmitLogEntryRequest: {
+			/** @description Required when any temperature reading is outside its field's range. */
+			corrective_action?: string | null;
+		};
 		SubmitLogEntryResponse: {
+			/** @description Corrective-action issue raised for out-of-range readings. */
+			issue_id?: string | null;
 			message: string;
 		};
 		TableSizeInfo: {
@@ -1480,6 +1767,7 @@ export interface components {
 			/** Format: uuid */
 			created_by: string;
 			schedule: components['schemas']['Schedule'];
+			template_id?: string;
 			template_layout: components['schemas']['Vec'];
 			template_name: string;
 			/** Format: date-time */
@@ -1489,13 +1777,21 @@ export interface components {
 			version_name?: string | null;
 		};
 		TemplateField: {
+			/**
+			 * @description Stable identifier used as the key for this field's value in `LogEntry.entry_data`.
+			 *     Generated when the field is first saved and preserved across layout edits.
+			 */
+			field_id?: string;
 			field_type: string;
 			position: components['schemas']['Position'];
 			props: components['schemas']['TemplateFieldProps'];
 		};
 		TemplateFieldProps: {
 			color?: string | null;
+			/** Format: int32 */
+			decimals?: number | null;
 			editable?: boolean | null;
+			expression?: string | null;
 			font_family?: string | null;
 			input_type?: string | null;
 			/** Format: float */
@@ -1509,18 +1805,23 @@ export interface components {
 			options?: string[] | null;
 			placeholder?: string | null;
 			required?: boolean | null;
+			required_if?: null | components['schemas']['FieldCondition'];
 			selected?: string | null;
 			size?: string | null;
 			text?: string | null;
 			text_decoration?: string | null;
 			unit?: string | null;
 			value?: string | null;
+			visible_if?: null | components['schemas']['FieldCondition'];
 			weight?: string | null;
 		};
 		TemplateInfo: {
+			archived_at?: string | null;
 			created_at: string;
 			created_by: string;
+			entry_mode: components['schemas']['EntryMode'];
 			schedule: components['schemas']['Schedule'];
+			template_id: string;
 			template_name: string;
 			updated_at: string;
 		};
@@ -1531,6 +1832,20 @@ export interface components {
 			version: number;
 			version_name?: string | null;
 		};
+		TimeSlot: {
+			/** @description Time the slot opens, as `HH:MM`. Also identifies the slot in period strings. */
+			available_from_time: string;
+			/** @description Time the slot is due, as `HH:MM`. */
+			due_at_time: string;
+		};
+		UnarchiveTemplateRequest: {
+			template_id?: string | null;
+			/** @description Accepted when `template_id` is not given. */
+			template_name?: string | null;
+		};
+		UnarchiveTemplateResponse: {
+			message: string;
+		};
 		UpdateBranchRequest: {
 			/** @example 123 Regent St, London */
 			address: string;
@@ -1538,14 +1853,28 @@ export interface components {
 			branch_id: string;
 			/** @example London Office */
 			name: string;
+			/**
+			 * @description IANA timezone of the branch. Omit to keep the current one; an empty string reverts
+			 *     to the company timezone.
+			 * @example Europe/Dublin
+			 */
+			timezone?: string | null;
 		};
 		UpdateCompanyRequest: {
 			address: string;
 			name: string;
+			/**
+			 * @description IANA timezone used for log periods and due times. Omit to keep the current one.
+			 * @example Europe/London
+			 */
+			timezone?: string | null;
 		};
 		UpdateLogEntryRequest: {
 			entry_data: unknown;
 		};
+		UpdateOpeningHoursRequest: {
+			opening_hours: components['schemas']['OpeningHoursDto'][];
+		};
 		UpdateProfileRequest: {
 			/** @example John */
 			first_name: string;
@@ -1556,9 +1885,14 @@ export interface components {
 			/** @example branch-uuid-here */
 			branch_id?: string | null;
 			schedule?: null | components['schemas']['Schedule'];
+			/** @example template-uuid-here */
+			template_id?: string | null;
 			template_layout?: null | components['schemas']['Vec'];
-			/** @example Kitchen Daily Log */
-			template_name: string;
+			/**
+			 * @description Accepted when `template_id` is not given.
+			 * @example Kitchen Daily Log
+			 */
+			template_name?: string | null;
 			/** @example Major Update */
 			version_name?: string | null;
 		};
@@ -3904,12 +4238,14 @@ export interface operations {
 	};
 	get_template: {
 		parameters: {
-			query: {
+			query?: {
+				/** @example template-uuid-here */
+				template_id?: string | null;
 				/**
-				 * @description Name of the template to retrieve
-				 * @example ErrorLog
+				 * @description Accepted when `template_id` is not given.
+				 * @example Kitchen Daily Log
 				 */
-				template_name: string;
+				template_name?: string | null;
 			};
 			header?: never;
 			path?: never;
@@ -4008,8 +4344,148 @@ export interface operations {
 	};
 	delete_template: {
 		parameters: {
-			query: {
-				template_name: string;
+			query?: {
+				template_id?: string | null;
+				/** @description Accepted when `template_id` is not given. */
+				template_name?: string | null;
+			};
+			header?: never;
+			path?: never;
+			cookie?: never;
+		};
+		requestBody?: never;
+		responses: {
+			/** @description Template archived successfully */
+			200: {
+				headers: {
+					[name: string]: unknown;
+				};
+				content: {
+					'application/json': components['schemas']['DeleteTemplateResponse'];
+				};
+			};
+			/** @description Unauthorized */
+			401: {
+				headers: {
+					[name: string]: unknown;
+				};
+				content: {
+					'application/json': components['schemas']['ErrorResponse'];
+				};
+			};
+			/** @description Forbidden */
+			403: {
+				headers: {
+					[name: string]: unknown;
+				};
+				content: {
+					'application/json': components['schemas']['ErrorResponse'];
+				};
+			};
+			/** @description Template not found */
+			404: {
+				headers: {
+					[name: string]: unknown;
+				};
+				content: {
+					'application/json': components['schemas']['ErrorResponse'];
+				};
+			};
+			/** @description Template is already archived */
+			409: {
+				headers: {
+					[name: string]: unknown;
+				};
+				content: {
+					'application/json': components['schemas']['ErrorResponse'];
+				};
+			};
+			/** @description Server error */
+			500: {
+				headers: {
+					[name: string]: unknown;
+				};
+				content: {
+					'application/json': components['schemas']['ErrorResponse'];
+				};
+			};
+		};
+	};
+	unarchive_template: {
+		parameters: {
+			query?: never;
+			header?: never;
+			path?: never;
+			cookie?: never;
+		};
+		requestBody: {
+			content: {
+				'application/json': components['schemas']['UnarchiveTemplateRequest'];
+			};
+		};
+		responses: {
+			/** @description Template restored successfully */
+			200: {
+				headers: {
+					[name: string]: unknown;
+				};
+				content: {
+					'application/json': components['schemas']['UnarchiveTemplateResponse'];
+				};
+			};
+			/** @description Unauthorized */
+			401: {
+				headers: {
+					[name: string]: unknown;
+				};
+				content: {
+					'application/json': components['schemas']['ErrorResponse'];
+				};
+			};
+			/** @description Forbidden */
+			403: {
+				headers: {
+					[name: string]: unknown;
+				};
+				content: {
+					'application/json': components['schemas']['ErrorResponse'];
+				};
+			};
+			/** @description Template not found */
+			404: {
+				headers: {
+					[name: string]: unknown;
+				};
+				content: {
+					'application/json': components['schemas']['ErrorResponse'];
+				};
+			};
+			/** @description Template is not archived */
+			409: {
+				headers: {
+					[name: string]: unknown;
+				};
+				content: {
+					'application/json': components['schemas']['ErrorResponse'];
+				};
+			};
+			/** @description Server error */
+			500: {
+				headers: {
+					[name: string]: unknown;
+				};
+				content: {
+					'application/json': components['schemas']['ErrorResponse'];
+				};
+			};
+		};
+	};
+	purge_template: {
+		parameters: {
+			query?: {
+				template_id?: string | null;
+				/** @description Accepted when `template_id` is not given. */
+				template_name?: string | null;
 			};
 			header?: never;
 			path?: never;
@@ -4044,6 +4520,24 @@ export interface operations {
 					'application/json': components['schemas']['ErrorResponse'];
 				};
 			};
+			/** @description Template not found */
+			404: {
+				headers: {
+					[name: string]: unknown;
+				};
+				content: {
+					'application/json': components['schemas']['ErrorResponse'];
+				};
+			};
+			/** @description Template has log entries */
+			409: {
+				headers: {
+					[name: string]: unknown;
+				};
+				content: {
+					'application/json': components['schemas']['ErrorResponse'];
+				};
+			};
 			/** @description Server error */
 			500: {
 				headers: {
@@ -4057,7 +4551,10 @@ export interface operations {
 	};
 	get_all_templates: {
 		parameters: {
-			query?: never;
+			query?: {
+				/** @description List archived templates instead of active ones. */
+				archived?: boolean;
+			};
 			header?: never;
 			path?: never;
 			cookie?: never;
@@ -4206,12 +4703,14 @@ export interface operations {
 	};
 	get_template_versions: {
 		parameters: {
-			query: {
+			query?: {
+				/** @example template-uuid-here */
+				template_id?: string | null;
 				/**
-				 * @description Name of the template to retrieve versions for
-				 * @example ErrorLog
+				 * @description Accepted when `template_id` is not given.
+				 * @example Kitchen Daily Log
 				 */
-				template_name: string;
+				template_name?: string | null;
 			};
 			header?: never;
 			path?: never;
@@ -4250,12 +4749,14 @@ export interface operations {
 	};
 	restore_template_version: {
 		parameters: {
-			query: {
+			query?: {
+				/** @example template-uuid-here */
+				template_id?: string | null;
 				/**
-				 * @description Name of the template to restore
-				 * @example ErrorLog
+				 * @description Accepted when `template_id` is not given.
+				 * @example Kitchen Daily Log
 				 */
-				template_name: string;
+				template_name?: string | null;
 			};
 			header?: never;
 			path?: never;
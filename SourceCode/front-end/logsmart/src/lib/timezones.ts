@@ -0,0 +1,12 @@
+export const DEFAULT_TIMEZONE = 'Europe/London';
+
+/**
+ * IANA timezones a company or branch can use. Falls back to the default where the runtime
+ * cannot list them.
+ */
+export function listTimezones(): string[] {
+	if (typeof Intl.supportedValuesOf === 'function') {
+		return Intl.supportedValuesOf('timeZone');
+	}
+	return [DEFAULT_TIMEZONE];
+}
@@ -47,7 +47,7 @@ export const load = async ({
 			const entry: LogEntryResponse = await response.json();
 
 			const templateResponse = await fetch(
-				`/api/logs/templates?template_name=${encodeURIComponent(entry.template_name)}`,
+				`/api/logs/templates?template_id=${encodeURIComponent(entry.template_id)}`,
 				{
 					headers: {
 						Authorization: `Bearer ${token}`
@@ -102,7 +102,7 @@ export const load = async ({
 					'Content-Type': 'application/json'
 				},
 				body: JSON.stringify({
-					template_name: templateName,
+					template_id: template.template_id,
 					...(period && { period })
 				})
 			});
@@ -21,6 +21,7 @@ export const load = async ({
 		return {
 			user: user as User,
 			dueToday: [],
+			startable: [],
 			pastLogs: [],
 			error: 'No authentication token'
 		};
@@ -53,7 +54,7 @@ export const load = async ({
 
 			const dueToday: DueFormsResponse = dueTodayResponse.ok
 				? await dueTodayResponse.json()
-				: { forms: [] };
+				: { forms: [], startable: [] };
 			const pastLogsData: ListLogEntriesResponse = pastLogsResponse.ok
 				? await pastLogsResponse.json()
 				: { entries: [] };
@@ -62,6 +63,7 @@ export const load = async ({
 			return {
 				user: user as User,
 				dueToday: dueToday.forms || [],
+				startable: dueToday.startable || [],
 				pastLogs: pastLogsData.entries || [],
 				clockStatus,
 				error: null
@@ -89,7 +91,7 @@ export const load = async ({
 
 			const dueToday: DueFormsResponse = dueTodayResponse.ok
 				? await dueTodayResponse.json()
-				: { forms: [] };
+				: { forms: [], startable: [] };
 			const allLogsData: ListLogEntriesResponse = allLogsResponse.ok
 				? await allLogsResponse.json()
 				: { entries: [] };
@@ -98,6 +100,7 @@ export const load = async ({
 			return {
 				user: user as User,
 				dueToday: dueToday.forms || [],
+				startable: dueToday.startable || [],
 				allLogs: allLogsData.entries || [],
 				clockStatus,
 				error: null
@@ -108,6 +111,7 @@ export const load = async ({
 		return {
 			user: user as User,
 			dueToday: [],
+			startable: [],
 			pastLogs: [],
 			allLogs: [],
 			error: 'Failed to fetch logs'
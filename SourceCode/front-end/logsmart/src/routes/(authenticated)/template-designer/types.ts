@@ -22,6 +22,8 @@ export type CanvasItemProps = {
 
 export type CanvasItem = {
 	id: string;
+	/** Server-assigned field id; absent until the field has been saved. */
+	fieldId?: string;
 	type: string;
 	x: number;
 	y: number;
@@ -38,6 +40,7 @@ export type ComponentType = {
 
 export type Template = {
 	id: number;
+	templateId: string;
 	name: string;
 	selected: boolean;
 };